@@ -0,0 +1,75 @@
+//! Fetch and decode a wallet's past trades
+
+use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::str::FromStr;
+use tracing::debug;
+
+use crate::decoder::{decode_wallet_trade, transaction_config, DetectedTrade};
+
+/// Signatures returned per `getSignaturesForAddress` page (the RPC maximum)
+const SIGNATURE_PAGE: usize = 1_000;
+
+/// Concurrent `getTransaction` requests
+const FETCH_CONCURRENCY: usize = 8;
+
+/// Trades in the wallet's `max_signatures` most recent successful transactions, oldest first
+pub async fn fetch_wallet_trades(
+    rpc_url: &str,
+    wallet: &Pubkey,
+    max_signatures: usize,
+) -> Result<Vec<DetectedTrade>> {
+    let rpc_client = AsyncRpcClient::new_with_commitment(rpc_url.to_string(), CommitmentConfig::confirmed());
+    
+    let mut signatures = Vec::new();
+    let mut before = None;
+    while signatures.len() < max_signatures {
+        let page = rpc_client
+            .get_signatures_for_address_with_config(wallet, GetConfirmedSignaturesForAddress2Config {
+                before,
+                until: None,
+                limit: Some(SIGNATURE_PAGE.min(max_signatures - signatures.len())),
+                commitment: Some(CommitmentConfig::confirmed()),
+            })
+            .await
+            .context("getSignaturesForAddress failed")?;
+        let Some(last) = page.last() else { break };
+        before = Some(Signature::from_str(&last.signature)?);
+        let exhausted = page.len() < SIGNATURE_PAGE;
+        signatures.extend(
+            page.into_iter()
+                .filter(|status| status.err.is_none())
+                .filter_map(|status| Signature::from_str(&status.signature).ok()),
+        );
+        if exhausted {
+            break;
+        }
+    }
+    debug!("Fetched {} signatures for {}", signatures.len(), wallet);
+    
+    let mut trades: Vec<DetectedTrade> = stream::iter(signatures)
+        .map(|signature| {
+            let rpc_client = &rpc_client;
+            async move {
+                match rpc_client.get_transaction_with_config(&signature, transaction_config()).await {
+                    Ok(tx) => decode_wallet_trade(&tx, wallet),
+                    Err(e) => {
+                        debug!("Skipping {}: {}", signature, e);
+                        None
+                    }
+                }
+            }
+        })
+        .buffer_unordered(FETCH_CONCURRENCY)
+        .filter_map(|trade| async move { trade })
+        .collect()
+        .await;
+    
+    trades.sort_by_key(|trade| trade.slot);
+    Ok(trades)
+}
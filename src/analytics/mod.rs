@@ -0,0 +1,9 @@
+//! Target wallet analytics: trade history, performance report and score
+
+pub mod history;
+pub mod report;
+pub mod scorer;
+
+pub use history::*;
+pub use report::*;
+pub use scorer::*;
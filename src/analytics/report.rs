@@ -0,0 +1,268 @@
+//! Performance statistics and score for a wallet's trade history
+
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::decoder::{DetectedTrade, TradeType};
+
+/// A sell this soon after the first buy counts as a quick flip (likely a bundler or sniper)
+pub const QUICK_SELL_SECS: i64 = 10;
+
+/// Closed tokens needed before the score is trusted fully (and before the scorer acts on it)
+pub const MIN_CLOSED_TOKENS: usize = 10;
+
+/// A token counts as closed once this share of the bought amount is sold
+const CLOSED_SOLD_RATIO: f64 = 0.95;
+
+/// One token's buys and sells
+#[derive(Debug, Default)]
+struct TokenRoundTrip {
+    first_buy: Option<i64>,
+    first_sell: Option<i64>,
+    last_sell: Option<i64>,
+    sol_in: u64,
+    sol_out: u64,
+    tokens_bought: u64,
+    tokens_sold: u64,
+    /// Highest lamports per token received on a sell
+    best_exit_price: f64,
+}
+
+impl TokenRoundTrip {
+    fn is_closed(&self) -> bool {
+        self.tokens_bought > 0 && self.tokens_sold as f64 >= self.tokens_bought as f64 * CLOSED_SOLD_RATIO
+    }
+    
+    fn entry_price(&self) -> f64 {
+        self.sol_in as f64 / self.tokens_bought as f64
+    }
+}
+
+/// How a wallet has traded, computed from its decoded trades
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletReport {
+    pub wallet: Pubkey,
+    /// Decoded buys and sells
+    pub trades: usize,
+    /// Tokens bought within the history
+    pub tokens_traded: usize,
+    /// Tokens (almost) fully sold again
+    pub closed_tokens: usize,
+    /// Closed tokens sold for more SOL than they cost
+    pub win_rate: f64,
+    /// Mean seconds from first buy to last sell of closed tokens
+    pub avg_hold_secs: Option<f64>,
+    /// Median of best exit price over average entry price, per closed token
+    pub median_peak_multiple: Option<f64>,
+    /// Median SOL per buy
+    pub median_buy_sol: f64,
+    /// Share of closed tokens first sold within `QUICK_SELL_SECS` of the first buy
+    pub quick_sell_rate: f64,
+    /// Realized PnL of closed tokens
+    pub realized_pnl_sol: f64,
+    /// 0-100, higher is a better copy target
+    pub score: f64,
+}
+
+impl WalletReport {
+    /// Build a report from trades sorted oldest first
+    /// Sells of tokens bought before the history starts are ignored.
+    pub fn from_trades(wallet: Pubkey, trades: &[DetectedTrade]) -> Self {
+        let mut tokens: HashMap<Pubkey, TokenRoundTrip> = HashMap::new();
+        let mut buy_sizes = Vec::new();
+        let mut counted = 0;
+        
+        for trade in trades {
+            let (Some(mint), Some(sol), Some(amount)) = (trade.token_mint, trade.sol_amount, trade.token_amount) else {
+                continue;
+            };
+            match trade.trade_type {
+                TradeType::Buy => {
+                    let token = tokens.entry(mint).or_default();
+                    token.first_buy = token.first_buy.or(trade.block_time);
+                    token.sol_in += sol;
+                    token.tokens_bought += amount;
+                    buy_sizes.push(sol as f64 / 1_000_000_000.0);
+                }
+                TradeType::Sell => {
+                    let Some(token) = tokens.get_mut(&mint) else { continue };
+                    token.first_sell = token.first_sell.or(trade.block_time);
+                    token.last_sell = trade.block_time.or(token.last_sell);
+                    token.sol_out += sol;
+                    token.tokens_sold += amount;
+                    if amount > 0 {
+                        token.best_exit_price = token.best_exit_price.max(sol as f64 / amount as f64);
+                    }
+                }
+                TradeType::Unknown => continue,
+            }
+            counted += 1;
+        }
+        
+        let closed: Vec<&TokenRoundTrip> = tokens.values().filter(|token| token.is_closed()).collect();
+        let wins = closed.iter().filter(|token| token.sol_out > token.sol_in).count();
+        let holds: Vec<f64> = closed.iter()
+            .filter_map(|token| Some((token.last_sell? - token.first_buy?) as f64))
+            .collect();
+        let quick_sells = closed.iter()
+            .filter(|token| match (token.first_buy, token.first_sell) {
+                (Some(buy), Some(sell)) => sell - buy <= QUICK_SELL_SECS,
+                _ => false,
+            })
+            .count();
+        let multiples: Vec<f64> = closed.iter()
+            .map(|token| token.best_exit_price / token.entry_price())
+            .filter(|multiple| multiple.is_finite())
+            .collect();
+        let realized: i64 = closed.iter().map(|token| token.sol_out as i64 - token.sol_in as i64).sum();
+        
+        let mut report = Self {
+            wallet,
+            trades: counted,
+            tokens_traded: tokens.len(),
+            closed_tokens: closed.len(),
+            win_rate: ratio(wins, closed.len()),
+            avg_hold_secs: (!holds.is_empty()).then(|| holds.iter().sum::<f64>() / holds.len() as f64),
+            median_peak_multiple: median(multiples),
+            median_buy_sol: median(buy_sizes).unwrap_or(0.0),
+            quick_sell_rate: ratio(quick_sells, closed.len()),
+            realized_pnl_sol: realized as f64 / 1_000_000_000.0,
+            score: 0.0,
+        };
+        report.score = report.compute_score();
+        report
+    }
+    
+    /// Whether there are enough closed tokens to act on the score
+    pub fn is_conclusive(&self) -> bool {
+        self.closed_tokens >= MIN_CLOSED_TOKENS
+    }
+    
+    /// Score out of 100: win rate (50), median peak multiple up to 3x (30) and holds long
+    /// enough to copy (20), minus up to 50 for quick flips, scaled down below `MIN_CLOSED_TOKENS`
+    fn compute_score(&self) -> f64 {
+        if self.closed_tokens == 0 {
+            return 0.0;
+        }
+        let multiple = self.median_peak_multiple.map_or(0.0, |m| ((m - 1.0) / 2.0).clamp(0.0, 1.0));
+        let hold = self.avg_hold_secs.map_or(0.0, |secs| (secs / 60.0).clamp(0.0, 1.0));
+        let raw = self.win_rate * 50.0 + multiple * 30.0 + hold * 20.0 - self.quick_sell_rate * 50.0;
+        let confidence = (self.closed_tokens as f64 / MIN_CLOSED_TOKENS as f64).min(1.0);
+        (raw * confidence).clamp(0.0, 100.0)
+    }
+}
+
+impl fmt::Display for WalletReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        writeln!(f, "Wallet:               {}", self.wallet)?;
+        writeln!(f, "Trades decoded:       {}", self.trades)?;
+        writeln!(f, "Tokens traded:        {} ({} closed)", self.tokens_traded, self.closed_tokens)?;
+        writeln!(f, "Win rate:             {:.1}%", self.win_rate * 100.0)?;
+        writeln!(f, "Realized PnL:         {:+.4} SOL", self.realized_pnl_sol)?;
+        writeln!(f, "Average hold:         {}", or_dash(self.avg_hold_secs.map(format_duration)))?;
+        writeln!(f, "Median entry-to-peak: {}", or_dash(self.median_peak_multiple.map(|m| format!("{:.2}x", m))))?;
+        writeln!(f, "Typical buy:          {:.4} SOL", self.median_buy_sol)?;
+        writeln!(f, "Sold within {}s:      {:.1}%", QUICK_SELL_SECS, self.quick_sell_rate * 100.0)?;
+        write!(f, "Score:                {:.0}/100", self.score)?;
+        if !self.is_conclusive() {
+            write!(f, " (only {} closed tokens, needs {})", self.closed_tokens, MIN_CLOSED_TOKENS)?;
+        }
+        Ok(())
+    }
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 { 0.0 } else { count as f64 / total as f64 }
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) { (values[mid - 1] + values[mid]) / 2.0 } else { values[mid] })
+}
+
+fn format_duration(secs: f64) -> String {
+    match secs {
+        s if s < 60.0 => format!("{:.0}s", s),
+        s if s < 3600.0 => format!("{:.1}m", s / 60.0),
+        s => format!("{:.1}h", s / 3600.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::DexProgram;
+    
+    fn trade(mint: Pubkey, trade_type: TradeType, sol: u64, tokens: u64, time: i64) -> DetectedTrade {
+        DetectedTrade {
+            dex: DexProgram::PumpFun,
+            trade_type,
+            token_mint: Some(mint),
+            sol_amount: Some(sol),
+            token_amount: Some(tokens),
+            signature: String::new(),
+            accounts: Vec::new(),
+            slot: time as u64,
+            block_time: Some(time),
+        }
+    }
+    
+    #[test]
+    fn test_report_statistics() {
+        let (winner, loser, flipped, open) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let trades = vec![
+            // Bought 1 SOL, sold half at 3x and half at 1x: 2 SOL back
+            trade(winner, TradeType::Buy, 1_000_000_000, 1_000, 0),
+            trade(winner, TradeType::Sell, 1_500_000_000, 500, 100),
+            trade(winner, TradeType::Sell, 500_000_000, 500, 300),
+            // Sold within seconds at a loss
+            trade(loser, TradeType::Buy, 3_000_000_000, 1_000, 400),
+            trade(loser, TradeType::Sell, 1_500_000_000, 1_000, 405),
+            // Sell of a token bought before the history is ignored
+            trade(flipped, TradeType::Sell, 1_000_000_000, 1_000, 500),
+            trade(open, TradeType::Buy, 2_000_000_000, 1_000, 600),
+        ];
+        
+        let report = WalletReport::from_trades(Pubkey::new_unique(), &trades);
+        assert_eq!(report.trades, 6);
+        assert_eq!((report.tokens_traded, report.closed_tokens), (3, 2));
+        assert_eq!(report.win_rate, 0.5);
+        assert_eq!(report.avg_hold_secs, Some((300.0 + 5.0) / 2.0));
+        assert_eq!(report.median_peak_multiple, Some((3.0 + 0.5) / 2.0));
+        assert_eq!(report.median_buy_sol, 2.0);
+        assert_eq!(report.quick_sell_rate, 0.5);
+        assert_eq!(report.realized_pnl_sol, -0.5);
+        assert!(!report.is_conclusive());
+    }
+    
+    #[test]
+    fn test_score_rewards_copyable_winners() {
+        let wallet = Pubkey::new_unique();
+        let round_trips = |sell_sol: u64, hold: i64| -> Vec<DetectedTrade> {
+            (0..MIN_CLOSED_TOKENS as i64)
+                .flat_map(|i| {
+                    let mint = Pubkey::new_unique();
+                    [
+                        trade(mint, TradeType::Buy, 1_000_000_000, 1_000, i * 1_000),
+                        trade(mint, TradeType::Sell, sell_sol, 1_000, i * 1_000 + hold),
+                    ]
+                })
+                .collect()
+        };
+        
+        let good = WalletReport::from_trades(wallet, &round_trips(3_000_000_000, 120));
+        assert!(good.is_conclusive());
+        assert_eq!(good.score, 100.0);
+        
+        let bundler = WalletReport::from_trades(wallet, &round_trips(1_100_000_000, 2));
+        assert!(bundler.score < 20.0, "score {}", bundler.score);
+        
+        assert_eq!(WalletReport::from_trades(wallet, &[]).score, 0.0);
+    }
+}
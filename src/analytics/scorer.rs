@@ -0,0 +1,125 @@
+//! Periodically score target wallets and bench the ones that stop performing
+
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::state::BotControl;
+use super::{fetch_wallet_trades, WalletReport};
+
+/// Target scorer settings
+#[derive(Debug, Clone)]
+pub struct ScorerConfig {
+    /// Targets scoring below this are removed until they score above it again
+    pub min_score: f64,
+    /// How often targets are rescored
+    pub interval: Duration,
+    /// Recent transactions analysed per target
+    pub max_signatures: usize,
+}
+
+/// What to do with a target after scoring it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreAction {
+    Keep,
+    Bench,
+    Restore,
+}
+
+/// Decide whether a scored target should be benched or restored
+/// Inconclusive reports (too few closed tokens) never change anything.
+pub fn score_action(report: &WalletReport, min_score: f64, benched: bool) -> ScoreAction {
+    if !report.is_conclusive() {
+        return ScoreAction::Keep;
+    }
+    match (benched, report.score >= min_score) {
+        (false, false) => ScoreAction::Bench,
+        (true, true) => ScoreAction::Restore,
+        _ => ScoreAction::Keep,
+    }
+}
+
+/// Rescore every target on an interval, removing low scorers from the copy list and
+/// adding them back once they recover. Targets removed by the operator are forgotten.
+pub fn spawn_target_scorer(control: Arc<BotControl>, rpc_url: String, config: ScorerConfig) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        info!(
+            "📊 Target scorer started (min score {:.0}, every {:?})",
+            config.min_score, config.interval
+        );
+        let mut benched: HashSet<Pubkey> = HashSet::new();
+        
+        let mut ticker = tokio::time::interval(config.interval);
+        loop {
+            ticker.tick().await;
+            
+            let mut candidates = control.target_wallets();
+            candidates.extend(benched.iter().copied());
+            for wallet in candidates {
+                let trades = match fetch_wallet_trades(&rpc_url, &wallet, config.max_signatures).await {
+                    Ok(trades) => trades,
+                    Err(e) => {
+                        warn!("⚠️ Could not score target {}: {:#}", wallet, e);
+                        continue;
+                    }
+                };
+                let report = WalletReport::from_trades(wallet, &trades);
+                info!(
+                    "📊 Target {} scored {:.0}/100 (win rate {:.0}%, {} closed tokens)",
+                    wallet, report.score, report.win_rate * 100.0, report.closed_tokens
+                );
+                
+                match score_action(&report, config.min_score, benched.contains(&wallet)) {
+                    ScoreAction::Bench => {
+                        if control.remove_target_wallet(&wallet) {
+                            warn!("📉 Target {} benched: score {:.0} below {:.0}", wallet, report.score, config.min_score);
+                            benched.insert(wallet);
+                        }
+                    }
+                    ScoreAction::Restore => {
+                        benched.remove(&wallet);
+                        control.add_target_wallet(wallet);
+                        info!("📈 Target {} restored: score {:.0}", wallet, report.score);
+                    }
+                    ScoreAction::Keep => {}
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::MIN_CLOSED_TOKENS;
+    
+    fn report(score: f64, closed_tokens: usize) -> WalletReport {
+        WalletReport {
+            wallet: Pubkey::new_unique(),
+            trades: closed_tokens * 2,
+            tokens_traded: closed_tokens,
+            closed_tokens,
+            win_rate: 0.5,
+            avg_hold_secs: None,
+            median_peak_multiple: None,
+            median_buy_sol: 0.1,
+            quick_sell_rate: 0.0,
+            realized_pnl_sol: 0.0,
+            score,
+        }
+    }
+    
+    #[test]
+    fn test_score_action() {
+        assert_eq!(score_action(&report(20.0, MIN_CLOSED_TOKENS), 40.0, false), ScoreAction::Bench);
+        assert_eq!(score_action(&report(60.0, MIN_CLOSED_TOKENS), 40.0, false), ScoreAction::Keep);
+        assert_eq!(score_action(&report(60.0, MIN_CLOSED_TOKENS), 40.0, true), ScoreAction::Restore);
+        assert_eq!(score_action(&report(20.0, MIN_CLOSED_TOKENS), 40.0, true), ScoreAction::Keep);
+        
+        // Too little history to judge
+        assert_eq!(score_action(&report(0.0, MIN_CLOSED_TOKENS - 1), 40.0, false), ScoreAction::Keep);
+    }
+}
@@ -0,0 +1,260 @@
+//! Control API request handlers
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use tracing::warn;
+
+use crate::config::TakeProfitTier;
+use crate::state::{Position, StateStats, TradeRecord, TradeRecordType};
+use crate::token::TokenInfo;
+use super::server::ApiState;
+
+/// Error returned to API clients as `{"error": "..."}`
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+    
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+    
+    fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(serde_json::json!({ "error": self.message }))).into_response()
+    }
+}
+
+/// Parse a pubkey path or body parameter
+fn parse_pubkey(value: &str, what: &str) -> Result<Pubkey, ApiError> {
+    Pubkey::from_str(value).map_err(|_| ApiError::bad_request(format!("invalid {}: {}", what, value)))
+}
+
+/// Open position as returned by the API
+#[derive(Debug, Serialize)]
+pub struct PositionView {
+    pub token_mint: String,
+    pub amount: u64,
+    pub invested_sol: u64,
+    pub current_value_sol: u64,
+    pub profit_percent: f64,
+    pub opened_at: DateTime<Utc>,
+    pub target_buy_signature: String,
+    pub our_buy_signature: String,
+    /// Metadata and holder distribution, once fetched
+    pub token_info: Option<TokenInfo>,
+}
+
+impl From<Position> for PositionView {
+    fn from(p: Position) -> Self {
+        Self {
+            token_mint: p.token_mint.to_string(),
+            amount: p.amount,
+            invested_sol: p.invested_sol,
+            current_value_sol: p.current_value_sol,
+            profit_percent: p.profit_percent(),
+            opened_at: p.opened_at,
+            target_buy_signature: p.target_buy_signature,
+            our_buy_signature: p.our_buy_signature,
+            token_info: p.token_info,
+        }
+    }
+}
+
+/// Trade history entry as returned by the API
+#[derive(Debug, Serialize)]
+pub struct TradeView {
+    pub id: String,
+    pub token_mint: String,
+    pub trade_type: TradeRecordType,
+    pub amount: u64,
+    pub sol_amount: u64,
+    pub signature: String,
+    pub timestamp: DateTime<Utc>,
+    pub pnl: Option<i64>,
+}
+
+impl From<TradeRecord> for TradeView {
+    fn from(r: TradeRecord) -> Self {
+        Self {
+            id: r.id.to_string(),
+            token_mint: r.token_mint.to_string(),
+            trade_type: r.trade_type,
+            amount: r.amount,
+            sol_amount: r.sol_amount,
+            signature: r.signature,
+            timestamp: r.timestamp,
+            pnl: r.pnl,
+        }
+    }
+}
+
+/// Statistics summary plus derived values
+#[derive(Debug, Serialize)]
+pub struct StatsView {
+    #[serde(flatten)]
+    pub stats: StateStats,
+    pub win_rate: f64,
+    pub total_pnl_sol: f64,
+    pub paused: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PauseView {
+    pub paused: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SellView {
+    pub token_mint: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TargetRequest {
+    pub wallet: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TakeProfitView {
+    pub enabled: bool,
+    pub tiers: Vec<TakeProfitTier>,
+}
+
+/// Partial take profit update; omitted fields are left unchanged
+#[derive(Debug, Deserialize)]
+pub struct TakeProfitUpdate {
+    pub enabled: Option<bool>,
+    pub tiers: Option<Vec<TakeProfitTier>>,
+}
+
+/// GET /positions
+pub async fn list_positions(State(api): State<ApiState>) -> Json<Vec<PositionView>> {
+    Json(api.state.get_all_positions().into_iter().map(PositionView::from).collect())
+}
+
+/// GET /trades
+pub async fn list_trades(State(api): State<ApiState>) -> Json<Vec<TradeView>> {
+    Json(api.state.get_trade_history().into_iter().map(TradeView::from).collect())
+}
+
+/// GET /stats
+pub async fn get_stats(State(api): State<ApiState>) -> Json<StatsView> {
+    let stats = api.state.get_stats();
+    Json(StatsView {
+        win_rate: stats.win_rate(),
+        total_pnl_sol: stats.total_pnl_sol(),
+        paused: api.control.is_paused(),
+        stats,
+    })
+}
+
+/// POST /pause
+pub async fn pause(State(api): State<ApiState>) -> Json<PauseView> {
+    api.control.set_paused(true);
+    Json(PauseView { paused: true })
+}
+
+/// POST /resume
+pub async fn resume(State(api): State<ApiState>) -> Json<PauseView> {
+    api.control.set_paused(false);
+    Json(PauseView { paused: false })
+}
+
+/// POST /positions/:mint/sell
+pub async fn force_sell(
+    State(api): State<ApiState>,
+    Path(mint): Path<String>,
+) -> Result<Json<SellView>, ApiError> {
+    let token_mint = parse_pubkey(&mint, "token mint")?;
+    if api.state.get_position(&token_mint).is_none() {
+        return Err(ApiError::not_found(format!("no open position for {}", token_mint)));
+    }
+    
+    let signature = api.seller.force_sell(&token_mint).await.map_err(|e| {
+        warn!("⚠️ Manual sell of {} failed: {:?}", token_mint, e);
+        ApiError::new(StatusCode::BAD_GATEWAY, format!("sell failed: {}", e))
+    })?;
+    
+    Ok(Json(SellView {
+        token_mint: token_mint.to_string(),
+        signature,
+    }))
+}
+
+/// GET /targets
+pub async fn list_targets(State(api): State<ApiState>) -> Json<Vec<String>> {
+    Json(api.control.target_wallets().iter().map(|w| w.to_string()).collect())
+}
+
+/// POST /targets
+pub async fn add_target(
+    State(api): State<ApiState>,
+    Json(request): Json<TargetRequest>,
+) -> Result<StatusCode, ApiError> {
+    let wallet = parse_pubkey(&request.wallet, "wallet")?;
+    if api.control.add_target_wallet(wallet) {
+        Ok(StatusCode::CREATED)
+    } else {
+        Err(ApiError::new(StatusCode::CONFLICT, format!("{} is already a target", wallet)))
+    }
+}
+
+/// DELETE /targets/:wallet
+pub async fn remove_target(
+    State(api): State<ApiState>,
+    Path(wallet): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let wallet = parse_pubkey(&wallet, "wallet")?;
+    if api.control.remove_target_wallet(&wallet) {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::not_found(format!("{} is not a target", wallet)))
+    }
+}
+
+/// GET /take-profit
+pub async fn get_take_profit(State(api): State<ApiState>) -> Json<TakeProfitView> {
+    Json(TakeProfitView {
+        enabled: api.control.take_profit_enabled(),
+        tiers: api.control.take_profit_tiers(),
+    })
+}
+
+/// PUT /take-profit
+pub async fn update_take_profit(
+    State(api): State<ApiState>,
+    Json(update): Json<TakeProfitUpdate>,
+) -> Result<Json<TakeProfitView>, ApiError> {
+    // Validate tiers before touching the enabled flag so a bad request changes nothing
+    if let Some(tiers) = update.tiers {
+        api.control.set_take_profit_tiers(tiers).map_err(ApiError::bad_request)?;
+    }
+    if let Some(enabled) = update.enabled {
+        api.control.set_take_profit_enabled(enabled);
+    }
+    
+    Ok(get_take_profit(State(api)).await)
+}
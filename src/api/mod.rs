@@ -0,0 +1,6 @@
+//! Local HTTP control API for the running bot
+
+pub mod server;
+pub mod handlers;
+
+pub use server::*;
@@ -0,0 +1,241 @@
+//! Control API server and authentication
+
+use anyhow::{Result, Context};
+use async_trait::async_trait;
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Router,
+};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tracing::info;
+
+use crate::state::{BotControl, StateManager};
+use super::handlers;
+
+/// Something that can sell a position on operator request
+#[async_trait]
+pub trait PositionSeller: Send + Sync {
+    /// Sell our whole balance of a token and return the transaction signature
+    async fn force_sell(&self, token_mint: &Pubkey) -> Result<String>;
+}
+
+/// Shared state for API handlers
+#[derive(Clone)]
+pub struct ApiState {
+    pub state: Arc<StateManager>,
+    pub control: Arc<BotControl>,
+    pub seller: Arc<dyn PositionSeller>,
+    token: Arc<String>,
+}
+
+impl ApiState {
+    /// Create API state; `token` is required as a bearer token on every request
+    pub fn new(
+        state: Arc<StateManager>,
+        control: Arc<BotControl>,
+        seller: Arc<dyn PositionSeller>,
+        token: String,
+    ) -> Self {
+        Self {
+            state,
+            control,
+            seller,
+            token: Arc::new(token),
+        }
+    }
+}
+
+/// Build the API router
+pub fn router(api: ApiState) -> Router {
+    Router::new()
+        .route("/positions", get(handlers::list_positions))
+        .route("/positions/:mint/sell", post(handlers::force_sell))
+        .route("/trades", get(handlers::list_trades))
+        .route("/stats", get(handlers::get_stats))
+        .route("/pause", post(handlers::pause))
+        .route("/resume", post(handlers::resume))
+        .route("/targets", get(handlers::list_targets).post(handlers::add_target))
+        .route("/targets/:wallet", delete(handlers::remove_target))
+        .route("/take-profit", get(handlers::get_take_profit).put(handlers::update_take_profit))
+        .layer(middleware::from_fn_with_state(api.clone(), require_token))
+        .with_state(api)
+}
+
+/// Bind the control API and serve it until the task is aborted
+pub async fn serve(bind: &str, api: ApiState) -> Result<()> {
+    let listener = TcpListener::bind(bind).await
+        .with_context(|| format!("Failed to bind control API on {}", bind))?;
+    info!("🛰️ Control API listening on http://{}", listener.local_addr()?);
+    serve_listener(listener, api).await
+}
+
+/// Serve the control API on an already bound listener
+pub async fn serve_listener(listener: TcpListener, api: ApiState) -> Result<()> {
+    axum::serve(listener, router(api)).await
+        .context("Control API server error")
+}
+
+/// Reject requests without the configured bearer token
+async fn require_token(State(api): State<ApiState>, request: Request, next: Next) -> Response {
+    let authorized = request.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|token| constant_time_eq(token.as_bytes(), api.token.as_bytes()))
+        .unwrap_or(false);
+    
+    if !authorized {
+        return handlers::ApiError::new(StatusCode::UNAUTHORIZED, "missing or invalid bearer token").into_response();
+    }
+    
+    next.run(request).await
+}
+
+/// Compare secrets without leaking the mismatch position through timing
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TakeProfitTier;
+    use crate::state::{Position, TradeRecordType};
+    use parking_lot::Mutex;
+    
+    const TOKEN: &str = "test-token";
+    
+    /// Records sells in the state manager like the live client does
+    struct MockSeller {
+        state: Arc<StateManager>,
+        sold: Mutex<Vec<Pubkey>>,
+    }
+    
+    #[async_trait]
+    impl PositionSeller for MockSeller {
+        async fn force_sell(&self, token_mint: &Pubkey) -> Result<String> {
+            self.sold.lock().push(*token_mint);
+            self.state.close_position(token_mint, 0, TradeRecordType::SellManual, "manual_sig".to_string());
+            Ok("manual_sig".to_string())
+        }
+    }
+    
+    async fn spawn_api() -> (String, Arc<StateManager>, Arc<BotControl>, Arc<MockSeller>) {
+        let state = Arc::new(StateManager::new());
+        let control = Arc::new(BotControl::new(
+            vec![Pubkey::new_unique()],
+            0.1,
+            10_000,
+            true,
+            vec![TakeProfitTier { multiplier: 2.0, sell_percent: 100 }],
+        ));
+        let seller = Arc::new(MockSeller { state: state.clone(), sold: Mutex::new(Vec::new()) });
+        let api = ApiState::new(state.clone(), control.clone(), seller.clone(), TOKEN.to_string());
+        
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve_listener(listener, api));
+        
+        (base, state, control, seller)
+    }
+    
+    #[tokio::test]
+    async fn test_requires_bearer_token() {
+        let (base, _, _, _) = spawn_api().await;
+        let client = reqwest::Client::new();
+        
+        let missing = client.get(format!("{}/stats", base)).send().await.unwrap();
+        assert_eq!(missing.status(), reqwest::StatusCode::UNAUTHORIZED);
+        
+        let wrong = client.get(format!("{}/stats", base)).bearer_auth("nope").send().await.unwrap();
+        assert_eq!(wrong.status(), reqwest::StatusCode::UNAUTHORIZED);
+        
+        let ok = client.get(format!("{}/stats", base)).bearer_auth(TOKEN).send().await.unwrap();
+        assert_eq!(ok.status(), reqwest::StatusCode::OK);
+    }
+    
+    #[tokio::test]
+    async fn test_pause_and_resume() {
+        let (base, _, control, _) = spawn_api().await;
+        let client = reqwest::Client::new();
+        
+        let body: serde_json::Value = client.post(format!("{}/pause", base)).bearer_auth(TOKEN)
+            .send().await.unwrap().json().await.unwrap();
+        assert_eq!(body["paused"], true);
+        assert!(control.is_paused());
+        
+        client.post(format!("{}/resume", base)).bearer_auth(TOKEN).send().await.unwrap();
+        assert!(!control.is_paused());
+    }
+    
+    #[tokio::test]
+    async fn test_force_sell() {
+        let (base, state, _, seller) = spawn_api().await;
+        let client = reqwest::Client::new();
+        let mint = Pubkey::new_unique();
+        
+        let missing = client.post(format!("{}/positions/{}/sell", base, mint)).bearer_auth(TOKEN)
+            .send().await.unwrap();
+        assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+        
+        state.open_position(Position::new(mint, 1_000, 100_000_000, "t".to_string(), "o".to_string()));
+        
+        let positions: serde_json::Value = client.get(format!("{}/positions", base)).bearer_auth(TOKEN)
+            .send().await.unwrap().json().await.unwrap();
+        assert_eq!(positions[0]["token_mint"], mint.to_string());
+        
+        let sold: serde_json::Value = client.post(format!("{}/positions/{}/sell", base, mint)).bearer_auth(TOKEN)
+            .send().await.unwrap().json().await.unwrap();
+        assert_eq!(sold["signature"], "manual_sig");
+        assert_eq!(*seller.sold.lock(), vec![mint]);
+        
+        let trades: serde_json::Value = client.get(format!("{}/trades", base)).bearer_auth(TOKEN)
+            .send().await.unwrap().json().await.unwrap();
+        assert_eq!(trades[1]["trade_type"], "SellManual");
+    }
+    
+    #[tokio::test]
+    async fn test_targets_and_take_profit() {
+        let (base, _, control, _) = spawn_api().await;
+        let client = reqwest::Client::new();
+        let wallet = Pubkey::new_unique();
+        
+        let added = client.post(format!("{}/targets", base)).bearer_auth(TOKEN)
+            .json(&serde_json::json!({ "wallet": wallet.to_string() }))
+            .send().await.unwrap();
+        assert_eq!(added.status(), reqwest::StatusCode::CREATED);
+        assert!(control.is_target(&wallet));
+        
+        let invalid = client.post(format!("{}/targets", base)).bearer_auth(TOKEN)
+            .json(&serde_json::json!({ "wallet": "not-a-wallet" }))
+            .send().await.unwrap();
+        assert_eq!(invalid.status(), reqwest::StatusCode::BAD_REQUEST);
+        
+        let removed = client.delete(format!("{}/targets/{}", base, wallet)).bearer_auth(TOKEN)
+            .send().await.unwrap();
+        assert_eq!(removed.status(), reqwest::StatusCode::NO_CONTENT);
+        assert!(!control.is_target(&wallet));
+        
+        let updated = client.put(format!("{}/take-profit", base)).bearer_auth(TOKEN)
+            .json(&serde_json::json!({ "enabled": false, "tiers": [{ "multiplier": 3.0, "sell_percent": 50 }] }))
+            .send().await.unwrap();
+        assert_eq!(updated.status(), reqwest::StatusCode::OK);
+        assert!(!control.take_profit_enabled());
+        assert_eq!(control.take_profit_tiers()[0].multiplier, 3.0);
+        
+        let rejected = client.put(format!("{}/take-profit", base)).bearer_auth(TOKEN)
+            .json(&serde_json::json!({ "tiers": [{ "multiplier": 3.0, "sell_percent": 150 }] }))
+            .send().await.unwrap();
+        assert_eq!(rejected.status(), reqwest::StatusCode::BAD_REQUEST);
+        assert_eq!(control.take_profit_tiers()[0].sell_percent, 50);
+    }
+}
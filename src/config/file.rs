@@ -0,0 +1,723 @@
+//! Config file schema, environment overrides and validation
+
+use anyhow::{Result, Context};
+use config::builder::DefaultState;
+use config::{ConfigBuilder, Environment, File};
+use serde::{Deserialize, Deserializer, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::notify::parse_event_kinds;
+use crate::risk::{BreakerConfig, EntryGate, PriceMoveAction, RiskLimits};
+use crate::safety::SafetyConfig;
+use crate::signer::{parse_keypair, AssignmentStrategy, SignerSource};
+use crate::state::validate_take_profit_tiers;
+use crate::swap::CopyQuoteMode;
+use super::settings::{Config, TakeProfitTier, parse_pubkey_list};
+
+/// Settings as written in the config file (TOML or YAML)
+/// Keys are the environment variable names in lowercase; a set environment variable
+/// overrides the file. Unknown keys are rejected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    // Wallet (exactly one signer backend)
+    pub private_key: Option<String>,
+    pub keypair_path: Option<String>,
+    pub keystore_path: Option<String>,
+    pub keystore_passphrase_file: Option<String>,
+    pub remote_signer_url: Option<String>,
+    pub remote_signer_token: Option<String>,
+    pub target_wallet: Option<String>,
+    
+    // Wallet pool
+    #[serde(deserialize_with = "string_list")]
+    pub wallet_pool: Vec<String>,
+    pub wallet_pool_passphrase_file: Option<String>,
+    pub wallet_assignment: String,
+    pub treasury_wallet: Option<String>,
+    pub wallet_sweep_enabled: bool,
+    pub wallet_sweep_interval_secs: u64,
+    pub wallet_sweep_reserve_sol: f64,
+    
+    // Helius
+    pub helius_grpc_url: String,
+    pub helius_api_key: Option<String>,
+    pub solana_rpc_url: String,
+    
+    // Jito
+    pub tip_amount_normal: u64,
+    pub tip_amount_emergency: u64,
+    pub tip_amount_max: u64,
+    #[serde(deserialize_with = "string_list")]
+    pub address_lookup_tables: Vec<String>,
+    
+    // Trading
+    pub buy_amount_sol: f64,
+    pub max_buy_amount_sol: f64,
+    pub slippage_bps: u16,
+    pub copy_quote_mode: String,
+    
+    // Rent reclamation for empty token accounts
+    pub auto_close_ata: bool,
+    pub ata_reclaim_interval_secs: u64,
+    pub ata_burn_dust_below: f64,
+    
+    // Risk limits (0 = disabled)
+    pub max_open_positions: usize,
+    pub max_total_exposure_sol: f64,
+    pub max_token_exposure_sol: f64,
+    pub daily_loss_limit_sol: f64,
+    pub max_buys_per_minute: u32,
+    pub min_sol_reserve: f64,
+    
+    // Circuit breakers (threshold 0 = disabled)
+    pub circuit_breaker_threshold: u32,
+    pub circuit_breaker_window_secs: u64,
+    pub circuit_breaker_cooldown_secs: u64,
+    
+    // Entry gating (0 = disabled)
+    pub entry_max_price_move_pct: f64,
+    pub entry_max_slot_lag: u64,
+    pub entry_price_move_action: String,
+    
+    // Rug and honeypot protection (0 = limit disabled)
+    pub safety_entry_checks: bool,
+    pub safety_probe_sol: f64,
+    pub safety_max_tax_pct: f64,
+    pub safety_max_transfer_fee_bps: u16,
+    pub safety_hold_watch: bool,
+    pub safety_watch_interval_secs: u64,
+    pub safety_max_liquidity_drop_pct: f64,
+    pub safety_max_creator_sell_pct: f64,
+    pub creator_exit_enabled: bool,
+    pub creator_exit_min_pct: f64,
+    
+    // Target scoring (min score 0 = disabled)
+    pub target_min_score: f64,
+    pub target_score_interval_secs: u64,
+    pub target_score_signatures: usize,
+    
+    // Take profit
+    pub take_profit_enabled: bool,
+    #[serde(deserialize_with = "take_profit_tiers")]
+    pub take_profit_tiers: Vec<TakeProfitTier>,
+    
+    // Durable nonces
+    pub nonce_pool_enabled: bool,
+    pub nonce_pool_spare: usize,
+    pub nonce_pool_max: usize,
+    
+    // Control API
+    pub api_enabled: bool,
+    pub api_bind: String,
+    pub api_token: Option<String>,
+    
+    // Metrics
+    pub metrics_enabled: bool,
+    pub metrics_bind: String,
+    
+    // Notifications
+    pub telegram_bot_token: Option<String>,
+    pub telegram_chat_id: Option<String>,
+    pub telegram_api_base: Option<String>,
+    pub discord_webhook_url: Option<String>,
+    pub notify_webhook_url: Option<String>,
+    #[serde(deserialize_with = "string_list")]
+    pub notify_events: Vec<String>,
+    pub notify_rate_limit_per_min: u32,
+    
+    // Performance
+    pub reconnect_delay_ms: u64,
+    pub max_reconnect_attempts: u32,
+    pub compute_unit_limit: u32,
+    pub priority_fee_micro_lamports: u64,
+}
+
+impl Default for ConfigFile {
+    fn default() -> Self {
+        Self {
+            private_key: None,
+            keypair_path: None,
+            keystore_path: None,
+            keystore_passphrase_file: None,
+            remote_signer_url: None,
+            remote_signer_token: None,
+            target_wallet: None,
+            
+            wallet_pool: Vec::new(),
+            wallet_pool_passphrase_file: None,
+            wallet_assignment: "round_robin".to_string(),
+            treasury_wallet: None,
+            wallet_sweep_enabled: false,
+            wallet_sweep_interval_secs: 300,
+            wallet_sweep_reserve_sol: 0.05,
+            
+            helius_grpc_url: "https://atlas-mainnet.helius-rpc.com".to_string(),
+            helius_api_key: None,
+            solana_rpc_url: "https://api.mainnet-beta.solana.com".to_string(),
+            
+            tip_amount_normal: 10_000,
+            tip_amount_emergency: 100_000,
+            tip_amount_max: 500_000,
+            address_lookup_tables: Vec::new(),
+            
+            buy_amount_sol: 0.1,
+            max_buy_amount_sol: 1.0,
+            slippage_bps: 500,
+            copy_quote_mode: "sol".to_string(),
+            
+            auto_close_ata: true,
+            ata_reclaim_interval_secs: 600,
+            ata_burn_dust_below: 0.0,
+            
+            max_open_positions: 0,
+            max_total_exposure_sol: 0.0,
+            max_token_exposure_sol: 0.0,
+            daily_loss_limit_sol: 0.0,
+            max_buys_per_minute: 0,
+            min_sol_reserve: 0.0,
+            
+            circuit_breaker_threshold: 5,
+            circuit_breaker_window_secs: 60,
+            circuit_breaker_cooldown_secs: 30,
+            
+            entry_max_price_move_pct: 0.0,
+            entry_max_slot_lag: 0,
+            entry_price_move_action: "skip".to_string(),
+            
+            safety_entry_checks: false,
+            safety_probe_sol: 0.001,
+            safety_max_tax_pct: 20.0,
+            safety_max_transfer_fee_bps: 0,
+            safety_hold_watch: false,
+            safety_watch_interval_secs: 10,
+            safety_max_liquidity_drop_pct: 50.0,
+            safety_max_creator_sell_pct: 5.0,
+            creator_exit_enabled: false,
+            creator_exit_min_pct: 1.0,
+            
+            target_min_score: 0.0,
+            target_score_interval_secs: 3600,
+            target_score_signatures: 500,
+            
+            take_profit_enabled: true,
+            take_profit_tiers: vec![
+                TakeProfitTier { multiplier: 2.0, sell_percent: 20 },
+                TakeProfitTier { multiplier: 3.0, sell_percent: 30 },
+                TakeProfitTier { multiplier: 5.0, sell_percent: 50 },
+            ],
+            
+            nonce_pool_enabled: false,
+            nonce_pool_spare: 2,
+            nonce_pool_max: 10,
+            
+            api_enabled: false,
+            api_bind: "127.0.0.1:8787".to_string(),
+            api_token: None,
+            
+            metrics_enabled: false,
+            metrics_bind: "127.0.0.1:9187".to_string(),
+            
+            telegram_bot_token: None,
+            telegram_chat_id: None,
+            telegram_api_base: None,
+            discord_webhook_url: None,
+            notify_webhook_url: None,
+            notify_events: Vec::new(),
+            notify_rate_limit_per_min: 20,
+            
+            reconnect_delay_ms: 1000,
+            max_reconnect_attempts: 10,
+            compute_unit_limit: 400_000,
+            priority_fee_micro_lamports: 10_000,
+        }
+    }
+}
+
+impl ConfigFile {
+    /// Read `path` (format from the extension) and apply overrides from `env`
+    pub fn load(path: Option<&Path>, env: impl IntoIterator<Item = (String, String)>) -> Result<Self> {
+        let mut builder = config::Config::builder();
+        if let Some(path) = path {
+            builder = builder.add_source(File::from(path).required(true));
+        }
+        
+        Self::build(builder, env).with_context(|| match path {
+            Some(path) => format!("Invalid configuration in {} or the environment", path.display()),
+            None => "Invalid configuration in the environment".to_string(),
+        })
+    }
+    
+    /// Layer environment overrides on `builder` and deserialize
+    /// Only variables named after a known key are considered; blank values count as unset
+    fn build(
+        builder: ConfigBuilder<DefaultState>,
+        env: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self> {
+        let keys = Self::keys();
+        let overrides: config::Map<String, String> = env.into_iter()
+            .filter(|(key, value)| !value.trim().is_empty() && keys.contains(&key.to_lowercase()))
+            .collect();
+        
+        let settings = builder
+            .add_source(Environment::default().source(Some(overrides)))
+            .build()?;
+        Ok(settings.try_deserialize()?)
+    }
+    
+    /// All recognised keys
+    fn keys() -> HashSet<String> {
+        match serde_json::to_value(Self::default()) {
+            Ok(serde_json::Value::Object(map)) => map.into_iter().map(|(key, _)| key).collect(),
+            _ => HashSet::new(),
+        }
+    }
+    
+    /// Validate every setting and build the runtime configuration
+    pub fn into_config(self) -> Result<Config> {
+        let signer = signer_source(
+            non_empty(self.private_key),
+            non_empty(self.keypair_path),
+            non_empty(self.keystore_path),
+            non_empty(self.keystore_passphrase_file),
+            non_empty(self.remote_signer_url),
+            non_empty(self.remote_signer_token),
+        )?;
+        let target_wallet = parse_pubkey(&required(self.target_wallet, "target_wallet")?, "target_wallet")?;
+        let helius_api_key = required(self.helius_api_key, "helius_api_key")?;
+        
+        // Wallet pool
+        let wallet_pool: Vec<PathBuf> = self.wallet_pool.iter().map(PathBuf::from).collect();
+        let wallet_pool_passphrase_file = non_empty(self.wallet_pool_passphrase_file).map(PathBuf::from);
+        if wallet_pool.is_empty() && (wallet_pool_passphrase_file.is_some() || self.wallet_sweep_enabled) {
+            anyhow::bail!("wallet_pool_passphrase_file and wallet_sweep_enabled require wallet_pool");
+        }
+        let wallet_assignment = AssignmentStrategy::from_str(&self.wallet_assignment)
+            .context("Invalid wallet_assignment")?;
+        let treasury_wallet = non_empty(self.treasury_wallet)
+            .map(|wallet| parse_pubkey(&wallet, "treasury_wallet"))
+            .transpose()?;
+        if self.wallet_sweep_interval_secs == 0 {
+            anyhow::bail!("wallet_sweep_interval_secs must be at least 1");
+        }
+        if !self.wallet_sweep_reserve_sol.is_finite() || self.wallet_sweep_reserve_sol < 0.0 {
+            anyhow::bail!("wallet_sweep_reserve_sol must not be negative");
+        }
+        
+        // Trading
+        if !self.buy_amount_sol.is_finite() || self.buy_amount_sol <= 0.0 {
+            anyhow::bail!("buy_amount_sol must be greater than 0");
+        }
+        if self.max_buy_amount_sol < self.buy_amount_sol {
+            anyhow::bail!(
+                "max_buy_amount_sol ({}) must be at least buy_amount_sol ({})",
+                self.max_buy_amount_sol, self.buy_amount_sol
+            );
+        }
+        if self.slippage_bps > 10_000 {
+            anyhow::bail!("slippage_bps must be at most 10000");
+        }
+        let copy_quote_mode = CopyQuoteMode::from_str(&self.copy_quote_mode)
+            .context("Invalid copy_quote_mode")?;
+        if self.ata_reclaim_interval_secs == 0 {
+            anyhow::bail!("ata_reclaim_interval_secs must be at least 1");
+        }
+        if !self.ata_burn_dust_below.is_finite() || self.ata_burn_dust_below < 0.0 {
+            anyhow::bail!("ata_burn_dust_below must not be negative");
+        }
+        
+        // Risk limits
+        for (key, value) in [
+            ("max_total_exposure_sol", self.max_total_exposure_sol),
+            ("max_token_exposure_sol", self.max_token_exposure_sol),
+            ("daily_loss_limit_sol", self.daily_loss_limit_sol),
+            ("min_sol_reserve", self.min_sol_reserve),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                anyhow::bail!("{} must not be negative", key);
+            }
+        }
+        
+        if self.circuit_breaker_threshold > 0
+            && (self.circuit_breaker_window_secs == 0 || self.circuit_breaker_cooldown_secs == 0)
+        {
+            anyhow::bail!("circuit_breaker_window_secs and circuit_breaker_cooldown_secs must be at least 1");
+        }
+        
+        if !self.entry_max_price_move_pct.is_finite() || self.entry_max_price_move_pct < 0.0 {
+            anyhow::bail!("entry_max_price_move_pct must not be negative");
+        }
+        let price_move_action = PriceMoveAction::from_str(&self.entry_price_move_action)
+            .context("Invalid entry_price_move_action")?;
+        
+        if !self.safety_probe_sol.is_finite() || self.safety_probe_sol <= 0.0 {
+            anyhow::bail!("safety_probe_sol must be positive");
+        }
+        for (key, value) in [
+            ("safety_max_tax_pct", self.safety_max_tax_pct),
+            ("safety_max_liquidity_drop_pct", self.safety_max_liquidity_drop_pct),
+            ("safety_max_creator_sell_pct", self.safety_max_creator_sell_pct),
+            ("creator_exit_min_pct", self.creator_exit_min_pct),
+        ] {
+            if !(0.0..=100.0).contains(&value) {
+                anyhow::bail!("{} must be between 0 and 100", key);
+            }
+        }
+        if self.safety_watch_interval_secs == 0 {
+            anyhow::bail!("safety_watch_interval_secs must be at least 1");
+        }
+        
+        if !(0.0..=100.0).contains(&self.target_min_score) {
+            anyhow::bail!("target_min_score must be between 0 and 100");
+        }
+        if self.target_score_interval_secs == 0 || self.target_score_signatures == 0 {
+            anyhow::bail!("target_score_interval_secs and target_score_signatures must be at least 1");
+        }
+        
+        // Tips
+        if self.tip_amount_normal > self.tip_amount_max || self.tip_amount_emergency > self.tip_amount_max {
+            anyhow::bail!("tip_amount_normal and tip_amount_emergency must not exceed tip_amount_max");
+        }
+        
+        // Take profit
+        validate_take_profit_tiers(&self.take_profit_tiers)
+            .map_err(|e| anyhow::anyhow!("Invalid take_profit_tiers: {}", e))?;
+        if self.take_profit_enabled && self.take_profit_tiers.is_empty() {
+            anyhow::bail!("take_profit_tiers is empty; set take_profit_enabled = false to disable take profit");
+        }
+        
+        if self.nonce_pool_spare > self.nonce_pool_max {
+            anyhow::bail!("nonce_pool_spare must not exceed nonce_pool_max");
+        }
+        
+        // Control API requires a bearer token when enabled
+        let api_token = non_empty(self.api_token).unwrap_or_default();
+        if self.api_enabled && api_token.is_empty() {
+            anyhow::bail!("api_token must be set when api_enabled is true");
+        }
+        
+        // Notifications are enabled per channel by setting its credentials
+        let telegram_bot_token = non_empty(self.telegram_bot_token);
+        let telegram_chat_id = non_empty(self.telegram_chat_id);
+        if telegram_bot_token.is_some() != telegram_chat_id.is_some() {
+            anyhow::bail!("telegram_bot_token and telegram_chat_id must be set together");
+        }
+        let notify_events = parse_event_kinds(&self.notify_events.join(","))
+            .context("Invalid notify_events")?;
+        
+        if self.max_reconnect_attempts == 0 {
+            anyhow::bail!("max_reconnect_attempts must be at least 1");
+        }
+        
+        let config = Config {
+            config_file: None,
+            signer,
+            target_wallet,
+            
+            wallet_pool,
+            wallet_pool_passphrase_file,
+            wallet_assignment,
+            treasury_wallet,
+            wallet_sweep_enabled: self.wallet_sweep_enabled,
+            wallet_sweep_interval_secs: self.wallet_sweep_interval_secs,
+            wallet_sweep_reserve_sol: self.wallet_sweep_reserve_sol,
+            
+            helius_grpc_url: self.helius_grpc_url,
+            helius_api_key,
+            solana_rpc_url: self.solana_rpc_url,
+            
+            tip_amount_normal: self.tip_amount_normal,
+            tip_amount_emergency: self.tip_amount_emergency,
+            tip_amount_max: self.tip_amount_max,
+            address_lookup_tables: parse_pubkey_list(&self.address_lookup_tables.join(","))
+                .context("Invalid address_lookup_tables")?,
+            
+            buy_amount_sol: self.buy_amount_sol,
+            slippage_bps: self.slippage_bps,
+            copy_quote_mode,
+            
+            auto_close_ata: self.auto_close_ata,
+            ata_reclaim_interval_secs: self.ata_reclaim_interval_secs,
+            ata_burn_dust_below: self.ata_burn_dust_below,
+            
+            risk_limits: RiskLimits {
+                max_open_positions: self.max_open_positions,
+                max_total_exposure_sol: self.max_total_exposure_sol,
+                max_token_exposure_sol: self.max_token_exposure_sol,
+                daily_loss_limit_sol: self.daily_loss_limit_sol,
+                max_buys_per_minute: self.max_buys_per_minute,
+                min_sol_reserve: self.min_sol_reserve,
+            },
+            circuit_breaker: BreakerConfig {
+                failure_threshold: self.circuit_breaker_threshold,
+                window: Duration::from_secs(self.circuit_breaker_window_secs),
+                cooldown: Duration::from_secs(self.circuit_breaker_cooldown_secs),
+            },
+            entry_gate: EntryGate {
+                max_price_move_pct: self.entry_max_price_move_pct,
+                max_slot_lag: self.entry_max_slot_lag,
+                price_move_action,
+            },
+            safety: SafetyConfig {
+                entry_checks: self.safety_entry_checks,
+                probe_sol: self.safety_probe_sol,
+                max_round_trip_tax_pct: self.safety_max_tax_pct,
+                max_transfer_fee_bps: self.safety_max_transfer_fee_bps,
+                hold_watch: self.safety_hold_watch,
+                watch_interval: Duration::from_secs(self.safety_watch_interval_secs),
+                max_liquidity_drop_pct: self.safety_max_liquidity_drop_pct,
+                max_creator_sell_pct: self.safety_max_creator_sell_pct,
+                creator_exit: self.creator_exit_enabled,
+                creator_exit_min_pct: self.creator_exit_min_pct,
+            },
+            
+            target_min_score: self.target_min_score,
+            target_score_interval_secs: self.target_score_interval_secs,
+            target_score_signatures: self.target_score_signatures,
+            
+            take_profit_enabled: self.take_profit_enabled,
+            take_profit_tiers: self.take_profit_tiers,
+            
+            nonce_pool_enabled: self.nonce_pool_enabled,
+            nonce_pool_spare: self.nonce_pool_spare,
+            nonce_pool_max: self.nonce_pool_max,
+            
+            api_enabled: self.api_enabled,
+            api_bind: self.api_bind,
+            api_token,
+            
+            metrics_enabled: self.metrics_enabled,
+            metrics_bind: self.metrics_bind,
+            
+            telegram_bot_token,
+            telegram_chat_id,
+            telegram_api_base: non_empty(self.telegram_api_base),
+            discord_webhook_url: non_empty(self.discord_webhook_url),
+            notify_webhook_url: non_empty(self.notify_webhook_url),
+            notify_events,
+            notify_rate_limit_per_min: self.notify_rate_limit_per_min,
+            
+            reconnect_delay_ms: self.reconnect_delay_ms,
+            max_reconnect_attempts: self.max_reconnect_attempts,
+            compute_unit_limit: self.compute_unit_limit,
+            priority_fee_micro_lamports: self.priority_fee_micro_lamports,
+        };
+        
+        Ok(config)
+    }
+}
+
+/// Treat missing or blank values as unset
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// Pick the single configured signer backend
+fn signer_source(
+    private_key: Option<String>,
+    keypair_path: Option<String>,
+    keystore_path: Option<String>,
+    passphrase_file: Option<String>,
+    remote_url: Option<String>,
+    remote_token: Option<String>,
+) -> Result<SignerSource> {
+    let configured = [&private_key, &keypair_path, &keystore_path, &remote_url]
+        .iter()
+        .filter(|value| value.is_some())
+        .count();
+    if configured != 1 {
+        anyhow::bail!(
+            "Set exactly one of keystore_path, keypair_path, remote_signer_url or private_key ({} set)",
+            configured
+        );
+    }
+    if passphrase_file.is_some() && keystore_path.is_none() {
+        anyhow::bail!("keystore_passphrase_file requires keystore_path");
+    }
+    
+    if let Some(key) = private_key {
+        parse_keypair(&key).context("Invalid private_key")?;
+        return Ok(SignerSource::PrivateKey(key));
+    }
+    if let Some(path) = keypair_path {
+        return Ok(SignerSource::KeypairFile(PathBuf::from(path)));
+    }
+    if let Some(path) = keystore_path {
+        return Ok(SignerSource::Keystore {
+            path: PathBuf::from(path),
+            passphrase_file: passphrase_file.map(PathBuf::from),
+        });
+    }
+    let url = remote_url.unwrap_or_default();
+    crate::signer::RemoteEndpoint::from_str(&url).context("Invalid remote_signer_url")?;
+    Ok(SignerSource::Remote { url, token: remote_token })
+}
+
+/// Require a non-blank value
+fn required(value: Option<String>, key: &str) -> Result<String> {
+    non_empty(value).with_context(|| format!("{} is not set", key))
+}
+
+fn parse_pubkey(value: &str, key: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value.trim()).with_context(|| format!("Invalid {}: {}", key, value))
+}
+
+/// Accept a list or a comma-separated string (as environment variables provide)
+fn string_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    use serde::de::Error;
+    
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) => Ok(s.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect()),
+        serde_json::Value::Array(items) => items.into_iter()
+            .map(|item| match item {
+                serde_json::Value::String(s) => Ok(s),
+                other => Err(D::Error::custom(format!("expected a string, found {}", other))),
+            })
+            .collect(),
+        other => Err(D::Error::custom(format!("expected a list of strings, found {}", other))),
+    }
+}
+
+/// Accept a list of tables or a JSON string (as `TAKE_PROFIT_TIERS` provides)
+fn take_profit_tiers<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<TakeProfitTier>, D::Error> {
+    use serde::de::Error;
+    
+    let tiers = match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) => serde_json::from_str(&s),
+        other => serde_json::from_value(other),
+    };
+    tiers.map_err(|e| D::Error::custom(format!("invalid take_profit_tiers: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use config::FileFormat;
+    
+    fn wallet_env() -> Vec<(String, String)> {
+        vec![
+            ("PRIVATE_KEY".to_string(), solana_sdk::signature::Keypair::new().to_base58_string()),
+            ("TARGET_WALLET".to_string(), Pubkey::new_unique().to_string()),
+            ("HELIUS_API_KEY".to_string(), "key".to_string()),
+        ]
+    }
+    
+    fn parse(contents: &str, format: FileFormat, env: Vec<(String, String)>) -> Result<ConfigFile> {
+        let builder = config::Config::builder().add_source(File::from_str(contents, format));
+        ConfigFile::build(builder, env)
+    }
+    
+    #[test]
+    fn test_toml_with_env_overrides() {
+        let toml = r#"
+            buy_amount_sol = 0.25
+            tip_amount_normal = 20000
+            take_profit_tiers = [
+                { multiplier = 2, sell_percent = 50 },
+                { multiplier = 4.5, sell_percent = 100 },
+            ]
+            notify_events = ["buy_failed", "stopped"]
+        "#;
+        let mut env = wallet_env();
+        env.push(("TIP_AMOUNT_NORMAL".to_string(), "30000".to_string()));
+        env.push(("PATH".to_string(), "/usr/bin".to_string()));
+        env.push(("TELEGRAM_BOT_TOKEN".to_string(), "".to_string()));
+        
+        let config = parse(toml, FileFormat::Toml, env).unwrap().into_config().unwrap();
+        assert_eq!(config.buy_amount_sol, 0.25);
+        assert_eq!(config.tip_amount_normal, 30_000);
+        assert_eq!(config.take_profit_tiers[1], TakeProfitTier { multiplier: 4.5, sell_percent: 100 });
+        assert_eq!(config.notify_events.len(), 2);
+        assert!(config.telegram_bot_token.is_none());
+        assert_eq!(config.slippage_bps, 500);
+        assert!(matches!(config.signer, SignerSource::PrivateKey(_)));
+    }
+    
+    #[test]
+    fn test_yaml_and_env_lists() {
+        let yaml = "buy_amount_sol: 0.5\ntake_profit_enabled: false\n";
+        let alt = Pubkey::new_unique();
+        let mut env = wallet_env();
+        env.push(("ADDRESS_LOOKUP_TABLES".to_string(), format!("{},", alt)));
+        env.push(("TAKE_PROFIT_TIERS".to_string(), r#"[{"multiplier": 3.0, "sell_percent": 100}]"#.to_string()));
+        
+        let config = parse(yaml, FileFormat::Yaml, env).unwrap().into_config().unwrap();
+        assert_eq!(config.buy_amount_sol, 0.5);
+        assert!(!config.take_profit_enabled);
+        assert_eq!(config.address_lookup_tables, vec![alt]);
+        assert_eq!(config.take_profit_tiers[0].multiplier, 3.0);
+    }
+    
+    #[test]
+    fn test_wallet_pool() {
+        let treasury = Pubkey::new_unique();
+        let mut env = wallet_env();
+        env.push(("WALLET_POOL".to_string(), "burner-1.json, burner-2.json".to_string()));
+        env.push(("TREASURY_WALLET".to_string(), treasury.to_string()));
+        
+        let toml = "wallet_assignment = \"least-exposed\"\nwallet_sweep_enabled = true\n";
+        let config = parse(toml, FileFormat::Toml, env).unwrap().into_config().unwrap();
+        assert_eq!(config.wallet_pool, vec![PathBuf::from("burner-1.json"), PathBuf::from("burner-2.json")]);
+        assert_eq!(config.wallet_assignment, AssignmentStrategy::LeastExposed);
+        assert_eq!(config.treasury_wallet, Some(treasury));
+        assert!(config.wallet_sweep_enabled);
+    }
+    
+    #[test]
+    fn test_example_config_is_valid() {
+        let example = include_str!("../../config.example.toml");
+        let env = wallet_env().into_iter().filter(|(key, _)| key != "PRIVATE_KEY").collect();
+        let config = parse(example, FileFormat::Toml, env).unwrap().into_config().unwrap();
+        assert_eq!(config.take_profit_tiers.len(), 3);
+        assert!(matches!(config.signer, SignerSource::Keystore { .. }));
+    }
+    
+    #[test]
+    fn test_rejects_unknown_and_malformed_values() {
+        let unknown = parse("buy_amout_sol = 0.2", FileFormat::Toml, wallet_env()).unwrap_err();
+        assert!(format!("{:#}", unknown).contains("buy_amout_sol"));
+        
+        let mut env = wallet_env();
+        env.push(("TAKE_PROFIT_TIERS".to_string(), "[{\"multiplier\": 2.0}".to_string()));
+        assert!(parse("", FileFormat::Toml, env).is_err());
+        
+        let mut env = wallet_env();
+        env.push(("BUY_AMOUNT_SOL".to_string(), "lots".to_string()));
+        assert!(parse("", FileFormat::Toml, env).is_err());
+    }
+    
+    #[test]
+    fn test_validation_errors() {
+        let err = |toml: &str| {
+            let raw = parse(toml, FileFormat::Toml, wallet_env()).unwrap();
+            format!("{:#}", raw.into_config().unwrap_err())
+        };
+        
+        assert!(err("address_lookup_tables = [\"not-a-table\"]").contains("address_lookup_tables"));
+        assert!(err("buy_amount_sol = 2.0").contains("max_buy_amount_sol"));
+        assert!(err("take_profit_tiers = []").contains("take_profit_tiers is empty"));
+        assert!(err("take_profit_tiers = [{ multiplier = 2.0, sell_percent = 0 }]").contains("sell_percent"));
+        assert!(err("api_enabled = true").contains("api_token"));
+        assert!(err("tip_amount_normal = 600000").contains("tip_amount_max"));
+        assert!(err("keystore_path = \"wallet.json\"").contains("Set exactly one of"));
+        assert!(err("daily_loss_limit_sol = -1.0").contains("daily_loss_limit_sol"));
+        assert!(err("wallet_sweep_enabled = true").contains("require wallet_pool"));
+        assert!(err("wallet_pool = [\"a.json\"]\nwallet_assignment = \"random\"").contains("wallet_assignment"));
+        assert!(err("entry_price_move_action = \"chase\"").contains("entry_price_move_action"));
+        assert!(err("copy_quote_mode = \"usdc\"").contains("copy_quote_mode"));
+        assert!(err("safety_max_tax_pct = 150.0").contains("safety_max_tax_pct"));
+        assert!(err("safety_probe_sol = 0.0").contains("safety_probe_sol"));
+        assert!(err("creator_exit_min_pct = -1.0").contains("creator_exit_min_pct"));
+        
+        let missing = parse("", FileFormat::Toml, Vec::new()).unwrap().into_config().unwrap_err();
+        assert!(format!("{:#}", missing).contains("Set exactly one of"));
+    }
+}
@@ -0,0 +1,9 @@
+//! Configuration loading (config file + environment overrides) and hot reload
+
+pub mod settings;
+pub mod file;
+pub mod reload;
+
+pub use settings::*;
+pub use file::*;
+pub use reload::*;
@@ -0,0 +1,189 @@
+//! Hot reload of the settings that can change without a restart
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::task::JoinHandle;
+use tracing::{info, warn, error};
+
+use crate::state::BotControl;
+use super::ConfigFile;
+use super::settings::TakeProfitTier;
+
+/// How often the config file is checked for changes
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Settings applied to the running bot when the config file changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReloadableSettings {
+    pub buy_amount_sol: f64,
+    pub tip_amount: u64,
+    pub take_profit_enabled: bool,
+    pub take_profit_tiers: Vec<TakeProfitTier>,
+}
+
+impl ReloadableSettings {
+    pub fn from_file(file: &ConfigFile) -> Self {
+        Self {
+            buy_amount_sol: file.buy_amount_sol,
+            tip_amount: file.tip_amount_normal,
+            take_profit_enabled: file.take_profit_enabled,
+            take_profit_tiers: file.take_profit_tiers.clone(),
+        }
+    }
+    
+    /// Push the settings to the running bot
+    pub fn apply(&self, control: &BotControl) -> Result<()> {
+        if control.take_profit_tiers() != self.take_profit_tiers {
+            control.set_take_profit_tiers(self.take_profit_tiers.clone())
+                .map_err(anyhow::Error::msg)?;
+        }
+        control.set_buy_amount_sol(self.buy_amount_sol);
+        control.set_tip_amount(self.tip_amount);
+        control.set_take_profit_enabled(self.take_profit_enabled);
+        Ok(())
+    }
+}
+
+/// Whether anything other than the reloadable settings differs between two files
+fn requires_restart(previous: &ConfigFile, current: &ConfigFile) -> bool {
+    let mut current = current.clone();
+    current.buy_amount_sol = previous.buy_amount_sol;
+    current.tip_amount_normal = previous.tip_amount_normal;
+    current.take_profit_enabled = previous.take_profit_enabled;
+    current.take_profit_tiers = previous.take_profit_tiers.clone();
+    current != *previous
+}
+
+/// Watch the config file and apply reloadable settings when it changes
+pub fn spawn_config_watcher(path: PathBuf, control: Arc<BotControl>) -> JoinHandle<()> {
+    tokio::spawn(watch(path, control, WATCH_INTERVAL))
+}
+
+async fn watch(path: PathBuf, control: Arc<BotControl>, interval: Duration) {
+    let mut modified = modified_at(&path);
+    let mut current = match ConfigFile::load(Some(&path), std::env::vars()) {
+        Ok(file) => file,
+        Err(e) => {
+            error!("Config watcher disabled: {:#}", e);
+            return;
+        }
+    };
+    info!("👀 Watching {} for changes", path.display());
+    
+    let mut ticker = tokio::time::interval(interval);
+    ticker.tick().await;
+    loop {
+        ticker.tick().await;
+        
+        let latest = modified_at(&path);
+        if latest == modified {
+            continue;
+        }
+        modified = latest;
+        
+        // Keep the running settings if the new file does not validate
+        let file = match ConfigFile::load(Some(&path), std::env::vars())
+            .and_then(|file| file.clone().into_config().map(|_| file))
+        {
+            Ok(file) => file,
+            Err(e) => {
+                error!("❌ Config reload rejected, keeping previous settings: {:#}", e);
+                continue;
+            }
+        };
+        
+        if requires_restart(&current, &file) {
+            warn!("⚠️ Config changes outside buy amount, tip and take profit settings need a restart");
+        }
+        match ReloadableSettings::from_file(&file).apply(&control) {
+            Ok(()) => info!("🔄 Config reloaded from {}", path.display()),
+            Err(e) => error!("❌ Config reload failed: {:#}", e),
+        }
+        current = file;
+    }
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Keypair;
+    
+    fn control() -> BotControl {
+        BotControl::new(
+            vec![Pubkey::new_unique()],
+            0.1,
+            10_000,
+            true,
+            vec![TakeProfitTier { multiplier: 2.0, sell_percent: 100 }],
+        )
+    }
+    
+    #[test]
+    fn test_apply_updates_control() {
+        let control = control();
+        let settings = ReloadableSettings {
+            buy_amount_sol: 0.3,
+            tip_amount: 25_000,
+            take_profit_enabled: false,
+            take_profit_tiers: vec![TakeProfitTier { multiplier: 4.0, sell_percent: 100 }],
+        };
+        
+        settings.apply(&control).unwrap();
+        assert_eq!(control.buy_amount_sol(), 0.3);
+        assert_eq!(control.tip_amount(), 25_000);
+        assert!(!control.take_profit_enabled());
+        assert_eq!(control.take_profit_tiers(), settings.take_profit_tiers);
+    }
+    
+    #[test]
+    fn test_requires_restart() {
+        let previous = ConfigFile::default();
+        let mut current = previous.clone();
+        current.buy_amount_sol = 0.5;
+        current.take_profit_tiers.clear();
+        assert!(!requires_restart(&previous, &current));
+        
+        current.solana_rpc_url = "http://localhost:8899".to_string();
+        assert!(requires_restart(&previous, &current));
+    }
+    
+    #[tokio::test]
+    async fn test_watcher_applies_valid_changes_only() {
+        let path = std::env::temp_dir().join(format!("copybot-reload-{}.toml", uuid::Uuid::new_v4()));
+        let write = |buy_amount: &str| {
+            let contents = format!(
+                "private_key = \"{}\"\ntarget_wallet = \"{}\"\nhelius_api_key = \"key\"\nbuy_amount_sol = {}\n",
+                Keypair::new().to_base58_string(),
+                Pubkey::new_unique(),
+                buy_amount,
+            );
+            std::fs::write(&path, contents).unwrap();
+        };
+        
+        write("0.1");
+        let control = Arc::new(control());
+        let handle = tokio::spawn(watch(path.clone(), control.clone(), Duration::from_millis(20)));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        
+        // mtime resolution can be coarse, so wait before each rewrite
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        write("0.4");
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(control.buy_amount_sol(), 0.4);
+        
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        write("-1");
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(control.buy_amount_sol(), 0.4);
+        
+        handle.abort();
+        let _ = std::fs::remove_file(&path);
+    }
+}
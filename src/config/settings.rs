@@ -0,0 +1,187 @@
+//! Resolved bot configuration
+
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::notify::EventKind;
+use crate::risk::{BreakerConfig, EntryGate, RiskLimits};
+use crate::safety::SafetyConfig;
+use crate::signer::{AssignmentStrategy, SignerSource};
+use crate::swap::CopyQuoteMode;
+use super::ConfigFile;
+
+/// Config file used when neither `--config` nor `CONFIG_FILE` is given
+pub const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+/// Take profit tier configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TakeProfitTier {
+    /// Price multiplier to trigger (e.g., 2.0 = 2x)
+    pub multiplier: f64,
+    /// Percentage of position to sell (0-100)
+    pub sell_percent: u8,
+}
+
+/// Main configuration structure
+#[derive(Debug)]
+pub struct Config {
+    /// Config file this was loaded from (None = environment only)
+    pub config_file: Option<PathBuf>,
+    
+    // Wallet configuration
+    pub signer: SignerSource,
+    pub target_wallet: Pubkey,
+    
+    // Wallet pool configuration
+    /// Burner wallet files (keypair or keystore); empty = trade from the main wallet only
+    pub wallet_pool: Vec<PathBuf>,
+    pub wallet_pool_passphrase_file: Option<PathBuf>,
+    pub wallet_assignment: AssignmentStrategy,
+    /// Where the sweeper sends burner SOL (None = main wallet)
+    pub treasury_wallet: Option<Pubkey>,
+    pub wallet_sweep_enabled: bool,
+    pub wallet_sweep_interval_secs: u64,
+    pub wallet_sweep_reserve_sol: f64,
+    
+    // Helius configuration
+    pub helius_grpc_url: String,
+    pub helius_api_key: String,
+    pub solana_rpc_url: String,
+    
+    // Jito configuration
+    pub tip_amount_normal: u64,
+    pub tip_amount_emergency: u64,
+    pub tip_amount_max: u64,
+    /// Address lookup tables used when compiling v0 bundle transactions
+    pub address_lookup_tables: Vec<Pubkey>,
+    
+    // Trading configuration
+    pub buy_amount_sol: f64,
+    pub slippage_bps: u16,
+    /// Whether stablecoin-priced trades are copied with SOL or the same stablecoin
+    pub copy_quote_mode: CopyQuoteMode,
+    
+    // Rent reclamation for empty token accounts
+    pub auto_close_ata: bool,
+    pub ata_reclaim_interval_secs: u64,
+    /// Burn balances below this many whole tokens so their accounts can be closed (0 = never)
+    pub ata_burn_dust_below: f64,
+    
+    // Risk limits
+    pub risk_limits: RiskLimits,
+    
+    // Per-venue circuit breakers
+    pub circuit_breaker: BreakerConfig,
+    
+    // Slot lag and price move limits on copy buys
+    pub entry_gate: EntryGate,
+    
+    // Rug and honeypot checks before entry and while holding
+    pub safety: SafetyConfig,
+    
+    // Target scoring (0 = disabled)
+    pub target_min_score: f64,
+    pub target_score_interval_secs: u64,
+    pub target_score_signatures: usize,
+    
+    // Take profit configuration
+    pub take_profit_enabled: bool,
+    pub take_profit_tiers: Vec<TakeProfitTier>,
+    
+    // Durable nonce configuration
+    pub nonce_pool_enabled: bool,
+    pub nonce_pool_spare: usize,
+    pub nonce_pool_max: usize,
+    
+    // Control API configuration
+    pub api_enabled: bool,
+    pub api_bind: String,
+    pub api_token: String,
+    
+    // Metrics configuration
+    pub metrics_enabled: bool,
+    pub metrics_bind: String,
+    
+    // Notification configuration
+    pub telegram_bot_token: Option<String>,
+    pub telegram_chat_id: Option<String>,
+    pub telegram_api_base: Option<String>,
+    pub discord_webhook_url: Option<String>,
+    pub notify_webhook_url: Option<String>,
+    pub notify_events: HashSet<EventKind>,
+    pub notify_rate_limit_per_min: u32,
+    
+    // Performance settings
+    pub reconnect_delay_ms: u64,
+    pub max_reconnect_attempts: u32,
+    pub compute_unit_limit: u32,
+    pub priority_fee_micro_lamports: u64,
+}
+
+impl Config {
+    /// Load configuration from the config file (if any) with environment overrides
+    pub fn load() -> Result<Self> {
+        dotenvy::dotenv().ok();
+        
+        let path = config_file_path();
+        let mut config = ConfigFile::load(path.as_deref(), std::env::vars())?.into_config()?;
+        config.config_file = path;
+        Ok(config)
+    }
+}
+
+/// Parse a comma-separated list of pubkeys
+pub(super) fn parse_pubkey_list(input: &str) -> Result<Vec<Pubkey>> {
+    input.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| Pubkey::from_str(s).with_context(|| format!("Invalid pubkey: {}", s)))
+        .collect()
+}
+
+/// Resolve the config file from `--config <path>`, `CONFIG_FILE`, or `config.toml` if present
+fn config_file_path() -> Option<PathBuf> {
+    let mut args = std::env::args().skip_while(|arg| arg != "--config").skip(1);
+    if let Some(path) = args.next() {
+        return Some(PathBuf::from(path));
+    }
+    if let Ok(path) = std::env::var("CONFIG_FILE") {
+        if !path.trim().is_empty() {
+            return Some(PathBuf::from(path));
+        }
+    }
+    let default = PathBuf::from(DEFAULT_CONFIG_FILE);
+    default.exists().then_some(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_default_take_profit_tiers() {
+        let tiers = [
+            TakeProfitTier { multiplier: 2.0, sell_percent: 20 },
+            TakeProfitTier { multiplier: 3.0, sell_percent: 30 },
+        ];
+        
+        assert_eq!(tiers.len(), 2);
+        assert_eq!(tiers[0].multiplier, 2.0);
+        assert_eq!(tiers[0].sell_percent, 20);
+    }
+    
+    #[test]
+    fn test_parse_pubkey_list() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        
+        let parsed = parse_pubkey_list(&format!("{}, {},", a, b)).unwrap();
+        assert_eq!(parsed, vec![a, b]);
+        assert!(parse_pubkey_list("").unwrap().is_empty());
+        assert!(parse_pubkey_list("not-a-pubkey").is_err());
+    }
+}
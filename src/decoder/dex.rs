@@ -8,9 +8,19 @@ use std::str::FromStr;
 pub enum DexProgram {
     RaydiumAmm,
     RaydiumClmm,
+    /// Raydium constant product AMM that LaunchLab tokens migrate to
+    RaydiumCpmm,
+    /// Raydium LaunchLab bonding curve
+    RaydiumLaunchLab,
     Jupiter,
     PumpFun,
+    /// Pump.fun AMM that graduated Pump.fun tokens migrate to
+    PumpSwap,
     OrcaWhirlpool,
+    /// Meteora liquidity book (DLMM)
+    MeteoraDlmm,
+    /// Meteora dynamic AMM (constant product over Meteora vaults)
+    MeteoraDynamicAmm,
     Unknown,
 }
 
@@ -22,9 +32,14 @@ impl DexProgram {
         match id_str.as_str() {
             "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8" => DexProgram::RaydiumAmm,
             "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK" => DexProgram::RaydiumClmm,
+            "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C" => DexProgram::RaydiumCpmm,
+            "LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj" => DexProgram::RaydiumLaunchLab,
             "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4" => DexProgram::Jupiter,
             "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P" => DexProgram::PumpFun,
+            "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA" => DexProgram::PumpSwap,
             "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc" => DexProgram::OrcaWhirlpool,
+            "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo" => DexProgram::MeteoraDlmm,
+            "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB" => DexProgram::MeteoraDynamicAmm,
             _ => DexProgram::Unknown,
         }
     }
@@ -34,9 +49,14 @@ impl DexProgram {
         match self {
             DexProgram::RaydiumAmm => Pubkey::from_str("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8").ok(),
             DexProgram::RaydiumClmm => Pubkey::from_str("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK").ok(),
+            DexProgram::RaydiumCpmm => Pubkey::from_str("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C").ok(),
+            DexProgram::RaydiumLaunchLab => Pubkey::from_str("LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj").ok(),
             DexProgram::Jupiter => Pubkey::from_str("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4").ok(),
             DexProgram::PumpFun => Pubkey::from_str("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P").ok(),
+            DexProgram::PumpSwap => Pubkey::from_str("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA").ok(),
             DexProgram::OrcaWhirlpool => Pubkey::from_str("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc").ok(),
+            DexProgram::MeteoraDlmm => Pubkey::from_str("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo").ok(),
+            DexProgram::MeteoraDynamicAmm => Pubkey::from_str("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB").ok(),
             DexProgram::Unknown => None,
         }
     }
@@ -95,6 +115,387 @@ pub mod raydium {
     }
 }
 
+/// Raydium CPMM (constant product) instruction, event and account decoders
+pub mod raydium_cpmm {
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+    use super::DexProgram;
+    use crate::decoder::events::Reader;
+    use crate::decoder::{TradeEvent, WSOL_MINT};
+    
+    /// swap_base_input discriminator (amount in, minimum amount out)
+    pub const SWAP_BASE_INPUT_DISCRIMINATOR: [u8; 8] = [0x8f, 0xbe, 0x5a, 0xda, 0xc4, 0x1e, 0x33, 0xde];
+    /// Pool state account discriminator (Anchor)
+    pub const POOL_STATE_DISCRIMINATOR: [u8; 8] = [0xf7, 0xed, 0xe3, 0xf5, 0xd7, 0xc3, 0xde, 0x46];
+    /// AMM config account discriminator (Anchor)
+    pub const AMM_CONFIG_DISCRIMINATOR: [u8; 8] = [0xda, 0xf4, 0x21, 0x68, 0xcb, 0xcb, 0x2b, 0x6f];
+    /// `SwapEvent` discriminator
+    pub const SWAP_EVENT_DISCRIMINATOR: [u8; 8] = [0x40, 0xc6, 0xcd, 0xe8, 0x26, 0x08, 0x71, 0xe2];
+    
+    /// Fee rates are in millionths
+    pub const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+    
+    /// Decode a `SwapEvent` payload
+    /// Events from program versions before the mints were added decode with no mints.
+    pub fn decode_swap_event(data: &[u8]) -> Option<SwapEvent> {
+        let (discriminator, body) = data.split_first_chunk::<8>()?;
+        if *discriminator != SWAP_EVENT_DISCRIMINATOR {
+            return None;
+        }
+        let mut reader = Reader(body);
+        Some(SwapEvent {
+            pool_id: reader.pubkey()?,
+            input_vault_before: reader.u64()?,
+            output_vault_before: reader.u64()?,
+            input_amount: reader.u64()?,
+            output_amount: reader.u64()?,
+            input_transfer_fee: reader.u64()?,
+            output_transfer_fee: reader.u64()?,
+            base_input: reader.bool()?,
+            input_mint: reader.pubkey(),
+            output_mint: reader.pubkey(),
+        })
+    }
+    
+    /// A swap through a CPMM pool, with the vault balances before it
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct SwapEvent {
+        pub pool_id: Pubkey,
+        pub input_vault_before: u64,
+        pub output_vault_before: u64,
+        pub input_amount: u64,
+        pub output_amount: u64,
+        pub input_transfer_fee: u64,
+        pub output_transfer_fee: u64,
+        pub base_input: bool,
+        pub input_mint: Option<Pubkey>,
+        pub output_mint: Option<Pubkey>,
+    }
+    
+    impl SwapEvent {
+        /// The swap as a buy or sell of a token against WSOL by `user`
+        /// None for token to token swaps and for events without mints.
+        pub fn to_trade_event(&self, user: Pubkey) -> Option<TradeEvent> {
+            let wsol = Pubkey::from_str(WSOL_MINT).ok()?;
+            let (input_mint, output_mint) = (self.input_mint?, self.output_mint?);
+            let input_vault_after = self.input_vault_before + self.input_amount;
+            let output_vault_after = self.output_vault_before.saturating_sub(self.output_amount);
+            let (is_buy, mint) = match (input_mint == wsol, output_mint == wsol) {
+                (true, false) => (true, output_mint),
+                (false, true) => (false, input_mint),
+                _ => return None,
+            };
+            let (sol_amount, token_amount, sol_reserves, token_reserves) = if is_buy {
+                (self.input_amount, self.output_amount, input_vault_after, output_vault_after)
+            } else {
+                (self.output_amount, self.input_amount, output_vault_after, input_vault_after)
+            };
+            Some(TradeEvent {
+                mint,
+                sol_amount,
+                token_amount,
+                is_buy,
+                user,
+                timestamp: 0,
+                virtual_sol_reserves: sol_reserves,
+                virtual_token_reserves: token_reserves,
+            })
+        }
+    }
+    
+    /// Authority owning every pool's vaults
+    pub fn authority_address() -> Option<Pubkey> {
+        let program = DexProgram::RaydiumCpmm.program_id()?;
+        Some(Pubkey::find_program_address(&[b"vault_and_lp_mint_auth_seed"], &program).0)
+    }
+    
+    /// Decode a pool state account
+    pub fn decode_pool_state(data: &[u8]) -> Option<PoolState> {
+        if data.len() < 389 || data[0..8] != POOL_STATE_DISCRIMINATOR {
+            return None;
+        }
+        let pubkey = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).ok();
+        let read_u64 = |offset: usize| data.get(offset..offset + 8)?.try_into().ok().map(u64::from_le_bytes);
+        Some(PoolState {
+            amm_config: pubkey(8)?,
+            token_0_vault: pubkey(72)?,
+            token_1_vault: pubkey(104)?,
+            token_0_mint: pubkey(168)?,
+            token_1_mint: pubkey(200)?,
+            token_0_program: pubkey(232)?,
+            token_1_program: pubkey(264)?,
+            observation_key: pubkey(296)?,
+            status: data[329],
+            protocol_fees_token_0: read_u64(341)?,
+            protocol_fees_token_1: read_u64(349)?,
+            fund_fees_token_0: read_u64(357)?,
+            fund_fees_token_1: read_u64(365)?,
+            // Pools created before creator fees have none accrued
+            creator_fees_token_0: read_u64(397).unwrap_or(0),
+            creator_fees_token_1: read_u64(405).unwrap_or(0),
+        })
+    }
+    
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct PoolState {
+        pub amm_config: Pubkey,
+        pub token_0_vault: Pubkey,
+        pub token_1_vault: Pubkey,
+        pub token_0_mint: Pubkey,
+        pub token_1_mint: Pubkey,
+        pub token_0_program: Pubkey,
+        pub token_1_program: Pubkey,
+        pub observation_key: Pubkey,
+        /// Bit flags disabling deposits, withdrawals and swaps
+        pub status: u8,
+        pub protocol_fees_token_0: u64,
+        pub protocol_fees_token_1: u64,
+        pub fund_fees_token_0: u64,
+        pub fund_fees_token_1: u64,
+        pub creator_fees_token_0: u64,
+        pub creator_fees_token_1: u64,
+    }
+    
+    impl PoolState {
+        /// Trading reserves from the vault balances, excluding fees not yet collected
+        pub fn reserves(&self, vault_0_amount: u64, vault_1_amount: u64) -> (u64, u64) {
+            let fees_0 = self.protocol_fees_token_0 + self.fund_fees_token_0 + self.creator_fees_token_0;
+            let fees_1 = self.protocol_fees_token_1 + self.fund_fees_token_1 + self.creator_fees_token_1;
+            (vault_0_amount.saturating_sub(fees_0), vault_1_amount.saturating_sub(fees_1))
+        }
+        
+        /// Whether swaps are enabled
+        pub fn can_swap(&self) -> bool {
+            self.status & 0b100 == 0
+        }
+    }
+    
+    /// Trade fee rate (millionths) from an AMM config account
+    pub fn decode_trade_fee_rate(data: &[u8]) -> Option<u64> {
+        if data.len() < 20 || data[0..8] != AMM_CONFIG_DISCRIMINATOR {
+            return None;
+        }
+        data[12..20].try_into().ok().map(u64::from_le_bytes)
+    }
+    
+    /// Output of swapping `amount_in` against constant product reserves, after the trade fee
+    pub fn quote_exact_input(reserve_in: u64, reserve_out: u64, amount_in: u64, trade_fee_rate: u64) -> u64 {
+        let fee = (amount_in as u128 * trade_fee_rate as u128).div_ceil(FEE_RATE_DENOMINATOR as u128);
+        let net_in = amount_in as u128 - fee.min(amount_in as u128);
+        (reserve_out as u128 * net_in / (reserve_in as u128 + net_in).max(1)) as u64
+    }
+}
+
+/// Raydium LaunchLab (bonding curve launchpad) instruction, event and account decoders
+pub mod launchlab {
+    use solana_sdk::pubkey::Pubkey;
+    use super::DexProgram;
+    use crate::decoder::events::Reader;
+    use crate::decoder::TradeEvent;
+    
+    /// buy_exact_in discriminator (quote amount in, minimum base out, share fee rate)
+    pub const BUY_EXACT_IN_DISCRIMINATOR: [u8; 8] = [0xfa, 0xea, 0x0d, 0x7b, 0xd5, 0x9c, 0x13, 0xec];
+    /// sell_exact_in discriminator (base amount in, minimum quote out, share fee rate)
+    pub const SELL_EXACT_IN_DISCRIMINATOR: [u8; 8] = [0x95, 0x27, 0xde, 0x9b, 0xd3, 0x7c, 0x98, 0x1a];
+    /// Pool state account discriminator (Anchor)
+    pub const POOL_STATE_DISCRIMINATOR: [u8; 8] = [0xf7, 0xed, 0xe3, 0xf5, 0xd7, 0xc3, 0xde, 0x46];
+    /// Global config account discriminator (Anchor)
+    pub const GLOBAL_CONFIG_DISCRIMINATOR: [u8; 8] = [0x95, 0x08, 0x9c, 0xca, 0xa0, 0xfc, 0xb0, 0xd9];
+    /// Platform config account discriminator (Anchor)
+    pub const PLATFORM_CONFIG_DISCRIMINATOR: [u8; 8] = [0xa0, 0x4e, 0x80, 0x00, 0xf8, 0x53, 0xe6, 0xa0];
+    /// `TradeEvent` discriminator (same name, and so the same bytes, as Pump.fun's)
+    pub const TRADE_EVENT_DISCRIMINATOR: [u8; 8] = [0xbd, 0xdb, 0x7f, 0xd3, 0x4e, 0xe6, 0x61, 0xee];
+    
+    /// Pool status while the bonding curve is trading
+    pub const STATUS_FUNDING: u8 = 0;
+    
+    /// Fee rates are in millionths
+    pub const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+    
+    /// Decode a `TradeEvent` payload
+    /// Trailing fee and status fields vary between program versions and are not read.
+    pub fn decode_trade_event(data: &[u8]) -> Option<LaunchLabTrade> {
+        let (discriminator, body) = data.split_first_chunk::<8>()?;
+        if *discriminator != TRADE_EVENT_DISCRIMINATOR {
+            return None;
+        }
+        let mut reader = Reader(body);
+        let pool_state = reader.pubkey()?;
+        let _total_base_sell = reader.u64()?;
+        Some(LaunchLabTrade {
+            pool_state,
+            virtual_base: reader.u64()?,
+            virtual_quote: reader.u64()?,
+            real_base_before: reader.u64()?,
+            real_quote_before: reader.u64()?,
+            real_base_after: reader.u64()?,
+            real_quote_after: reader.u64()?,
+            amount_in: reader.u64()?,
+            amount_out: reader.u64()?,
+        })
+    }
+    
+    /// A trade on a LaunchLab bonding curve
+    /// The event names the pool, not the mint; the mint is read from the pool account.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct LaunchLabTrade {
+        pub pool_state: Pubkey,
+        pub virtual_base: u64,
+        pub virtual_quote: u64,
+        pub real_base_before: u64,
+        pub real_quote_before: u64,
+        pub real_base_after: u64,
+        pub real_quote_after: u64,
+        pub amount_in: u64,
+        pub amount_out: u64,
+    }
+    
+    impl LaunchLabTrade {
+        /// Buys add quote tokens (SOL) to the curve, sells take them out
+        pub fn is_buy(&self) -> bool {
+            self.real_quote_after > self.real_quote_before
+        }
+        
+        /// The trade as a buy or sell of `mint` by `user`, with the curve after it
+        pub fn to_trade_event(&self, mint: Pubkey, user: Pubkey) -> TradeEvent {
+            let is_buy = self.is_buy();
+            let (sol_amount, token_amount) = if is_buy {
+                (self.amount_in, self.amount_out)
+            } else {
+                (self.amount_out, self.amount_in)
+            };
+            TradeEvent {
+                mint,
+                sol_amount,
+                token_amount,
+                is_buy,
+                user,
+                timestamp: 0,
+                virtual_sol_reserves: self.virtual_quote + self.real_quote_after,
+                virtual_token_reserves: self.virtual_base.saturating_sub(self.real_base_after),
+            }
+        }
+    }
+    
+    /// Pool of a token launched against `quote_mint`
+    pub fn pool_address(base_mint: &Pubkey, quote_mint: &Pubkey) -> Option<Pubkey> {
+        let program = DexProgram::RaydiumLaunchLab.program_id()?;
+        Some(Pubkey::find_program_address(&[b"pool", base_mint.as_ref(), quote_mint.as_ref()], &program).0)
+    }
+    
+    /// Authority owning every pool's vaults
+    pub fn authority_address() -> Option<Pubkey> {
+        let program = DexProgram::RaydiumLaunchLab.program_id()?;
+        Some(Pubkey::find_program_address(&[b"vault_auth_seed"], &program).0)
+    }
+    
+    /// Anchor event authority the program self-invokes to emit events
+    pub fn event_authority_address() -> Option<Pubkey> {
+        let program = DexProgram::RaydiumLaunchLab.program_id()?;
+        Some(Pubkey::find_program_address(&[b"__event_authority"], &program).0)
+    }
+    
+    /// Decode a pool state account
+    pub fn decode_pool_state(data: &[u8]) -> Option<PoolState> {
+        if data.len() < 365 || data[0..8] != POOL_STATE_DISCRIMINATOR {
+            return None;
+        }
+        let pubkey = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).ok();
+        let read_u64 = |offset: usize| data[offset..offset + 8].try_into().ok().map(u64::from_le_bytes);
+        Some(PoolState {
+            status: data[17],
+            base_decimals: data[18],
+            quote_decimals: data[19],
+            supply: read_u64(21)?,
+            total_base_sell: read_u64(29)?,
+            virtual_base: read_u64(37)?,
+            virtual_quote: read_u64(45)?,
+            real_base: read_u64(53)?,
+            real_quote: read_u64(61)?,
+            global_config: pubkey(141)?,
+            platform_config: pubkey(173)?,
+            base_mint: pubkey(205)?,
+            quote_mint: pubkey(237)?,
+            base_vault: pubkey(269)?,
+            quote_vault: pubkey(301)?,
+            creator: pubkey(333)?,
+        })
+    }
+    
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct PoolState {
+        /// 0 while trading on the curve, then migrating and migrated
+        pub status: u8,
+        pub base_decimals: u8,
+        pub quote_decimals: u8,
+        pub supply: u64,
+        /// Base tokens the curve sells before it migrates
+        pub total_base_sell: u64,
+        pub virtual_base: u64,
+        pub virtual_quote: u64,
+        /// Base tokens sold so far
+        pub real_base: u64,
+        /// Quote tokens raised so far
+        pub real_quote: u64,
+        pub global_config: Pubkey,
+        pub platform_config: Pubkey,
+        pub base_mint: Pubkey,
+        pub quote_mint: Pubkey,
+        pub base_vault: Pubkey,
+        pub quote_vault: Pubkey,
+        pub creator: Pubkey,
+    }
+    
+    /// Constant product curve math (the curve type LaunchLab launches use)
+    impl PoolState {
+        /// Whether the curve is still trading (it has not migrated)
+        pub fn is_trading(&self) -> bool {
+            self.status == STATUS_FUNDING
+        }
+        
+        /// Current price in raw quote units per raw base unit
+        pub fn price(&self) -> Option<f64> {
+            let base = self.virtual_base.checked_sub(self.real_base).filter(|base| *base > 0)?;
+            Some((self.virtual_quote + self.real_quote) as f64 / base as f64)
+        }
+        
+        /// Base tokens bought with `quote_in` after `fee_rate` (millionths) is taken from it
+        pub fn buy_quote(&self, quote_in: u64, fee_rate: u64) -> u64 {
+            let net_in = quote_in as u128 - (quote_in as u128 * fee_rate as u128).div_ceil(FEE_RATE_DENOMINATOR as u128).min(quote_in as u128);
+            let base = self.virtual_base.saturating_sub(self.real_base) as u128;
+            let quote = (self.virtual_quote + self.real_quote) as u128;
+            let out = base * net_in / (quote + net_in).max(1);
+            // The curve never sells past its allocation
+            out.min(self.total_base_sell.saturating_sub(self.real_base) as u128) as u64
+        }
+        
+        /// Quote tokens received for `base_in` after `fee_rate` (millionths) is taken from the output
+        pub fn sell_quote(&self, base_in: u64, fee_rate: u64) -> u64 {
+            let base = self.virtual_base.saturating_sub(self.real_base) as u128;
+            let quote = (self.virtual_quote + self.real_quote) as u128;
+            let gross = quote * base_in as u128 / (base + base_in as u128).max(1);
+            let gross = gross.min(self.real_quote as u128);
+            (gross - (gross * fee_rate as u128).div_ceil(FEE_RATE_DENOMINATOR as u128).min(gross)) as u64
+        }
+    }
+    
+    /// Trade fee rate (millionths) from the global config account
+    pub fn decode_trade_fee_rate(data: &[u8]) -> Option<u64> {
+        if data.len() < 35 || data[0..8] != GLOBAL_CONFIG_DISCRIMINATOR {
+            return None;
+        }
+        data[27..35].try_into().ok().map(u64::from_le_bytes)
+    }
+    
+    /// Fee rate (millionths) the launch platform adds on top of the trade fee
+    pub fn decode_platform_fee_rate(data: &[u8]) -> Option<u64> {
+        if data.len() < 112 || data[0..8] != PLATFORM_CONFIG_DISCRIMINATOR {
+            return None;
+        }
+        data[104..112].try_into().ok().map(u64::from_le_bytes)
+    }
+}
+
 /// Jupiter instruction decoders
 pub mod jupiter {
     use super::*;
@@ -130,6 +531,9 @@ pub mod jupiter {
 
 /// Pump.fun instruction decoders
 pub mod pumpfun {
+    use solana_sdk::pubkey::Pubkey;
+    use super::DexProgram;
+    
     /// Buy discriminator for Pump.fun
     pub const BUY_DISCRIMINATOR: [u8; 8] = [0x66, 0x06, 0x3d, 0x12, 0x01, 0xda, 0xeb, 0xea];
     /// Sell discriminator for Pump.fun
@@ -167,6 +571,447 @@ pub mod pumpfun {
         Buy { amount: u64, max_sol_cost: u64 },
         Sell { amount: u64, min_sol_output: u64 },
     }
+    
+    /// Bonding curve account discriminator (Anchor)
+    pub const BONDING_CURVE_DISCRIMINATOR: [u8; 8] = [0x17, 0xb7, 0xf8, 0x37, 0x60, 0xd8, 0xac, 0x60];
+    /// Real token reserves of a freshly launched curve; the curve completes when they run out
+    pub const INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+    
+    /// Bonding curve PDA for a mint
+    pub fn bonding_curve_address(mint: &Pubkey) -> Option<Pubkey> {
+        let program = DexProgram::PumpFun.program_id()?;
+        Some(Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &program).0)
+    }
+    
+    /// Decode a bonding curve account
+    pub fn decode_bonding_curve(data: &[u8]) -> Option<BondingCurve> {
+        if data.len() < 49 || data[0..8] != BONDING_CURVE_DISCRIMINATOR {
+            return None;
+        }
+        let read_u64 = |offset: usize| data[offset..offset + 8].try_into().ok().map(u64::from_le_bytes);
+        Some(BondingCurve {
+            virtual_token_reserves: read_u64(8)?,
+            virtual_sol_reserves: read_u64(16)?,
+            real_token_reserves: read_u64(24)?,
+            real_sol_reserves: read_u64(32)?,
+            token_total_supply: read_u64(40)?,
+            complete: data[48] != 0,
+            // Older curves end before the creator field
+            creator: data.get(49..81).and_then(|bytes| bytes.try_into().ok()).map(Pubkey::new_from_array),
+        })
+    }
+    
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct BondingCurve {
+        pub virtual_token_reserves: u64,
+        pub virtual_sol_reserves: u64,
+        pub real_token_reserves: u64,
+        pub real_sol_reserves: u64,
+        pub token_total_supply: u64,
+        /// The curve has migrated and no longer trades
+        pub complete: bool,
+        /// Wallet that launched the token, None on curves created before it was recorded
+        pub creator: Option<Pubkey>,
+    }
+    
+    impl BondingCurve {
+        /// Spot price in lamports per raw token unit
+        pub fn price(&self) -> Option<f64> {
+            (self.virtual_token_reserves > 0)
+                .then(|| self.virtual_sol_reserves as f64 / self.virtual_token_reserves as f64)
+        }
+        
+        /// Percent of the curve's tokens sold, 100 once complete
+        pub fn progress(&self) -> f64 {
+            if self.complete {
+                return 100.0;
+            }
+            let sold = INITIAL_REAL_TOKEN_RESERVES.saturating_sub(self.real_token_reserves);
+            sold as f64 / INITIAL_REAL_TOKEN_RESERVES as f64 * 100.0
+        }
+    }
+}
+
+/// Pump.fun AMM (PumpSwap) instruction and account decoders
+pub mod pumpswap {
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+    use super::DexProgram;
+    use crate::decoder::WSOL_MINT;
+    
+    /// Buy discriminator (base amount out, max quote amount in)
+    pub const BUY_DISCRIMINATOR: [u8; 8] = [0x66, 0x06, 0x3d, 0x12, 0x01, 0xda, 0xeb, 0xea];
+    /// Sell discriminator (base amount in, min quote amount out)
+    pub const SELL_DISCRIMINATOR: [u8; 8] = [0x33, 0xe6, 0x85, 0xa4, 0x01, 0x7f, 0x83, 0xad];
+    /// Pool account discriminator (Anchor)
+    pub const POOL_DISCRIMINATOR: [u8; 8] = [0xf1, 0x9a, 0x6d, 0x04, 0x11, 0xb1, 0x6d, 0xbc];
+    /// Global config account discriminator (Anchor)
+    pub const GLOBAL_CONFIG_DISCRIMINATOR: [u8; 8] = [0x95, 0x08, 0x9c, 0xca, 0xa0, 0xfc, 0xb0, 0xd9];
+    
+    /// Offset of the base mint in a pool account
+    pub const POOL_BASE_MINT_OFFSET: usize = 43;
+    /// Offset of the quote mint in a pool account
+    pub const POOL_QUOTE_MINT_OFFSET: usize = 75;
+    
+    /// Pool Pump.fun creates when a bonding curve migrates
+    pub fn canonical_pool_address(mint: &Pubkey) -> Option<Pubkey> {
+        let pump = DexProgram::PumpFun.program_id()?;
+        let program = DexProgram::PumpSwap.program_id()?;
+        let wsol = Pubkey::from_str(WSOL_MINT).ok()?;
+        let authority = Pubkey::find_program_address(&[b"pool-authority", mint.as_ref()], &pump).0;
+        let seeds: [&[u8]; 5] = [b"pool", &0u16.to_le_bytes(), authority.as_ref(), mint.as_ref(), wsol.as_ref()];
+        Some(Pubkey::find_program_address(&seeds, &program).0)
+    }
+    
+    /// Decode a pool account
+    pub fn decode_pool(data: &[u8]) -> Option<Pool> {
+        if data.len() < 211 || data[0..8] != POOL_DISCRIMINATOR {
+            return None;
+        }
+        let pubkey = |offset: usize| data.get(offset..offset + 32).and_then(|bytes| Pubkey::try_from(bytes).ok());
+        Some(Pool {
+            creator: pubkey(11)?,
+            base_mint: pubkey(POOL_BASE_MINT_OFFSET)?,
+            quote_mint: pubkey(POOL_QUOTE_MINT_OFFSET)?,
+            lp_mint: pubkey(107)?,
+            pool_base_token_account: pubkey(139)?,
+            pool_quote_token_account: pubkey(171)?,
+            lp_supply: u64::from_le_bytes(data[203..211].try_into().ok()?),
+            // Pools created before creator fees have no coin creator
+            coin_creator: pubkey(211).unwrap_or_default(),
+        })
+    }
+    
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Pool {
+        pub creator: Pubkey,
+        pub base_mint: Pubkey,
+        pub quote_mint: Pubkey,
+        pub lp_mint: Pubkey,
+        /// Vault holding the pool's base tokens
+        pub pool_base_token_account: Pubkey,
+        /// Vault holding the pool's quote tokens
+        pub pool_quote_token_account: Pubkey,
+        pub lp_supply: u64,
+        /// Receives the creator fee on every trade
+        pub coin_creator: Pubkey,
+    }
+    
+    /// Decode the program's global config account
+    pub fn decode_global_config(data: &[u8]) -> Option<GlobalConfig> {
+        if data.len() < 313 || data[0..8] != GLOBAL_CONFIG_DISCRIMINATOR {
+            return None;
+        }
+        let read_u64 = |offset: usize| data.get(offset..offset + 8)?.try_into().ok().map(u64::from_le_bytes);
+        let protocol_fee_recipients = (0..8)
+            .filter_map(|i| Pubkey::try_from(&data[57 + i * 32..89 + i * 32]).ok())
+            .collect();
+        Some(GlobalConfig {
+            lp_fee_basis_points: read_u64(40)?,
+            protocol_fee_basis_points: read_u64(48)?,
+            protocol_fee_recipients,
+            coin_creator_fee_basis_points: read_u64(313).unwrap_or(0),
+        })
+    }
+    
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct GlobalConfig {
+        pub lp_fee_basis_points: u64,
+        pub protocol_fee_basis_points: u64,
+        pub protocol_fee_recipients: Vec<Pubkey>,
+        pub coin_creator_fee_basis_points: u64,
+    }
+    
+    impl GlobalConfig {
+        /// All fees taken from the quote side of a trade
+        pub fn total_fee_basis_points(&self) -> u64 {
+            self.lp_fee_basis_points + self.protocol_fee_basis_points + self.coin_creator_fee_basis_points
+        }
+    }
+}
+
+/// Meteora DLMM (liquidity book) instruction and account decoders
+pub mod meteora_dlmm {
+    use solana_sdk::pubkey::Pubkey;
+    use super::DexProgram;
+    
+    /// swap discriminator (amount in, minimum amount out)
+    pub const SWAP_DISCRIMINATOR: [u8; 8] = [0xf8, 0xc6, 0x9e, 0x91, 0xe1, 0x75, 0x87, 0xc8];
+    /// LbPair account discriminator (Anchor)
+    pub const LB_PAIR_DISCRIMINATOR: [u8; 8] = [0x21, 0x0b, 0x31, 0x62, 0xb5, 0x65, 0xb1, 0x0d];
+    /// BinArray account discriminator (Anchor)
+    pub const BIN_ARRAY_DISCRIMINATOR: [u8; 8] = [0x5c, 0x8e, 0x5c, 0xdc, 0x05, 0x94, 0x46, 0xb5];
+    
+    /// Bins per bin array
+    pub const MAX_BIN_PER_ARRAY: i32 = 70;
+    /// Fee rates are in billionths
+    pub const FEE_PRECISION: u64 = 1_000_000_000;
+    /// Fees are capped at 10%
+    pub const MAX_FEE_RATE: u64 = 100_000_000;
+    
+    /// Offset of `token_x_mint` in an LbPair account (for getProgramAccounts filters)
+    pub const TOKEN_X_MINT_OFFSET: usize = 88;
+    /// Offset of `token_y_mint` in an LbPair account
+    pub const TOKEN_Y_MINT_OFFSET: usize = 120;
+    
+    /// Size of one bin in a bin array account
+    const BIN_LEN: usize = 144;
+    /// Bin arrays tracked by the pair's own bitmap (either side of index 0)
+    const BITMAP_ARRAYS: i64 = 512;
+    
+    /// Decode an LbPair account
+    pub fn decode_lb_pair(data: &[u8]) -> Option<LbPair> {
+        if data.len() < 882 || data[0..8] != LB_PAIR_DISCRIMINATOR {
+            return None;
+        }
+        let pubkey = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).ok();
+        let mut bin_array_bitmap = [0u64; 16];
+        for (i, word) in bin_array_bitmap.iter_mut().enumerate() {
+            *word = u64::from_le_bytes(data[584 + i * 8..592 + i * 8].try_into().ok()?);
+        }
+        Some(LbPair {
+            base_factor: u16::from_le_bytes(data[8..10].try_into().ok()?),
+            variable_fee_control: u32::from_le_bytes(data[16..20].try_into().ok()?),
+            base_fee_power_factor: data[34],
+            volatility_accumulator: u32::from_le_bytes(data[40..44].try_into().ok()?),
+            active_id: i32::from_le_bytes(data[76..80].try_into().ok()?),
+            bin_step: u16::from_le_bytes(data[80..82].try_into().ok()?),
+            status: data[82],
+            token_x_mint: pubkey(TOKEN_X_MINT_OFFSET)?,
+            token_y_mint: pubkey(TOKEN_Y_MINT_OFFSET)?,
+            reserve_x: pubkey(152)?,
+            reserve_y: pubkey(184)?,
+            oracle: pubkey(552)?,
+            bin_array_bitmap,
+            token_x_is_token_2022: data[880] == 1,
+            token_y_is_token_2022: data[881] == 1,
+        })
+    }
+    
+    /// The pair fields quotes and swaps need
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct LbPair {
+        pub base_factor: u16,
+        pub variable_fee_control: u32,
+        pub base_fee_power_factor: u8,
+        pub volatility_accumulator: u32,
+        /// Bin the price is currently in
+        pub active_id: i32,
+        /// Price step between bins in basis points
+        pub bin_step: u16,
+        /// 0 when trading is enabled
+        pub status: u8,
+        pub token_x_mint: Pubkey,
+        pub token_y_mint: Pubkey,
+        pub reserve_x: Pubkey,
+        pub reserve_y: Pubkey,
+        pub oracle: Pubkey,
+        /// Initialized bin arrays within `BITMAP_ARRAYS` of index 0
+        pub bin_array_bitmap: [u64; 16],
+        pub token_x_is_token_2022: bool,
+        pub token_y_is_token_2022: bool,
+    }
+    
+    impl LbPair {
+        /// Raw Y per raw X in a bin
+        pub fn bin_price(&self, bin_id: i32) -> f64 {
+            (1.0 + self.bin_step as f64 / 10_000.0).powi(bin_id)
+        }
+        
+        /// Raw Y per raw X in the active bin
+        pub fn price(&self) -> f64 {
+            self.bin_price(self.active_id)
+        }
+        
+        /// Base plus variable fee in billionths, as of the pair's last swap
+        pub fn fee_rate(&self) -> u64 {
+            let base = self.base_factor as u64 * self.bin_step as u64 * 10 * 10u64.pow(self.base_fee_power_factor as u32);
+            let volatility = self.volatility_accumulator as u128 * self.bin_step as u128;
+            let variable = (volatility * volatility * self.variable_fee_control as u128).div_ceil(100_000_000_000) as u64;
+            (base + variable).min(MAX_FEE_RATE)
+        }
+        
+        /// Indexes of the first `count` initialized bin arrays in the swap direction,
+        /// starting with the array holding the active bin
+        pub fn initialized_bin_arrays(&self, swap_for_y: bool, count: usize) -> Vec<i64> {
+            let mut offset = bin_array_index(self.active_id) + BITMAP_ARRAYS;
+            let mut indexes = Vec::new();
+            while (0..BITMAP_ARRAYS * 2).contains(&offset) && indexes.len() < count {
+                if self.bin_array_bitmap[offset as usize / 64] >> (offset % 64) & 1 == 1 {
+                    indexes.push(offset - BITMAP_ARRAYS);
+                }
+                // Selling X for Y walks the price down
+                offset += if swap_for_y { -1 } else { 1 };
+            }
+            indexes
+        }
+    }
+    
+    /// Index of the bin array holding a bin
+    pub fn bin_array_index(bin_id: i32) -> i64 {
+        bin_id.div_euclid(MAX_BIN_PER_ARRAY) as i64
+    }
+    
+    /// Bin array PDA
+    pub fn bin_array_address(lb_pair: &Pubkey, index: i64) -> Option<Pubkey> {
+        let program = DexProgram::MeteoraDlmm.program_id()?;
+        Some(Pubkey::find_program_address(&[b"bin_array", lb_pair.as_ref(), &index.to_le_bytes()], &program).0)
+    }
+    
+    /// Anchor event authority the program self-invokes to emit events
+    pub fn event_authority_address() -> Option<Pubkey> {
+        let program = DexProgram::MeteoraDlmm.program_id()?;
+        Some(Pubkey::find_program_address(&[b"__event_authority"], &program).0)
+    }
+    
+    /// Decode a bin array account
+    pub fn decode_bin_array(data: &[u8]) -> Option<BinArray> {
+        let bins_end = 56 + MAX_BIN_PER_ARRAY as usize * BIN_LEN;
+        if data.len() < bins_end || data[0..8] != BIN_ARRAY_DISCRIMINATOR {
+            return None;
+        }
+        let bins = data[56..bins_end]
+            .chunks_exact(BIN_LEN)
+            .filter_map(|bin| {
+                Some(Bin {
+                    amount_x: u64::from_le_bytes(bin[0..8].try_into().ok()?),
+                    amount_y: u64::from_le_bytes(bin[8..16].try_into().ok()?),
+                })
+            })
+            .collect();
+        Some(BinArray {
+            index: i64::from_le_bytes(data[8..16].try_into().ok()?),
+            bins,
+        })
+    }
+    
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct BinArray {
+        pub index: i64,
+        pub bins: Vec<Bin>,
+    }
+    
+    impl BinArray {
+        /// Id of the first bin in the array
+        pub fn first_bin_id(&self) -> i32 {
+            (self.index * MAX_BIN_PER_ARRAY as i64) as i32
+        }
+    }
+    
+    /// Liquidity in a bin
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Bin {
+        pub amount_x: u64,
+        pub amount_y: u64,
+    }
+}
+
+/// Meteora dynamic AMM instruction and account decoders
+/// Pool liquidity sits in Meteora vaults; the pool holds vault LP tokens.
+pub mod meteora_amm {
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+    
+    /// Meteora vault program holding dynamic AMM liquidity
+    pub const VAULT_PROGRAM_ID: &str = "24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi";
+    
+    /// swap discriminator (amount in, minimum amount out)
+    pub const SWAP_DISCRIMINATOR: [u8; 8] = [0xf8, 0xc6, 0x9e, 0x91, 0xe1, 0x75, 0x87, 0xc8];
+    /// Pool account discriminator (Anchor)
+    pub const POOL_DISCRIMINATOR: [u8; 8] = [0xf1, 0x9a, 0x6d, 0x04, 0x11, 0xb1, 0x6d, 0xbc];
+    /// Vault account discriminator (Anchor)
+    pub const VAULT_DISCRIMINATOR: [u8; 8] = [0xd3, 0x08, 0xe8, 0x2b, 0x02, 0x98, 0x75, 0x77];
+    
+    /// Offset of `token_a_mint` in a pool account (for getProgramAccounts filters)
+    pub const TOKEN_A_MINT_OFFSET: usize = 40;
+    /// Offset of `token_b_mint` in a pool account
+    pub const TOKEN_B_MINT_OFFSET: usize = 72;
+    
+    /// Meteora vault program
+    pub fn vault_program_id() -> Option<Pubkey> {
+        Pubkey::from_str(VAULT_PROGRAM_ID).ok()
+    }
+    
+    /// Decode a pool account
+    pub fn decode_pool(data: &[u8]) -> Option<Pool> {
+        if data.len() < 363 || data[0..8] != POOL_DISCRIMINATOR {
+            return None;
+        }
+        let pubkey = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).ok();
+        let read_u64 = |offset: usize| data[offset..offset + 8].try_into().ok().map(u64::from_le_bytes);
+        Some(Pool {
+            token_a_mint: pubkey(TOKEN_A_MINT_OFFSET)?,
+            token_b_mint: pubkey(TOKEN_B_MINT_OFFSET)?,
+            a_vault: pubkey(104)?,
+            b_vault: pubkey(136)?,
+            a_vault_lp: pubkey(168)?,
+            b_vault_lp: pubkey(200)?,
+            enabled: data[233] != 0,
+            protocol_token_a_fee: pubkey(234)?,
+            protocol_token_b_fee: pubkey(266)?,
+            trade_fee_numerator: read_u64(330)?,
+            trade_fee_denominator: read_u64(338)?,
+        })
+    }
+    
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Pool {
+        pub token_a_mint: Pubkey,
+        pub token_b_mint: Pubkey,
+        pub a_vault: Pubkey,
+        pub b_vault: Pubkey,
+        /// The pool's LP token account in vault A
+        pub a_vault_lp: Pubkey,
+        /// The pool's LP token account in vault B
+        pub b_vault_lp: Pubkey,
+        pub enabled: bool,
+        pub protocol_token_a_fee: Pubkey,
+        pub protocol_token_b_fee: Pubkey,
+        pub trade_fee_numerator: u64,
+        pub trade_fee_denominator: u64,
+    }
+    
+    impl Pool {
+        /// Output of swapping `amount_in` against constant product reserves, after the trade fee
+        /// Stable-curve pools quote differently; they pair stablecoins, not SOL and a token.
+        pub fn quote_exact_input(&self, reserve_in: u64, reserve_out: u64, amount_in: u64) -> u64 {
+            let fee = (amount_in as u128 * self.trade_fee_numerator as u128)
+                .div_ceil(self.trade_fee_denominator.max(1) as u128)
+                .min(amount_in as u128);
+            let net_in = amount_in as u128 - fee;
+            (reserve_out as u128 * net_in / (reserve_in as u128 + net_in).max(1)) as u64
+        }
+    }
+    
+    /// Decode a Meteora vault account
+    pub fn decode_vault(data: &[u8]) -> Option<Vault> {
+        if data.len() < 147 || data[0..8] != VAULT_DISCRIMINATOR {
+            return None;
+        }
+        let pubkey = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).ok();
+        Some(Vault {
+            total_amount: u64::from_le_bytes(data[11..19].try_into().ok()?),
+            token_vault: pubkey(19)?,
+            token_mint: pubkey(83)?,
+            lp_mint: pubkey(115)?,
+        })
+    }
+    
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Vault {
+        /// Tokens the vault holds, including those lent out to strategies
+        pub total_amount: u64,
+        pub token_vault: Pubkey,
+        pub token_mint: Pubkey,
+        pub lp_mint: Pubkey,
+    }
+    
+    impl Vault {
+        /// Tokens backing `lp_amount` of the vault's `lp_supply` LP tokens
+        pub fn amount_for_lp(&self, lp_amount: u64, lp_supply: u64) -> u64 {
+            (self.total_amount as u128 * lp_amount as u128 / lp_supply.max(1) as u128) as u64
+        }
+    }
 }
 
 /// Orca Whirlpool instruction decoders
@@ -207,3 +1052,134 @@ pub mod orca {
         pub amount_specified_is_input: bool,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_decode_bonding_curve() {
+        let mut data = pumpfun::BONDING_CURVE_DISCRIMINATOR.to_vec();
+        for value in [1_000_000_000_000u64, 30_000_000_000, 800_000_000_000, 0, 1_000_000_000_000_000] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.push(0);
+        
+        let curve = pumpfun::decode_bonding_curve(&data).unwrap();
+        assert!(!curve.complete);
+        assert_eq!(curve.price(), Some(0.03));
+        assert_eq!(curve.creator, None);
+        let fresh = pumpfun::BondingCurve { real_token_reserves: pumpfun::INITIAL_REAL_TOKEN_RESERVES, ..curve.clone() };
+        assert_eq!(fresh.progress(), 0.0);
+        let half_sold = pumpfun::BondingCurve { real_token_reserves: pumpfun::INITIAL_REAL_TOKEN_RESERVES / 2, ..curve };
+        assert_eq!(half_sold.progress(), 50.0);
+        
+        let creator = Pubkey::new_unique();
+        data.extend_from_slice(creator.as_ref());
+        assert_eq!(pumpfun::decode_bonding_curve(&data).unwrap().creator, Some(creator));
+        
+        data[0] = 0;
+        assert!(pumpfun::decode_bonding_curve(&data).is_none());
+        assert!(pumpfun::bonding_curve_address(&Pubkey::new_unique()).is_some());
+    }
+    
+    #[test]
+    fn test_decode_pumpswap() {
+        let keys: Vec<Pubkey> = (0..7).map(|_| Pubkey::new_unique()).collect();
+        let mut data = pumpswap::POOL_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[255, 0, 0]);
+        for key in &keys[..6] {
+            data.extend_from_slice(key.as_ref());
+        }
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(keys[6].as_ref());
+        let pool = pumpswap::decode_pool(&data).unwrap();
+        assert_eq!((pool.base_mint, pool.quote_mint), (keys[1], keys[2]));
+        assert_eq!((pool.pool_quote_token_account, pool.lp_supply, pool.coin_creator), (keys[5], 1_000, keys[6]));
+        
+        assert_eq!(DexProgram::from_program_id(&DexProgram::PumpSwap.program_id().unwrap()), DexProgram::PumpSwap);
+    }
+    
+    #[test]
+    fn test_decode_raydium_cpmm() {
+        // Sell of 5_000 tokens for 2_000 lamports
+        let (pool, mint, user) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = raydium_cpmm::SWAP_EVENT_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(pool.as_ref());
+        for value in [95_000u64, 40_000, 5_000, 2_000, 0, 0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.push(1);
+        let legacy = raydium_cpmm::decode_swap_event(&data).unwrap();
+        assert_eq!((legacy.pool_id, legacy.input_mint), (pool, None));
+        assert!(legacy.to_trade_event(user).is_none());
+        
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(Pubkey::from_str(crate::decoder::WSOL_MINT).unwrap().as_ref());
+        let trade = raydium_cpmm::decode_swap_event(&data).unwrap().to_trade_event(user).unwrap();
+        assert_eq!((trade.mint, trade.is_buy, trade.sol_amount, trade.token_amount), (mint, false, 2_000, 5_000));
+        assert_eq!((trade.virtual_sol_reserves, trade.virtual_token_reserves), (38_000, 100_000));
+        
+        assert_eq!(raydium_cpmm::quote_exact_input(1_000_000, 1_000_000, 1_000, 2_500), 996);
+    }
+    
+    #[test]
+    fn test_decode_launchlab() {
+        // Buy of 1_000_000 lamports for 3_000_000 tokens
+        let pool = Pubkey::new_unique();
+        let mut data = launchlab::TRADE_EVENT_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(pool.as_ref());
+        for value in [793_100_000u64, 1_073_025_605, 30_000_852, 0, 0, 3_000_000, 1_000_000, 1_000_000, 3_000_000, 2_500, 0, 0, 0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&[0, 0, 1]);
+        let trade = launchlab::decode_trade_event(&data).unwrap();
+        assert_eq!(trade.pool_state, pool);
+        let mint = Pubkey::new_unique();
+        let event = trade.to_trade_event(mint, Pubkey::new_unique());
+        assert_eq!((event.mint, event.is_buy, event.sol_amount, event.token_amount), (mint, true, 1_000_000, 3_000_000));
+        assert_eq!(event.virtual_sol_reserves, 31_000_852);
+        
+        let mut data = launchlab::POOL_STATE_DISCRIMINATOR.to_vec();
+        data.resize(429, 0);
+        for (offset, value) in [(29, 793_100_000u64), (37, 1_073_025_605), (45, 30_000_852), (53, 3_000_000), (61, 1_000_000)] {
+            data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+        }
+        data[205..237].copy_from_slice(mint.as_ref());
+        let state = launchlab::decode_pool_state(&data).unwrap();
+        assert!(state.is_trading());
+        assert_eq!(state.base_mint, mint);
+        assert!((state.price().unwrap() - 31_000_852.0 / 1_070_025_605.0).abs() < 1e-12);
+        assert!(state.buy_quote(1_000_000, 2_500) < state.buy_quote(1_000_000, 0));
+        assert!(state.sell_quote(3_000_000, 0) <= 1_000_000);
+        assert_ne!(launchlab::pool_address(&mint, &Pubkey::new_unique()), None);
+    }
+    
+    #[test]
+    fn test_decode_meteora() {
+        // Bin step 25 with base factor 10_000 is a 0.25% base fee
+        let mut data = meteora_dlmm::LB_PAIR_DISCRIMINATOR.to_vec();
+        data.resize(904, 0);
+        data[8..10].copy_from_slice(&10_000u16.to_le_bytes());
+        data[76..80].copy_from_slice(&(-71i32).to_le_bytes());
+        data[80..82].copy_from_slice(&25u16.to_le_bytes());
+        data[584 + 63] = 0b1100_0000;
+        let pair = meteora_dlmm::decode_lb_pair(&data).unwrap();
+        assert_eq!(pair.fee_rate(), 2_500_000);
+        assert!((pair.price() - 1.0025f64.powi(-71)).abs() < 1e-12);
+        // Bin -71 is in array -2; arrays -2 and -1 (offsets 510 and 511) are initialized
+        assert_eq!(meteora_dlmm::bin_array_index(-71), -2);
+        assert_eq!(pair.initialized_bin_arrays(false, 3), vec![-2, -1]);
+        assert_eq!(pair.initialized_bin_arrays(true, 3), vec![-2]);
+        
+        let mut data = meteora_amm::POOL_DISCRIMINATOR.to_vec();
+        data.resize(944, 0);
+        data[233] = 1;
+        data[330..338].copy_from_slice(&25u64.to_le_bytes());
+        data[338..346].copy_from_slice(&10_000u64.to_le_bytes());
+        let pool = meteora_amm::decode_pool(&data).unwrap();
+        assert!(pool.enabled);
+        assert_eq!(pool.quote_exact_input(1_000_000, 1_000_000, 1_000), 996);
+        assert_eq!(DexProgram::from_program_id(&DexProgram::MeteoraDlmm.program_id().unwrap()), DexProgram::MeteoraDlmm);
+    }
+}
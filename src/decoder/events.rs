@@ -0,0 +1,266 @@
+//! Pump.fun Anchor events decoded from `Program data:` log lines
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use super::DexProgram;
+
+/// Log prefix Anchor uses for emitted events
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// `TradeEvent` discriminator
+pub const TRADE_EVENT_DISCRIMINATOR: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+/// `CreateEvent` discriminator
+pub const CREATE_EVENT_DISCRIMINATOR: [u8; 8] = [27, 114, 169, 77, 222, 235, 99, 118];
+/// `CompleteEvent` discriminator
+pub const COMPLETE_EVENT_DISCRIMINATOR: [u8; 8] = [95, 114, 97, 156, 212, 46, 152, 8];
+
+/// A buy or sell on the bonding curve, with the reserves after it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeEvent {
+    pub mint: Pubkey,
+    /// Lamports paid or received
+    pub sol_amount: u64,
+    /// Raw tokens bought or sold
+    pub token_amount: u64,
+    pub is_buy: bool,
+    pub user: Pubkey,
+    pub timestamp: i64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+}
+
+impl TradeEvent {
+    /// Average fill price in lamports per raw token
+    pub fn fill_price(&self) -> Option<f64> {
+        (self.token_amount > 0).then(|| self.sol_amount as f64 / self.token_amount as f64)
+    }
+    
+    /// Curve price after the trade in lamports per raw token
+    pub fn post_trade_price(&self) -> Option<f64> {
+        (self.virtual_token_reserves > 0)
+            .then(|| self.virtual_sol_reserves as f64 / self.virtual_token_reserves as f64)
+    }
+}
+
+/// A new token launched on Pump.fun
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateEvent {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub user: Pubkey,
+}
+
+/// A bonding curve filled up and stopped trading (the token migrates)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompleteEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Pump.fun events the bot understands
+#[derive(Debug, Clone, PartialEq)]
+pub enum PumpEvent {
+    Trade(TradeEvent),
+    Create(CreateEvent),
+    Complete(CompleteEvent),
+}
+
+impl PumpEvent {
+    /// Decode event bytes (discriminator followed by the Borsh-encoded fields)
+    /// Fields appended by newer program versions are ignored.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let (discriminator, body) = data.split_first_chunk::<8>()?;
+        let mut reader = Reader(body);
+        match *discriminator {
+            TRADE_EVENT_DISCRIMINATOR => Some(PumpEvent::Trade(TradeEvent {
+                mint: reader.pubkey()?,
+                sol_amount: reader.u64()?,
+                token_amount: reader.u64()?,
+                is_buy: reader.bool()?,
+                user: reader.pubkey()?,
+                timestamp: reader.i64()?,
+                virtual_sol_reserves: reader.u64()?,
+                virtual_token_reserves: reader.u64()?,
+            })),
+            CREATE_EVENT_DISCRIMINATOR => Some(PumpEvent::Create(CreateEvent {
+                name: reader.string()?,
+                symbol: reader.string()?,
+                uri: reader.string()?,
+                mint: reader.pubkey()?,
+                bonding_curve: reader.pubkey()?,
+                user: reader.pubkey()?,
+            })),
+            COMPLETE_EVENT_DISCRIMINATOR => Some(PumpEvent::Complete(CompleteEvent {
+                user: reader.pubkey()?,
+                mint: reader.pubkey()?,
+                bonding_curve: reader.pubkey()?,
+                timestamp: reader.i64()?,
+            })),
+            _ => None,
+        }
+    }
+}
+
+/// Every Pump.fun event in a transaction's logs, in order
+/// Other programs emitting events with the same names (LaunchLab's `TradeEvent`) are skipped.
+pub fn pump_events(logs: &[String]) -> Vec<PumpEvent> {
+    let Some(pump) = DexProgram::PumpFun.program_id() else { return Vec::new() };
+    program_data(logs)
+        .into_iter()
+        .filter(|(program, _)| *program == pump)
+        .filter_map(|(_, data)| PumpEvent::decode(&data))
+        .collect()
+}
+
+/// Every `Program data:` payload in a transaction's logs with the program that logged it
+/// The emitting program is tracked through the `invoke` / `success` / `failed` lines.
+pub fn program_data(logs: &[String]) -> Vec<(Pubkey, Vec<u8>)> {
+    let mut stack: Vec<Pubkey> = Vec::new();
+    let mut payloads = Vec::new();
+    for line in logs {
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if let (Some(program), Ok(data)) = (stack.last(), BASE64.decode(data.trim())) {
+                payloads.push((*program, data));
+            }
+            continue;
+        }
+        let mut words = line.split_whitespace();
+        let (Some("Program"), Some(program), Some(status)) = (words.next(), words.next(), words.next()) else { continue };
+        match status {
+            "invoke" => stack.extend(Pubkey::from_str(program).ok()),
+            "success" | "failed:" => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    payloads
+}
+
+/// Borsh field reader over event bytes
+pub(crate) struct Reader<'a>(pub(crate) &'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (bytes, rest) = self.0.split_first_chunk::<N>()?;
+        self.0 = rest;
+        Some(*bytes)
+    }
+    
+    pub(crate) fn u64(&mut self) -> Option<u64> {
+        self.take().map(u64::from_le_bytes)
+    }
+    
+    pub(crate) fn i64(&mut self) -> Option<i64> {
+        self.take().map(i64::from_le_bytes)
+    }
+    
+    pub(crate) fn bool(&mut self) -> Option<bool> {
+        self.take::<1>().map(|[byte]| byte != 0)
+    }
+    
+    pub(crate) fn pubkey(&mut self) -> Option<Pubkey> {
+        self.take::<32>().map(Pubkey::new_from_array)
+    }
+    
+    pub(crate) fn string(&mut self) -> Option<String> {
+        let len = u32::from_le_bytes(self.take()?) as usize;
+        if len > self.0.len() {
+            return None;
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn data_log(bytes: &[u8]) -> String {
+        format!("{}{}", PROGRAM_DATA_PREFIX, BASE64.encode(bytes))
+    }
+    
+    #[test]
+    fn test_decode_trade_event() {
+        let (mint, user) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = TRADE_EVENT_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(&500_000_000u64.to_le_bytes());
+        data.extend_from_slice(&17_000_000_000u64.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(user.as_ref());
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        data.extend_from_slice(&31_000_000_000u64.to_le_bytes());
+        data.extend_from_slice(&1_000_000_000_000u64.to_le_bytes());
+        // Newer program versions append more fields
+        data.extend_from_slice(&[0; 16]);
+        
+        let logs = vec![
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P invoke [1]".to_string(),
+            "Program log: Instruction: Buy".to_string(),
+            data_log(&data),
+        ];
+        let events = pump_events(&logs);
+        let PumpEvent::Trade(trade) = &events[0] else { panic!("expected a trade: {:?}", events) };
+        assert_eq!((trade.mint, trade.user, trade.is_buy), (mint, user, true));
+        assert_eq!((trade.sol_amount, trade.token_amount), (500_000_000, 17_000_000_000));
+        assert_eq!(trade.post_trade_price(), Some(0.031));
+        
+        // Truncated events are rejected
+        assert!(PumpEvent::decode(&data[..60]).is_none());
+    }
+    
+    #[test]
+    fn test_decode_create_and_complete_events() {
+        let (mint, curve, user) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = CREATE_EVENT_DISCRIMINATOR.to_vec();
+        for field in ["Token", "TKN", "https://example.com/tkn.json"] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field.as_bytes());
+        }
+        for key in [mint, curve, user] {
+            data.extend_from_slice(key.as_ref());
+        }
+        let Some(PumpEvent::Create(create)) = PumpEvent::decode(&data) else { panic!() };
+        assert_eq!((create.symbol.as_str(), create.mint, create.user), ("TKN", mint, user));
+        
+        let mut data = COMPLETE_EVENT_DISCRIMINATOR.to_vec();
+        for key in [user, mint, curve] {
+            data.extend_from_slice(key.as_ref());
+        }
+        data.extend_from_slice(&42i64.to_le_bytes());
+        let Some(PumpEvent::Complete(complete)) = PumpEvent::decode(&data) else { panic!() };
+        assert_eq!((complete.mint, complete.bonding_curve, complete.timestamp), (mint, curve, 42));
+    }
+    
+    #[test]
+    fn test_program_data_follows_invokes() {
+        let pump = DexProgram::PumpFun.program_id().unwrap();
+        let other = Pubkey::new_unique();
+        let mut data = COMPLETE_EVENT_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[7; 104]);
+        let logs = vec![
+            format!("Program {} invoke [1]", pump),
+            format!("Program {} invoke [2]", other),
+            data_log(&data),
+            format!("Program {} success", other),
+            data_log(&data),
+            format!("Program {} consumed 30000 of 200000 compute units", pump),
+            format!("Program {} success", pump),
+            data_log(&data),
+        ];
+        let payloads = program_data(&logs);
+        assert_eq!(payloads.iter().map(|(program, _)| *program).collect::<Vec<_>>(), vec![other, pump]);
+        // Only the event logged by Pump.fun itself counts
+        assert_eq!(pump_events(&logs).len(), 1);
+    }
+}
@@ -2,6 +2,8 @@
 
 pub mod parser;
 pub mod dex;
+pub mod events;
 
 pub use parser::*;
 pub use dex::*;
+pub use events::*;
@@ -3,12 +3,19 @@
 use anyhow::Result;
 use solana_sdk::pubkey::Pubkey;
 use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::signature::Signature;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, UiInstruction, UiTransactionEncoding,
+    UiTransactionStatusMeta,
+};
+use std::collections::HashMap;
 use std::str::FromStr;
 use tracing::debug;
 
 use super::dex::DexProgram;
+use super::events::TradeEvent;
 
 /// Represents a detected trade action
 #[derive(Debug, Clone)]
@@ -27,6 +34,10 @@ pub struct DetectedTrade {
     pub signature: String,
     /// Accounts involved in the instruction
     pub accounts: Vec<Pubkey>,
+    /// Slot the transaction landed in
+    pub slot: u64,
+    /// Unix time of the block, when the node reports it
+    pub block_time: Option<i64>,
 }
 
 /// Trade direction
@@ -37,6 +48,49 @@ pub enum TradeType {
     Unknown,
 }
 
+/// What a wallet's SOL (including WSOL) and token balance changes in one transaction add up to
+#[derive(Debug, Clone, PartialEq)]
+pub enum BalanceSwap {
+    /// SOL spent on or received for a single mint
+    Trade { trade_type: TradeType, mint: Pubkey, sol_amount: u64, token_amount: u64 },
+    /// One mint sold for another; any SOL change is fees and rent
+    TokenSwap { sold: Pubkey, sold_amount: u64, bought: Pubkey, bought_amount: u64 },
+    /// Several mints bought or sold at once, which does not read as one trade
+    Ambiguous { sol_delta: i128, token_deltas: Vec<(Pubkey, i128)> },
+}
+
+/// A wallet's swap in a fetched transaction, read from its balance changes
+#[derive(Debug, Clone)]
+pub struct WalletSwap {
+    pub swap: BalanceSwap,
+    /// First known DEX program invoked, Unknown for venues without a decoder
+    pub dex: DexProgram,
+    pub signature: String,
+    pub accounts: Vec<Pubkey>,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+}
+
+impl WalletSwap {
+    /// The swap as a trade event of `user`, None unless it is a SOL trade
+    /// Balance changes carry no pool reserves, so those are left at zero.
+    pub fn to_trade_event(&self, user: Pubkey) -> Option<TradeEvent> {
+        let BalanceSwap::Trade { trade_type, mint, sol_amount, token_amount } = self.swap else {
+            return None;
+        };
+        Some(TradeEvent {
+            mint,
+            sol_amount,
+            token_amount,
+            is_buy: trade_type == TradeType::Buy,
+            user,
+            timestamp: self.block_time.unwrap_or_default(),
+            virtual_sol_reserves: 0,
+            virtual_token_reserves: 0,
+        })
+    }
+}
+
 /// Native SOL mint (wrapped SOL)
 pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
 
@@ -56,9 +110,14 @@ impl TransactionParser {
             known_dex_programs: vec![
                 DexProgram::RaydiumAmm.program_id().unwrap(),
                 DexProgram::RaydiumClmm.program_id().unwrap(),
+                DexProgram::RaydiumCpmm.program_id().unwrap(),
+                DexProgram::RaydiumLaunchLab.program_id().unwrap(),
                 DexProgram::Jupiter.program_id().unwrap(),
                 DexProgram::PumpFun.program_id().unwrap(),
+                DexProgram::PumpSwap.program_id().unwrap(),
                 DexProgram::OrcaWhirlpool.program_id().unwrap(),
+                DexProgram::MeteoraDlmm.program_id().unwrap(),
+                DexProgram::MeteoraDynamicAmm.program_id().unwrap(),
             ],
             rpc_client,
         }
@@ -68,23 +127,283 @@ impl TransactionParser {
     pub fn parse_transaction(
         &self,
         signature: &str,
-        _target_wallet: &Pubkey,
+        target_wallet: &Pubkey,
     ) -> Result<Vec<DetectedTrade>> {
         // Fetch transaction details
         let sig = Signature::from_str(signature)?;
-        let _tx = self.rpc_client.get_transaction(&sig, solana_transaction_status::UiTransactionEncoding::JsonParsed)?;
-        
-        let trades = Vec::new();
+        let tx = self.rpc_client.get_transaction_with_config(&sig, transaction_config())?;
         
-        // For now, just return empty until we implement full parsing
-        // In a real implementation, you would:
-        // 1. Parse transaction.message.instructions
-        // 2. Identify DEX programs
-        // 3. Decode instruction data
-        // 4. Extract trade details
+        let trades: Vec<DetectedTrade> = decode_wallet_trade(&tx, target_wallet).into_iter().collect();
         
-        debug!("Transaction parsed: {} (placeholder implementation)", signature);
+        debug!("Transaction parsed: {} ({} trade(s))", signature, trades.len());
         
         Ok(trades)
     }
 }
+
+/// `getTransaction` settings the decoder expects (binary encoding, v0 transactions)
+pub fn transaction_config() -> RpcTransactionConfig {
+    RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    }
+}
+
+/// Decode the trade `wallet` made in a fetched transaction from its balance changes
+/// SOL (including WSOL) swapped for exactly one other mint is a trade; failed transactions,
+/// transfers and token-to-token swaps return None. The DEX is the first known program invoked.
+pub fn decode_wallet_trade(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    wallet: &Pubkey,
+) -> Option<DetectedTrade> {
+    let swap = decode_wallet_swap(tx, wallet)?;
+    let BalanceSwap::Trade { trade_type, mint, sol_amount, token_amount } = swap.swap else {
+        return None;
+    };
+    Some(DetectedTrade {
+        dex: swap.dex,
+        trade_type,
+        token_mint: Some(mint),
+        sol_amount: Some(sol_amount),
+        token_amount: Some(token_amount),
+        signature: swap.signature,
+        accounts: swap.accounts,
+        slot: swap.slot,
+        block_time: swap.block_time,
+    })
+}
+
+/// Decode whatever swap `wallet` made in a fetched transaction from its balance changes
+/// Works for any venue, decoded or not; failed transactions and transfers return None.
+pub fn decode_wallet_swap(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    wallet: &Pubkey,
+) -> Option<WalletSwap> {
+    let meta = tx.transaction.meta.as_ref()?;
+    if meta.err.is_some() {
+        return None;
+    }
+    let transaction = tx.transaction.transaction.decode()?;
+    let accounts = account_keys(transaction.message.static_account_keys(), meta);
+    
+    // The fee and the rent of token accounts opened for the wallet are not part of the trade
+    let index = accounts.iter().position(|key| key == wallet)?;
+    let fee = if index == 0 { meta.fee as i128 } else { 0 };
+    let sol_delta = *meta.post_balances.get(index)? as i128 - *meta.pre_balances.get(index)? as i128
+        + fee
+        + created_account_rent(meta, wallet) as i128;
+    let swap = classify_swap(sol_delta, &token_deltas(meta, wallet))?;
+    
+    let inner_programs = Option::<&Vec<_>>::from(meta.inner_instructions.as_ref())
+        .into_iter()
+        .flatten()
+        .flat_map(|inner| inner.instructions.iter())
+        .filter_map(|instruction| match instruction {
+            UiInstruction::Compiled(compiled) => Some(compiled.program_id_index),
+            _ => None,
+        });
+    let dex = transaction.message.instructions().iter()
+        .map(|instruction| instruction.program_id_index)
+        .chain(inner_programs)
+        .filter_map(|program_index| accounts.get(program_index as usize))
+        .map(DexProgram::from_program_id)
+        .find(|dex| *dex != DexProgram::Unknown)
+        .unwrap_or(DexProgram::Unknown);
+    
+    Some(WalletSwap {
+        swap,
+        dex,
+        signature: transaction.signatures.first()?.to_string(),
+        accounts,
+        slot: tx.slot,
+        block_time: tx.block_time,
+    })
+}
+
+/// SOL change (fee excluded) and raw token change per mint of `wallet` in a fetched transaction
+/// Unlike `decode_wallet_swap` this keeps transfers; failed transactions return None.
+pub fn decode_wallet_deltas(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    wallet: &Pubkey,
+) -> Option<(i128, HashMap<String, i128>)> {
+    let meta = tx.transaction.meta.as_ref()?;
+    if meta.err.is_some() {
+        return None;
+    }
+    let transaction = tx.transaction.transaction.decode()?;
+    let accounts = account_keys(transaction.message.static_account_keys(), meta);
+    let index = accounts.iter().position(|key| key == wallet)?;
+    let fee = if index == 0 { meta.fee as i128 } else { 0 };
+    let sol_delta = *meta.post_balances.get(index)? as i128 - *meta.pre_balances.get(index)? as i128 + fee;
+    Some((sol_delta, token_deltas(meta, wallet)))
+}
+
+/// Static account keys followed by addresses loaded from lookup tables
+fn account_keys(static_keys: &[Pubkey], meta: &UiTransactionStatusMeta) -> Vec<Pubkey> {
+    let mut keys = static_keys.to_vec();
+    if let Some(loaded) = Option::<&_>::from(meta.loaded_addresses.as_ref()) {
+        keys.extend(
+            loaded.writable.iter()
+                .chain(loaded.readonly.iter())
+                .filter_map(|key| Pubkey::from_str(key).ok()),
+        );
+    }
+    keys
+}
+
+/// Lamports held by the token accounts `wallet` owns that were opened in the transaction
+/// An account with no pre token balance and no lamports before holds only its rent afterwards.
+fn created_account_rent(meta: &UiTransactionStatusMeta, wallet: &Pubkey) -> u64 {
+    let owner = wallet.to_string();
+    let pre: Vec<u8> = Option::<&Vec<_>>::from(meta.pre_token_balances.as_ref())
+        .into_iter()
+        .flatten()
+        .map(|balance| balance.account_index)
+        .collect();
+    Option::<&Vec<_>>::from(meta.post_token_balances.as_ref())
+        .into_iter()
+        .flatten()
+        .filter(|balance| Option::<&String>::from(balance.owner.as_ref()) == Some(&owner))
+        .map(|balance| balance.account_index)
+        .filter(|account_index| !pre.contains(account_index))
+        .filter_map(|account_index| {
+            let account_index = account_index as usize;
+            (*meta.pre_balances.get(account_index)? == 0).then(|| meta.post_balances.get(account_index).copied())?
+        })
+        .sum()
+}
+
+/// Change in raw token amount per mint across the token accounts `wallet` owns
+fn token_deltas(meta: &UiTransactionStatusMeta, wallet: &Pubkey) -> HashMap<String, i128> {
+    let owner = wallet.to_string();
+    let mut deltas: HashMap<String, i128> = HashMap::new();
+    for (balances, sign) in [(&meta.pre_token_balances, -1), (&meta.post_token_balances, 1)] {
+        for balance in Option::<&Vec<_>>::from(balances.as_ref()).into_iter().flatten() {
+            if Option::<&String>::from(balance.owner.as_ref()) != Some(&owner) {
+                continue;
+            }
+            let amount = balance.ui_token_amount.amount.parse::<i128>().unwrap_or(0);
+            *deltas.entry(balance.mint.clone()).or_insert(0) += sign * amount;
+        }
+    }
+    deltas
+}
+
+/// Classify SOL and token balance changes as a swap
+/// WSOL counts as SOL, and mints that net to zero (the middle hops of a route through the
+/// wallet's own accounts) are ignored. SOL for one mint is a trade, one mint for another a
+/// token swap; more than one mint bought or sold is ambiguous. Anything else (a transfer, or
+/// SOL and the token moving the same way) is not a swap and returns None.
+pub fn classify_swap(sol_delta: i128, token_deltas: &HashMap<String, i128>) -> Option<BalanceSwap> {
+    let sol_delta = sol_delta + token_deltas.get(WSOL_MINT).copied().unwrap_or(0);
+    let mut changed: Vec<(Pubkey, i128)> = token_deltas.iter()
+        .filter(|(mint, delta)| mint.as_str() != WSOL_MINT && **delta != 0)
+        .filter_map(|(mint, delta)| Some((Pubkey::from_str(mint).ok()?, *delta)))
+        .collect();
+    changed.sort();
+    let bought: Vec<(Pubkey, u64)> = changed.iter()
+        .filter(|(_, delta)| *delta > 0)
+        .filter_map(|(mint, delta)| Some((*mint, u64::try_from(*delta).ok()?)))
+        .collect();
+    let sold: Vec<(Pubkey, u64)> = changed.iter()
+        .filter(|(_, delta)| *delta < 0)
+        .filter_map(|(mint, delta)| Some((*mint, u64::try_from(delta.unsigned_abs()).ok()?)))
+        .collect();
+    let sol_amount = u64::try_from(sol_delta.unsigned_abs()).ok()?;
+    
+    match (bought.as_slice(), sold.as_slice()) {
+        ([(mint, token_amount)], []) if sol_delta < 0 => Some(BalanceSwap::Trade {
+            trade_type: TradeType::Buy,
+            mint: *mint,
+            sol_amount,
+            token_amount: *token_amount,
+        }),
+        ([], [(mint, token_amount)]) if sol_delta > 0 => Some(BalanceSwap::Trade {
+            trade_type: TradeType::Sell,
+            mint: *mint,
+            sol_amount,
+            token_amount: *token_amount,
+        }),
+        ([(bought, bought_amount)], [(sold, sold_amount)]) => Some(BalanceSwap::TokenSwap {
+            sold: *sold,
+            sold_amount: *sold_amount,
+            bought: *bought,
+            bought_amount: *bought_amount,
+        }),
+        (bought, sold) if bought.len() + sold.len() > 1 => Some(BalanceSwap::Ambiguous { sol_delta, token_deltas: changed }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_classify_swap() {
+        let mint = Pubkey::new_unique();
+        
+        // SOL down, token up is a buy (WSOL counts as SOL)
+        let deltas = HashMap::from([(mint.to_string(), 1_000), (WSOL_MINT.to_string(), -400)]);
+        assert_eq!(
+            classify_swap(-100, &deltas),
+            Some(BalanceSwap::Trade { trade_type: TradeType::Buy, mint, sol_amount: 500, token_amount: 1_000 })
+        );
+        
+        // A route through another mint that nets to zero is still a sell
+        let hop = Pubkey::new_unique();
+        let deltas = HashMap::from([(mint.to_string(), -1_000), (hop.to_string(), 0)]);
+        assert_eq!(
+            classify_swap(750, &deltas),
+            Some(BalanceSwap::Trade { trade_type: TradeType::Sell, mint, sol_amount: 750, token_amount: 1_000 })
+        );
+        
+        // Token for token, paying rent for the new account
+        let deltas = HashMap::from([(mint.to_string(), 10), (hop.to_string(), -20)]);
+        assert_eq!(
+            classify_swap(-2_039_280, &deltas),
+            Some(BalanceSwap::TokenSwap { sold: hop, sold_amount: 20, bought: mint, bought_amount: 10 })
+        );
+        
+        // Two mints bought with SOL is flagged rather than guessed
+        let deltas = HashMap::from([(mint.to_string(), 10), (hop.to_string(), 5)]);
+        assert!(matches!(classify_swap(-5_000, &deltas), Some(BalanceSwap::Ambiguous { token_deltas, .. }) if token_deltas.len() == 2));
+        
+        // Transfers are not swaps
+        assert_eq!(classify_swap(-5_000, &HashMap::from([(mint.to_string(), -10)])), None);
+        assert_eq!(classify_swap(0, &HashMap::from([(mint.to_string(), 10)])), None);
+        assert_eq!(classify_swap(-5_000, &HashMap::new()), None);
+    }
+    
+    #[test]
+    fn test_created_account_rent() {
+        let wallet = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let mint = Pubkey::new_unique().to_string();
+        let balance = |index: u8, owner: &Pubkey, amount: &str| serde_json::json!({
+            "accountIndex": index,
+            "mint": mint,
+            "uiTokenAmount": { "uiAmount": null, "decimals": 6, "amount": amount, "uiAmountString": "0" },
+            "owner": owner.to_string(),
+        });
+        
+        // Account 1 is the wallet's new ATA, 2 an existing one, 3 someone else's new account
+        let meta: UiTransactionStatusMeta = serde_json::from_value(serde_json::json!({
+            "err": null,
+            "status": { "Ok": null },
+            "fee": 105_000,
+            "preBalances": [1_000_000_000u64, 0, 2_039_280, 0],
+            "postBalances": [897_855_720u64, 2_039_280, 2_039_280, 2_039_280],
+            "preTokenBalances": [balance(2, &wallet, "5")],
+            "postTokenBalances": [balance(1, &wallet, "1000"), balance(2, &wallet, "5"), balance(3, &other, "7")],
+        })).unwrap();
+        assert_eq!(created_account_rent(&meta, &wallet), 2_039_280);
+        
+        // Fee and rent left out, the buy spent 0.1 SOL
+        let sol_delta = meta.post_balances[0] as i128 - meta.pre_balances[0] as i128
+            + meta.fee as i128
+            + created_account_rent(&meta, &wallet) as i128;
+        assert_eq!(sol_delta, -100_000_000);
+    }
+}
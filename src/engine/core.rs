@@ -1,41 +1,74 @@
 //! Core trading engine implementation
 
 use anyhow::Result;
-use solana_sdk::signature::Keypair;
 use std::sync::Arc;
 use tracing::info;
 
+use crate::api::PositionSeller;
 use crate::config::Config;
 use crate::grpc::{HeliusGrpcClient, HeliusClientBuilder};
-use crate::state::StateManager;
+use crate::notify::NotificationDispatcher;
+use crate::signer::WalletPool;
+use crate::state::{BotControl, StateManager};
+use crate::token::ReclaimConfig;
 
 /// Core trading engine that orchestrates the copytrading logic
 pub struct TradingEngine {
-    helius_client: HeliusGrpcClient,
+    helius_client: Arc<HeliusGrpcClient>,
 }
 
 impl TradingEngine {
     /// Create a new trading engine
-    pub async fn new(config: &Config, keypair: Arc<Keypair>, _state: Arc<StateManager>) -> Result<Self> {
+    pub async fn new(
+        config: &Config,
+        wallets: Arc<WalletPool>,
+        state: Arc<StateManager>,
+        notifier: Arc<NotificationDispatcher>,
+    ) -> Result<Self> {
         // Build Helius WebSocket client with trading parameters
         let helius_client = HeliusClientBuilder::new()
             .endpoint(&config.helius_grpc_url)
             .api_key(&config.helius_api_key)
             .target_wallet(config.target_wallet)
-            .keypair(keypair)
+            .wallets(wallets)
+            .state(state)
+            .notifier(notifier)
             .buy_amount_sol(config.buy_amount_sol)
             .tip_amount(config.tip_amount_normal)
             .take_profit_enabled(config.take_profit_enabled)
             .take_profit_tiers(config.take_profit_tiers.clone())
+            .risk_limits(config.risk_limits.clone())
+            .circuit_breaker(config.circuit_breaker)
+            .entry_gate(config.entry_gate.clone())
+            .copy_quote_mode(config.copy_quote_mode)
+            .safety(config.safety.clone())
+            .emergency_tip(config.tip_amount_emergency)
+            .ata_reclaim(config.auto_close_ata.then(|| ReclaimConfig {
+                interval: std::time::Duration::from_secs(config.ata_reclaim_interval_secs),
+                burn_dust_below: config.ata_burn_dust_below,
+            }))
+            .nonce_pool_enabled(config.nonce_pool_enabled)
+            .nonce_pool_spare(config.nonce_pool_spare)
+            .nonce_pool_max(config.nonce_pool_max)
             .reconnect_delay_ms(config.reconnect_delay_ms)
             .max_reconnect_attempts(config.max_reconnect_attempts)
             .build()?;
         
         Ok(Self {
-            helius_client,
+            helius_client: Arc::new(helius_client),
         })
     }
     
+    /// Runtime controls (pause, target wallets, take profit tiers)
+    pub fn control(&self) -> Arc<BotControl> {
+        self.helius_client.control()
+    }
+    
+    /// Seller used for operator-requested sells
+    pub fn seller(&self) -> Arc<dyn PositionSeller> {
+        self.helius_client.clone()
+    }
+    
     /// Run the trading engine
     pub async fn run(self) -> Result<()> {
         info!("🚀 Starting trading engine...");
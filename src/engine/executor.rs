@@ -3,21 +3,23 @@
 use anyhow::{Result, Context};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
+    account::Account,
+    address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
-    signature::{Keypair, Signer},
     system_instruction,
     transaction::Transaction,
-    program_pack::Pack,
 };
-use spl_token::state::Account as TokenAccount;
 use std::sync::Arc;
 use tracing::{info, warn, error, debug};
 
 use crate::config::Config;
 use crate::decoder::DexProgram;
 use crate::jito::{JitoClient, BundleBuilder, TipLevel, TipConfig};
+use crate::signer::WalletSigner;
 use crate::state::StateManager;
+use crate::swap::{fetch_accounts, launchlab, meteora_amm, meteora_dlmm, raydium_clmm, raydium_cpmm, whirlpool, wsol_mint};
+use crate::token::{TokenAccountService, TokenProgram};
 
 /// Result of a buy execution
 #[derive(Debug)]
@@ -41,6 +43,7 @@ pub struct TradeExecutor {
     jito_client: JitoClient,
     rpc_client: RpcClient,
     bundle_builder: BundleBuilder,
+    token_accounts: TokenAccountService,
     state: Arc<StateManager>,
 }
 
@@ -48,6 +51,7 @@ impl TradeExecutor {
     /// Create a new trade executor
     pub fn new(
         config: Config,
+        signer: Arc<WalletSigner>,
         jito_client: JitoClient,
         state: Arc<StateManager>,
     ) -> Result<Self> {
@@ -59,26 +63,48 @@ impl TradeExecutor {
             config.tip_amount_max,
         );
         
-        // Clone the keypair for the bundle builder
-        let keypair_bytes = config.keypair.to_bytes();
-        let keypair = Keypair::from_bytes(&keypair_bytes)?;
+        let lookup_tables = Self::load_lookup_tables(&rpc_client, &config.address_lookup_tables)?;
         
         let bundle_builder = BundleBuilder::new(
-            keypair,
+            signer,
             tip_config,
             config.compute_unit_limit,
             config.priority_fee_micro_lamports,
-        );
+        ).with_lookup_tables(lookup_tables);
+        
+        let token_accounts = TokenAccountService::new(config.solana_rpc_url.clone());
         
         Ok(Self {
             config,
             jito_client,
             rpc_client,
             bundle_builder,
+            token_accounts,
             state,
         })
     }
     
+    /// Fetch and decode address lookup tables from chain
+    fn load_lookup_tables(
+        rpc_client: &RpcClient,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<AddressLookupTableAccount>> {
+        addresses.iter()
+            .map(|key| {
+                let account = rpc_client.get_account(key)
+                    .with_context(|| format!("Failed to fetch lookup table {}", key))?;
+                let table = AddressLookupTable::deserialize(&account.data)
+                    .map_err(|e| anyhow::anyhow!("Invalid lookup table {}: {:?}", key, e))?;
+                
+                info!("Loaded address lookup table {} ({} addresses)", key, table.addresses.len());
+                Ok(AddressLookupTableAccount {
+                    key: *key,
+                    addresses: table.addresses.to_vec(),
+                })
+            })
+            .collect()
+    }
+    
     /// Execute a buy order
     pub async fn execute_buy(
         &self,
@@ -105,6 +131,24 @@ impl TradeExecutor {
             DexProgram::PumpFun => {
                 self.build_pumpfun_buy_instructions(token_mint, sol_amount, reference_accounts)?
             }
+            DexProgram::RaydiumClmm => {
+                self.build_raydium_clmm_instructions(token_mint, sol_amount, true, reference_accounts)?
+            }
+            DexProgram::OrcaWhirlpool => {
+                self.build_whirlpool_instructions(token_mint, sol_amount, true, reference_accounts)?
+            }
+            DexProgram::RaydiumCpmm => {
+                self.build_raydium_cpmm_instructions(token_mint, sol_amount, true, reference_accounts)?
+            }
+            DexProgram::RaydiumLaunchLab => {
+                self.build_launchlab_instructions(token_mint, sol_amount, true)?
+            }
+            DexProgram::MeteoraDlmm => {
+                self.build_meteora_dlmm_instructions(token_mint, sol_amount, true, reference_accounts)?
+            }
+            DexProgram::MeteoraDynamicAmm => {
+                self.build_meteora_amm_instructions(token_mint, sol_amount, true, reference_accounts)?
+            }
             DexProgram::Jupiter => {
                 self.build_jupiter_buy_instructions(token_mint, sol_amount, reference_accounts)?
             }
@@ -149,7 +193,11 @@ impl TradeExecutor {
         tip_level: TipLevel,
     ) -> Result<SellResult> {
         // Check current balance to determine if this is a full sell
-        let current_balance = self.get_token_balance(&token_mint).await.unwrap_or(0);
+        let balance = self.token_accounts
+            .balance(&self.bundle_builder.pubkey(), &token_mint)
+            .await
+            .unwrap_or_default();
+        let current_balance = balance.amount;
         let is_full_sell = token_amount >= current_balance && current_balance > 0;
         
         info!(
@@ -169,6 +217,24 @@ impl TradeExecutor {
             DexProgram::PumpFun => {
                 self.build_pumpfun_sell_instructions(token_mint, token_amount, reference_accounts)?
             }
+            DexProgram::RaydiumClmm => {
+                self.build_raydium_clmm_instructions(token_mint, token_amount, false, reference_accounts)?
+            }
+            DexProgram::OrcaWhirlpool => {
+                self.build_whirlpool_instructions(token_mint, token_amount, false, reference_accounts)?
+            }
+            DexProgram::RaydiumCpmm => {
+                self.build_raydium_cpmm_instructions(token_mint, token_amount, false, reference_accounts)?
+            }
+            DexProgram::RaydiumLaunchLab => {
+                self.build_launchlab_instructions(token_mint, token_amount, false)?
+            }
+            DexProgram::MeteoraDlmm => {
+                self.build_meteora_dlmm_instructions(token_mint, token_amount, false, reference_accounts)?
+            }
+            DexProgram::MeteoraDynamicAmm => {
+                self.build_meteora_amm_instructions(token_mint, token_amount, false, reference_accounts)?
+            }
             DexProgram::Jupiter => {
                 self.build_jupiter_sell_instructions(token_mint, token_amount, reference_accounts)?
             }
@@ -178,9 +244,9 @@ impl TradeExecutor {
         };
         
         // If selling 100%, add instruction to close the ATA and recover rent
-        if is_full_sell {
+        if let (true, Some(program)) = (is_full_sell, balance.program()) {
             info!("📦 Adding close ATA instruction to recover ~0.002 SOL rent");
-            let close_ix = self.build_close_ata_instruction(&token_mint);
+            let close_ix = self.build_close_ata_instruction(&token_mint, program)?;
             instructions.push(close_ix);
         }
         
@@ -290,6 +356,165 @@ impl TradeExecutor {
         Ok(vec![])
     }
     
+    /// Build a Raydium CLMM swap between SOL and `token_mint` (SOL in when buying)
+    /// The pool is taken from the target transaction's accounts.
+    fn build_raydium_clmm_instructions(
+        &self,
+        token_mint: Pubkey,
+        amount_in: u64,
+        is_buy: bool,
+        reference_accounts: &[Pubkey],
+    ) -> Result<Vec<Instruction>> {
+        let accounts = self.fetch_reference_accounts(reference_accounts)?;
+        let (pool_address, pool) = raydium_clmm::find_pool(&accounts, &token_mint, &wsol_mint())
+            .with_context(|| format!("No Raydium CLMM pool for {} among the reference accounts", token_mint))?;
+        let input_mint = if is_buy { wsol_mint() } else { token_mint };
+        let (instructions, quote) = raydium_clmm::swap_instructions(
+            &self.rpc_client,
+            &self.bundle_builder.pubkey(),
+            &pool_address,
+            &pool,
+            &input_mint,
+            amount_in,
+            self.config.slippage_bps as u64,
+        )?;
+        debug!("Raydium CLMM quote via {}: {} in, {} out, {} ticks crossed", pool_address, quote.amount_in, quote.amount_out, quote.ticks_crossed);
+        Ok(instructions)
+    }
+    
+    /// Build an Orca Whirlpool swap between SOL and `token_mint` (SOL in when buying)
+    /// The whirlpool is taken from the target transaction's accounts.
+    fn build_whirlpool_instructions(
+        &self,
+        token_mint: Pubkey,
+        amount_in: u64,
+        is_buy: bool,
+        reference_accounts: &[Pubkey],
+    ) -> Result<Vec<Instruction>> {
+        let accounts = self.fetch_reference_accounts(reference_accounts)?;
+        let (whirlpool_address, whirlpool) = whirlpool::find_pool(&accounts, &token_mint, &wsol_mint())
+            .with_context(|| format!("No Orca whirlpool for {} among the reference accounts", token_mint))?;
+        let input_mint = if is_buy { wsol_mint() } else { token_mint };
+        let (instructions, quote) = whirlpool::swap_instructions(
+            &self.rpc_client,
+            &self.bundle_builder.pubkey(),
+            &whirlpool_address,
+            &whirlpool,
+            &input_mint,
+            amount_in,
+            self.config.slippage_bps as u64,
+        )?;
+        debug!("Whirlpool quote via {}: {} in, {} out, {} ticks crossed", whirlpool_address, quote.amount_in, quote.amount_out, quote.ticks_crossed);
+        Ok(instructions)
+    }
+    
+    /// Build a Raydium CPMM swap between SOL and `token_mint` (SOL in when buying)
+    /// The pool is taken from the target transaction's accounts.
+    fn build_raydium_cpmm_instructions(
+        &self,
+        token_mint: Pubkey,
+        amount_in: u64,
+        is_buy: bool,
+        reference_accounts: &[Pubkey],
+    ) -> Result<Vec<Instruction>> {
+        let accounts = self.fetch_reference_accounts(reference_accounts)?;
+        let (pool_address, pool) = raydium_cpmm::find_pool(&accounts, &token_mint, &wsol_mint())
+            .with_context(|| format!("No Raydium CPMM pool for {} among the reference accounts", token_mint))?;
+        let input_mint = if is_buy { wsol_mint() } else { token_mint };
+        let (instructions, amount_out) = raydium_cpmm::swap_instructions(
+            &self.rpc_client,
+            &self.bundle_builder.pubkey(),
+            &pool_address,
+            &pool,
+            &input_mint,
+            amount_in,
+            self.config.slippage_bps as u64,
+        )?;
+        debug!("Raydium CPMM quote via {}: {} in, {} out", pool_address, amount_in, amount_out);
+        Ok(instructions)
+    }
+    
+    /// Build a LaunchLab bonding curve buy (`amount_in` lamports) or sell (`amount_in` tokens)
+    /// The pool is derived from the mint, so no reference accounts are needed.
+    fn build_launchlab_instructions(
+        &self,
+        token_mint: Pubkey,
+        amount_in: u64,
+        is_buy: bool,
+    ) -> Result<Vec<Instruction>> {
+        let (pool_address, pool) = launchlab::fetch_pool(&self.rpc_client, &token_mint)?
+            .with_context(|| format!("{} has no SOL LaunchLab pool", token_mint))?;
+        let (instructions, amount_out) = launchlab::swap_instructions(
+            &self.rpc_client,
+            &self.bundle_builder.pubkey(),
+            &pool_address,
+            &pool,
+            is_buy,
+            amount_in,
+            self.config.slippage_bps as u64,
+        )?;
+        debug!("LaunchLab quote via {}: {} in, {} out", pool_address, amount_in, amount_out);
+        Ok(instructions)
+    }
+    
+    /// Build a Meteora DLMM swap between SOL and `token_mint` (SOL in when buying)
+    /// The pair is taken from the target transaction's accounts.
+    fn build_meteora_dlmm_instructions(
+        &self,
+        token_mint: Pubkey,
+        amount_in: u64,
+        is_buy: bool,
+        reference_accounts: &[Pubkey],
+    ) -> Result<Vec<Instruction>> {
+        let accounts = self.fetch_reference_accounts(reference_accounts)?;
+        let (pair_address, pair) = meteora_dlmm::find_pair(&accounts, &token_mint, &wsol_mint())
+            .with_context(|| format!("No Meteora DLMM pair for {} among the reference accounts", token_mint))?;
+        let input_mint = if is_buy { wsol_mint() } else { token_mint };
+        let (instructions, quote) = meteora_dlmm::swap_instructions(
+            &self.rpc_client,
+            &self.bundle_builder.pubkey(),
+            &pair_address,
+            &pair,
+            &input_mint,
+            amount_in,
+            self.config.slippage_bps as u64,
+        )?;
+        debug!("Meteora DLMM quote via {}: {} in, {} out, {} bins crossed", pair_address, quote.amount_in, quote.amount_out, quote.bins_crossed);
+        Ok(instructions)
+    }
+    
+    /// Build a Meteora dynamic AMM swap between SOL and `token_mint` (SOL in when buying)
+    /// The pool is taken from the target transaction's accounts.
+    fn build_meteora_amm_instructions(
+        &self,
+        token_mint: Pubkey,
+        amount_in: u64,
+        is_buy: bool,
+        reference_accounts: &[Pubkey],
+    ) -> Result<Vec<Instruction>> {
+        let accounts = self.fetch_reference_accounts(reference_accounts)?;
+        let (pool_address, pool) = meteora_amm::find_pool(&accounts, &token_mint, &wsol_mint())
+            .with_context(|| format!("No Meteora dynamic AMM pool for {} among the reference accounts", token_mint))?;
+        let input_mint = if is_buy { wsol_mint() } else { token_mint };
+        let (instructions, amount_out) = meteora_amm::swap_instructions(
+            &self.rpc_client,
+            &self.bundle_builder.pubkey(),
+            &pool_address,
+            &pool,
+            &input_mint,
+            amount_in,
+            self.config.slippage_bps as u64,
+        )?;
+        debug!("Meteora dynamic AMM quote via {}: {} in, {} out", pool_address, amount_in, amount_out);
+        Ok(instructions)
+    }
+    
+    /// Reference accounts paired with their on-chain state
+    fn fetch_reference_accounts(&self, reference_accounts: &[Pubkey]) -> Result<Vec<(Pubkey, Option<Account>)>> {
+        let accounts = fetch_accounts(&self.rpc_client, reference_accounts)?;
+        Ok(reference_accounts.iter().copied().zip(accounts).collect())
+    }
+    
     /// Build Jupiter aggregator buy instructions (SOL -> Token)
     fn build_jupiter_buy_instructions(
         &self,
@@ -326,11 +551,6 @@ impl TradeExecutor {
         Ok(vec![])
     }
     
-    /// Get the associated token account for a wallet and mint
-    fn get_associated_token_address(&self, wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
-        spl_associated_token_account::get_associated_token_address(wallet, mint)
-    }
-    
     /// Check if we have enough SOL balance
     pub async fn check_sol_balance(&self, required_lamports: u64) -> Result<bool> {
         let balance = self.rpc_client.get_balance(&self.bundle_builder.pubkey())
@@ -342,59 +562,40 @@ impl TradeExecutor {
         Ok(balance >= required_with_buffer)
     }
     
-    /// Get token balance for a specific mint
+    /// Get token balance for a specific mint (SPL Token or Token-2022)
     pub async fn get_token_balance(&self, mint: &Pubkey) -> Result<u64> {
-        let token_account = self.get_associated_token_address(
-            &self.bundle_builder.pubkey(),
-            mint,
-        );
-        
-        match self.rpc_client.get_token_account_balance(&token_account) {
-            Ok(balance) => {
-                Ok(balance.amount.parse().unwrap_or(0))
-            }
-            Err(_) => Ok(0), // Account doesn't exist
-        }
+        let balance = self.token_accounts.balance(&self.bundle_builder.pubkey(), mint).await?;
+        Ok(balance.amount)
     }
     
     /// Build instruction to close an empty token account and recover rent
-    pub fn build_close_ata_instruction(&self, token_mint: &Pubkey) -> Instruction {
-        let token_account = self.get_associated_token_address(
-            &self.bundle_builder.pubkey(),
-            token_mint,
-        );
+    pub fn build_close_ata_instruction(&self, token_mint: &Pubkey, program: TokenProgram) -> Result<Instruction> {
+        let owner = self.bundle_builder.pubkey();
+        let token_account = program.associated_token_address(&owner, token_mint);
         
-        spl_token::instruction::close_account(
-            &spl_token::id(),
-            &token_account,
-            &self.bundle_builder.pubkey(), // SOL destination (recover rent here)
-            &self.bundle_builder.pubkey(), // Owner
-            &[],
-        ).expect("Failed to create close account instruction")
+        // Rent goes back to the owner
+        program.close_account(&token_account, &owner, &owner)
     }
     
     /// Close an empty ATA and recover the rent (~0.002 SOL)
     pub async fn close_empty_ata(&self, token_mint: &Pubkey) -> Result<String> {
         // First check if the account exists and is empty
-        let balance = self.get_token_balance(token_mint).await?;
+        let (token_account, program) = self.token_accounts
+            .associated_token_address(&self.bundle_builder.pubkey(), token_mint)
+            .await?;
+        
+        let balance = match self.rpc_client.get_token_account_balance(&token_account) {
+            Ok(balance) => balance.amount.parse::<u64>().unwrap_or(0),
+            Err(_) => anyhow::bail!("Token account does not exist"),
+        };
         
         if balance > 0 {
             anyhow::bail!("Cannot close ATA with balance > 0. Current balance: {}", balance);
         }
         
-        let token_account = self.get_associated_token_address(
-            &self.bundle_builder.pubkey(),
-            token_mint,
-        );
-        
-        // Check if account exists
-        if self.rpc_client.get_account(&token_account).is_err() {
-            anyhow::bail!("Token account does not exist");
-        }
-        
-        info!("Closing empty ATA: {} for mint: {}", token_account, token_mint);
+        info!("Closing empty {} ATA: {} for mint: {}", program, token_account, token_mint);
         
-        let close_ix = self.build_close_ata_instruction(token_mint);
+        let close_ix = self.build_close_ata_instruction(token_mint, program)?;
         
         let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
         
@@ -423,29 +624,13 @@ impl TradeExecutor {
         
         let owner = self.bundle_builder.pubkey();
         
-        // Get all token accounts for this wallet
-        let token_accounts = self.rpc_client
-            .get_token_accounts_by_owner(
-                &owner,
-                solana_client::rpc_request::TokenAccountsFilter::ProgramId(spl_token::id()),
-            )
-            .context("Failed to fetch token accounts")?;
-        
-        let mut empty_accounts: Vec<(Pubkey, Pubkey)> = Vec::new(); // (token_account, mint)
-        
-        for account in &token_accounts {
-            let account_pubkey = Pubkey::from_str(&account.pubkey)
-                .context("Invalid account pubkey")?;
-            
-            // Decode the token account to get mint and balance
-            if let Some(data) = &account.account.data.decode() {
-                if let Ok(token_account) = TokenAccount::unpack(data) {
-                    if token_account.amount == 0 {
-                        empty_accounts.push((account_pubkey, token_account.mint));
-                    }
-                }
-            }
-        }
+        // Get all token accounts for this wallet (SPL Token and Token-2022)
+        let empty_accounts: Vec<_> = self.token_accounts
+            .accounts(&owner)
+            .await?
+            .into_iter()
+            .filter(|account| account.amount == 0)
+            .collect();
         
         if empty_accounts.is_empty() {
             info!("✨ No empty token accounts found");
@@ -454,7 +639,6 @@ impl TradeExecutor {
         
         info!("Found {} empty token accounts to close", empty_accounts.len());
         
-        let rent_per_account: u64 = 2_039_280; // ~0.00203928 SOL per ATA
         let mut closed_count = 0;
         let mut total_recovered: u64 = 0;
         
@@ -462,15 +646,8 @@ impl TradeExecutor {
         for chunk in empty_accounts.chunks(5) {
             let mut instructions: Vec<Instruction> = Vec::new();
             
-            for (token_account, _mint) in chunk {
-                let close_ix = spl_token::instruction::close_account(
-                    &spl_token::id(),
-                    token_account,
-                    &owner,
-                    &owner,
-                    &[],
-                )?;
-                instructions.push(close_ix);
+            for account in chunk {
+                instructions.push(account.program.close_account(&account.address, &owner, &owner)?);
             }
             
             let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
@@ -484,7 +661,8 @@ impl TradeExecutor {
             match self.jito_client.submit_bundle(&bundle).await {
                 Ok(result) if result.success => {
                     closed_count += chunk.len();
-                    total_recovered += rent_per_account * chunk.len() as u64;
+                    // Rent differs per account (Token-2022 extensions make accounts larger)
+                    total_recovered += chunk.iter().map(|account| account.lamports).sum::<u64>();
                     info!("✅ Closed {} accounts in batch", chunk.len());
                 }
                 Ok(result) => {
@@ -509,4 +687,3 @@ impl TradeExecutor {
     }
 }
 
-use std::str::FromStr;
@@ -9,25 +9,54 @@ use futures::StreamExt;
 use solana_client::nonblocking::pubsub_client::PubsubClient;
 use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
 use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter, RpcTransactionConfig};
+use solana_client::rpc_response::{Response, RpcLogsResponse};
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::signature::{Keypair, Signer, Signature};
+use solana_sdk::signature::{Signer, Signature};
 use solana_sdk::transaction::VersionedTransaction;
-use solana_transaction_status::UiTransactionEncoding;
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
 use std::str::FromStr;
 use std::time::Duration;
 use std::sync::Arc;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tokio::time::sleep;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, Mutex, Notify, RwLock};
+use dashmap::DashMap;
 use tracing::{info, warn, error, debug};
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 
+use crate::api::PositionSeller;
 use crate::config::TakeProfitTier;
+use crate::metrics::{
+    metrics, ENDPOINT_JUPITER_QUOTE, ENDPOINT_JUPITER_SWAP, ENDPOINT_PUMPPORTAL_QUOTE,
+    ENDPOINT_PUMPPORTAL_TRADE, OUTCOME_DROPPED, OUTCOME_FAILED, OUTCOME_LANDED,
+};
+use crate::state::{BotControl, Position, StateManager, TargetEvent, TradeRecordType};
+use crate::notify::{NotificationDispatcher, NotifyEvent};
+use crate::decoder::{
+    decode_wallet_deltas, decode_wallet_swap, launchlab::{self, LaunchLabTrade}, program_data, pump_events, pumpfun, raydium_cpmm,
+    transaction_config, BalanceSwap, DexProgram, PumpEvent, TradeEvent,
+};
+use crate::risk::{BreakerConfig, CircuitBreakers, EntryDecision, EntryGate, RiskLimits, RiskManager, Venue};
+use crate::safety::{
+    creator_outflows, creator_sells, simulate_round_trip, CreatorMove, HoldWatcher, MintRisks, RoundTrip, SafetyAlert,
+    SafetyConfig, SafetyIssue,
+};
+use crate::signer::{WalletPool, WalletSigner};
+use crate::swap::{CopyQuoteMode, MeteoraPools, NativeRoute, NativeSwaps, PumpSwap, QuoteAsset, QuotedTrade};
+use crate::token::{AtaReclaimer, ReclaimConfig, TokenAccountService, TokenInfoService, TokenProgram};
+use crate::jito::TipLevel;
+use crate::nonce::{NoncePool, PresignedSell, apply_durable_nonce, fetch_message_lookup_tables};
 
 /// Pump.fun program ID
 pub const PUMPFUN_PROGRAM: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
 
+/// Pump.fun AMM (PumpSwap) program ID
+pub const PUMPSWAP_PROGRAM: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
+
+/// Raydium LaunchLab bonding curve program ID
+pub const LAUNCHLAB_PROGRAM: &str = "LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj";
+
 /// Wrapped SOL mint
 pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
 
@@ -42,6 +71,45 @@ pub const JITO_FRANKFURT_BLOCK_ENGINE: &str = "https://frankfurt.mainnet.block-e
 pub const JITO_NY_BLOCK_ENGINE: &str = "https://ny.mainnet.block-engine.jito.wtf";
 pub const JITO_TOKYO_BLOCK_ENGINE: &str = "https://tokyo.mainnet.block-engine.jito.wtf";
 
+/// How often pre-signed emergency sells are checked and rebuilt
+const PRESIGN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Rebuild pre-signed sells older than this so their quote stays close to market
+const PRESIGNED_SELL_MAX_AGE: Duration = Duration::from_secs(30);
+
+/// How long to wait for a pre-signed sell to settle before reading the advanced nonce
+const NONCE_ROTATE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to wait for a sent transaction to appear on chain before counting it as dropped
+const LANDING_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Slippage for swaps built directly against a pool (same as our Jupiter quotes)
+const NATIVE_SWAP_SLIPPAGE_BPS: u64 = 2500;
+
+/// Programs a plain transfer or account setup invokes; a transaction calling anything else may
+/// be a swap on a venue without a decoder
+const NON_SWAP_PROGRAMS: [&str; 6] = [
+    "11111111111111111111111111111111",
+    "ComputeBudget111111111111111111111111111111",
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+    "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr",
+];
+
+/// Attempts at fetching a target transaction for its balance changes, which only becomes
+/// available once confirmed (the log stream is at processed)
+const SWAP_FETCH_ATTEMPTS: u32 = 10;
+const SWAP_FETCH_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Why a wallet's logs are subscribed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Watched {
+    Target,
+    /// Creator of a held token
+    Creator,
+}
+
 /// Position info for take profit tracking
 #[derive(Debug, Clone)]
 pub struct PositionInfo {
@@ -51,16 +119,67 @@ pub struct PositionInfo {
     pub entry_time: std::time::Instant,
     /// Total percent of the original position already sold via TP (0-100)
     pub sold_percent: u8,
+    /// Wallet holding the tokens (signs every sell of this position)
+    pub wallet: Arc<WalletSigner>,
 }
 
 /// Detected trade action from logs
 #[derive(Debug, Clone)]
 pub enum DetectedAction {
-    Buy { signature: String, slot: u64 },
-    Sell { signature: String, slot: u64 },
+    /// `trade` is the target's trade from a Pump.fun or Raydium CPMM event, None if the logs
+    /// were truncated. LaunchLab events name the pool rather than the mint, so they arrive as
+    /// `launchlab` and are turned into a `trade` once the pool is read. Trades priced in a
+    /// stablecoin carry its SOL value as `sol_amount` and name the stablecoin in `quote`. A buy
+    /// on a pool we can build swaps for carries its `route`.
+    Buy {
+        signature: String,
+        slot: u64,
+        trade: Option<TradeEvent>,
+        launchlab: Option<LaunchLabTrade>,
+        quote: QuoteAsset,
+        route: Option<NativeRoute>,
+    },
+    Sell { signature: String, slot: u64, trade: Option<TradeEvent>, launchlab: Option<LaunchLabTrade>, quote: QuoteAsset },
+    /// A token swapped straight into another, both legs valued in SOL
+    Rotate { signature: String, slot: u64, sell: TradeEvent, buy: TradeEvent },
+    /// No trade event, but a program that may have swapped was invoked; the transaction is
+    /// fetched and classified by the target's balance changes
+    Swap { signature: String, slot: u64 },
     Unknown { signature: String, slot: u64 },
 }
 
+/// A target's action read from its balance changes, and when its logs arrived
+#[derive(Debug)]
+struct BalanceAction {
+    target: Pubkey,
+    action: DetectedAction,
+    detected_at: std::time::Instant,
+}
+
+/// Moves of held tokens read from a creator's balance changes, which are fetched off the stream loop
+#[derive(Debug)]
+struct CreatorMoves {
+    creator: Pubkey,
+    signature: String,
+    moves: Vec<CreatorMove>,
+}
+
+/// Handles shared by the take profit and pre-signed sell monitors, which run in their own tasks
+#[derive(Clone)]
+struct MonitorContext {
+    positions: Arc<RwLock<HashMap<String, PositionInfo>>>,
+    rpc_url: String,
+    control: Arc<BotControl>,
+    state: Arc<StateManager>,
+    notifier: Arc<NotificationDispatcher>,
+    breakers: Arc<CircuitBreakers>,
+    token_accounts: Arc<TokenAccountService>,
+    reclaimer: Option<Arc<AtaReclaimer>>,
+    pumpswap: Arc<PumpSwap>,
+    meteora: Arc<MeteoraPools>,
+    native: Arc<NativeSwaps>,
+}
+
 /// Represents a transaction log notification
 #[derive(Debug, Clone)]
 pub struct TransactionUpdate {
@@ -76,22 +195,65 @@ pub struct TransactionUpdate {
 pub struct HeliusGrpcClient {
     ws_url: String,
     rpc_url: String,
-    target_wallet: Pubkey,
-    our_keypair: Arc<Keypair>,
-    buy_amount_sol: f64,
-    tip_amount: u64,
+    /// Main wallet (nonce authority for pre-signed sells)
+    signer: Arc<WalletSigner>,
+    /// Wallets copy buys are spread across
+    wallets: Arc<WalletPool>,
     reconnect_delay: Duration,
     max_reconnect_attempts: u32,
     /// Active positions for take profit monitoring (token_mint -> PositionInfo)
     positions: Arc<RwLock<HashMap<String, PositionInfo>>>,
-    /// Take profit enabled
-    take_profit_enabled: bool,
-    /// Take profit tiers (multiplier + cumulative sell_percent)
-    take_profit_tiers: Vec<TakeProfitTier>,
+    /// Runtime controls (pause, target wallets, buy size, tip, take profit tiers)
+    control: Arc<BotControl>,
+    /// Position and trade history shared with the control API
+    state: Arc<StateManager>,
+    /// Trade and lifecycle notifications
+    notifier: Arc<NotificationDispatcher>,
+    /// Limits checked before every copy buy
+    risk: Arc<RiskManager>,
+    /// Per-venue circuit breakers for PumpPortal, Jupiter and RPC sends
+    breakers: Arc<CircuitBreakers>,
+    /// Slot lag and price move limits on copy buys
+    entry_gate: EntryGate,
+    /// Whether copies of stablecoin trades pay in SOL or the same stablecoin
+    copy_quote_mode: CopyQuoteMode,
+    /// Rug and honeypot checks before entry and while holding
+    safety: SafetyConfig,
+    /// Tip paid on emergency exits
+    emergency_tip: u64,
+    /// Rug signals from the hold watcher, handled in the stream loop
+    safety_alerts: mpsc::UnboundedSender<SafetyAlert>,
+    safety_alert_receiver: Arc<Mutex<mpsc::UnboundedReceiver<SafetyAlert>>>,
+    /// Target transactions classified by their balance changes, which are fetched off the
+    /// stream loop
+    balance_actions: mpsc::UnboundedSender<BalanceAction>,
+    balance_action_receiver: Arc<Mutex<mpsc::UnboundedReceiver<BalanceAction>>>,
+    /// Creator of each held mint, from CreateEvents and token info (mint -> creator)
+    creators: Arc<DashMap<Pubkey, Pubkey>>,
+    creator_moves: mpsc::UnboundedSender<CreatorMoves>,
+    creator_move_receiver: Arc<Mutex<mpsc::UnboundedReceiver<CreatorMoves>>>,
+    /// Wakes the stream loop to subscribe to a held token's newly known creator
+    creators_changed: Arc<Notify>,
+    /// Balances across SPL Token and Token-2022 accounts
+    token_accounts: Arc<TokenAccountService>,
+    /// Metadata, authorities and holder distribution of bought tokens
+    token_info: Arc<TokenInfoService>,
+    /// Closes empty token accounts after full sells and on an interval (None = disabled)
+    reclaimer: Option<Arc<AtaReclaimer>>,
+    /// Pools of graduated Pump.fun tokens, used once their bonding curve completes
+    pumpswap: Arc<PumpSwap>,
+    /// Meteora DLMM and dynamic AMM pools, priced for take profit
+    meteora: Arc<MeteoraPools>,
+    /// Swaps built against the pool the target traded on, and the routes of held tokens
+    native: Arc<NativeSwaps>,
     /// Use Jito Block Engine for MEV priority
     use_jito: bool,
     /// Jito Block Engine URL
     jito_url: String,
+    /// Durable nonce pool for pre-signed emergency sells (None = disabled)
+    nonce_pool: Option<Arc<NoncePool>>,
+    /// Pre-signed emergency sells (token_mint -> PresignedSell)
+    presigned_sells: Arc<RwLock<HashMap<String, PresignedSell>>>,
 }
 
 impl HeliusGrpcClient {
@@ -100,7 +262,7 @@ impl HeliusGrpcClient {
         _endpoint: String,
         api_key: String,
         target_wallet: Pubkey,
-        our_keypair: Arc<Keypair>,
+        signer: Arc<WalletSigner>,
         buy_amount_sol: f64,
         tip_amount: u64,
         reconnect_delay_ms: u64,
@@ -122,20 +284,59 @@ impl HeliusGrpcClient {
             format!("https://mainnet.helius-rpc.com/?api-key={}", api_key)
         };
         
+        let state = Arc::new(StateManager::new());
+        let notifier = Arc::new(NotificationDispatcher::disabled());
+        let risk = Arc::new(RiskManager::new(RiskLimits::default(), state.clone(), notifier.clone(), rpc_url.clone()));
+        let token_accounts = Arc::new(TokenAccountService::new(rpc_url.clone()));
+        let pumpswap = Arc::new(PumpSwap::new(rpc_url.clone(), token_accounts.clone()));
+        let meteora = Arc::new(MeteoraPools::new(rpc_url.clone()));
+        let native = Arc::new(NativeSwaps::new(rpc_url.clone(), NATIVE_SWAP_SLIPPAGE_BPS));
+        let token_info = Arc::new(TokenInfoService::new(rpc_url.clone()));
+        let (safety_alerts, safety_alert_receiver) = mpsc::unbounded_channel();
+        let (balance_actions, balance_action_receiver) = mpsc::unbounded_channel();
+        let (creator_moves, creator_move_receiver) = mpsc::unbounded_channel();
+        
         Self {
             ws_url,
             rpc_url,
-            target_wallet,
-            our_keypair,
-            buy_amount_sol,
-            tip_amount,
+            wallets: Arc::new(WalletPool::single(signer.clone())),
+            signer,
             reconnect_delay: Duration::from_millis(reconnect_delay_ms),
             max_reconnect_attempts,
             positions: Arc::new(RwLock::new(HashMap::new())),
-            take_profit_enabled,
-            take_profit_tiers,
+            control: Arc::new(BotControl::new(
+                vec![target_wallet],
+                buy_amount_sol,
+                tip_amount,
+                take_profit_enabled,
+                take_profit_tiers,
+            )),
+            state,
+            notifier,
+            risk,
+            breakers: Arc::new(CircuitBreakers::default()),
+            entry_gate: EntryGate::default(),
+            copy_quote_mode: CopyQuoteMode::default(),
+            safety: SafetyConfig::default(),
+            emergency_tip: tip_amount,
+            safety_alerts,
+            safety_alert_receiver: Arc::new(Mutex::new(safety_alert_receiver)),
+            balance_actions,
+            balance_action_receiver: Arc::new(Mutex::new(balance_action_receiver)),
+            creators: Arc::new(DashMap::new()),
+            creator_moves,
+            creator_move_receiver: Arc::new(Mutex::new(creator_move_receiver)),
+            creators_changed: Arc::new(Notify::new()),
+            token_accounts,
+            token_info,
+            reclaimer: None,
+            pumpswap,
+            meteora,
+            native,
             use_jito: false, // Disabled - was "Jito light", not real bundles
             jito_url: JITO_MAINNET_BLOCK_ENGINE.to_string(),
+            nonce_pool: None,
+            presigned_sells: Arc::new(RwLock::new(HashMap::new())),
         }
     }
     
@@ -154,31 +355,62 @@ impl HeliusGrpcClient {
         };
         
         info!("📤 Sending transaction...");
-        let sig = rpc_client.send_transaction_with_config(versioned_tx, config).await?;
+        let sig = send_rpc_transaction(&self.breakers, rpc_client, versioned_tx, config).await?;
         info!("✅ TX sent: {}", sig);
+        metrics().tip_lamports.inc_by(self.control.tip_amount());
         
         // Fire and forget - don't wait for confirmation (adds 2-30 seconds latency)
         // User can check on Solscan if needed
         Ok(sig.to_string())
     }
     
+    /// Handles for the monitors spawned by `stream_transactions`
+    fn monitor_context(&self) -> MonitorContext {
+        MonitorContext {
+            positions: self.positions.clone(),
+            rpc_url: self.rpc_url.clone(),
+            control: self.control.clone(),
+            state: self.state.clone(),
+            notifier: self.notifier.clone(),
+            breakers: self.breakers.clone(),
+            token_accounts: self.token_accounts.clone(),
+            reclaimer: self.reclaimer.clone(),
+            pumpswap: self.pumpswap.clone(),
+            meteora: self.meteora.clone(),
+            native: self.native.clone(),
+        }
+    }
+    
     /// Start streaming transactions and take profit monitoring
     pub async fn stream_transactions(
         &self,
     ) -> Result<()> {
-        // Spawn take profit monitor task (only if enabled and tiers are provided)
-        if self.take_profit_enabled && !self.take_profit_tiers.is_empty() {
-            let positions = self.positions.clone();
-            let rpc_url = self.rpc_url.clone();
-            let keypair = self.our_keypair.clone();
-            let tip_amount = self.tip_amount;
-            let tiers = self.take_profit_tiers.clone();
-
-            tokio::spawn(async move {
-                Self::take_profit_monitor(positions, rpc_url, keypair, tip_amount, tiers).await;
-            });
-        } else {
-            info!("📈 Take Profit disabled (TAKE_PROFIT_ENABLED=false or no tiers)");
+        // Spawn take profit monitor task (tiers, tip and the enabled flag are read from control each pass)
+        let context = self.monitor_context();
+        tokio::spawn(Self::take_profit_monitor(context.clone()));
+        
+        // Spawn the rent reclaimer for empty token accounts
+        if let Some(reclaimer) = &self.reclaimer {
+            reclaimer.clone().spawn();
+        }
+        
+        // Spawn the hold watcher, whose alerts are handled in the stream loop
+        if self.safety.hold_watch {
+            Arc::new(HoldWatcher::new(
+                self.safety.clone(),
+                self.rpc_url.clone(),
+                self.token_accounts.clone(),
+                self.pumpswap.clone(),
+                self.native.clone(),
+                self.state.clone(),
+                self.safety_alerts.clone(),
+            ))
+            .spawn();
+        }
+        
+        // Spawn pre-signed emergency sell maintenance (only if a nonce pool is configured)
+        if let Some(pool) = &self.nonce_pool {
+            tokio::spawn(Self::presign_monitor(context, pool.clone(), self.presigned_sells.clone(), self.signer.clone()));
         }
         
         let mut reconnect_attempts = 0;
@@ -195,14 +427,22 @@ impl HeliusGrpcClient {
                         "Stream error (attempt {}/{}): {:?}",
                         reconnect_attempts, self.max_reconnect_attempts, e
                     );
+                    self.notifier.notify(NotifyEvent::Disconnected {
+                        error: format!("{:#}", e),
+                        attempt: reconnect_attempts,
+                    });
                     
                     if reconnect_attempts >= self.max_reconnect_attempts {
                         error!("Max reconnection attempts reached, giving up");
+                        self.notifier.notify(NotifyEvent::Stopped {
+                            reason: format!("gave up after {} reconnect attempts", reconnect_attempts),
+                        });
                         return Err(e);
                     }
                 }
             }
             
+            metrics().stream_reconnects.inc();
             let backoff = self.reconnect_delay * reconnect_attempts;
             warn!("Reconnecting in {:?}...", backoff);
             sleep(backoff).await;
@@ -210,486 +450,816 @@ impl HeliusGrpcClient {
     }
     
     /// Internal stream runner using WebSocket subscription
+    /// Returns Ok when the target wallet set changes so the caller resubscribes
     async fn run_stream(&self) -> Result<()> {
         info!("Connecting to Helius WebSocket...");
         
         let pubsub_client = PubsubClient::new(&self.ws_url).await
             .context("Failed to connect to WebSocket")?;
         
+        let targets = self.control.target_wallets();
+        if targets.is_empty() {
+            warn!("⚠️ No target wallets configured - waiting for one to be added");
+            self.control.targets_changed().await;
+            return Ok(());
+        }
+        
         info!("✅ Connected to Helius WebSocket");
-        info!("📡 Subscribing to logs for target wallet: {}", self.target_wallet);
-        info!("💰 Buy amount: {} SOL | Tip: {} lamports", self.buy_amount_sol, self.tip_amount);
-        info!("🔑 Our wallet: {}", self.our_keypair.pubkey());
+        info!("💰 Buy amount: {} SOL | Tip: {} lamports", self.control.buy_amount_sol(), self.control.tip_amount());
+        info!("🔑 Our wallet: {}", self.signer.pubkey());
+        if !self.wallets.burners().is_empty() {
+            info!(
+                "👛 Spreading buys across {} burner wallet(s) ({:?})",
+                self.wallets.burners().len(),
+                self.wallets.strategy()
+            );
+        }
+        
+        // Creators of held tokens are followed too when creator exits are enabled
+        let creators = if self.safety.creator_exit { self.held_creators().await } else { Vec::new() };
+        let wallets: Vec<(Pubkey, Watched)> = targets.iter()
+            .map(|target| (*target, Watched::Target))
+            .chain(creators.into_iter()
+                .filter(|creator| !targets.contains(creator))
+                .map(|creator| (creator, Watched::Creator)))
+            .collect();
         
-        // Subscribe to logs mentioning the target wallet
+        // Subscribe to logs mentioning each wallet (Mentions accepts a single address)
         // Use PROCESSED for maximum speed - we react ASAP, don't wait for confirmation
-        let (mut stream, _unsub) = pubsub_client
-            .logs_subscribe(
-                RpcTransactionLogsFilter::Mentions(vec![self.target_wallet.to_string()]),
-                RpcTransactionLogsConfig {
-                    commitment: Some(CommitmentConfig::processed()),
-                },
-            )
-            .await
-            .context("Failed to subscribe to logs")?;
+        let subscribe = |wallet: Pubkey, watched: Watched| {
+            let pubsub_client = &pubsub_client;
+            async move {
+                info!("📡 Subscribing to logs for {:?} wallet: {}", watched, wallet);
+                let (stream, unsub) = pubsub_client
+                    .logs_subscribe(
+                        RpcTransactionLogsFilter::Mentions(vec![wallet.to_string()]),
+                        RpcTransactionLogsConfig {
+                            commitment: Some(CommitmentConfig::processed()),
+                        },
+                    )
+                    .await
+                    .with_context(|| format!("Failed to subscribe to logs for {}", wallet))?;
+                anyhow::Ok((stream.map(move |response| (wallet, watched, response)).boxed(), unsub))
+            }
+        };
+        let mut subscribed: HashSet<Pubkey> = wallets.iter().map(|(wallet, _)| *wallet).collect();
+        let mut stream = futures::stream::SelectAll::new();
+        let mut unsubscribers = Vec::with_capacity(wallets.len());
+        for (wallet, watched) in wallets {
+            let (wallet_stream, unsub) = subscribe(wallet, watched).await?;
+            stream.push(wallet_stream);
+            unsubscribers.push(unsub);
+        }
         
-        info!("🎯 Listening for transactions from target wallet");
+        info!("🎯 Listening for transactions from target wallets");
         
-        // Process incoming log notifications
-        while let Some(response) = stream.next().await {
-            let signature = response.value.signature.clone();
-            let slot = response.context.slot;
-            let is_success = response.value.err.is_none();
-            let logs = response.value.logs;
-            
-            if !is_success {
-                debug!("❌ Failed transaction: {}", signature);
-                continue;
-            }
-            
-            // Analyze logs to detect Buy/Sell
-            let action = self.detect_action_from_logs(&logs, &signature, slot);
-            
-            match action {
-                DetectedAction::Buy { signature, slot } => {
-                    info!("🎯 TARGET BUY DETECTED! Signature: {} (slot: {})", signature, slot);
-                    
-                    // DEBUG: Print first 5 log lines to see what we're working with
-                    info!("📋 Log sample (first 5 lines): {:?}", logs.iter().take(5).collect::<Vec<_>>());
-                    
-                    // Extract token mint ONLY from logs - NEVER fetch transaction (adds 50-150ms latency)
-                    let token_mint = match self.extract_token_from_logs(&logs) {
-                        Some(m) => Some(m),
-                        None => {
-                            // Some log streams do not include the mint pubkey reliably.
-                            // Fallback (slower): fetch the full transaction once to extract the mint.
-                            warn!(
-                                "⚠️ Could not extract token mint from logs; falling back to getTransaction (adds latency)"
-                            );
-                            match self.extract_token_from_transaction(&signature).await {
-                                Ok(v) => v,
-                                Err(e) => {
-                                    warn!("⚠️ getTransaction mint fallback failed: {:?}", e);
-                                    None
-                                }
-                            }
-                        }
-                    };
-                    
-                    if let Some(mint) = token_mint {
-                        info!("🪙 Token mint: {}", mint);
-                        
-                        // Check if we already have a position in this token (avoid duplicate buys)
-                        {
-                            let positions = self.positions.read().await;
-                            if positions.contains_key(&mint) {
-                                info!("⏭️ Already have position in {} - skipping duplicate buy", &mint[..8.min(mint.len())]);
-                                continue;
-                            }
-                        }
-                        
-                        // Execute copy buy IMMEDIATELY - no delays
-                        match self.execute_copy_buy(&mint, &signature).await {
-                            Ok(our_sig) => {
-                                info!("✅ COPY BUY EXECUTED! Sig: {}", our_sig);
-                                // ALWAYS add position (needed for sell fallback + TP tracking)
-                                self.add_position(mint.clone(), self.buy_amount_sol, 0).await;
-                            }
-                            Err(e) => {
-                                error!("❌ Copy buy failed: {:?}", e);
-                            }
-                        }
-                    } else {
-                        warn!("⚠️ Could not extract token mint from logs - skipping");
-                    }
+        // Process incoming log notifications and rug signals on held tokens
+        let mut safety_alerts = self.safety_alert_receiver.lock().await;
+        let mut balance_actions = self.balance_action_receiver.lock().await;
+        let mut creator_moves = self.creator_move_receiver.lock().await;
+        loop {
+            tokio::select! {
+                item = stream.next() => match item {
+                    Some((target, Watched::Target, response)) => self.handle_log_notification(&target, response).await,
+                    Some((creator, Watched::Creator, response)) => self.handle_creator_notification(&creator, response).await,
+                    None => break,
+                },
+                Some(detected) = balance_actions.recv() => {
+                    self.handle_detected_action(&detected.target, detected.action, detected.detected_at).await;
                 }
-                DetectedAction::Sell { signature, slot } => {
-                    info!("🚨 TARGET SELL DETECTED! Signature: {} (slot: {})", signature, slot);
-                    
-                    // DEBUG: Print first 5 log lines to see what we're working with
-                    info!("📋 Sell Log sample (first 5 lines): {:?}", logs.iter().take(5).collect::<Vec<_>>());
-                    
-                    // Try to extract token mint from logs first (fastest)
-                    let token_mint = match self.extract_token_from_logs(&logs) {
-                        Some(m) => Some(m),
-                        None => {
-                            // Fallback 1: fetch the full transaction to extract the mint
-                            warn!("⚠️ Could not extract token mint from sell logs; trying getTransaction fallback...");
-                            match self.extract_token_from_transaction(&signature).await {
-                                Ok(Some(m)) => {
-                                    info!("✅ Found mint from transaction: {}", m);
-                                    Some(m)
-                                }
-                                Ok(None) => {
-                                    // Fallback 2: Use our active positions - if we only have ONE position, sell that
-                                    warn!("⚠️ getTransaction didn't return mint; checking active positions...");
-                                    let positions = self.positions.read().await;
-                                    if positions.len() == 1 {
-                                        // We only have one position - this must be the one being sold
-                                        let mint = positions.keys().next().unwrap().clone();
-                                        info!("📍 Found single active position: {}", mint);
-                                        Some(mint)
-                                    } else if positions.len() > 1 {
-                                        warn!("⚠️ Multiple positions active ({}), cannot determine which to sell", positions.len());
-                                        // List the positions for debugging
-                                        for (mint, _) in positions.iter() {
-                                            info!("   - Position: {}", mint);
-                                        }
-                                        None
-                                    } else {
-                                        warn!("⚠️ No active positions to sell");
-                                        None
-                                    }
-                                }
-                                Err(e) => {
-                                    warn!("⚠️ getTransaction fallback failed: {:?}", e);
-                                    // Still try positions fallback
-                                    let positions = self.positions.read().await;
-                                    if positions.len() == 1 {
-                                        let mint = positions.keys().next().unwrap().clone();
-                                        info!("📍 Using single active position: {}", mint);
-                                        Some(mint)
-                                    } else {
-                                        None
-                                    }
-                                }
-                            }
-                        }
-                    };
-                    
-                    if let Some(mint) = token_mint {
-                        info!("🪙 Token being sold: {}", mint);
-                        
-                        // Execute copy sell - sell ALL our tokens of this mint
-                        match self.execute_copy_sell(&mint, &signature).await {
-                            Ok(our_sig) => {
-                                info!("✅ COPY SELL EXECUTED! Our signature: {}", our_sig);
-                                // Remove from tracked positions
-                                self.remove_position(&mint).await;
-                            }
-                            Err(e) => {
-                                // Check if it's just "no tokens" - that's not really an error
-                                let err_str = format!("{:?}", e);
-                                if err_str.contains("No tokens to sell") || err_str.contains("account not found") {
-                                    info!("ℹ️ No tokens to sell for {} - probably already sold (TP or manually)", &mint[..8.min(mint.len())]);
-                                    // Also remove from tracking
-                                    self.remove_position(&mint).await;
-                                } else {
-                                    error!("❌ Copy sell failed: {:?}", e);
-                                }
-                            }
+                Some(found) = creator_moves.recv() => {
+                    self.handle_creator_moves(&found.creator, &found.signature, found.moves).await;
+                }
+                Some(alert) = safety_alerts.recv() => {
+                    self.emergency_exit(&alert.mint, alert.issue.to_string(), TradeRecordType::SellSafetyExit).await;
+                }
+                _ = self.control.targets_changed() => {
+                    info!("🎯 Target wallets changed, resubscribing...");
+                    break;
+                }
+                _ = self.creators_changed.notified() => {
+                    // New creators join the open socket. Creators of sold tokens stay subscribed
+                    // until the next reconnect; their transactions match no position.
+                    for creator in self.held_creators().await {
+                        if subscribed.insert(creator) {
+                            let (creator_stream, unsub) = subscribe(creator, Watched::Creator).await?;
+                            stream.push(creator_stream);
+                            unsubscribers.push(unsub);
                         }
-                    } else {
-                        warn!("⚠️ Could not extract token mint from sell transaction - all fallbacks failed");
                     }
                 }
-                DetectedAction::Unknown { signature, slot } => {
-                    debug!("📋 Other transaction from target: {} (slot: {})", signature, slot);
-                }
             }
         }
         
+        drop(stream);
+        for unsub in unsubscribers {
+            unsub().await;
+        }
+        
         Ok(())
     }
     
-    /// Detect if transaction is a Buy or Sell from logs
-    fn detect_action_from_logs(&self, logs: &[String], signature: &str, slot: u64) -> DetectedAction {
-        let logs_str = logs.join(" ");
-        
-        // Check for Pump.fun Buy
-        if logs_str.contains("Instruction: Buy") && logs_str.contains(PUMPFUN_PROGRAM) {
-            return DetectedAction::Buy {
-                signature: signature.to_string(),
-                slot,
-            };
+    /// Handle a single log notification from a target wallet
+    async fn handle_log_notification(
+        &self,
+        target: &Pubkey,
+        response: Response<RpcLogsResponse>,
+    ) {
+        let detected_at = std::time::Instant::now();
+        let signature = response.value.signature.clone();
+        let slot = response.context.slot;
+        let is_success = response.value.err.is_none();
+        let logs = response.value.logs;
+        
+        if !is_success {
+            debug!("❌ Failed transaction: {}", signature);
+            return;
         }
         
-        // Check for Pump.fun Sell
-        if logs_str.contains("Instruction: Sell") && logs_str.contains(PUMPFUN_PROGRAM) {
-            return DetectedAction::Sell {
-                signature: signature.to_string(),
-                slot,
-            };
-        }
+        // Who launched tokens the target buys in their launch transaction, kept if we copy the buy
+        let launched: Vec<(Pubkey, Pubkey)> = if self.safety.creator_exit {
+            pump_events(&logs).into_iter()
+                .filter_map(|event| match event {
+                    PumpEvent::Create(create) => Some((create.mint, create.user)),
+                    _ => None,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
         
-        // Check for Raydium/Jupiter swaps (simplified)
-        if logs_str.contains("swap") || logs_str.contains("Swap") {
-            // Try to determine direction from context
-            if logs_str.contains("SOL") {
-                // Could be buy or sell, need more context
+        // Analyze logs to detect Buy/Sell, falling back to the target's balance changes. Those
+        // need the confirmed transaction, so they are read in the background and handled when
+        // they come back over the channel.
+        match Self::detect_action_from_logs(&logs, target, &signature, slot) {
+            // Trades known only by their instruction name (PumpSwap, truncated logs) need the mint
+            DetectedAction::Swap { signature, slot } | DetectedAction::Buy { signature, slot, trade: None, launchlab: None, .. } => {
+                self.spawn_balance_detection(target, signature, slot, false, detected_at);
+            }
+            DetectedAction::Sell { signature, slot, trade: None, launchlab: None, .. } => {
+                self.spawn_balance_detection(target, signature, slot, true, detected_at);
+            }
+            action => {
+                self.handle_detected_action(target, action, detected_at).await;
+                self.remember_creators(launched).await;
             }
         }
-        
-        DetectedAction::Unknown {
-            signature: signature.to_string(),
-            slot,
+    }
+    
+    /// Classify a target transaction from its balance changes in the background, handing the
+    /// action back over the channel
+    /// A sell named in the logs whose balance changes cannot be read is still copied, as a
+    /// sell of our single open position.
+    fn spawn_balance_detection(&self, target: &Pubkey, signature: String, slot: u64, named_sell: bool, detected_at: std::time::Instant) {
+        let (rpc_url, breakers) = (self.rpc_url.clone(), self.breakers.clone());
+        let (sender, target) = (self.balance_actions.clone(), *target);
+        tokio::spawn(async move {
+            let action = match Self::detect_action_from_balances(&rpc_url, &breakers, &target, signature, slot).await {
+                DetectedAction::Unknown { signature, slot } if named_sell => {
+                    DetectedAction::Sell { signature, slot, trade: None, launchlab: None, quote: QuoteAsset::Sol }
+                }
+                action => action,
+            };
+            let _ = sender.send(BalanceAction { target, action, detected_at });
+        });
+    }
+    
+    /// Keep the creators of launched tokens we now hold and follow them
+    async fn remember_creators(&self, launched: Vec<(Pubkey, Pubkey)>) {
+        let positions = self.positions.read().await;
+        for (mint, creator) in launched {
+            if positions.contains_key(&mint.to_string()) && self.creators.insert(mint, creator).is_none() {
+                self.creators_changed.notify_one();
+            }
         }
     }
     
-    /// Extract token mint from transaction logs
-    fn extract_token_from_logs(&self, logs: &[String]) -> Option<String> {
-        // Pump.fun logs are not always cleanly whitespace-delimited; pubkeys can be embedded
-        // with punctuation (e.g. "mint=<pubkey>") or appear outside of "Program log:" lines.
-        // We therefore scan every line for base58-like substrings and validate as Pubkey.
-
-        fn is_base58_char(b: u8) -> bool {
-            matches!(
-                b,
-                b'1'..=b'9'
-                    | b'A'..=b'H'
-                    | b'J'..=b'N'
-                    | b'P'..=b'Z'
-                    | b'a'..=b'k'
-                    | b'm'..=b'z'
-            )
+    /// Copy a target's detected Buy, Sell or rotation
+    async fn handle_detected_action(&self, target: &Pubkey, action: DetectedAction, detected_at: std::time::Instant) {
+        // Balance reads come back after the fact, and the target may have been removed since
+        if !self.control.is_target(target) {
+            debug!("⏭️ {} is no longer a target, not copying its trade", target);
+            return;
         }
-
-        fn extract_pubkeys_from_line(line: &str) -> Vec<String> {
-            let bytes = line.as_bytes();
-            let mut out = Vec::new();
-            let mut i = 0usize;
-            while i < bytes.len() {
-                while i < bytes.len() && !is_base58_char(bytes[i]) {
-                    i += 1;
+        match action {
+            DetectedAction::Buy { signature, slot, trade, launchlab, quote, route } => {
+                info!("🎯 TARGET BUY DETECTED! Target: {} | Signature: {} (slot: {})", target, signature, slot);
+                
+                if self.control.is_paused() {
+                    info!("⏸️ Copying paused - skipping buy");
+                    return;
                 }
-                let start = i;
-                while i < bytes.len() && is_base58_char(bytes[i]) {
-                    i += 1;
+                
+                let trade = match (trade, launchlab) {
+                    (None, Some(event)) => self.launchlab_trade(&event, target).await,
+                    (trade, _) => trade,
+                };
+                // Routes are kept for held tokens only
+                let mint = trade.as_ref().map(|trade| trade.mint);
+                if let (Some(mint), Some(route)) = (mint, route) {
+                    self.native.remember(mint, route);
                 }
-                let end = i;
-                let len = end.saturating_sub(start);
-                if (32..=44).contains(&len) {
-                    if let Ok(s) = std::str::from_utf8(&bytes[start..end]) {
-                        // Validate it is a real Solana pubkey
-                        if Pubkey::from_str(s).is_ok() {
-                            out.push(s.to_string());
-                        }
+                self.copy_target_buy(target, &signature, slot, trade, quote, detected_at).await;
+                if let Some(mint) = mint {
+                    if !self.positions.read().await.contains_key(&mint.to_string()) {
+                        self.native.forget(&mint);
                     }
                 }
             }
-            out
-        }
-
-        let excluded: [&str; 7] = [
-            PUMPFUN_PROGRAM,
-            WSOL_MINT,
-            // common programs (avoid accidental selection)
-            "11111111111111111111111111111111", // system
-            "ComputeBudget111111111111111111111111111111", // compute budget
-            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", // SPL Token
-            "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb", // Token-2022
-            "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL", // Associated Token
-        ];
-
-        let target_wallet = self.target_wallet.to_string();
-        let mut candidates: Vec<String> = Vec::new();
-        for log in logs {
-            candidates.extend(extract_pubkeys_from_line(log));
-        }
-
-        // Prefer pump mints first (most reliable for this bot’s pump-focused flow).
-        if let Some(pump) = candidates
-            .iter()
-            .find(|s| s.ends_with("pump") && *s != &target_wallet && !excluded.contains(&s.as_str()))
-        {
-            return Some(pump.clone());
-        }
-
-        // Otherwise, fall back to the first non-excluded, non-target pubkey.
-        for cand in candidates {
-            if cand == target_wallet {
-                continue;
+            DetectedAction::Sell { signature, slot, trade, launchlab, quote } => {
+                info!("🚨 TARGET SELL DETECTED! Target: {} | Signature: {} (slot: {})", target, signature, slot);
+                
+                let trade = match (trade, launchlab) {
+                    (None, Some(event)) => self.launchlab_trade(&event, target).await,
+                    (trade, _) => trade,
+                };
+                self.copy_target_sell(target, &signature, slot, trade, quote, detected_at).await;
             }
-            if excluded.contains(&cand.as_str()) {
-                continue;
+            DetectedAction::Rotate { signature, slot, sell, buy } => {
+                info!(
+                    "🔀 TARGET ROTATION DETECTED! Target: {} | {} -> {} | Signature: {} (slot: {})",
+                    target, sell.mint, buy.mint, signature, slot
+                );
+                
+                // Only the leg out of a token we hold needs copying on the sell side
+                if self.positions.read().await.contains_key(&sell.mint.to_string()) {
+                    self.copy_target_sell(target, &signature, slot, Some(sell), QuoteAsset::Sol, detected_at).await;
+                }
+                if self.control.is_paused() {
+                    info!("⏸️ Copying paused - skipping buy");
+                    return;
+                }
+                self.copy_target_buy(target, &signature, slot, Some(buy), QuoteAsset::Sol, detected_at).await;
             }
-            if cand.starts_with("1111") {
-                continue;
+            DetectedAction::Unknown { signature, slot } | DetectedAction::Swap { signature, slot } => {
+                debug!("📋 Other transaction from target: {} (slot: {})", signature, slot);
             }
-            return Some(cand);
         }
-
-        debug!(
-            "⚠️ extract_token_from_logs: no mint found; sample logs: {:?}",
-            logs.iter().take(8).collect::<Vec<_>>()
-        );
-        None
     }
     
-    /// Extract token mint by fetching full transaction details
-    async fn extract_token_from_transaction(&self, signature: &str) -> Result<Option<String>> {
-        let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
+    /// Copy a target's buy, paying in `quote` when copying stablecoin trades in kind
+    async fn copy_target_buy(
+        &self,
+        target: &Pubkey,
+        signature: &str,
+        slot: u64,
+        trade: Option<TradeEvent>,
+        quote: QuoteAsset,
+        detected_at: std::time::Instant,
+    ) {
+        // The mint and amounts come straight from the TradeEvent - no transaction fetch
+        let Some(trade) = trade else {
+            warn!("⚠️ No trade event in the logs (truncated?) - skipping buy");
+            return;
+        };
+        let signature = signature.to_string();
+        let mint = trade.mint.to_string();
+        info!("🪙 Token mint: {}", mint);
+        info!(
+            "   Target paid {:.4} SOL{} for {} raw tokens (curve price after: {:?} lamports/token)",
+            trade.sol_amount as f64 / 1_000_000_000.0,
+            if quote.is_stable() { format!(" worth of {:?}", quote) } else { String::new() },
+            trade.token_amount,
+            trade.post_trade_price()
+        );
+        self.record_target_event(target, true, &mint, &signature);
+        self.notifier.notify(NotifyEvent::TargetBuy {
+            target: target.to_string(),
+            token_mint: mint.clone(),
+            signature: signature.clone(),
+        });
         
-        let sig = Signature::from_str(signature)
-            .context("Invalid signature")?;
+        // Check if we already have a position in this token (avoid duplicate buys)
+        {
+            let positions = self.positions.read().await;
+            if positions.contains_key(&mint) {
+                info!("⏭️ Already have position in {} - skipping duplicate buy", &mint[..8.min(mint.len())]);
+                return;
+            }
+        }
         
-        // Fetch full transaction with metadata
-        let tx_config = RpcTransactionConfig {
-            encoding: Some(UiTransactionEncoding::JsonParsed),
-            // Use processed for speed; this is a fallback path only.
-            commitment: Some(CommitmentConfig::processed()),
-            max_supported_transaction_version: Some(0),
+        // Skip or shrink buys that land too long after the target's
+        let Some(buy_sol) = self.gate_entry(&mint, trade.fill_price(), slot, self.control.buy_amount_sol()).await else {
+            return;
         };
         
-        let tx = rpc_client.get_transaction_with_config(&sig, tx_config).await?;
+        let wallet = self.wallets.assign(target, &self.state.get_all_positions());
         
-        // Parse the transaction to find token mints from metadata
-        if let Some(meta) = tx.transaction.meta {
-            // Use skip_serialization for OptionSerializer
-            use solana_transaction_status::option_serializer::OptionSerializer;
-            
-            // Look for token balance changes
-            if let OptionSerializer::Some(post_token_balances) = meta.post_token_balances {
-                // Prefer pump mints if present.
-                if let Some(pump) = post_token_balances
-                    .iter()
-                    .map(|b| b.mint.clone())
-                    .find(|m| m != WSOL_MINT && m.ends_with("pump"))
-                {
-                    return Ok(Some(pump));
-                }
-
-                // Otherwise return the first non-WSOL mint.
-                for balance in post_token_balances.iter() {
-                    let mint_str = balance.mint.clone();
-                    if mint_str != WSOL_MINT {
-                        return Ok(Some(mint_str));
-                    }
-                }
+        // Risk limits pause new entries only; sells are never checked
+        if let Ok(mint_pubkey) = Pubkey::from_str(&mint) {
+            let buy_lamports = (buy_sol * 1_000_000_000.0) as u64;
+            if let Err(breach) = self.risk.check_buy(&mint_pubkey, &wallet.pubkey(), buy_lamports).await {
+                info!("🛡️ Skipping buy of {}: {}", &mint[..8.min(mint.len())], breach);
+                return;
+            }
+            if self.safety.entry_checks && !self.check_entry_safety(&mint_pubkey, &wallet.pubkey()).await {
+                return;
             }
         }
         
-        Ok(None)
+        // Execute copy buy IMMEDIATELY - no delays
+        match self.execute_copy_buy(&mint, &signature, &wallet, buy_sol, quote).await {
+            Ok(our_sig) => {
+                self.risk.record_buy();
+                info!("✅ COPY BUY EXECUTED! Sig: {} (wallet {})", our_sig, wallet.pubkey());
+                metrics().observe_detection_to_send("buy", detected_at.elapsed());
+                self.track_copy_buy(mint.clone(), our_sig.clone(), slot);
+                // ALWAYS add position (needed for sell fallback + TP tracking)
+                self.add_position(mint.clone(), buy_sol, 0, wallet.clone()).await;
+                self.record_copy_buy(&mint, &signature, &our_sig, &wallet, buy_sol);
+                self.enrich_position(&mint);
+            }
+            Err(e) => {
+                error!("❌ Copy buy failed: {:?}", e);
+                self.notifier.notify(NotifyEvent::BuyFailed {
+                    token_mint: mint.clone(),
+                    error: format!("{:#}", e),
+                });
+            }
+        }
     }
     
-    /// Get token balance checking both SPL Token and Token-2022 programs
-    /// This is needed because Pump.fun tokens use Token-2022
-    async fn get_token_balance_any_program(
+    /// Copy a target's sell of everything we hold of the mint, into `quote` when copying
+    /// stablecoin trades in kind
+    async fn copy_target_sell(
         &self,
-        rpc_client: &AsyncRpcClient,
-        token_mint: &Pubkey,
-    ) -> Result<(u64, u8)> {
-        use solana_client::rpc_request::TokenAccountsFilter;
+        target: &Pubkey,
+        signature: &str,
+        slot: u64,
+        trade: Option<TradeEvent>,
+        quote: QuoteAsset,
+        detected_at: std::time::Instant,
+    ) {
+        // The trade event or balance changes name the mint; without either the only safe
+        // guess is our single open position
+        let token_mint = match trade {
+            Some(trade) => Some(trade.mint.to_string()),
+            None => {
+                warn!("⚠️ Sold mint unknown from the logs and balance changes; checking active positions...");
+                let positions = self.positions.read().await;
+                if positions.len() == 1 {
+                    // We only have one position - this must be the one being sold
+                    let mint = positions.keys().next().unwrap().clone();
+                    info!("📍 Found single active position: {}", mint);
+                    Some(mint)
+                } else {
+                    warn!("⚠️ {} positions active, cannot determine which to sell", positions.len());
+                    None
+                }
+            }
+        };
         
-        let our_pubkey = self.our_keypair.pubkey();
+        let Some(mint) = token_mint else {
+            warn!("⚠️ Could not determine the token being sold - skipping");
+            return;
+        };
+        info!("🪙 Token being sold: {}", mint);
+        self.record_target_event(target, false, &mint, signature);
+        
+        // Execute copy sell - sell ALL our tokens of this mint
+        match self.execute_copy_sell(&mint, signature, quote, TipLevel::Normal).await {
+            Ok(our_sig) => {
+                info!("✅ COPY SELL EXECUTED! Our signature: {}", our_sig);
+                metrics().observe_detection_to_send("sell", detected_at.elapsed());
+                Self::track_landing(self.rpc_url.clone(), self.state.clone(), &mint, our_sig.clone(), Some(slot));
+                self.notifier.notify(NotifyEvent::CopySell {
+                    token_mint: mint.clone(),
+                    signature: our_sig.clone(),
+                    manual: false,
+                });
+                // Remove from tracked positions
+                self.remove_position(&mint).await;
+                self.record_exit(&mint, TradeRecordType::SellCopyExit, our_sig);
+            }
+            Err(e) => {
+                // Check if it's just "no tokens" - that's not really an error
+                let err_str = format!("{:?}", e);
+                if err_str.contains("No tokens to sell") || err_str.contains("account not found") {
+                    info!("ℹ️ No tokens to sell for {} - probably already sold (TP or manually)", &mint[..8.min(mint.len())]);
+                    // Also remove from tracking
+                    self.remove_position(&mint).await;
+                } else {
+                    error!("❌ Copy sell failed: {:?}", e);
+                }
+            }
+        }
+    }
+    
+    /// Detect a target's Buy or Sell from its Pump.fun, LaunchLab or Raydium CPMM trade event
+    /// PumpSwap trades (which emit no events) and truncated logs are recognised by instruction
+    /// name, without a trade. Anything else that invokes a program beyond transfers and account
+    /// setup is returned as a possible `Swap`.
+    fn detect_action_from_logs(logs: &[String], target: &Pubkey, signature: &str, slot: u64) -> DetectedAction {
+        let signature = signature.to_string();
+        let mut trade = None;
+        for event in pump_events(logs) {
+            match event {
+                PumpEvent::Trade(event) if event.user == *target => trade = Some(event),
+                PumpEvent::Trade(_) => {}
+                PumpEvent::Create(create) => debug!("🆕 Target created {} ({})", create.symbol, create.mint),
+                PumpEvent::Complete(complete) => debug!("🎓 Bonding curve completed for {}", complete.mint),
+            }
+        }
         
-        info!("🔍 Searching for token accounts for mint: {}", token_mint);
+        // Raydium events carry no user; the log subscription already limits them to the target's
+        // transactions. CPMM swaps between two tokens or from older program versions are skipped.
+        let mut launchlab_trade = None;
+        let mut cpmm_pool = None;
+        for (program, data) in program_data(logs) {
+            match DexProgram::from_program_id(&program) {
+                DexProgram::RaydiumLaunchLab => launchlab_trade = launchlab::decode_trade_event(&data).or(launchlab_trade),
+                DexProgram::RaydiumCpmm => {
+                    if let Some((pool, cpmm_trade)) = raydium_cpmm::decode_swap_event(&data)
+                        .and_then(|event| Some((event.pool_id, event.to_trade_event(*target)?)))
+                    {
+                        cpmm_pool = Some(pool);
+                        trade = Some(cpmm_trade);
+                    }
+                }
+                _ => {}
+            }
+        }
         
-        // Use getTokenAccountsByOwner which works for both SPL Token and Token-2022
-        // Filter by mint to find all accounts we own for this specific token
-        let filter = TokenAccountsFilter::Mint(*token_mint);
+        let is_buy = match (&trade, &launchlab_trade) {
+            (Some(event), _) => Some(event.is_buy),
+            (None, Some(event)) => Some(event.is_buy()),
+            (None, None) => None,
+        };
+        let launchlab = if trade.is_some() { None } else { launchlab_trade };
+        match is_buy {
+            Some(true) => {
+                let route = match (cpmm_pool, &launchlab) {
+                    (Some(pool), _) => NativeRoute::new(DexProgram::RaydiumCpmm, vec![pool]),
+                    (None, Some(event)) => NativeRoute::new(DexProgram::RaydiumLaunchLab, vec![event.pool_state]),
+                    (None, None) => None,
+                };
+                return DetectedAction::Buy { signature, slot, trade, launchlab, quote: QuoteAsset::Sol, route };
+            }
+            Some(false) => return DetectedAction::Sell { signature, slot, trade, launchlab, quote: QuoteAsset::Sol },
+            None => {}
+        }
         
-        let accounts = rpc_client
-            .get_token_accounts_by_owner(&our_pubkey, filter)
-            .await;
+        // Trades named in the logs without their event still need the balance changes for
+        // their mint
+        let logs_str = logs.join(" ");
+        if [PUMPFUN_PROGRAM, PUMPSWAP_PROGRAM, LAUNCHLAB_PROGRAM].iter().any(|program| logs_str.contains(program)) {
+            if logs_str.contains("Instruction: Buy") {
+                return DetectedAction::Buy { signature, slot, trade: None, launchlab: None, quote: QuoteAsset::Sol, route: None };
+            }
+            if logs_str.contains("Instruction: Sell") {
+                return DetectedAction::Sell { signature, slot, trade: None, launchlab: None, quote: QuoteAsset::Sol };
+            }
+        }
         
-        match accounts {
-            Ok(token_accounts) => {
-                info!("📋 Found {} token account(s) for this mint", token_accounts.len());
-
-                let mut total_balance: u64 = 0;
-                let mut decimals: Option<u8> = None;
-
-                for account in token_accounts.iter() {
-                    // Parse the account data to get balance
-                    if let solana_account_decoder::UiAccountData::Json(parsed) = &account.account.data {
-                        if let Some(info) = parsed.parsed.get("info") {
-                            if let Some(token_amount) = info.get("tokenAmount") {
-                                if decimals.is_none() {
-                                    decimals = token_amount
-                                        .get("decimals")
-                                        .and_then(|v| v.as_u64())
-                                        .and_then(|v| u8::try_from(v).ok());
-                                }
-                                if let Some(amount_str) = token_amount.get("amount") {
-                                    if let Some(amount) = amount_str.as_str() {
-                                        if let Ok(balance) = amount.parse::<u64>() {
-                                            info!("  💰 Account {} has {} tokens", account.pubkey, balance);
-                                            total_balance += balance;
-                                        }
-                                    }
-                                }
-                            }
+        let invokes_other_program = logs.iter().any(|line| {
+            let mut words = line.split_whitespace();
+            let (Some("Program"), Some(program), Some("invoke")) = (words.next(), words.next(), words.next()) else {
+                return false;
+            };
+            !NON_SWAP_PROGRAMS.contains(&program)
+        });
+        if invokes_other_program {
+            return DetectedAction::Swap { signature, slot };
+        }
+        DetectedAction::Unknown { signature, slot }
+    }
+    
+    /// Fetch a transaction for its balance changes, retrying until it is confirmed
+    async fn fetch_confirmed_transaction(rpc_url: &str, signature: &str) -> Option<EncodedConfirmedTransactionWithStatusMeta> {
+        let parsed = Signature::from_str(signature).ok()?;
+        let rpc_client = AsyncRpcClient::new(rpc_url.to_string());
+        for attempt in 1..=SWAP_FETCH_ATTEMPTS {
+            match rpc_client.get_transaction_with_config(&parsed, transaction_config()).await {
+                Ok(fetched) => return Some(fetched),
+                Err(e) if attempt == SWAP_FETCH_ATTEMPTS => {
+                    debug!("Could not fetch {} for its balance changes: {}", signature, e);
+                }
+                Err(_) => sleep(SWAP_FETCH_INTERVAL).await,
+            }
+        }
+        None
+    }
+    
+    /// Classify a target transaction by the target's SOL and token balance changes
+    /// Works on any venue, at the cost of waiting for the transaction to confirm. Trades against
+    /// a stablecoin and token-to-token rotations are valued in SOL through a Jupiter quote;
+    /// ambiguous swaps (several mints bought or sold) are logged and not copied.
+    async fn detect_action_from_balances(
+        rpc_url: &str,
+        breakers: &CircuitBreakers,
+        target: &Pubkey,
+        signature: String,
+        slot: u64,
+    ) -> DetectedAction {
+        let tx = Self::fetch_confirmed_transaction(rpc_url, &signature).await;
+        let Some(swap) = tx.as_ref().and_then(|tx| decode_wallet_swap(tx, target)) else {
+            return DetectedAction::Unknown { signature, slot };
+        };
+        
+        match &swap.swap {
+            BalanceSwap::Trade { .. } => {
+                let trade = swap.to_trade_event(*target);
+                debug!("⚖️ Balance changes of {} read as {:?} on {:?}", signature, swap.swap, swap.dex);
+                if trade.as_ref().is_some_and(|trade| trade.is_buy) {
+                    let route = NativeRoute::new(swap.dex, swap.accounts.clone());
+                    DetectedAction::Buy { signature, slot, trade, launchlab: None, quote: QuoteAsset::Sol, route }
+                } else {
+                    DetectedAction::Sell { signature, slot, trade, launchlab: None, quote: QuoteAsset::Sol }
+                }
+            }
+            BalanceSwap::TokenSwap { sold, sold_amount, bought, bought_amount } => {
+                debug!("⚖️ Balance changes of {} read as {:?} on {:?}", signature, swap.swap, swap.dex);
+                let timestamp = swap.block_time.unwrap_or_default();
+                let trade = |mint, sol_amount, token_amount, is_buy| TradeEvent {
+                    mint,
+                    sol_amount,
+                    token_amount,
+                    is_buy,
+                    user: *target,
+                    timestamp,
+                    virtual_sol_reserves: 0,
+                    virtual_token_reserves: 0,
+                };
+                let quoted = QuotedTrade::classify(*sold, *sold_amount, *bought, *bought_amount);
+                match quoted {
+                    Some(QuotedTrade::Buy { mint, quote, quote_amount, token_amount }) => {
+                        let Some(sol_amount) = Self::sol_value(breakers, &quote.mint(), quote_amount).await else {
+                            return DetectedAction::Unknown { signature, slot };
+                        };
+                        let trade = Some(trade(mint, sol_amount, token_amount, true));
+                        DetectedAction::Buy { signature, slot, trade, launchlab: None, quote, route: None }
+                    }
+                    Some(QuotedTrade::Sell { mint, quote, quote_amount, token_amount }) => {
+                        let Some(sol_amount) = Self::sol_value(breakers, &quote.mint(), quote_amount).await else {
+                            warn!("⚠️ Sell of {} for {:?} in {} could not be valued - not copied", mint, quote, signature);
+                            return DetectedAction::Unknown { signature, slot };
+                        };
+                        let trade = Some(trade(mint, sol_amount, token_amount, false));
+                        DetectedAction::Sell { signature, slot, trade, launchlab: None, quote }
+                    }
+                    Some(QuotedTrade::Rotate { sold, sold_amount, bought, bought_amount }) => {
+                        // What the target gave up is what it paid for the new token
+                        let Some(sol_amount) = Self::sol_value(breakers, &sold, sold_amount).await else {
+                            return DetectedAction::Unknown { signature, slot };
+                        };
+                        DetectedAction::Rotate {
+                            signature,
+                            slot,
+                            sell: trade(sold, sol_amount, sold_amount, false),
+                            buy: trade(bought, sol_amount, bought_amount, true),
                         }
                     }
+                    None => {
+                        debug!("Target swapped {} for {} - a quote asset swap, not copied", sold, bought);
+                        DetectedAction::Unknown { signature, slot }
+                    }
                 }
-                
-                let decimals = decimals.unwrap_or(0);
-                info!("💵 Total balance across all accounts: {} (decimals={})", total_balance, decimals);
-                Ok((total_balance, decimals))
             }
+            BalanceSwap::Ambiguous { sol_delta, token_deltas } => {
+                warn!(
+                    "⚠️ Ambiguous swap {} on {:?}: SOL {:+} lamports, tokens {:?} - not copied",
+                    signature, swap.dex, sol_delta, token_deltas
+                );
+                DetectedAction::Unknown { signature, slot }
+            }
+        }
+    }
+    
+    /// The target's LaunchLab trade with its mint, read from the pool the event names
+    /// Costs one account read; pools quoted in anything but SOL are not copied.
+    async fn launchlab_trade(&self, event: &LaunchLabTrade, target: &Pubkey) -> Option<TradeEvent> {
+        let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
+        let data = match rpc_client.get_account_data(&event.pool_state).await {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("⚠️ Could not read LaunchLab pool {}: {}", event.pool_state, e);
+                return None;
+            }
+        };
+        let pool = launchlab::decode_pool_state(&data)?;
+        if pool.quote_mint.to_string() != WSOL_MINT {
+            info!("⏭️ LaunchLab pool {} is quoted in {}, not SOL - skipping", event.pool_state, pool.quote_mint);
+            return None;
+        }
+        Some(event.to_trade_event(pool.base_mint, *target))
+    }
+    
+    /// Lamports `amount` of `mint` would swap for on Jupiter, None if it cannot be quoted
+    async fn sol_value(breakers: &CircuitBreakers, mint: &Pubkey, amount: u64) -> Option<u64> {
+        match Self::jupiter_out_amount(breakers, mint, &QuoteAsset::Sol.mint(), amount).await {
+            Ok(lamports) => Some(lamports),
             Err(e) => {
-                warn!("⚠️ Error getting token accounts: {}", e);
-                
-                // Fallback: Try traditional ATA derivation for SPL Token
-                let spl_ata = spl_associated_token_account::get_associated_token_address(
-                    &our_pubkey,
-                    token_mint,
+                warn!("⚠️ Could not value {} raw {} in SOL: {:#}", amount, mint, e);
+                None
+            }
+        }
+    }
+    
+    /// Output amount of a Jupiter quote for swapping `amount` of `input_mint` into `output_mint`
+    async fn jupiter_out_amount(breakers: &CircuitBreakers, input_mint: &Pubkey, output_mint: &Pubkey, amount: u64) -> Result<u64> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        let quote_url = format!(
+            "https://quote-api.jup.ag/v6/quote?inputMint={}&outputMint={}&amount={}&slippageBps=2500",
+            input_mint, output_mint, amount
+        );
+        let response = send_api_request(breakers, client.get(&quote_url), ENDPOINT_JUPITER_QUOTE).await?;
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Jupiter quote failed: {}", error_text));
+        }
+        let quote_data: serde_json::Value = response.json().await?;
+        quote_data.get("outAmount")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<u64>().ok())
+            .context("Jupiter quote has no outAmount")
+    }
+    
+    /// Check a mint and simulate a small buy-then-sell before copying a buy, false to skip it
+    /// Checks that cannot run (no buy route, RPC errors) pass so an outage never blocks entries.
+    async fn check_entry_safety(&self, mint: &Pubkey, owner: &Pubkey) -> bool {
+        let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
+        let mut issues = match rpc_client.get_account_data(mint).await {
+            Ok(data) => MintRisks::decode(&data)
+                .map(|risks| self.safety.mint_issues(&risks))
+                .unwrap_or_default(),
+            Err(e) => {
+                warn!("⚠️ Could not read mint {} for safety checks: {}", mint, e);
+                Vec::new()
+            }
+        };
+        
+        if issues.is_empty() {
+            let probe_lamports = (self.safety.probe_sol * 1_000_000_000.0) as u64;
+            match simulate_round_trip(&self.breakers, &rpc_client, owner, mint, probe_lamports).await {
+                Ok(RoundTrip::Sellable { sol_in, sol_out }) => issues.extend(self.safety.round_trip_issue(sol_in, sol_out)),
+                Ok(RoundTrip::Unsellable(reason)) => issues.push(SafetyIssue::Unsellable(reason)),
+                Err(e) => warn!("⚠️ Round trip check of {} could not run: {:#}", mint, e),
+            }
+        }
+        
+        for issue in &issues {
+            info!("☠️ Skipping buy of {}: {}", mint, issue);
+        }
+        issues.is_empty()
+    }
+    
+    /// Apply the entry gate to a copy buy, returning the SOL to spend or None to skip it
+    /// `target_price` is the target's fill price from its TradeEvent. The current slot and
+    /// price are fetched concurrently; a figure that cannot be fetched skips its check.
+    async fn gate_entry(&self, token_mint: &str, target_price: Option<f64>, target_slot: u64, buy_sol: f64) -> Option<f64> {
+        if !self.entry_gate.is_enabled() {
+            return Some(buy_sol);
+        }
+        let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
+        let checks_price = self.entry_gate.checks_price();
+        
+        let (current_slot, current_price) = tokio::join!(
+            async { rpc_client.get_slot_with_commitment(CommitmentConfig::processed()).await.ok() },
+            async {
+                if checks_price { self.current_entry_price(&rpc_client, token_mint, buy_sol).await } else { None }
+            },
+        );
+        debug!(
+            "Entry gate for {}: target slot {} now {:?}, target price {:?} now {:?}",
+            token_mint, target_slot, current_slot, target_price, current_price
+        );
+        
+        match self.entry_gate.evaluate(buy_sol, target_slot, current_slot, target_price, current_price) {
+            EntryDecision::Proceed => Some(buy_sol),
+            EntryDecision::Resize { buy_sol: resized, price_move_pct } => {
+                info!(
+                    "⏱️ Price up {:.1}% since the target's entry, resizing buy of {} to {:.4} SOL",
+                    price_move_pct, &token_mint[..8.min(token_mint.len())], resized
                 );
-                
-                info!("🔄 Trying SPL Token ATA: {}", spl_ata);
-                
-                if let Ok(balance) = rpc_client.get_token_account_balance(&spl_ata).await {
-                    if let Ok(amount) = balance.amount.parse::<u64>() {
-                        let decimals = balance.decimals as u8;
-                        info!("✅ Found balance via SPL ATA: {} (decimals={})", amount, decimals);
-                        return Ok((amount, decimals));
-                    }
+                Some(resized)
+            }
+            EntryDecision::Skip(reason) => {
+                info!("⏱️ Skipping buy of {}: {}", &token_mint[..8.min(token_mint.len())], reason);
+                None
+            }
+        }
+    }
+    
+    /// Current lamports per raw token: the Pump.fun or LaunchLab bonding curve while the token
+    /// is on one, otherwise a Jupiter quote for the buy size
+    async fn current_entry_price(&self, rpc_client: &AsyncRpcClient, token_mint: &str, buy_sol: f64) -> Option<f64> {
+        let mint = Pubkey::from_str(token_mint).ok()?;
+        if Self::is_pumpfun_token(token_mint) {
+            let curve = match pumpfun::bonding_curve_address(&mint) {
+                Some(address) => rpc_client.get_account_data(&address).await.ok(),
+                None => None,
+            };
+            if let Some(curve) = curve.as_deref().and_then(pumpfun::decode_bonding_curve) {
+                if !curve.complete {
+                    return curve.price();
                 }
-                
-                // Try Token-2022 ATA
-                let token_2022_program = Pubkey::from_str("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb")
-                    .expect("Invalid Token-2022 program ID");
-                
-                let token_2022_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
-                    &our_pubkey,
-                    token_mint,
-                    &token_2022_program,
-                );
-                
-                info!("🔄 Trying Token-2022 ATA: {}", token_2022_ata);
-                
-                if let Ok(balance) = rpc_client.get_token_account_balance(&token_2022_ata).await {
-                    if let Ok(amount) = balance.amount.parse::<u64>() {
-                        let decimals = balance.decimals as u8;
-                        info!("✅ Found balance via Token-2022 ATA: {} (decimals={})", amount, decimals);
-                        return Ok((amount, decimals));
-                    }
+                if let Ok(price) = self.pumpswap.price(&mint).await {
+                    return Some(price);
                 }
-                
-                info!("❌ No token accounts found for this mint");
-                Ok((0, 0))
             }
+        } else if let Some(address) = launchlab::pool_address(&mint, &Pubkey::from_str(WSOL_MINT).ok()?) {
+            // Tokens still on a LaunchLab curve are priced from the pool itself
+            let pool = rpc_client.get_account_data(&address).await.ok();
+            if let Some(pool) = pool.as_deref().and_then(launchlab::decode_pool_state).filter(|pool| pool.is_trading()) {
+                return pool.price();
+            }
+        }
+        
+        let buy_lamports = (buy_sol * 1_000_000_000.0) as u64;
+        let quote_url = format!(
+            "https://quote-api.jup.ag/v6/quote?inputMint={}&outputMint={}&amount={}&slippageBps=2500",
+            WSOL_MINT, token_mint, buy_lamports
+        );
+        let client = reqwest::Client::new();
+        let response = send_api_request(&self.breakers, client.get(&quote_url).timeout(Duration::from_secs(3)), ENDPOINT_JUPITER_QUOTE)
+            .await
+            .ok()?;
+        if !response.status().is_success() {
+            return None;
         }
+        let quote: serde_json::Value = response.json().await.ok()?;
+        let out_amount: u64 = quote.get("outAmount")?.as_str()?.parse().ok()?;
+        (out_amount > 0).then(|| buy_lamports as f64 / out_amount as f64)
     }
-
+    
     /// Check if a token is a Pump.fun token (ends with "pump")
     fn is_pumpfun_token(token_mint: &str) -> bool {
         token_mint.ends_with("pump")
     }
     
     /// Execute a copy buy transaction - uses Pump.fun API for pump tokens (faster!)
-    async fn execute_copy_buy(&self, token_mint: &str, _target_signature: &str) -> Result<String> {
-        let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
-        
-        let _token_mint_pubkey = Pubkey::from_str(token_mint)
+    /// Buys the target made with a stablecoin are paid in that stablecoin when the copy quote
+    /// mode is `same` and the wallet holds enough of it. Tokens with a native route are bought
+    /// on the target's pool, falling back to Jupiter if that fails.
+    async fn execute_copy_buy(
+        &self,
+        token_mint: &str,
+        _target_signature: &str,
+        wallet: &Arc<WalletSigner>,
+        buy_sol: f64,
+        quote: QuoteAsset,
+    ) -> Result<String> {
+        let token_mint_pubkey = Pubkey::from_str(token_mint)
             .context("Invalid token mint")?;
         
         info!("🔄 Building copy buy transaction for token: {}", token_mint);
         
+        if self.copy_quote_mode == CopyQuoteMode::Same && quote.is_stable() {
+            match self.stable_buy_amount(quote, wallet, buy_sol).await {
+                Some(amount) => {
+                    info!("💵 Paying {} raw {:?} like the target", amount, quote);
+                    return self.execute_jupiter_swap(&quote.mint().to_string(), token_mint, amount, wallet).await;
+                }
+                None => info!("💵 Not enough {:?} in {} - paying in SOL", quote, wallet.pubkey()),
+            }
+        }
+        
         // Check if it's a pump.fun token - use their API for speed
         if Self::is_pumpfun_token(token_mint) {
-            info!("🚀 Using PUMP.FUN API (faster for pump tokens)");
-            return self.execute_pumpfun_buy(token_mint).await;
+            // Only the cached migration state is checked here to keep the buy path fast
+            if self.pumpswap.is_known_migrated(&token_mint_pubkey) {
+                info!("🎓 Token has graduated, buying on PumpSwap");
+                return self.execute_pumpswap_buy(&token_mint_pubkey, wallet, buy_sol).await;
+            }
+            if !self.breakers.is_open(Venue::PumpPortal) {
+                info!("🚀 Using PUMP.FUN API (faster for pump tokens)");
+                return self.execute_pumpfun_buy(token_mint, wallet, buy_sol).await;
+            }
+            warn!("🔌 PumpPortal circuit open, routing BUY through Jupiter");
+        }
+        
+        if let Some(route) = self.native.route(&token_mint_pubkey) {
+            info!("🏊 Buying on the target's {:?} pool", route.dex);
+            let buy_lamports = (buy_sol * 1_000_000_000.0) as u64;
+            match self.execute_native_swap(&route, &token_mint_pubkey, true, buy_lamports, wallet, self.control.tip_amount()).await {
+                Ok(sig) => return Ok(sig),
+                Err(e) => warn!("⚠️ {:?} BUY failed, falling back to Jupiter: {:#}", route.dex, e),
+            }
         }
         
         // Fallback to Jupiter for non-pump tokens
         info!("📊 Using Jupiter API (non-pump token)");
-        self.execute_jupiter_buy(token_mint).await
+        self.execute_jupiter_buy(token_mint, wallet, buy_sol).await
+    }
+    
+    /// Stablecoin amount worth `buy_sol`, None if it cannot be quoted or the wallet holds less
+    async fn stable_buy_amount(&self, quote: QuoteAsset, wallet: &Arc<WalletSigner>, buy_sol: f64) -> Option<u64> {
+        let lamports = (buy_sol * 1_000_000_000.0) as u64;
+        let amount = match Self::jupiter_out_amount(&self.breakers, &QuoteAsset::Sol.mint(), &quote.mint(), lamports).await {
+            Ok(amount) => amount,
+            Err(e) => {
+                warn!("⚠️ Could not price {} SOL in {:?}: {:#}", buy_sol, quote, e);
+                return None;
+            }
+        };
+        let balance = self.token_accounts.balance(&wallet.pubkey(), &quote.mint()).await.ok()?;
+        (balance.amount >= amount).then_some(amount)
     }
     
     /// Execute buy via Pump.fun API (fastest for pump tokens)
-    async fn execute_pumpfun_buy(&self, token_mint: &str) -> Result<String> {
+    async fn execute_pumpfun_buy(&self, token_mint: &str, wallet: &Arc<WalletSigner>, buy_sol: f64) -> Result<String> {
         let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(10))
@@ -698,22 +1268,25 @@ impl HeliusGrpcClient {
         // Pump.fun trade API - much faster than Jupiter for pump tokens
         // IMPORTANT: When denominatedInSol=true, amount is in SOL (not lamports!)
         let trade_request = serde_json::json!({
-            "publicKey": self.our_keypair.pubkey().to_string(),
+            "publicKey": wallet.pubkey().to_string(),
             "action": "buy",
             "mint": token_mint,
-            "amount": self.buy_amount_sol,  // Amount in SOL when denominatedInSol=true
+            "amount": buy_sol,  // Amount in SOL when denominatedInSol=true
             "denominatedInSol": true,  // MUST be boolean, not string!
             "slippage": 50,  // 50% slippage for safety
-            "priorityFee": self.tip_amount as f64 / 1_000_000_000.0,  // Convert to SOL
+            "priorityFee": self.control.tip_amount() as f64 / 1_000_000_000.0,  // Convert to SOL
             "pool": "pump"
         });
         
         info!("⚡ Requesting Pump.fun BUY transaction...");
-        let response = client.post(PUMPFUN_TRADE_API)
-            .json(&trade_request)
-            .send()
-            .await
-            .context("Failed to get Pump.fun trade")?;
+        let response = match send_api_request(&self.breakers, client.post(PUMPFUN_TRADE_API).json(&trade_request), ENDPOINT_PUMPPORTAL_TRADE).await {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Pump.fun API request failed: {:#}", e);
+                info!("⚠️ Falling back to Jupiter...");
+                return self.execute_jupiter_buy(token_mint, wallet, buy_sol).await;
+            }
+        };
         
         if !response.status().is_success() {
             let status = response.status();
@@ -721,7 +1294,7 @@ impl HeliusGrpcClient {
             error!("Pump.fun API error ({}): {}", status, error_text);
             // Fallback to Jupiter if Pump.fun fails
             info!("⚠️ Falling back to Jupiter...");
-            return self.execute_jupiter_buy(token_mint).await;
+            return self.execute_jupiter_buy(token_mint, wallet, buy_sol).await;
         }
         
         // The API returns the raw transaction bytes
@@ -735,7 +1308,7 @@ impl HeliusGrpcClient {
         // Sign the transaction
         info!("✍️ Signing Pump.fun BUY transaction...");
         let message_bytes = versioned_tx.message.serialize();
-        let signature = self.our_keypair.sign_message(&message_bytes);
+        let signature = wallet.try_sign_message(&message_bytes)?;
         versioned_tx.signatures[0] = signature;
         
         // Send via RPC - fast, no waiting
@@ -747,26 +1320,49 @@ impl HeliusGrpcClient {
         Ok(sig)
     }
     
+    /// Execute buy directly against the PumpSwap pool of a graduated token
+    async fn execute_pumpswap_buy(&self, token_mint: &Pubkey, wallet: &Arc<WalletSigner>, buy_sol: f64) -> Result<String> {
+        let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
+        let buy_lamports = (buy_sol * 1_000_000_000.0) as u64;
+        
+        let mut versioned_tx = self.pumpswap
+            .buy_transaction(&wallet.pubkey(), token_mint, buy_lamports, NATIVE_SWAP_SLIPPAGE_BPS, self.control.tip_amount())
+            .await
+            .context("Failed to build PumpSwap BUY")?;
+        
+        let message_bytes = versioned_tx.message.serialize();
+        let signature = wallet.try_sign_message(&message_bytes)?;
+        versioned_tx.signatures[0] = signature;
+        
+        let sig = self.send_transaction_fast(&rpc_client, &versioned_tx).await
+            .context("Failed to send PumpSwap transaction")?;
+        info!("🚀 PumpSwap BUY sent: {}", sig);
+        Ok(sig)
+    }
+    
     /// Execute buy via Jupiter API (fallback for non-pump tokens)
-    async fn execute_jupiter_buy(&self, token_mint: &str) -> Result<String> {
+    async fn execute_jupiter_buy(&self, token_mint: &str, wallet: &Arc<WalletSigner>, buy_sol: f64) -> Result<String> {
+        let buy_amount_lamports = (buy_sol * 1_000_000_000.0) as u64;
+        self.execute_jupiter_swap(WSOL_MINT, token_mint, buy_amount_lamports, wallet).await
+    }
+    
+    /// Buy `output_mint` with `amount` raw units of `input_mint` via Jupiter API
+    async fn execute_jupiter_swap(&self, input_mint: &str, output_mint: &str, amount: u64, wallet: &Arc<WalletSigner>) -> Result<String> {
         let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(15))
             .build()?;
         
-        let buy_amount_lamports = (self.buy_amount_sol * 1_000_000_000.0) as u64;
-        
         // Step 1: Get quote from Jupiter
         let quote_url = format!(
             "https://quote-api.jup.ag/v6/quote?inputMint={}&outputMint={}&amount={}&slippageBps=2500",
-            WSOL_MINT,
-            token_mint,
-            buy_amount_lamports
+            input_mint,
+            output_mint,
+            amount
         );
         
         info!("📊 Getting Jupiter quote...");
-        let quote_response = client.get(&quote_url)
-            .send()
+        let quote_response = send_api_request(&self.breakers, client.get(&quote_url), ENDPOINT_JUPITER_QUOTE)
             .await
             .context("Failed to get Jupiter quote")?;
         
@@ -783,16 +1379,14 @@ impl HeliusGrpcClient {
         // Step 2: Get swap transaction
         let swap_request = serde_json::json!({
             "quoteResponse": quote_data,
-            "userPublicKey": self.our_keypair.pubkey().to_string(),
+            "userPublicKey": wallet.pubkey().to_string(),
             "wrapAndUnwrapSol": true,
             "dynamicComputeUnitLimit": true,
-            "prioritizationFeeLamports": self.tip_amount
+            "prioritizationFeeLamports": self.control.tip_amount()
         });
         
         info!("🔨 Building Jupiter swap...");
-        let swap_response = client.post("https://quote-api.jup.ag/v6/swap")
-            .json(&swap_request)
-            .send()
+        let swap_response = send_api_request(&self.breakers, client.post("https://quote-api.jup.ag/v6/swap").json(&swap_request), ENDPOINT_JUPITER_SWAP)
             .await
             .context("Failed to get Jupiter swap")?;
         
@@ -811,7 +1405,7 @@ impl HeliusGrpcClient {
         
         // Sign
         let message_bytes = versioned_tx.message.serialize();
-        let signature = self.our_keypair.sign_message(&message_bytes);
+        let signature = wallet.try_sign_message(&message_bytes)?;
         versioned_tx.signatures[0] = signature;
         
         // Send via RPC - fast
@@ -822,49 +1416,168 @@ impl HeliusGrpcClient {
     }
     
     /// Execute a copy sell transaction - uses Pump.fun API for pump tokens (faster!)
-    async fn execute_copy_sell(&self, token_mint: &str, _target_signature: &str) -> Result<String> {
-        let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
-        
+    /// Sells the target made into a stablecoin go to that stablecoin through Jupiter when the
+    /// copy quote mode is `same`.
+    async fn execute_copy_sell(&self, token_mint: &str, _target_signature: &str, quote: QuoteAsset, tip: TipLevel) -> Result<String> {
         info!("🔄 Preparing copy SELL for token: {}", token_mint);
+        let tip_amount = tip.get_amount(self.control.tip_amount(), self.emergency_tip);
+        let in_kind = self.copy_quote_mode == CopyQuoteMode::Same && quote.is_stable();
         
-        // First, get our token balance
+        // Fire the pre-signed sell if we have one - no blockhash or quote needed
+        if !in_kind {
+            if let Some(sig) = self.send_presigned_sell(token_mint).await {
+                return Ok(sig);
+            }
+        }
+        
+        // First, get our token balance in the wallet holding the position
         let token_mint_pubkey = Pubkey::from_str(token_mint)
             .context("Invalid token mint")?;
+        let wallet = self.position_wallet(token_mint).await;
+        
+        // Balance + decimals across SPL Token and Token-2022 accounts (needed for PumpPortal SELL formatting)
+        let balance = self.token_accounts.balance(&wallet.pubkey(), &token_mint_pubkey).await?;
+        let (token_balance, token_decimals) = (balance.amount, balance.decimals);
         
-        // Try to get token balance + decimals (needed for PumpPortal SELL formatting)
-        let (token_balance, token_decimals) = self
-            .get_token_balance_any_program(&rpc_client, &token_mint_pubkey)
-            .await?;
-
         info!(
             "💰 Our token balance: {} raw units (decimals={})",
             token_balance, token_decimals
         );
-
+        
         if token_balance == 0 {
             return Err(anyhow::anyhow!("No tokens to sell - balance is 0"));
         }
         
-        // Use Pump.fun API for pump tokens (faster!)
+        if in_kind {
+            info!("💵 Selling into {:?} like the target", quote);
+            return self.execute_jupiter_sell(token_mint, &quote.mint().to_string(), token_balance, &wallet, tip_amount).await;
+        }
+        
+        // Use Pump.fun API for pump tokens (faster!), or their PumpSwap pool once graduated
         if Self::is_pumpfun_token(token_mint) {
-            info!("🚀 Using PUMP.FUN API for SELL (faster)");
-            return self
-                .execute_pumpfun_sell(token_mint, token_balance, token_decimals)
-                .await;
+            if self.pumpswap.is_migrated(&token_mint_pubkey).await {
+                info!("🎓 Token has graduated, selling on PumpSwap");
+                return self.execute_pumpswap_sell(&token_mint_pubkey, token_balance, &wallet, tip_amount).await;
+            }
+            if !self.breakers.is_open(Venue::PumpPortal) {
+                info!("🚀 Using PUMP.FUN API for SELL (faster)");
+                return self
+                    .execute_pumpfun_sell(token_mint, token_balance, token_decimals, &wallet, tip_amount)
+                    .await;
+            }
+            warn!("🔌 PumpPortal circuit open, routing SELL through Jupiter");
+        }
+        
+        if let Some(route) = self.native.route(&token_mint_pubkey) {
+            info!("🏊 Selling on the {:?} pool the token was bought on", route.dex);
+            match self.execute_native_swap(&route, &token_mint_pubkey, false, token_balance, &wallet, tip_amount).await {
+                Ok(sig) => return Ok(sig),
+                Err(e) => warn!("⚠️ {:?} SELL failed, falling back to Jupiter: {:#}", route.dex, e),
+            }
         }
         
         // Fallback to Jupiter for non-pump tokens
         info!("📊 Using Jupiter API for SELL");
-        self.execute_jupiter_sell(token_mint, token_balance).await
+        self.execute_jupiter_sell(token_mint, WSOL_MINT, token_balance, &wallet, tip_amount).await
     }
     
     /// Execute sell via Pump.fun API (fastest for pump tokens)
-    async fn execute_pumpfun_sell(&self, token_mint: &str, token_amount_raw: u64, token_decimals: u8) -> Result<String> {
+    async fn execute_pumpfun_sell(
+        &self,
+        token_mint: &str,
+        token_amount_raw: u64,
+        token_decimals: u8,
+        wallet: &Arc<WalletSigner>,
+        tip_amount: u64,
+    ) -> Result<String> {
         let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(10))
             .build()?;
-
+        
+        let mut versioned_tx = Self::fetch_pumpfun_sell_transaction(
+            &self.breakers,
+            &client,
+            &wallet.pubkey(),
+            token_mint,
+            token_amount_raw,
+            token_decimals,
+            tip_amount,
+        )
+        .await?;
+        
+        info!("✍️ Signing Pump.fun SELL transaction...");
+        let message_bytes = versioned_tx.message.serialize();
+        let signature = wallet.try_sign_message(&message_bytes)?;
+        versioned_tx.signatures[0] = signature;
+        
+        info!("📤 Sending SELL transaction...");
+        let sig = self
+            .send_transaction_fast(&rpc_client, &versioned_tx)
+            .await
+            .context("Failed to send Pump.fun SELL transaction")?;
+        
+        info!("🚀 Pump.fun SELL sent: {}", sig);
+        Ok(sig)
+    }
+    
+    /// Execute sell directly against the PumpSwap pool of a graduated token
+    async fn execute_pumpswap_sell(&self, token_mint: &Pubkey, token_amount_raw: u64, wallet: &Arc<WalletSigner>, tip_amount: u64) -> Result<String> {
+        let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
+        
+        let mut versioned_tx = self.pumpswap
+            .sell_transaction(&wallet.pubkey(), token_mint, token_amount_raw, NATIVE_SWAP_SLIPPAGE_BPS, tip_amount)
+            .await
+            .context("Failed to build PumpSwap SELL")?;
+        
+        let message_bytes = versioned_tx.message.serialize();
+        let signature = wallet.try_sign_message(&message_bytes)?;
+        versioned_tx.signatures[0] = signature;
+        
+        let sig = self.send_transaction_fast(&rpc_client, &versioned_tx).await
+            .context("Failed to send PumpSwap SELL transaction")?;
+        info!("🚀 PumpSwap SELL sent: {}", sig);
+        Ok(sig)
+    }
+    
+    /// Execute a buy (`amount_in` lamports) or sell (`amount_in` raw tokens) against a route's pool
+    async fn execute_native_swap(
+        &self,
+        route: &NativeRoute,
+        token_mint: &Pubkey,
+        is_buy: bool,
+        amount_in: u64,
+        wallet: &Arc<WalletSigner>,
+        tip_amount: u64,
+    ) -> Result<String> {
+        let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
+        let side = if is_buy { "BUY" } else { "SELL" };
+        
+        let mut versioned_tx = self.native
+            .swap_transaction(route, &wallet.pubkey(), token_mint, is_buy, amount_in, tip_amount)
+            .await
+            .with_context(|| format!("Failed to build {:?} {}", route.dex, side))?;
+        
+        let message_bytes = versioned_tx.message.serialize();
+        let signature = wallet.try_sign_message(&message_bytes)?;
+        versioned_tx.signatures[0] = signature;
+        
+        let sig = self.send_transaction_fast(&rpc_client, &versioned_tx).await
+            .with_context(|| format!("Failed to send {:?} {} transaction", route.dex, side))?;
+        info!("🚀 {:?} {} sent: {}", route.dex, side, sig);
+        Ok(sig)
+    }
+    
+    /// Request an unsigned Pump.fun SELL transaction from PumpPortal
+    async fn fetch_pumpfun_sell_transaction(
+        breakers: &CircuitBreakers,
+        client: &reqwest::Client,
+        owner: &Pubkey,
+        token_mint: &str,
+        token_amount_raw: u64,
+        token_decimals: u8,
+        tip_amount: u64,
+    ) -> Result<VersionedTransaction> {
         fn format_ui_amount(raw: u64, decimals: u8) -> String {
             if decimals == 0 {
                 return raw.to_string();
@@ -883,7 +1596,7 @@ impl HeliusGrpcClient {
                 format!("{}.{}", whole, frac_str)
             }
         }
-
+        
         // PumpPortal SELL: many implementations expect amount in UI token units when denominatedInSol=false.
         let ui_amount = (token_amount_raw as f64) / 10f64.powi(token_decimals as i32);
         let ui_amount_str = format_ui_amount(token_amount_raw, token_decimals);
@@ -891,7 +1604,7 @@ impl HeliusGrpcClient {
             "🧮 SELL amounts: raw={} decimals={} ui={} ui_str={}",
             token_amount_raw, token_decimals, ui_amount, ui_amount_str
         );
-
+        
         // We try a couple of encodings (Pump-only) because PumpPortal can be picky and returns generic 400.
         // 1) UI amount as exact decimal string
         // 2) UI amount as number (f64)
@@ -903,36 +1616,33 @@ impl HeliusGrpcClient {
             serde_json::json!(token_amount_raw),
             serde_json::json!("100%"),
         ];
-
+        
         let attempts_len = attempts.len();
         let mut last_error: Option<anyhow::Error> = None;
-
+        
         for (idx, amount_value) in attempts.into_iter().enumerate() {
             let trade_request = serde_json::json!({
-                "publicKey": self.our_keypair.pubkey().to_string(),
+                "publicKey": owner.to_string(),
                 "action": "sell",
                 "mint": token_mint,
                 "amount": amount_value,
                 "denominatedInSol": false,
                 "slippage": 50,
-                "priorityFee": self.tip_amount as f64 / 1_000_000_000.0,
+                "priorityFee": tip_amount as f64 / 1_000_000_000.0,
                 "pool": "pump"
             });
-
+            
             info!(
                 "⚡ Pump.fun SELL attempt {}/{} request: {}",
                 idx + 1,
                 attempts_len,
                 serde_json::to_string_pretty(&trade_request).unwrap_or_default()
             );
-
-            let response = client
-                .post(PUMPFUN_TRADE_API)
-                .json(&trade_request)
-                .send()
+            
+            let response = send_api_request(breakers, client.post(PUMPFUN_TRADE_API).json(&trade_request), ENDPOINT_PUMPPORTAL_TRADE)
                 .await
                 .context("Failed to get Pump.fun trade")?;
-
+            
             if !response.status().is_success() {
                 let status = response.status();
                 let error_text = response.text().await.unwrap_or_default();
@@ -940,41 +1650,65 @@ impl HeliusGrpcClient {
                 last_error = Some(err);
                 continue;
             }
-
+            
             let tx_bytes = response
                 .bytes()
                 .await
                 .context("Failed to get transaction bytes")?;
-
-            let mut versioned_tx: VersionedTransaction = bincode::deserialize(&tx_bytes)
-                .context("Failed to deserialize Pump.fun SELL transaction")?;
-
-            info!("✍️ Signing Pump.fun SELL transaction...");
-            let message_bytes = versioned_tx.message.serialize();
-            let signature = self.our_keypair.sign_message(&message_bytes);
-            versioned_tx.signatures[0] = signature;
-
-            info!("📤 Sending SELL transaction...");
-            let sig = self
-                .send_transaction_fast(&rpc_client, &versioned_tx)
-                .await
-                .context("Failed to send Pump.fun SELL transaction")?;
-
-            info!("🚀 Pump.fun SELL sent: {}", sig);
-            return Ok(sig);
+            
+            return bincode::deserialize(&tx_bytes)
+                .context("Failed to deserialize Pump.fun SELL transaction");
         }
-
-        return Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Pump.fun SELL failed")));
         
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Pump.fun SELL failed")))
     }
     
-    /// Execute sell via Jupiter API (fallback)
-    async fn execute_jupiter_sell(&self, token_mint: &str, token_balance: u64) -> Result<String> {
+    /// Execute sell into `output_mint` via Jupiter API (fallback)
+    async fn execute_jupiter_sell(
+        &self,
+        token_mint: &str,
+        output_mint: &str,
+        token_balance: u64,
+        wallet: &Arc<WalletSigner>,
+        tip_amount: u64,
+    ) -> Result<String> {
         let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(15))
             .build()?;
         
+        let mut versioned_tx = Self::fetch_jupiter_sell_transaction(
+            &self.breakers,
+            &client,
+            &wallet.pubkey(),
+            token_mint,
+            output_mint,
+            token_balance,
+            tip_amount,
+        ).await?;
+        
+        // Sign
+        let message_bytes = versioned_tx.message.serialize();
+        let signature = wallet.try_sign_message(&message_bytes)?;
+        versioned_tx.signatures[0] = signature;
+        
+        // Send via RPC - fast
+        let sig = self.send_transaction_fast(&rpc_client, &versioned_tx).await?;
+        info!("🚀 Jupiter SELL sent: {}", sig);
+        
+        Ok(sig)
+    }
+    
+    /// Request an unsigned Jupiter SELL (token -> `output_mint`, usually WSOL) transaction
+    async fn fetch_jupiter_sell_transaction(
+        breakers: &CircuitBreakers,
+        client: &reqwest::Client,
+        owner: &Pubkey,
+        token_mint: &str,
+        output_mint: &str,
+        token_balance: u64,
+        tip_amount: u64,
+    ) -> Result<VersionedTransaction> {
         if token_balance == 0 {
             return Err(anyhow::anyhow!("No tokens to sell - balance is 0"));
         }
@@ -983,13 +1717,12 @@ impl HeliusGrpcClient {
         let quote_url = format!(
             "https://quote-api.jup.ag/v6/quote?inputMint={}&outputMint={}&amount={}&slippageBps=2500",
             token_mint,
-            WSOL_MINT,
+            output_mint,
             token_balance
         );
         
         info!("📊 Getting Jupiter SELL quote for {} tokens...", token_balance);
-        let quote_response = client.get(&quote_url)
-            .send()
+        let quote_response = send_api_request(breakers, client.get(&quote_url), ENDPOINT_JUPITER_QUOTE)
             .await
             .context("Failed to get Jupiter quote")?;
         
@@ -1004,20 +1737,22 @@ impl HeliusGrpcClient {
             .and_then(|s| s.parse::<u64>().ok())
             .unwrap_or(0);
         
-        info!("✅ Will receive ~{:.4} SOL", out_amount as f64 / 1_000_000_000.0);
+        if output_mint == WSOL_MINT {
+            info!("✅ Will receive ~{:.4} SOL", out_amount as f64 / 1_000_000_000.0);
+        } else {
+            info!("✅ Will receive ~{} raw {}", out_amount, output_mint);
+        }
         
         // Step 2: Get swap transaction
         let swap_request = serde_json::json!({
             "quoteResponse": quote_data,
-            "userPublicKey": self.our_keypair.pubkey().to_string(),
+            "userPublicKey": owner.to_string(),
             "wrapAndUnwrapSol": true,
             "dynamicComputeUnitLimit": true,
-            "prioritizationFeeLamports": self.tip_amount
+            "prioritizationFeeLamports": tip_amount
         });
         
-        let swap_response = client.post("https://quote-api.jup.ag/v6/swap")
-            .json(&swap_request)
-            .send()
+        let swap_response = send_api_request(breakers, client.post("https://quote-api.jup.ag/v6/swap").json(&swap_request), ENDPOINT_JUPITER_SWAP)
             .await?;
         
         if !swap_response.status().is_success() {
@@ -1031,38 +1766,170 @@ impl HeliusGrpcClient {
             .context("No swap transaction")?;
         
         let tx_bytes = BASE64.decode(swap_transaction)?;
-        let mut versioned_tx: VersionedTransaction = bincode::deserialize(&tx_bytes)?;
+        Ok(bincode::deserialize(&tx_bytes)?)
+    }
+    
+    /// Send the pre-signed emergency sell for a token, if one is ready
+    /// The nonce account is rotated in the background once the transaction settles
+    async fn send_presigned_sell(&self, token_mint: &str) -> Option<String> {
+        let pool = self.nonce_pool.as_ref()?;
+        let sell = self.presigned_sells.write().await.remove(token_mint)?;
+        let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
         
-        // Sign
-        let message_bytes = versioned_tx.message.serialize();
-        let signature = self.our_keypair.sign_message(&message_bytes);
-        versioned_tx.signatures[0] = signature;
+        info!(
+            "⚡ Firing pre-signed SELL for {} ({} raw, nonce {})",
+            sell.token_mint, sell.token_amount, sell.nonce_account
+        );
         
-        // Send via RPC - fast
-        let sig = self.send_transaction_fast(&rpc_client, &versioned_tx).await?;
-        info!("🚀 Jupiter SELL sent: {}", sig);
+        match self.send_transaction_fast(&rpc_client, &sell.transaction).await {
+            Ok(sig) => {
+                let pool = pool.clone();
+                let nonce_account = sell.nonce_account;
+                let signature = sell.transaction.signatures[0];
+                tokio::spawn(async move {
+                    if let Err(e) = pool.rotate(&nonce_account, &signature, NONCE_ROTATE_TIMEOUT).await {
+                        warn!("⚠️ Failed to rotate nonce account {}: {:?}", nonce_account, e);
+                    }
+                });
+                Some(sig)
+            }
+            Err(e) => {
+                warn!("⚠️ Pre-signed SELL failed to send, building a fresh one: {:?}", e);
+                pool.release(&sell.nonce_account);
+                None
+            }
+        }
+    }
+    
+    /// Pre-signed sell monitor - keeps a nonce-based emergency sell ready for every position
+    async fn presign_monitor(
+        context: MonitorContext,
+        pool: Arc<NoncePool>,
+        presigned: Arc<RwLock<HashMap<String, PresignedSell>>>,
+        signer: Arc<WalletSigner>,
+    ) {
+        info!("🔏 Pre-signed sell monitor started");
         
-        Ok(sig)
+        if let Err(e) = pool.load_existing().await {
+            warn!("⚠️ Could not load existing nonce accounts: {:?}", e);
+        }
+        
+        let rpc_client = AsyncRpcClient::new(context.rpc_url.clone());
+        let MonitorContext { positions, token_accounts, .. } = &context;
+        
+        loop {
+            if let Err(e) = pool.maintain().await {
+                warn!("⚠️ Nonce pool maintenance failed: {:?}", e);
+            }
+            
+            // Nonce accounts belong to the main wallet, so only its positions can be pre-signed
+            let mints: Vec<String> = positions.read().await
+                .values()
+                .filter(|p| p.wallet.pubkey() == signer.pubkey())
+                .map(|p| p.token_mint.clone())
+                .collect();
+            
+            // Drop pre-signed sells (and free their nonces) for positions that are gone
+            presigned.write().await.retain(|mint, sell| {
+                let keep = mints.contains(mint);
+                if !keep {
+                    pool.release(&sell.nonce_account);
+                }
+                keep
+            });
+            
+            for mint in mints {
+                let token_mint = match Pubkey::from_str(&mint) {
+                    Ok(p) => p,
+                    Err(_) => continue,
+                };
+                
+                let (token_balance, token_decimals) = match token_accounts.balance(&signer.pubkey(), &token_mint).await {
+                    Ok(balance) => (balance.amount, balance.decimals),
+                    Err(_) => continue,
+                };
+                
+                if token_balance == 0 {
+                    continue;
+                }
+                
+                let is_fresh = presigned.read().await
+                    .get(&mint)
+                    .map(|s| s.token_amount == token_balance && s.created_at.elapsed() < PRESIGNED_SELL_MAX_AGE)
+                    .unwrap_or(false);
+                if is_fresh {
+                    continue;
+                }
+                
+                match Self::presign_sell(&context, &pool, &rpc_client, &signer, &token_mint, token_balance, token_decimals).await {
+                    Ok(sell) => {
+                        debug!(
+                            "🔏 Pre-signed SELL ready for {} ({} raw, nonce {})",
+                            mint, sell.token_amount, sell.nonce_account
+                        );
+                        presigned.write().await.insert(mint, sell);
+                    }
+                    Err(e) => {
+                        warn!("⚠️ Could not pre-sign SELL for {}: {:?}", mint, e);
+                    }
+                }
+            }
+            
+            sleep(PRESIGN_INTERVAL).await;
+        }
+    }
+    
+    /// Build and sign a nonce-based sell for our full balance of a token
+    async fn presign_sell(
+        context: &MonitorContext,
+        pool: &NoncePool,
+        rpc_client: &AsyncRpcClient,
+        signer: &Arc<WalletSigner>,
+        token_mint: &Pubkey,
+        token_balance: u64,
+        token_decimals: u8,
+    ) -> Result<PresignedSell> {
+        let MonitorContext { breakers, pumpswap, control, .. } = context;
+        let tip_amount = control.tip_amount();
+        let nonce = pool.acquire(token_mint)
+            .context("No idle nonce account available")?;
+        
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        
+        let mint_str = token_mint.to_string();
+        let pump_token = Self::is_pumpfun_token(&mint_str);
+        let unsigned = if pump_token && pumpswap.is_migrated(token_mint).await {
+            pumpswap.sell_transaction(&signer.pubkey(), token_mint, token_balance, NATIVE_SWAP_SLIPPAGE_BPS, tip_amount).await?
+        } else if pump_token && !breakers.is_open(Venue::PumpPortal) {
+            Self::fetch_pumpfun_sell_transaction(breakers, &client, &signer.pubkey(), &mint_str, token_balance, token_decimals, tip_amount).await?
+        } else {
+            Self::fetch_jupiter_sell_transaction(breakers, &client, &signer.pubkey(), &mint_str, WSOL_MINT, token_balance, tip_amount).await?
+        };
+        
+        let lookup_tables = fetch_message_lookup_tables(rpc_client, &unsigned.message).await?;
+        let transaction = apply_durable_nonce(&unsigned, &nonce.address, nonce.nonce, signer, &lookup_tables)?;
+        
+        Ok(PresignedSell {
+            token_mint: *token_mint,
+            token_amount: token_balance,
+            nonce_account: nonce.address,
+            transaction,
+            created_at: std::time::Instant::now(),
+        })
     }
     
     /// Take profit monitor - runs in background checking positions
     /// Uses Pump.fun bonding curve for price (faster than Jupiter)
-    async fn take_profit_monitor(
-        positions: Arc<RwLock<HashMap<String, PositionInfo>>>,
-        rpc_url: String,
-        keypair: Arc<Keypair>,
-        tip_amount: u64,
-        mut tiers: Vec<TakeProfitTier>,
-    ) {
-        // Sort tiers by multiplier asc (and keep them stable)
-        tiers.sort_by(|a, b| a.multiplier.partial_cmp(&b.multiplier).unwrap_or(std::cmp::Ordering::Equal));
+    async fn take_profit_monitor(context: MonitorContext) {
+        let MonitorContext { positions, rpc_url, control, state, notifier, breakers, token_accounts, reclaimer, pumpswap, meteora, native } = &context;
         info!(
-            "📈 Take Profit Monitor started (tiers={})",
-            serde_json::to_string(&tiers).unwrap_or_else(|_| "[]".to_string())
+            "📈 Take Profit Monitor started (enabled={}, tiers={})",
+            control.take_profit_enabled(),
+            serde_json::to_string(&control.take_profit_tiers()).unwrap_or_else(|_| "[]".to_string())
         );
         
-        let rpc_client = AsyncRpcClient::new(rpc_url.clone());
-        
         loop {
             // Check every 2 seconds - faster TP reaction
             sleep(Duration::from_secs(2)).await;
@@ -1076,6 +1943,15 @@ impl HeliusGrpcClient {
                 continue;
             }
             
+            let tip_amount = control.tip_amount();
+            
+            // Tiers are sorted by multiplier asc; no tiers means nothing to sell this pass
+            let tiers = if control.take_profit_enabled() {
+                control.take_profit_tiers()
+            } else {
+                Vec::new()
+            };
+            
             for position in positions_to_check {
                 // Get token balance first
                 let token_mint = match Pubkey::from_str(&position.token_mint) {
@@ -1084,14 +1960,8 @@ impl HeliusGrpcClient {
                 };
                 
                 // Get current token balance + decimals
-                let (token_balance, token_decimals) = match Self::get_token_balance_static(
-                    &rpc_client,
-                    &keypair.pubkey(),
-                    &token_mint,
-                )
-                .await
-                {
-                    Ok(v) => v,
+                let (token_balance, token_decimals) = match token_accounts.balance(&position.wallet.pubkey(), &token_mint).await {
+                    Ok(balance) => (balance.amount, balance.decimals),
                     Err(_) => continue,
                 };
                 
@@ -1102,141 +1972,165 @@ impl HeliusGrpcClient {
                     continue;
                 }
                 
-                // Get price from Pump.fun bonding curve (faster than Jupiter)
-                match Self::get_pump_price(&position.token_mint).await {
-                    Ok(price_sol) => {
-                        let current_value = (token_balance as f64) * price_sol;
-                        let profit_ratio = current_value / position.entry_sol;
-
-                        // Find the highest tier that is reached and not yet applied
-                        let mut selected: Option<&TakeProfitTier> = None;
-                        for tier in tiers.iter() {
-                            if profit_ratio >= tier.multiplier && tier.sell_percent > position.sold_percent {
-                                selected = Some(tier);
-                            }
+                state.sync_position_amount(&token_mint, token_balance);
+                
+                // Price from the bonding curve, the PumpSwap pool once the token has graduated,
+                // the token's Meteora, CPMM or LaunchLab pool, or a Jupiter quote for the balance
+                if let Ok(price_sol) = Self::get_position_price(breakers, pumpswap, meteora, native, &token_mint, token_balance).await {
+                    let current_value = (token_balance as f64) * price_sol;
+                    let profit_ratio = current_value / position.entry_sol;
+                    state.update_position_value(&token_mint, (current_value * 1_000_000_000.0) as u64);
+                    
+                    if tiers.is_empty() {
+                        continue;
+                    }
+                    
+                    // Find the highest tier that is reached and not yet applied
+                    let mut selected: Option<(usize, &TakeProfitTier)> = None;
+                    for (index, tier) in tiers.iter().enumerate() {
+                        if profit_ratio >= tier.multiplier && tier.sell_percent > position.sold_percent {
+                            selected = Some((index, tier));
                         }
-
-                        if let Some(tier) = selected {
-                            let target_percent = tier.sell_percent.min(100);
-                            let already_percent = position.sold_percent.min(100);
-                            if target_percent <= already_percent {
-                                continue;
-                            }
-
-                            // Compute how many tokens to sell to reach target_percent cumulatively.
-                            // We infer the initial token amount from remaining balance and already sold percent.
-                            let current_balance_u128 = token_balance as u128;
-                            let remaining_percent = 100u128.saturating_sub(already_percent as u128);
-                            if remaining_percent == 0 {
-                                // Shouldn't happen; treat as fully sold
+                    }
+                    
+                    if let Some((tier_index, tier)) = selected {
+                        let target_percent = tier.sell_percent.min(100);
+                        let already_percent = position.sold_percent.min(100);
+                        if target_percent <= already_percent {
+                            continue;
+                        }
+                        
+                        // Compute how many tokens to sell to reach target_percent cumulatively.
+                        // We infer the initial token amount from remaining balance and already sold percent.
+                        let current_balance_u128 = token_balance as u128;
+                        let remaining_percent = 100u128.saturating_sub(already_percent as u128);
+                        if remaining_percent == 0 {
+                            // Shouldn't happen; treat as fully sold
+                            let mut positions_guard = positions.write().await;
+                            positions_guard.remove(&position.token_mint);
+                            continue;
+                        }
+                        let initial_est = current_balance_u128
+                            .saturating_mul(100u128)
+                            .checked_div(remaining_percent)
+                            .unwrap_or(current_balance_u128);
+                        
+                        let delta_percent = (target_percent - already_percent) as u128;
+                        let mut amount_to_sell = initial_est
+                            .saturating_mul(delta_percent)
+                            .checked_div(100u128)
+                            .unwrap_or(0);
+                        
+                        if amount_to_sell > current_balance_u128 {
+                            amount_to_sell = current_balance_u128;
+                        }
+                        if amount_to_sell == 0 {
+                            continue;
+                        }
+                        
+                        info!(
+                            "🎯 TP {} reached: {:.2}x >= {:.2}x | sell {}% -> {}% (selling {} raw)",
+                            &position.token_mint[..8],
+                            profit_ratio,
+                            tier.multiplier,
+                            already_percent,
+                            target_percent,
+                            amount_to_sell
+                        );
+                        
+                        match Self::execute_take_profit_sell(
+                            &context,
+                            &position.token_mint,
+                            amount_to_sell as u64,
+                            token_decimals,
+                            &position.wallet,
+                            tip_amount,
+                        )
+                        .await
+                        {
+                            Ok(sig) => {
+                                info!("✅ TP SELL: {}", sig);
+                                metrics().tip_lamports.inc_by(tip_amount);
+                                Self::track_landing(rpc_url.clone(), state.clone(), &position.token_mint, sig.clone(), None);
+                                for index in 0..=tier_index {
+                                    state.mark_tp_triggered(&token_mint, index);
+                                }
+                                notifier.notify(NotifyEvent::TakeProfit {
+                                    token_mint: position.token_mint.clone(),
+                                    multiplier: tier.multiplier,
+                                    sell_percent: target_percent,
+                                    signature: sig.clone(),
+                                });
+                                // Fire-and-forget send, so SOL received is estimated from the quote
+                                let sol_estimate = current_value * amount_to_sell as f64 / token_balance as f64;
+                                state.reduce_position(
+                                    &token_mint,
+                                    amount_to_sell as u64,
+                                    (sol_estimate * 1_000_000_000.0) as u64,
+                                    TradeRecordType::SellTakeProfit,
+                                    sig,
+                                );
                                 let mut positions_guard = positions.write().await;
-                                positions_guard.remove(&position.token_mint);
-                                continue;
-                            }
-                            let initial_est = current_balance_u128
-                                .saturating_mul(100u128)
-                                .checked_div(remaining_percent)
-                                .unwrap_or(current_balance_u128);
-
-                            let delta_percent = (target_percent - already_percent) as u128;
-                            let mut amount_to_sell = initial_est
-                                .saturating_mul(delta_percent)
-                                .checked_div(100u128)
-                                .unwrap_or(0);
-
-                            if amount_to_sell > current_balance_u128 {
-                                amount_to_sell = current_balance_u128;
-                            }
-                            if amount_to_sell == 0 {
-                                continue;
-                            }
-
-                            info!(
-                                "🎯 TP {} reached: {:.2}x >= {:.2}x | sell {}% -> {}% (selling {} raw)",
-                                &position.token_mint[..8],
-                                profit_ratio,
-                                tier.multiplier,
-                                already_percent,
-                                target_percent,
-                                amount_to_sell
-                            );
-
-                            match Self::execute_take_profit_sell(
-                                &position.token_mint,
-                                amount_to_sell as u64,
-                                token_decimals,
-                                &rpc_url,
-                                &keypair,
-                                tip_amount,
-                            )
-                            .await
-                            {
-                                Ok(sig) => {
-                                    info!("✅ TP SELL: {}", sig);
-                                    let mut positions_guard = positions.write().await;
-                                    if target_percent >= 100 {
-                                        positions_guard.remove(&position.token_mint);
-                                    } else if let Some(p) = positions_guard.get_mut(&position.token_mint) {
-                                        p.sold_percent = target_percent;
+                                if target_percent >= 100 {
+                                    positions_guard.remove(&position.token_mint);
+                                    if let Some(reclaimer) = &reclaimer {
+                                        reclaimer.request();
                                     }
-                                }
-                                Err(e) => {
-                                    error!("❌ TP sell failed: {:?}", e);
+                                } else if let Some(p) = positions_guard.get_mut(&position.token_mint) {
+                                    p.sold_percent = target_percent;
                                 }
                             }
+                            Err(e) => {
+                                error!("❌ TP sell failed: {:?}", e);
+                            }
                         }
                     }
-                    Err(_) => {}
                 }
             }
         }
     }
     
-    /// Get token balance (static version for TP monitor)
-    async fn get_token_balance_static(
-        rpc_client: &AsyncRpcClient,
-        owner: &Pubkey,
+    /// Price of a held token in SOL per raw unit, read from the PumpSwap pool once the
+    /// token has graduated and from PumpPortal before that. Other tokens are priced from
+    /// their Meteora SOL pool, or the CPMM pool or LaunchLab curve they were bought on, else
+    /// from a Jupiter quote selling `token_balance` into SOL (tokens bought against a
+    /// stablecoin or another token).
+    async fn get_position_price(
+        breakers: &CircuitBreakers,
+        pumpswap: &PumpSwap,
+        meteora: &MeteoraPools,
+        native: &NativeSwaps,
         token_mint: &Pubkey,
-    ) -> Result<(u64, u8)> {
-        use solana_client::rpc_request::TokenAccountsFilter;
-        
-        let filter = TokenAccountsFilter::Mint(*token_mint);
-        let accounts = rpc_client.get_token_accounts_by_owner(owner, filter).await?;
-        
-        let mut total: u64 = 0;
-        let mut decimals: Option<u8> = None;
-        for account in accounts.iter() {
-            if let solana_account_decoder::UiAccountData::Json(parsed) = &account.account.data {
-                if let Some(info) = parsed.parsed.get("info") {
-                    if let Some(token_amount) = info.get("tokenAmount") {
-                        if decimals.is_none() {
-                            decimals = token_amount
-                                .get("decimals")
-                                .and_then(|v| v.as_u64())
-                                .and_then(|v| u8::try_from(v).ok());
-                        }
-                        if let Some(amount_str) = token_amount.get("amount") {
-                            if let Some(amount) = amount_str.as_str() {
-                                total += amount.parse::<u64>().unwrap_or(0);
-                            }
-                        }
-                    }
-                }
+        token_balance: u64,
+    ) -> Result<f64> {
+        let mint = token_mint.to_string();
+        if Self::is_pumpfun_token(&mint) {
+            if pumpswap.is_migrated(token_mint).await {
+                return Ok(pumpswap.price(token_mint).await? / 1_000_000_000.0);
             }
+            return Self::get_pump_price(breakers, &mint).await;
+        }
+        if let Some(price) = meteora.price(token_mint).await? {
+            return Ok(price / 1_000_000_000.0);
+        }
+        if let Some(price) = native.price(token_mint).await? {
+            return Ok(price / 1_000_000_000.0);
+        }
+        if token_balance == 0 {
+            anyhow::bail!("No balance of {} to quote", mint);
         }
-        Ok((total, decimals.unwrap_or(0)))
+        let lamports = Self::jupiter_out_amount(breakers, token_mint, &QuoteAsset::Sol.mint(), token_balance).await?;
+        Ok(lamports as f64 / 1_000_000_000.0 / token_balance as f64)
     }
     
     /// Get price from Pump.fun bonding curve (much faster than Jupiter)
-    async fn get_pump_price(token_mint: &str) -> Result<f64> {
+    async fn get_pump_price(breakers: &CircuitBreakers, token_mint: &str) -> Result<f64> {
         let client = reqwest::Client::new();
         
         // Pump.fun quote API - gives us the bonding curve price
         let url = format!("https://pumpportal.fun/api/quote?mint={}&sol=0.001&isBuy=true", token_mint);
         
-        let response = client.get(&url)
-            .timeout(Duration::from_secs(3))
-            .send()
+        let response = send_api_request(breakers, client.get(&url).timeout(Duration::from_secs(3)), ENDPOINT_PUMPPORTAL_QUOTE)
             .await?;
         
         if !response.status().is_success() {
@@ -1256,22 +2150,69 @@ impl HeliusGrpcClient {
         Err(anyhow::anyhow!("Could not parse price"))
     }
     
-    /// Execute take profit sell - uses Pump.fun for pump tokens
+    /// Execute take profit sell - uses Pump.fun for pump tokens and the pool a token was bought on
+    /// when it has a native route
     async fn execute_take_profit_sell(
+        context: &MonitorContext,
         token_mint: &str,
         token_amount: u64,
         token_decimals: u8,
-        rpc_url: &str,
-        keypair: &Arc<Keypair>,
+        signer: &Arc<WalletSigner>,
         tip_amount: u64,
     ) -> Result<String> {
-        let rpc_client = AsyncRpcClient::new(rpc_url.to_string());
+        let MonitorContext { breakers, pumpswap, native, .. } = context;
+        let rpc_client = AsyncRpcClient::new(context.rpc_url.clone());
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(10))
             .build()?;
         
-        // Use Pump.fun API for pump tokens (faster!)
-        if token_mint.ends_with("pump") {
+        // Graduated pump tokens sell straight into their PumpSwap pool
+        if let Ok(mint) = Pubkey::from_str(token_mint) {
+            if token_mint.ends_with("pump") && pumpswap.is_migrated(&mint).await {
+                let mut versioned_tx = pumpswap
+                    .sell_transaction(&signer.pubkey(), &mint, token_amount, NATIVE_SWAP_SLIPPAGE_BPS, tip_amount)
+                    .await?;
+                let message_bytes = versioned_tx.message.serialize();
+                let signature = signer.try_sign_message(&message_bytes)?;
+                versioned_tx.signatures[0] = signature;
+                
+                let config = solana_client::rpc_config::RpcSendTransactionConfig {
+                    skip_preflight: true,
+                    preflight_commitment: Some(solana_sdk::commitment_config::CommitmentLevel::Processed),
+                    max_retries: Some(0),
+                    ..Default::default()
+                };
+                let sig = send_rpc_transaction(breakers, &rpc_client, &versioned_tx, config).await?;
+                return Ok(sig.to_string());
+            }
+            
+            // Tokens bought through a native builder sell back into the same pool
+            if let Some(route) = native.route(&mint) {
+                info!("🏊 TP selling on the {:?} pool the token was bought on", route.dex);
+                match native.swap_transaction(&route, &signer.pubkey(), &mint, false, token_amount, tip_amount).await {
+                    Ok(mut versioned_tx) => {
+                        let message_bytes = versioned_tx.message.serialize();
+                        let signature = signer.try_sign_message(&message_bytes)?;
+                        versioned_tx.signatures[0] = signature;
+                        
+                        let config = solana_client::rpc_config::RpcSendTransactionConfig {
+                            skip_preflight: true,
+                            preflight_commitment: Some(solana_sdk::commitment_config::CommitmentLevel::Processed),
+                            max_retries: Some(0),
+                            ..Default::default()
+                        };
+                        match send_rpc_transaction(breakers, &rpc_client, &versioned_tx, config).await {
+                            Ok(sig) => return Ok(sig.to_string()),
+                            Err(e) => warn!("⚠️ {:?} TP SELL failed to send, falling back to Jupiter: {:#}", route.dex, e),
+                        }
+                    }
+                    Err(e) => warn!("⚠️ {:?} TP SELL failed, falling back to Jupiter: {:#}", route.dex, e),
+                }
+            }
+        }
+        
+        // Use Pump.fun API for pump tokens (faster!), Jupiter while PumpPortal's circuit is open
+        if token_mint.ends_with("pump") && !breakers.is_open(Venue::PumpPortal) {
             fn format_ui_amount(raw: u64, decimals: u8) -> String {
                 if decimals == 0 {
                     return raw.to_string();
@@ -1290,14 +2231,14 @@ impl HeliusGrpcClient {
                     format!("{}.{}", whole, frac_str)
                 }
             }
-
+            
             let ui_amount = (token_amount as f64) / 10f64.powi(token_decimals as i32);
             let ui_amount_str = format_ui_amount(token_amount, token_decimals);
             info!(
                 "⚡ Pump.fun TP SELL raw={} decimals={} ui={} ui_str={}",
                 token_amount, token_decimals, ui_amount, ui_amount_str
             );
-
+            
             // Pump-only: try a few encodings because PumpPortal can return generic 400.
             let attempts: [serde_json::Value; 4] = [
                 serde_json::json!(ui_amount_str),
@@ -1305,12 +2246,12 @@ impl HeliusGrpcClient {
                 serde_json::json!(token_amount),
                 serde_json::json!("100%"),
             ];
-
+            
             let attempts_len = attempts.len();
             let mut last_error: Option<anyhow::Error> = None;
             for (idx, amount_value) in attempts.into_iter().enumerate() {
                 let trade_request = serde_json::json!({
-                    "publicKey": keypair.pubkey().to_string(),
+                    "publicKey": signer.pubkey().to_string(),
                     "action": "sell",
                     "mint": token_mint,
                     "amount": amount_value,
@@ -1319,40 +2260,40 @@ impl HeliusGrpcClient {
                     "priorityFee": tip_amount as f64 / 1_000_000_000.0,
                     "pool": "pump"
                 });
-
+                
                 info!(
                     "⚡ Pump.fun TP SELL attempt {}/{} request: {}",
                     idx + 1,
                     attempts_len,
                     serde_json::to_string_pretty(&trade_request).unwrap_or_default()
                 );
-
-                let response = client.post(PUMPFUN_TRADE_API).json(&trade_request).send().await?;
+                
+                let response = send_api_request(breakers, client.post(PUMPFUN_TRADE_API).json(&trade_request), ENDPOINT_PUMPPORTAL_TRADE).await?;
                 if !response.status().is_success() {
                     let status = response.status();
                     let error_text = response.text().await.unwrap_or_default();
                     last_error = Some(anyhow::anyhow!("Pump.fun TP SELL API error ({}): {}", status, error_text));
                     continue;
                 }
-
+                
                 let tx_bytes = response.bytes().await?;
                 let mut versioned_tx: VersionedTransaction = bincode::deserialize(&tx_bytes)?;
-
+                
                 let message_bytes = versioned_tx.message.serialize();
-                let signature = keypair.sign_message(&message_bytes);
+                let signature = signer.try_sign_message(&message_bytes)?;
                 versioned_tx.signatures[0] = signature;
-
+                
                 let config = solana_client::rpc_config::RpcSendTransactionConfig {
                     skip_preflight: true,
                     preflight_commitment: Some(solana_sdk::commitment_config::CommitmentLevel::Processed),
                     max_retries: Some(0),
                     ..Default::default()
                 };
-
-                let sig = rpc_client.send_transaction_with_config(&versioned_tx, config).await?;
+                
+                let sig = send_rpc_transaction(breakers, &rpc_client, &versioned_tx, config).await?;
                 return Ok(sig.to_string());
             }
-
+            
             return Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Pump.fun TP SELL failed")));
         }
         
@@ -1364,20 +2305,18 @@ impl HeliusGrpcClient {
             token_amount
         );
         
-        let quote_response = client.get(&quote_url).send().await?;
+        let quote_response = send_api_request(breakers, client.get(&quote_url), ENDPOINT_JUPITER_QUOTE).await?;
         let quote_data: serde_json::Value = quote_response.json().await?;
         
         let swap_request = serde_json::json!({
             "quoteResponse": quote_data,
-            "userPublicKey": keypair.pubkey().to_string(),
+            "userPublicKey": signer.pubkey().to_string(),
             "wrapAndUnwrapSol": true,
             "dynamicComputeUnitLimit": true,
             "prioritizationFeeLamports": tip_amount
         });
         
-        let swap_response = client.post("https://quote-api.jup.ag/v6/swap")
-            .json(&swap_request)
-            .send()
+        let swap_response = send_api_request(breakers, client.post("https://quote-api.jup.ag/v6/swap").json(&swap_request), ENDPOINT_JUPITER_SWAP)
             .await?;
         let swap_data: serde_json::Value = swap_response.json().await?;
         
@@ -1389,7 +2328,7 @@ impl HeliusGrpcClient {
         let mut versioned_tx: VersionedTransaction = bincode::deserialize(&tx_bytes)?;
         
         let message_bytes = versioned_tx.message.serialize();
-        let signature = keypair.sign_message(&message_bytes);
+        let signature = signer.try_sign_message(&message_bytes)?;
         versioned_tx.signatures[0] = signature;
         
         let config = solana_client::rpc_config::RpcSendTransactionConfig {
@@ -1399,18 +2338,337 @@ impl HeliusGrpcClient {
             ..Default::default()
         };
         
-        let sig = rpc_client.send_transaction_with_config(&versioned_tx, config).await?;
+        let sig = send_rpc_transaction(breakers, &rpc_client, &versioned_tx, config).await?;
         Ok(sig.to_string())
     }
     
+    /// Watch a sent transaction in the background and record landing latency, outcome and fee
+    /// `detected_slot` is the slot of the target transaction we copied, if any
+    fn track_landing(
+        rpc_url: String,
+        state: Arc<StateManager>,
+        token_mint: &str,
+        signature: String,
+        detected_slot: Option<u64>,
+    ) {
+        let token_mint = token_mint.to_string();
+        tokio::spawn(async move {
+            Self::watch_landing(rpc_url, state, token_mint, signature, detected_slot).await;
+        });
+    }
+    
+    /// Wait for a sent transaction to land and record its metrics
+    /// The transaction is listed as pending in the state manager until it lands or times out
+    /// Returns the outcome label (landed, failed or dropped)
+    async fn watch_landing(
+        rpc_url: String,
+        state: Arc<StateManager>,
+        token_mint: String,
+        signature: String,
+        detected_slot: Option<u64>,
+    ) -> &'static str {
+        let sig = match Signature::from_str(&signature) {
+            Ok(s) => s,
+            Err(_) => return OUTCOME_DROPPED,
+        };
+        if let Ok(mint) = Pubkey::from_str(&token_mint) {
+            state.add_pending_tx(signature.clone(), mint);
+        }
+        
+        let rpc_client = AsyncRpcClient::new(rpc_url);
+        let sent_at = std::time::Instant::now();
+        
+        let status = loop {
+            if sent_at.elapsed() >= LANDING_TIMEOUT {
+                break None;
+            }
+            if let Ok(response) = rpc_client.get_signature_statuses(&[sig]).await {
+                if let Some(Some(status)) = response.value.into_iter().next() {
+                    break Some(status);
+                }
+            }
+            sleep(Duration::from_millis(400)).await;
+        };
+        state.remove_pending_tx(&signature);
+        
+        let status = match status {
+            Some(s) => s,
+            None => {
+                debug!("Transaction {} not seen within {:?}", sig, LANDING_TIMEOUT);
+                metrics().record_transaction(OUTCOME_DROPPED);
+                return OUTCOME_DROPPED;
+            }
+        };
+        
+        let m = metrics();
+        let outcome = if status.err.is_some() { OUTCOME_FAILED } else { OUTCOME_LANDED };
+        m.send_to_land.observe(sent_at.elapsed().as_secs_f64());
+        m.record_transaction(outcome);
+        if let Some(detected) = detected_slot {
+            m.detection_to_land_slots.observe(status.slot.saturating_sub(detected) as f64);
+        }
+        
+        // Fees are charged for failed transactions too; the full transaction is only
+        // served once confirmed, so retry for a few seconds
+        let tx_config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        for _ in 0..10 {
+            if let Ok(tx) = rpc_client.get_transaction_with_config(&sig, tx_config).await {
+                if let Some(meta) = tx.transaction.meta {
+                    m.fee_lamports.inc_by(meta.fee);
+                }
+                break;
+            }
+            sleep(Duration::from_secs(1)).await;
+        }
+        
+        outcome
+    }
+    
+    /// Track a copy buy and notify whether it landed
+    fn track_copy_buy(&self, token_mint: String, signature: String, detected_slot: u64) {
+        let rpc_url = self.rpc_url.clone();
+        let state = self.state.clone();
+        let notifier = self.notifier.clone();
+        let token_info = self.token_info.clone();
+        
+        tokio::spawn(async move {
+            let outcome = Self::watch_landing(
+                rpc_url,
+                state,
+                token_mint.clone(),
+                signature.clone(),
+                Some(detected_slot),
+            ).await;
+            let event = match outcome {
+                OUTCOME_LANDED => {
+                    // Usually cached by now from enriching the position
+                    let token = match Pubkey::from_str(&token_mint) {
+                        Ok(mint) => token_info.get(&mint).await.ok().map(Box::new),
+                        Err(_) => None,
+                    };
+                    NotifyEvent::BuyLanded { token_mint, signature, token }
+                }
+                OUTCOME_FAILED => NotifyEvent::BuyFailed {
+                    token_mint,
+                    error: format!("Transaction {} failed on chain", signature),
+                },
+                _ => NotifyEvent::BuyFailed {
+                    token_mint,
+                    error: format!("Transaction {} did not land within {:?}", signature, LANDING_TIMEOUT),
+                },
+            };
+            notifier.notify(event);
+        });
+    }
+    
+    /// Runtime controls shared with the control API
+    pub fn control(&self) -> Arc<BotControl> {
+        self.control.clone()
+    }
+    
+    /// Sell our whole balance of a token on operator request
+    pub async fn force_sell(&self, token_mint: &Pubkey) -> Result<String> {
+        let mint = token_mint.to_string();
+        info!("🖐️ MANUAL SELL requested for {}", mint);
+        
+        let sig = self.execute_copy_sell(&mint, "manual", QuoteAsset::Sol, TipLevel::Normal).await?;
+        info!("✅ MANUAL SELL EXECUTED! Our signature: {}", sig);
+        Self::track_landing(self.rpc_url.clone(), self.state.clone(), &mint, sig.clone(), None);
+        self.notifier.notify(NotifyEvent::CopySell {
+            token_mint: mint.clone(),
+            signature: sig.clone(),
+            manual: true,
+        });
+        
+        self.remove_position(&mint).await;
+        self.record_exit(&mint, TradeRecordType::SellManual, sig.clone());
+        Ok(sig)
+    }
+    
+    /// Sell a held token flagged by the hold watcher or its creator's wallet, at the emergency tip
+    async fn emergency_exit(&self, token_mint: &Pubkey, reason: String, trade_type: TradeRecordType) {
+        let mint = token_mint.to_string();
+        if !self.positions.read().await.contains_key(&mint) {
+            return;
+        }
+        warn!("☠️ EMERGENCY EXIT from {}: {}", mint, reason);
+        
+        match self.execute_copy_sell(&mint, "safety", QuoteAsset::Sol, TipLevel::Emergency).await {
+            Ok(sig) => {
+                info!("✅ EMERGENCY SELL EXECUTED! Our signature: {}", sig);
+                Self::track_landing(self.rpc_url.clone(), self.state.clone(), &mint, sig.clone(), None);
+                self.notifier.notify(NotifyEvent::SafetyExit {
+                    token_mint: mint.clone(),
+                    reason,
+                    signature: sig.clone(),
+                });
+                self.remove_position(&mint).await;
+                self.record_exit(&mint, trade_type, sig);
+            }
+            Err(e) if format!("{:?}", e).contains("No tokens to sell") => {
+                info!("ℹ️ No tokens left of {} - already sold", &mint[..8.min(mint.len())]);
+                self.remove_position(&mint).await;
+            }
+            // The hold watcher raises each issue once, so the position is left to the take profit
+            // monitor and any new signal
+            Err(e) => error!("❌ Emergency sell of {} failed: {:?}", mint, e),
+        }
+    }
+    
+    /// Creator of a mint from its CreateEvent, else the position's token info
+    fn creator_of(&self, mint: &Pubkey) -> Option<Pubkey> {
+        let known = self.creators.get(mint).map(|creator| *creator);
+        known.or_else(|| self.state.get_position(mint)?.token_info?.creator)
+    }
+    
+    /// Creators of the tokens we hold
+    async fn held_creators(&self) -> Vec<Pubkey> {
+        let positions = self.positions.read().await;
+        let mut creators: Vec<Pubkey> = positions.keys()
+            .filter_map(|mint| Pubkey::from_str(mint).ok())
+            .filter_map(|mint| self.creator_of(&mint))
+            .collect();
+        creators.sort();
+        creators.dedup();
+        creators
+    }
+    
+    /// Exit held tokens whose creator sold or transferred enough of their supply
+    /// Pump.fun sells are read from the logs; sells on other venues and transfers need the
+    /// creator's balance changes, so those transactions are fetched off the stream loop.
+    async fn handle_creator_notification(&self, creator: &Pubkey, response: Response<RpcLogsResponse>) {
+        if response.value.err.is_some() {
+            return;
+        }
+        let held: Vec<Pubkey> = {
+            let positions = self.positions.read().await;
+            positions.keys()
+                .filter_map(|mint| Pubkey::from_str(mint).ok())
+                .filter(|mint| self.creator_of(mint) == Some(*creator))
+                .collect()
+        };
+        if held.is_empty() {
+            return;
+        }
+        
+        let signature = response.value.signature;
+        let logs = response.value.logs;
+        let moves = creator_sells(&logs, creator, &held);
+        let token_programs = TokenProgram::ALL.map(|program| program.id().to_string());
+        let moves_tokens = logs.iter().any(|line| token_programs.iter().any(|program| line.contains(program.as_str())));
+        if moves.is_empty() && moves_tokens {
+            let (rpc_url, sender, creator) = (self.rpc_url.clone(), self.creator_moves.clone(), *creator);
+            tokio::spawn(async move {
+                let Some(tx) = Self::fetch_confirmed_transaction(&rpc_url, &signature).await else { return };
+                let Some((sol_delta, token_deltas)) = decode_wallet_deltas(&tx, &creator) else { return };
+                let moves = creator_outflows(sol_delta, &token_deltas, &held);
+                if !moves.is_empty() {
+                    let _ = sender.send(CreatorMoves { creator, signature, moves });
+                }
+            });
+            return;
+        }
+        self.handle_creator_moves(creator, &signature, moves).await;
+    }
+    
+    /// Exit held tokens a creator moved over the threshold
+    async fn handle_creator_moves(&self, creator: &Pubkey, signature: &str, moves: Vec<CreatorMove>) {
+        for creator_move in moves {
+            let supply = self.mint_supply(&creator_move.mint).await;
+            match self.safety.creator_move_issue(&creator_move, supply) {
+                Some(issue) => {
+                    info!("👤 Creator {} {:?} on {}: {}", creator, creator_move.kind, signature, issue);
+                    self.emergency_exit(&creator_move.mint, issue.to_string(), TradeRecordType::SellCreatorExit).await;
+                }
+                None => debug!("👤 Creator {} moved {} raw {} - under the exit threshold", creator, creator_move.amount, creator_move.mint),
+            }
+        }
+    }
+    
+    /// Raw supply of a mint from the position's token info, else read from the mint (0 if unknown)
+    async fn mint_supply(&self, mint: &Pubkey) -> u64 {
+        if let Some(supply) = self.state.get_position(mint).and_then(|position| position.token_info).map(|info| info.supply) {
+            return supply;
+        }
+        let rpc_client = AsyncRpcClient::new(self.rpc_url.clone());
+        rpc_client.get_account_data(mint).await.ok()
+            .and_then(|data| MintRisks::decode(&data))
+            .map(|mint| mint.supply)
+            .unwrap_or_default()
+    }
+    
+    /// Record a target wallet trade for the dashboard
+    fn record_target_event(&self, target: &Pubkey, is_buy: bool, token_mint: &str, signature: &str) {
+        self.state.record_target_event(TargetEvent::new(
+            *target,
+            Pubkey::from_str(token_mint).ok(),
+            is_buy,
+            signature.to_string(),
+        ));
+    }
+    
+    /// Record a copy buy in the state manager
+    /// The token amount is filled in by the TP monitor once our balance lands
+    fn record_copy_buy(&self, token_mint: &str, target_signature: &str, our_signature: &str, wallet: &Arc<WalletSigner>, buy_sol: f64) {
+        if let Ok(mint) = Pubkey::from_str(token_mint) {
+            self.state.open_position(Position::new(
+                mint,
+                0,
+                (buy_sol * 1_000_000_000.0) as u64,
+                target_signature.to_string(),
+                our_signature.to_string(),
+            ).with_wallet(wallet.pubkey()));
+        }
+    }
+    
+    /// Fetch a bought token's metadata and holders in the background, log them and attach
+    /// them to the position
+    fn enrich_position(&self, token_mint: &str) {
+        let Ok(mint) = Pubkey::from_str(token_mint) else { return };
+        let token_info = self.token_info.clone();
+        let state = self.state.clone();
+        let creators = self.safety.creator_exit.then(|| (self.creators.clone(), self.creators_changed.clone()));
+        tokio::spawn(async move {
+            match token_info.get(&mint).await {
+                Ok(info) => {
+                    info!("🏷️ {}: {}", mint, info.summary());
+                    if let (Some((creators, changed)), Some(creator)) = (&creators, info.creator) {
+                        if creators.insert(mint, creator).is_none() {
+                            changed.notify_one();
+                        }
+                    }
+                    state.set_position_info(&mint, info);
+                }
+                Err(e) => warn!("⚠️ Could not fetch token info for {}: {:#}", mint, e),
+            }
+        });
+    }
+    
+    /// Record a full exit in the state manager
+    /// Sends are fire-and-forget, so SOL received is the last value the TP monitor saw
+    fn record_exit(&self, token_mint: &str, trade_type: TradeRecordType, signature: String) {
+        let mint = match Pubkey::from_str(token_mint) {
+            Ok(m) => m,
+            Err(_) => return,
+        };
+        if let Some(position) = self.state.get_position(&mint) {
+            self.state.close_position(&mint, position.current_value_sol, trade_type, signature);
+        }
+    }
+    
     /// Add a position to track for take profit
-    pub async fn add_position(&self, token_mint: String, entry_sol: f64, token_amount: u64) {
+    pub async fn add_position(&self, token_mint: String, entry_sol: f64, token_amount: u64, wallet: Arc<WalletSigner>) {
         let position = PositionInfo {
             token_mint: token_mint.clone(),
             entry_sol,
             token_amount,
             entry_time: std::time::Instant::now(),
             sold_percent: 0,
+            wallet,
         };
         
         let mut positions = self.positions.write().await;
@@ -1423,12 +2681,86 @@ impl HeliusGrpcClient {
         );
     }
     
+    /// Wallet holding a position, falling back to the state manager and then the main wallet
+    async fn position_wallet(&self, token_mint: &str) -> Arc<WalletSigner> {
+        if let Some(position) = self.positions.read().await.get(token_mint) {
+            return position.wallet.clone();
+        }
+        let position = Pubkey::from_str(token_mint).ok().and_then(|mint| self.state.get_position(&mint));
+        self.wallets.for_position(position.as_ref())
+    }
+    
     /// Remove a position (when sold)
     pub async fn remove_position(&self, token_mint: &str) {
         let mut positions = self.positions.write().await;
         if positions.remove(token_mint).is_some() {
             info!("📝 Position removed: {}", &token_mint[..8]);
+            if let Ok(mint) = Pubkey::from_str(token_mint) {
+                self.creators.remove(&mint);
+                self.native.forget(&mint);
+            }
+            // The token account is empty once the sell lands
+            if let Some(reclaimer) = &self.reclaimer {
+                reclaimer.request();
+            }
+        }
+    }
+}
+
+/// Send an API request, counting transport errors and non-success responses per endpoint
+/// Fails fast while the endpoint's venue circuit is open. Transport errors, 429s and 5xx
+/// responses count towards tripping it; other 4xx responses mean the venue is up.
+pub(crate) async fn send_api_request(
+    breakers: &CircuitBreakers,
+    request: reqwest::RequestBuilder,
+    endpoint: &str,
+) -> Result<reqwest::Response> {
+    let venue = if endpoint.starts_with("jupiter") { Venue::Jupiter } else { Venue::PumpPortal };
+    let breaker = breakers.get(venue);
+    if !breaker.allow() {
+        metrics().record_api_error(endpoint);
+        anyhow::bail!("{} circuit open, skipping {} request", venue, endpoint);
+    }
+    
+    let result = request.send().await;
+    match &result {
+        Ok(response) if response.status().is_success() => breaker.record_success(),
+        Ok(response) if response.status().is_server_error() || response.status().as_u16() == 429 => {
+            metrics().record_api_error(endpoint);
+            breaker.record_failure();
+        }
+        Ok(_) => {
+            metrics().record_api_error(endpoint);
+            breaker.record_success();
         }
+        Err(_) => {
+            metrics().record_api_error(endpoint);
+            breaker.record_failure();
+        }
+    }
+    Ok(result?)
+}
+
+/// Send a signed transaction over RPC, guarded by the RPC circuit breaker
+async fn send_rpc_transaction(
+    breakers: &CircuitBreakers,
+    rpc_client: &AsyncRpcClient,
+    transaction: &VersionedTransaction,
+    config: solana_client::rpc_config::RpcSendTransactionConfig,
+) -> Result<solana_sdk::signature::Signature> {
+    let breaker = breakers.get(Venue::Rpc);
+    if !breaker.allow() {
+        anyhow::bail!("RPC circuit open, transaction not sent");
+    }
+    let result = rpc_client.send_transaction_with_config(transaction, config).await;
+    breaker.record(&result);
+    Ok(result?)
+}
+
+#[async_trait::async_trait]
+impl PositionSeller for HeliusGrpcClient {
+    async fn force_sell(&self, token_mint: &Pubkey) -> Result<String> {
+        HeliusGrpcClient::force_sell(self, token_mint).await
     }
 }
 
@@ -1437,13 +2769,25 @@ pub struct HeliusClientBuilder {
     endpoint: Option<String>,
     api_key: Option<String>,
     target_wallet: Option<Pubkey>,
-    our_keypair: Option<Arc<Keypair>>,
+    wallets: Option<Arc<WalletPool>>,
     buy_amount_sol: f64,
     tip_amount: u64,
     reconnect_delay_ms: u64,
     max_reconnect_attempts: u32,
     take_profit_enabled: bool,
     take_profit_tiers: Vec<TakeProfitTier>,
+    risk_limits: RiskLimits,
+    circuit_breaker: BreakerConfig,
+    entry_gate: EntryGate,
+    copy_quote_mode: CopyQuoteMode,
+    safety: SafetyConfig,
+    emergency_tip: u64,
+    ata_reclaim: Option<ReclaimConfig>,
+    nonce_pool_enabled: bool,
+    nonce_pool_spare: usize,
+    nonce_pool_max: usize,
+    state: Option<Arc<StateManager>>,
+    notifier: Option<Arc<NotificationDispatcher>>,
 }
 
 impl Default for HeliusClientBuilder {
@@ -1452,7 +2796,7 @@ impl Default for HeliusClientBuilder {
             endpoint: None,
             api_key: None,
             target_wallet: None,
-            our_keypair: None,
+            wallets: None,
             buy_amount_sol: 0.1,
             tip_amount: 10_000,
             reconnect_delay_ms: 1000,
@@ -1461,6 +2805,18 @@ impl Default for HeliusClientBuilder {
             take_profit_tiers: vec![
                 TakeProfitTier { multiplier: 2.0, sell_percent: 100 },
             ],
+            risk_limits: RiskLimits::default(),
+            circuit_breaker: BreakerConfig::default(),
+            entry_gate: EntryGate::default(),
+            copy_quote_mode: CopyQuoteMode::default(),
+            safety: SafetyConfig::default(),
+            emergency_tip: 100_000,
+            ata_reclaim: None,
+            nonce_pool_enabled: false,
+            nonce_pool_spare: 2,
+            nonce_pool_max: 10,
+            state: None,
+            notifier: None,
         }
     }
 }
@@ -1485,8 +2841,9 @@ impl HeliusClientBuilder {
         self
     }
     
-    pub fn keypair(mut self, keypair: Arc<Keypair>) -> Self {
-        self.our_keypair = Some(keypair);
+    /// Wallets to trade from (a single-wallet pool keeps everything on the main wallet)
+    pub fn wallets(mut self, wallets: Arc<WalletPool>) -> Self {
+        self.wallets = Some(wallets);
         self
     }
     
@@ -1499,17 +2856,56 @@ impl HeliusClientBuilder {
         self.tip_amount = tip;
         self
     }
-
+    
     pub fn take_profit_enabled(mut self, enabled: bool) -> Self {
         self.take_profit_enabled = enabled;
         self
     }
-
+    
     pub fn take_profit_tiers(mut self, tiers: Vec<TakeProfitTier>) -> Self {
         self.take_profit_tiers = tiers;
         self
     }
     
+    pub fn risk_limits(mut self, limits: RiskLimits) -> Self {
+        self.risk_limits = limits;
+        self
+    }
+    
+    pub fn circuit_breaker(mut self, config: BreakerConfig) -> Self {
+        self.circuit_breaker = config;
+        self
+    }
+    
+    pub fn entry_gate(mut self, gate: EntryGate) -> Self {
+        self.entry_gate = gate;
+        self
+    }
+    
+    /// Pay for copies of stablecoin trades in SOL or the same stablecoin
+    pub fn copy_quote_mode(mut self, mode: CopyQuoteMode) -> Self {
+        self.copy_quote_mode = mode;
+        self
+    }
+    
+    /// Rug and honeypot checks before entry and while holding
+    pub fn safety(mut self, config: SafetyConfig) -> Self {
+        self.safety = config;
+        self
+    }
+    
+    /// Tip paid when selling a held token on a rug signal
+    pub fn emergency_tip(mut self, tip: u64) -> Self {
+        self.emergency_tip = tip;
+        self
+    }
+    
+    /// Close empty token accounts after full sells and on an interval (None = disabled)
+    pub fn ata_reclaim(mut self, config: Option<ReclaimConfig>) -> Self {
+        self.ata_reclaim = config;
+        self
+    }
+    
     pub fn reconnect_delay_ms(mut self, delay: u64) -> Self {
         self.reconnect_delay_ms = delay;
         self
@@ -1520,18 +2916,160 @@ impl HeliusClientBuilder {
         self
     }
     
+    pub fn nonce_pool_enabled(mut self, enabled: bool) -> Self {
+        self.nonce_pool_enabled = enabled;
+        self
+    }
+    
+    pub fn nonce_pool_spare(mut self, spare: usize) -> Self {
+        self.nonce_pool_spare = spare;
+        self
+    }
+    
+    pub fn nonce_pool_max(mut self, max: usize) -> Self {
+        self.nonce_pool_max = max;
+        self
+    }
+    
+    pub fn state(mut self, state: Arc<StateManager>) -> Self {
+        self.state = Some(state);
+        self
+    }
+    
+    pub fn notifier(mut self, notifier: Arc<NotificationDispatcher>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+    
     pub fn build(self) -> Result<HeliusGrpcClient> {
-        Ok(HeliusGrpcClient::new(
+        let wallets = self.wallets.context("Wallets are required")?;
+        let mut client = HeliusGrpcClient::new(
             self.endpoint.context("Endpoint is required")?,
             self.api_key.context("API key is required")?,
             self.target_wallet.context("Target wallet is required")?,
-            self.our_keypair.context("Keypair is required")?,
+            wallets.main().clone(),
             self.buy_amount_sol,
             self.tip_amount,
             self.reconnect_delay_ms,
             self.max_reconnect_attempts,
             self.take_profit_enabled,
             self.take_profit_tiers,
-        ))
+        );
+        
+        client.wallets = wallets;
+        client.breakers = Arc::new(CircuitBreakers::new(self.circuit_breaker));
+        client.entry_gate = self.entry_gate;
+        client.copy_quote_mode = self.copy_quote_mode;
+        client.safety = self.safety;
+        client.emergency_tip = self.emergency_tip;
+        
+        if let Some(state) = self.state {
+            client.state = state;
+        }
+        
+        if let Some(notifier) = self.notifier {
+            client.notifier = notifier;
+        }
+        
+        client.risk = Arc::new(RiskManager::new(
+            self.risk_limits,
+            client.state.clone(),
+            client.notifier.clone(),
+            client.rpc_url.clone(),
+        ));
+        
+        client.reclaimer = self.ata_reclaim.map(|config| {
+            Arc::new(AtaReclaimer::new(
+                client.wallets.clone(),
+                client.state.clone(),
+                client.token_accounts.clone(),
+                client.rpc_url.clone(),
+                config,
+            ))
+        });
+        
+        if self.nonce_pool_enabled {
+            client.nonce_pool = Some(Arc::new(NoncePool::new(
+                client.rpc_url.clone(),
+                client.signer.clone(),
+                self.nonce_pool_spare,
+                self.nonce_pool_max,
+            )));
+        }
+        
+        Ok(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::TRADE_EVENT_DISCRIMINATOR;
+    
+    /// Logs of a single top-level instruction, with an event if given
+    fn instruction_logs(program: &str, instruction: &str, event: Option<&[u8]>) -> Vec<String> {
+        let mut logs = vec![format!("Program {} invoke [1]", program), format!("Program log: Instruction: {}", instruction)];
+        logs.extend(event.map(|data| format!("Program data: {}", BASE64.encode(data))));
+        logs.push(format!("Program {} success", program));
+        logs
+    }
+    
+    #[test]
+    fn test_detect_action_from_logs() {
+        let (target, mint, pool) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let detect = |logs: &[String]| HeliusGrpcClient::detect_action_from_logs(logs, &target, "sig", 7);
+        
+        // Pump.fun trades carry the target's trade event
+        for is_buy in [true, false] {
+            let mut event = TRADE_EVENT_DISCRIMINATOR.to_vec();
+            event.extend_from_slice(mint.as_ref());
+            event.extend_from_slice(&1_000_000u64.to_le_bytes());
+            event.extend_from_slice(&5_000u64.to_le_bytes());
+            event.push(is_buy as u8);
+            event.extend_from_slice(target.as_ref());
+            for value in [0u64, 30_000_000_000, 1_000_000_000_000] {
+                event.extend_from_slice(&value.to_le_bytes());
+            }
+            let logs = instruction_logs(PUMPFUN_PROGRAM, if is_buy { "Buy" } else { "Sell" }, Some(&event));
+            match detect(&logs) {
+                DetectedAction::Buy { trade: Some(trade), route: None, .. } => assert!(is_buy && trade.mint == mint),
+                DetectedAction::Sell { trade: Some(trade), .. } => assert!(!is_buy && trade.mint == mint),
+                action => panic!("Pump.fun {} read as {:?}", is_buy, action),
+            }
+        }
+        
+        // LaunchLab events name the pool, which is also the buy's route
+        for is_buy in [true, false] {
+            let mut event = launchlab::TRADE_EVENT_DISCRIMINATOR.to_vec();
+            event.extend_from_slice(pool.as_ref());
+            let (quote_before, quote_after) = if is_buy { (1_000, 2_000) } else { (2_000, 1_000) };
+            for value in [0u64, 0, 0, 0, quote_before, 0, quote_after, 1_000, 1_000] {
+                event.extend_from_slice(&value.to_le_bytes());
+            }
+            let logs = instruction_logs(LAUNCHLAB_PROGRAM, if is_buy { "BuyExactIn" } else { "SellExactIn" }, Some(&event));
+            match detect(&logs) {
+                DetectedAction::Buy { trade: None, launchlab: Some(event), route: Some(route), .. } => {
+                    assert!(is_buy && event.pool_state == pool);
+                    assert_eq!(route, NativeRoute { dex: DexProgram::RaydiumLaunchLab, accounts: vec![pool] });
+                }
+                DetectedAction::Sell { trade: None, launchlab: Some(event), .. } => assert!(!is_buy && event.pool_state == pool),
+                action => panic!("LaunchLab {} read as {:?}", is_buy, action),
+            }
+        }
+        
+        // PumpSwap emits no events, so only the side is known from the logs
+        let buy = detect(&instruction_logs(PUMPSWAP_PROGRAM, "Buy", None));
+        assert!(matches!(buy, DetectedAction::Buy { trade: None, launchlab: None, route: None, .. }), "{:?}", buy);
+        let sell = detect(&instruction_logs(PUMPSWAP_PROGRAM, "Sell", None));
+        assert!(matches!(sell, DetectedAction::Sell { trade: None, launchlab: None, .. }), "{:?}", sell);
+        
+        // Unknown venues are classified by their balance changes; transfers are not trades
+        let unknown_venue = Pubkey::new_unique().to_string();
+        for instruction in ["Buy", "Sell", "Swap"] {
+            let swap = detect(&instruction_logs(&unknown_venue, instruction, None));
+            assert!(matches!(swap, DetectedAction::Swap { ref signature, slot: 7 } if signature == "sig"), "{:?}", swap);
+        }
+        let transfer = detect(&instruction_logs("11111111111111111111111111111111", "Transfer", None));
+        assert!(matches!(transfer, DetectedAction::Unknown { .. }), "{:?}", transfer);
     }
 }
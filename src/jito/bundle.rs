@@ -2,23 +2,28 @@
 
 use anyhow::{Result, Context};
 use solana_sdk::{
-    instruction::Instruction,
+    address_lookup_table::AddressLookupTableAccount,
+    instruction::{CompiledInstruction, Instruction},
+    message::{v0, MessageHeader, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
     pubkey::Pubkey,
-    signature::{Keypair, Signature, Signer},
+    signature::{Signature, Signer},
     system_instruction,
-    transaction::Transaction,
+    transaction::VersionedTransaction,
     hash::Hash,
     compute_budget::ComputeBudgetInstruction,
 };
+use std::sync::Arc;
 use tracing::{debug, info};
 
+use crate::signer::WalletSigner;
 use super::tip::{get_random_tip_account, TipLevel, TipConfig};
 
 /// A Jito bundle containing one or more transactions
 #[derive(Debug)]
 pub struct JitoBundle {
     /// Transactions in the bundle (max 5)
-    pub transactions: Vec<Transaction>,
+    pub transactions: Vec<VersionedTransaction>,
     /// Bundle tip level
     pub tip_level: TipLevel,
 }
@@ -32,12 +37,12 @@ impl JitoBundle {
         }
     }
     
-    /// Add a transaction to the bundle
-    pub fn add_transaction(&mut self, tx: Transaction) -> Result<()> {
+    /// Add a transaction to the bundle (legacy transactions are converted)
+    pub fn add_transaction(&mut self, tx: impl Into<VersionedTransaction>) -> Result<()> {
         if self.transactions.len() >= 5 {
             anyhow::bail!("Bundle cannot contain more than 5 transactions");
         }
-        self.transactions.push(tx);
+        self.transactions.push(tx.into());
         Ok(())
     }
     
@@ -54,35 +59,40 @@ impl JitoBundle {
 
 /// Bundle builder for constructing Jito bundles
 pub struct BundleBuilder {
-    keypair: Keypair,
+    signer: Arc<WalletSigner>,
     tip_config: TipConfig,
     compute_unit_limit: u32,
     priority_fee_micro_lamports: u64,
+    /// Address lookup tables used when compiling v0 messages
+    lookup_tables: Vec<AddressLookupTableAccount>,
 }
 
 impl BundleBuilder {
     /// Create a new bundle builder
     pub fn new(
-        keypair: Keypair,
+        signer: Arc<WalletSigner>,
         tip_config: TipConfig,
         compute_unit_limit: u32,
         priority_fee_micro_lamports: u64,
     ) -> Self {
         Self {
-            keypair,
+            signer,
             tip_config,
             compute_unit_limit,
             priority_fee_micro_lamports,
+            lookup_tables: Vec::new(),
         }
     }
     
-    /// Build a transaction with compute budget and priority fee
-    pub fn build_transaction(
-        &self,
-        instructions: Vec<Instruction>,
-        recent_blockhash: Hash,
-    ) -> Result<Transaction> {
-        let mut all_instructions = Vec::with_capacity(instructions.len() + 2);
+    /// Set the address lookup tables used to compile v0 messages
+    pub fn with_lookup_tables(mut self, lookup_tables: Vec<AddressLookupTableAccount>) -> Self {
+        self.lookup_tables = lookup_tables;
+        self
+    }
+    
+    /// Prepend compute budget and priority fee instructions
+    fn with_compute_budget(&self, instructions: Vec<Instruction>) -> Vec<Instruction> {
+        let mut all_instructions = Vec::with_capacity(instructions.len() + 3);
         
         // Add compute budget instruction
         all_instructions.push(
@@ -96,18 +106,40 @@ impl BundleBuilder {
         
         // Add the actual instructions
         all_instructions.extend(instructions);
-        
-        let tx = Transaction::new_signed_with_payer(
-            &all_instructions,
-            Some(&self.keypair.pubkey()),
-            &[&self.keypair],
+        all_instructions
+    }
+    
+    /// Compile and sign a v0 transaction using the configured lookup tables
+    fn compile_and_sign(
+        &self,
+        instructions: &[Instruction],
+        recent_blockhash: Hash,
+    ) -> Result<VersionedTransaction> {
+        let message = v0::Message::try_compile(
+            &self.signer.pubkey(),
+            instructions,
+            &self.lookup_tables,
             recent_blockhash,
-        );
+        ).context("Failed to compile v0 message")?;
         
-        Ok(tx)
+        VersionedTransaction::try_new(VersionedMessage::V0(message), &[self.signer.as_ref()])
+            .context("Failed to sign versioned transaction")
+    }
+    
+    /// Build a transaction with compute budget and priority fee
+    pub fn build_transaction(
+        &self,
+        instructions: Vec<Instruction>,
+        recent_blockhash: Hash,
+    ) -> Result<VersionedTransaction> {
+        let all_instructions = self.with_compute_budget(instructions);
+        self.compile_and_sign(&all_instructions, recent_blockhash)
     }
     
     /// Build a bundle with a single transaction and tip
+    ///
+    /// The tip transfer is appended to the main transaction when the result
+    /// still fits in a packet; otherwise it is sent as a separate transaction.
     pub fn build_bundle(
         &self,
         instructions: Vec<Instruction>,
@@ -115,9 +147,21 @@ impl BundleBuilder {
         tip_level: TipLevel,
     ) -> Result<JitoBundle> {
         let mut bundle = JitoBundle::new(tip_level);
+        let mut all_instructions = self.with_compute_budget(instructions);
+        
+        // Try the single-transaction layout first
+        all_instructions.push(self.build_tip_instruction(tip_level));
+        if let Ok(tx) = self.compile_and_sign(&all_instructions, recent_blockhash) {
+            if fits_in_packet(&tx) {
+                debug!("Tip instruction embedded in main transaction");
+                bundle.add_transaction(tx)?;
+                return Ok(bundle);
+            }
+        }
+        all_instructions.pop();
         
         // Build the main transaction
-        let main_tx = self.build_transaction(instructions, recent_blockhash)?;
+        let main_tx = self.compile_and_sign(&all_instructions, recent_blockhash)?;
         bundle.add_transaction(main_tx)?;
         
         // Build the tip transaction
@@ -127,35 +171,99 @@ impl BundleBuilder {
         Ok(bundle)
     }
     
-    /// Build a tip transaction for the bundle
-    pub fn build_tip_transaction(
+    /// Build a bundle from a pre-built versioned transaction (e.g. from Jupiter or PumpPortal)
+    ///
+    /// The transaction is signed with our wallet. The tip is appended into the
+    /// transaction itself when possible, otherwise a separate tip transaction
+    /// using the same blockhash is added to the bundle.
+    pub fn build_bundle_from_versioned(
         &self,
-        recent_blockhash: Hash,
+        tx: VersionedTransaction,
+        tip_level: TipLevel,
+    ) -> Result<JitoBundle> {
+        let mut bundle = JitoBundle::new(tip_level);
+        let recent_blockhash = *tx.message.recent_blockhash();
+        
+        if let Some(tipped) = self.append_tip_instruction(&tx, tip_level) {
+            debug!("Tip instruction appended to pre-built transaction");
+            bundle.add_transaction(tipped)?;
+            return Ok(bundle);
+        }
+        
+        let signed = self.sign_versioned_transaction(tx)?;
+        bundle.add_transaction(signed)?;
+        
+        let tip_tx = self.build_tip_transaction(recent_blockhash, tip_level)?;
+        bundle.add_transaction(tip_tx)?;
+        
+        Ok(bundle)
+    }
+    
+    /// Sign a pre-built versioned transaction where we are the fee payer
+    pub fn sign_versioned_transaction(&self, mut tx: VersionedTransaction) -> Result<VersionedTransaction> {
+        let payer = tx.message.static_account_keys().first().copied();
+        if payer != Some(self.signer.pubkey()) {
+            anyhow::bail!("Transaction fee payer is not our wallet");
+        }
+        
+        let num_signatures = tx.message.header().num_required_signatures as usize;
+        tx.signatures.resize(num_signatures.max(1), Signature::default());
+        tx.signatures[0] = self.signer.try_sign_message(&tx.message.serialize())?;
+        Ok(tx)
+    }
+    
+    /// Try to append the tip instruction to a pre-built transaction
+    ///
+    /// Returns `None` when the transaction has other signers, when a required
+    /// account may already be loaded through an unresolved lookup table, or
+    /// when the result would exceed the packet size limit.
+    pub fn append_tip_instruction(
+        &self,
+        tx: &VersionedTransaction,
         tip_level: TipLevel,
-    ) -> Result<Transaction> {
+    ) -> Option<VersionedTransaction> {
+        let tip_ix = self.build_tip_instruction(tip_level);
+        let mut message = tx.message.clone();
+        
+        if message.header().num_required_signatures != 1
+            || message.static_account_keys().first() != Some(&self.signer.pubkey())
+        {
+            return None;
+        }
+        
+        let position = message.instructions().len();
+        insert_instruction(&mut message, position, &tip_ix, &self.lookup_tables).ok()?;
+        
+        let signed = VersionedTransaction::try_new(message, &[self.signer.as_ref()]).ok()?;
+        fits_in_packet(&signed).then_some(signed)
+    }
+    
+    /// Build the tip transfer instruction for a tip level
+    pub fn build_tip_instruction(&self, tip_level: TipLevel) -> Instruction {
         let tip_account = get_random_tip_account();
         let tip_amount = self.tip_config.get_tip(tip_level);
         
         info!(
-            "Building tip transaction: {} lamports to {}",
+            "Building tip instruction: {} lamports to {}",
             tip_amount,
             tip_account
         );
         
-        let tip_instruction = system_instruction::transfer(
-            &self.keypair.pubkey(),
+        system_instruction::transfer(
+            &self.signer.pubkey(),
             &tip_account,
             tip_amount,
-        );
-        
-        let tx = Transaction::new_signed_with_payer(
-            &[tip_instruction],
-            Some(&self.keypair.pubkey()),
-            &[&self.keypair],
-            recent_blockhash,
-        );
-        
-        Ok(tx)
+        )
+    }
+    
+    /// Build a tip transaction for the bundle
+    pub fn build_tip_transaction(
+        &self,
+        recent_blockhash: Hash,
+        tip_level: TipLevel,
+    ) -> Result<VersionedTransaction> {
+        let tip_instruction = self.build_tip_instruction(tip_level);
+        self.compile_and_sign(&[tip_instruction], recent_blockhash)
     }
     
     /// Build a bundle that follows a target transaction
@@ -176,10 +284,171 @@ impl BundleBuilder {
         self.build_bundle(instructions, recent_blockhash, actual_tip_level)
     }
     
-    /// Get the keypair's public key
+    /// Get the signer's public key
     pub fn pubkey(&self) -> Pubkey {
-        self.keypair.pubkey()
+        self.signer.pubkey()
+    }
+}
+
+/// Check whether a signed transaction fits in a single packet
+pub fn fits_in_packet(tx: &VersionedTransaction) -> bool {
+    bincode::serialized_size(tx)
+        .map(|size| size as usize <= PACKET_DATA_SIZE)
+        .unwrap_or(false)
+}
+
+/// Insert an instruction into an already compiled message at `position`
+///
+/// New accounts are inserted into the static account keys at the position
+/// required by the message header, and every compiled account index at or
+/// after the insertion point (including lookup-table indices) is shifted.
+/// Only non-signer accounts can be added.
+pub fn insert_instruction(
+    message: &mut VersionedMessage,
+    position: usize,
+    instruction: &Instruction,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<()> {
+    let loaded_addresses = match message {
+        VersionedMessage::Legacy(_) => Vec::new(),
+        VersionedMessage::V0(msg) => resolve_loaded_addresses(msg, lookup_tables)?,
+    };
+    
+    let (header, account_keys, instructions) = match message {
+        VersionedMessage::Legacy(msg) => (&mut msg.header, &mut msg.account_keys, &mut msg.instructions),
+        VersionedMessage::V0(msg) => (&mut msg.header, &mut msg.account_keys, &mut msg.instructions),
+    };
+    
+    for meta in &instruction.accounts {
+        ensure_account(
+            header,
+            account_keys,
+            instructions,
+            &loaded_addresses,
+            &meta.pubkey,
+            meta.is_signer,
+            meta.is_writable,
+        )?;
+    }
+    ensure_account(
+        header,
+        account_keys,
+        instructions,
+        &loaded_addresses,
+        &instruction.program_id,
+        false,
+        false,
+    )?;
+    
+    // Look indices up only once all insertions are done, since inserts shift them
+    let accounts = instruction.accounts.iter()
+        .map(|meta| position_of(account_keys, &meta.pubkey))
+        .collect::<Option<Vec<u8>>>()
+        .context("Account missing after insertion")?;
+    let program_id_index = position_of(account_keys, &instruction.program_id)
+        .context("Program id missing after insertion")?;
+    
+    let position = position.min(instructions.len());
+    instructions.insert(position, CompiledInstruction {
+        program_id_index,
+        accounts,
+        data: instruction.data.clone(),
+    });
+    
+    Ok(())
+}
+
+/// Resolve the addresses a v0 message loads from lookup tables
+fn resolve_loaded_addresses(
+    message: &v0::Message,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<Vec<Pubkey>> {
+    let mut loaded = Vec::new();
+    for lookup in &message.address_table_lookups {
+        let table = lookup_tables.iter()
+            .find(|t| t.key == lookup.account_key)
+            .with_context(|| format!("Unknown address lookup table {}", lookup.account_key))?;
+        
+        for index in lookup.writable_indexes.iter().chain(lookup.readonly_indexes.iter()) {
+            let address = table.addresses.get(*index as usize)
+                .context("Lookup table index out of range")?;
+            loaded.push(*address);
+        }
+    }
+    Ok(loaded)
+}
+
+/// Find a key in the static account keys
+fn position_of(account_keys: &[Pubkey], key: &Pubkey) -> Option<u8> {
+    account_keys.iter()
+        .position(|k| k == key)
+        .and_then(|i| u8::try_from(i).ok())
+}
+
+/// Make sure an account is present in the static keys with compatible permissions
+fn ensure_account(
+    header: &mut MessageHeader,
+    account_keys: &mut Vec<Pubkey>,
+    instructions: &mut [CompiledInstruction],
+    loaded_addresses: &[Pubkey],
+    key: &Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+) -> Result<u8> {
+    let num_signers = header.num_required_signatures as usize;
+    let num_readonly_signed = header.num_readonly_signed_accounts as usize;
+    let num_readonly_unsigned = header.num_readonly_unsigned_accounts as usize;
+    
+    if let Some(index) = account_keys.iter().position(|k| k == key) {
+        let writable = if index < num_signers {
+            index < num_signers - num_readonly_signed
+        } else {
+            index < account_keys.len() - num_readonly_unsigned
+        };
+        
+        if is_signer && index >= num_signers {
+            anyhow::bail!("Account {} is not a signer in this message", key);
+        }
+        if is_writable && !writable {
+            anyhow::bail!("Account {} is read-only in this message", key);
+        }
+        return u8::try_from(index).context("Too many accounts");
+    }
+    
+    if is_signer {
+        anyhow::bail!("Cannot add new signer {} to a compiled message", key);
+    }
+    if loaded_addresses.contains(key) {
+        anyhow::bail!("Account {} is already loaded from a lookup table", key);
+    }
+    
+    let insert_at = if is_writable {
+        account_keys.len() - num_readonly_unsigned
+    } else {
+        account_keys.len()
+    };
+    let insert_index = u8::try_from(insert_at).context("Too many accounts")?;
+    if account_keys.len() + loaded_addresses.len() >= u8::MAX as usize {
+        anyhow::bail!("Too many accounts");
     }
+    
+    account_keys.insert(insert_at, *key);
+    if !is_writable {
+        header.num_readonly_unsigned_accounts += 1;
+    }
+    
+    for ix in instructions.iter_mut() {
+        if ix.program_id_index >= insert_index {
+            ix.program_id_index += 1;
+        }
+        for account in ix.accounts.iter_mut() {
+            if *account >= insert_index {
+                *account += 1;
+            }
+        }
+    }
+    
+    Ok(insert_index)
 }
 
 /// Serialize a bundle for submission
@@ -192,6 +461,13 @@ pub fn serialize_bundle(bundle: &JitoBundle) -> Result<Vec<Vec<u8>>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use solana_sdk::instruction::AccountMeta;
+    use solana_sdk::message::v0::MessageAddressTableLookup;
+    use solana_sdk::signature::Keypair;
+    
+    fn test_builder() -> BundleBuilder {
+        BundleBuilder::new(Arc::new(Keypair::new().into()), TipConfig::default(), 200_000, 1_000)
+    }
     
     #[test]
     fn test_bundle_creation() {
@@ -203,8 +479,132 @@ mod tests {
     #[test]
     fn test_bundle_max_transactions() {
         let mut bundle = JitoBundle::new(TipLevel::Normal);
+        let builder = test_builder();
+        
+        for _ in 0..5 {
+            let tx = builder.build_tip_transaction(Hash::default(), TipLevel::Normal).unwrap();
+            bundle.add_transaction(tx).unwrap();
+        }
+        
+        let tx = builder.build_tip_transaction(Hash::default(), TipLevel::Normal).unwrap();
+        assert!(bundle.add_transaction(tx).is_err());
+    }
+    
+    #[test]
+    fn test_build_bundle_embeds_tip() {
+        let builder = test_builder();
+        let ix = system_instruction::transfer(&builder.pubkey(), &Pubkey::new_unique(), 1);
+        
+        let bundle = builder.build_bundle(vec![ix], Hash::default(), TipLevel::Normal).unwrap();
+        assert_eq!(bundle.len(), 1);
+        assert!(matches!(bundle.transactions[0].message, VersionedMessage::V0(_)));
+        assert_eq!(bundle.transactions[0].message.instructions().len(), 4);
+    }
+    
+    #[test]
+    fn test_build_bundle_uses_lookup_tables() {
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: (0..10).map(|_| Pubkey::new_unique()).collect(),
+        };
+        let builder = test_builder().with_lookup_tables(vec![table.clone()]);
+        
+        let accounts = table.addresses.iter()
+            .map(|k| AccountMeta::new_readonly(*k, false))
+            .collect();
+        let ix = Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3], accounts);
+        
+        let bundle = builder.build_bundle(vec![ix], Hash::default(), TipLevel::Normal).unwrap();
+        match &bundle.transactions[0].message {
+            VersionedMessage::V0(msg) => {
+                assert_eq!(msg.address_table_lookups.len(), 1);
+                assert_eq!(msg.address_table_lookups[0].readonly_indexes.len(), 10);
+            }
+            _ => panic!("expected v0 message"),
+        }
+    }
+    
+    #[test]
+    fn test_build_bundle_splits_oversized_transaction() {
+        let builder = test_builder();
+        let ix = Instruction::new_with_bytes(Pubkey::new_unique(), &[7u8; 1000], vec![]);
+        
+        let bundle = builder.build_bundle(vec![ix], Hash::default(), TipLevel::Normal).unwrap();
+        assert_eq!(bundle.len(), 2);
+    }
+    
+    #[test]
+    fn test_append_tip_to_prebuilt_transaction() {
+        let builder = test_builder();
+        let payer = builder.pubkey();
+        let writable = Pubkey::new_unique();
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        };
+        
+        let program = Pubkey::new_unique();
+        let ix = Instruction::new_with_bytes(program, &[9], vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(writable, false),
+            AccountMeta::new_readonly(table.addresses[0], false),
+            AccountMeta::new(table.addresses[1], false),
+        ]);
+        let message = v0::Message::try_compile(&payer, &[ix], std::slice::from_ref(&table), Hash::default()).unwrap();
+        let unsigned = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::V0(message),
+        };
+        
+        let builder = builder.with_lookup_tables(vec![table.clone()]);
+        let tipped = builder.append_tip_instruction(&unsigned, TipLevel::Emergency).unwrap();
+        
+        assert!(tipped.verify_with_results().iter().all(|ok| *ok));
+        let keys = tipped.message.static_account_keys();
+        let instructions = tipped.message.instructions();
+        assert_eq!(instructions.len(), 2);
+        
+        // The original instruction still points at the same accounts
+        let original = &instructions[0];
+        assert_eq!(keys[original.program_id_index as usize], program);
+        assert_eq!(keys[original.accounts[0] as usize], payer);
+        assert_eq!(keys[original.accounts[1] as usize], writable);
+        let lookup_base = keys.len() as u8;
+        assert_eq!(original.accounts[2], lookup_base + 1);
+        assert_eq!(original.accounts[3], lookup_base);
+        
+        // The tip goes from the payer to a writable tip account via the system program
+        let tip = &instructions[1];
+        assert_eq!(keys[tip.program_id_index as usize], solana_sdk::system_program::id());
+        assert_eq!(keys[tip.accounts[0] as usize], payer);
+        assert!(tipped.message.is_maybe_writable(tip.accounts[1] as usize));
+        assert!(!tipped.message.is_maybe_writable(tip.program_id_index as usize));
+    }
+    
+    #[test]
+    fn test_append_tip_rejects_unknown_lookup_table() {
+        let builder = test_builder();
+        let payer = builder.pubkey();
+        let mut message = v0::Message::try_compile(
+            &payer,
+            &[system_instruction::transfer(&payer, &Pubkey::new_unique(), 1)],
+            &[],
+            Hash::default(),
+        ).unwrap();
+        message.address_table_lookups.push(MessageAddressTableLookup {
+            account_key: Pubkey::new_unique(),
+            writable_indexes: vec![0],
+            readonly_indexes: vec![],
+        });
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::V0(message),
+        };
+        
+        assert!(builder.append_tip_instruction(&tx, TipLevel::Normal).is_none());
         
-        // This will fail without a valid transaction, but tests the limit logic
-        assert!(bundle.transactions.len() < 5);
+        let bundle = builder.build_bundle_from_versioned(tx, TipLevel::Normal).unwrap();
+        assert_eq!(bundle.len(), 2);
+        assert!(bundle.transactions[0].verify_with_results().iter().all(|ok| *ok));
     }
 }
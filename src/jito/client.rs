@@ -4,11 +4,13 @@ use anyhow::{Result, Context};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use solana_sdk::signature::Signature;
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::{info, warn, error, debug};
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 
 use super::bundle::JitoBundle;
+use crate::risk::CircuitBreaker;
 
 /// Jito Block Engine client
 pub struct JitoClient {
@@ -18,6 +20,8 @@ pub struct JitoClient {
     block_engine_url: String,
     /// Bundle submission timeout
     timeout: Duration,
+    /// Stops submissions while the block engine keeps failing
+    breaker: Option<Arc<CircuitBreaker>>,
 }
 
 impl JitoClient {
@@ -32,6 +36,7 @@ impl JitoClient {
             http_client,
             block_engine_url,
             timeout: Duration::from_secs(30),
+            breaker: None,
         }
     }
     
@@ -41,8 +46,25 @@ impl JitoClient {
         self
     }
     
+    /// Guard submissions with a circuit breaker
+    pub fn with_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+    
     /// Submit a bundle to Jito Block Engine via HTTP
     pub async fn submit_bundle(&self, bundle: &JitoBundle) -> Result<BundleSubmissionResult> {
+        if self.breaker.as_ref().is_some_and(|breaker| !breaker.allow()) {
+            anyhow::bail!("Jito circuit open, bundle not submitted");
+        }
+        let result = self.send_bundle(bundle).await;
+        if let Some(breaker) = &self.breaker {
+            breaker.record(&result);
+        }
+        result
+    }
+    
+    async fn send_bundle(&self, bundle: &JitoBundle) -> Result<BundleSubmissionResult> {
         let serialized_txs: Vec<String> = bundle.transactions.iter()
             .map(|tx| {
                 let bytes = bincode::serialize(tx)?;
@@ -8,42 +8,120 @@ use solana_sdk::signer::Signer;
 use tracing::{info, error};
 use tracing_subscriber::{fmt, EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
 use tokio::signal;
+use std::str::FromStr;
 use std::sync::Arc;
 
+mod analytics;
 mod config;
+mod signer;
 mod grpc;
 mod decoder;
 mod jito;
 mod state;
 mod engine;
+mod nonce;
+mod api;
+mod metrics;
+mod notify;
+mod risk;
+mod safety;
+mod swap;
+mod token;
+mod tui;
 
 use config::Config;
 use engine::TradingEngine;
+use notify::{NotificationDispatcher, NotifyEvent};
 use state::StateManager;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // `import-keystore <keypair.json> <keystore.json>` encrypts a CLI keypair file and exits
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("import-keystore") {
+        let (Some(keypair_path), Some(keystore_path)) = (args.get(2), args.get(3)) else {
+            anyhow::bail!("Usage: import-keystore <keypair.json> <keystore.json>");
+        };
+        signer::import_keystore(keypair_path.as_ref(), keystore_path.as_ref())?;
+        println!("Keystore written to {}", keystore_path);
+        return Ok(());
+    }
+    
+    // `analyze-wallet <wallet> [transactions]` prints a wallet's trading report and score
+    if args.get(1).map(String::as_str) == Some("analyze-wallet") {
+        let Some(wallet) = args.get(2) else {
+            anyhow::bail!("Usage: analyze-wallet <wallet> [transactions]");
+        };
+        let wallet = solana_sdk::pubkey::Pubkey::from_str(wallet)?;
+        let max_signatures = args.get(3).map(|n| n.parse()).transpose()?.unwrap_or(1_000);
+        dotenvy::dotenv().ok();
+        let rpc_url = std::env::var("SOLANA_RPC_URL")
+            .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string());
+        let trades = analytics::fetch_wallet_trades(&rpc_url, &wallet, max_signatures).await?;
+        println!("{}", analytics::WalletReport::from_trades(wallet, &trades));
+        return Ok(());
+    }
+    
+    // Dashboard mode draws its own log pane instead of writing to stdout
+    let tui_mode = std::env::args().any(|arg| arg == "--tui");
+    let log_buffer = Arc::new(tui::LogBuffer::new());
+    
     // Initialize logging
-    init_logging();
+    init_logging(tui_mode.then(|| log_buffer.clone()));
     
     info!("🚀 Starting Solana Copytrading Bot...");
     
     // Load configuration
-    let (config, keypair) = Config::from_env()?;
-    let keypair = Arc::new(keypair);
+    let config = Config::load()?;
+    
+    // Open the wallets once; everything else shares these signers
+    let signer = Arc::new(config.signer.open()?);
+    let burners = config.wallet_pool.iter()
+        .map(|path| signer::open_wallet_file(path, config.wallet_pool_passphrase_file.as_deref()).map(Arc::new))
+        .collect::<Result<Vec<_>>>()?;
+    let wallets = Arc::new(signer::WalletPool::new(signer.clone(), burners, config.wallet_assignment)?);
     
     info!("✅ Configuration loaded successfully");
+    if let Some(path) = &config.config_file {
+        info!("📄 Config file: {}", path.display());
+    }
     info!("📍 Target wallet: {}", config.target_wallet);
     info!("💰 Buy amount: {} SOL", config.buy_amount_sol);
-    info!("🔑 Our wallet: {}", keypair.pubkey());
+    info!("🔑 Our wallet: {} ({} signer)", signer.pubkey(), signer.backend());
+    for burner in wallets.burners() {
+        info!("👛 Pool wallet: {} ({} signer)", burner.pubkey(), burner.backend());
+    }
     
     // Initialize state manager
     let state = Arc::new(StateManager::new());
     info!("✅ State manager initialized");
     
+    // Start notifiers (no-op when no channel is configured)
+    let notifier = Arc::new(NotificationDispatcher::from_config(&config));
+    
     // Initialize trading engine
-    let engine = TradingEngine::new(&config, keypair.clone(), state.clone()).await?;
+    let engine = TradingEngine::new(&config, wallets.clone(), state.clone(), notifier.clone()).await?;
     info!("✅ Trading engine initialized");
+    let control = engine.control();
+    let seller = engine.seller();
+    
+    // Start the control API (bound to localhost by default)
+    let api_handle = if config.api_enabled {
+        let api_state = api::ApiState::new(
+            state.clone(),
+            control.clone(),
+            seller.clone(),
+            config.api_token.clone(),
+        );
+        let bind = config.api_bind.clone();
+        Some(tokio::spawn(async move {
+            if let Err(e) = api::serve(&bind, api_state).await {
+                error!("Control API error: {:?}", e);
+            }
+        }))
+    } else {
+        None
+    };
     
     // Start the engine in a separate task
     let engine_handle = tokio::spawn(async move {
@@ -52,24 +130,79 @@ async fn main() -> Result<()> {
         }
     });
     
-    // Wait for shutdown signal
-    info!("🎯 Bot is running. Press Ctrl+C to stop.");
-    shutdown_signal().await;
+    // Start the Prometheus metrics endpoint
+    let metrics_handle = if config.metrics_enabled {
+        let bind = config.metrics_bind.clone();
+        let state = state.clone();
+        Some(tokio::spawn(async move {
+            if let Err(e) = metrics::serve_metrics(&bind, state).await {
+                error!("Metrics server error: {:?}", e);
+            }
+        }))
+    } else {
+        None
+    };
+    
+    // Consolidate SOL from idle burner wallets
+    let sweeper_handle = (config.wallet_sweep_enabled && !wallets.burners().is_empty()).then(|| {
+        signer::spawn_sweeper(wallets.clone(), state.clone(), config.solana_rpc_url.clone(), signer::SweepConfig {
+            treasury: config.treasury_wallet.unwrap_or_else(|| signer.pubkey()),
+            interval: std::time::Duration::from_secs(config.wallet_sweep_interval_secs),
+            reserve_lamports: (config.wallet_sweep_reserve_sol * 1_000_000_000.0) as u64,
+        })
+    });
+    
+    // Bench targets whose recent trading scores below the minimum
+    let scorer_handle = (config.target_min_score > 0.0).then(|| {
+        analytics::spawn_target_scorer(control.clone(), config.solana_rpc_url.clone(), analytics::ScorerConfig {
+            min_score: config.target_min_score,
+            interval: std::time::Duration::from_secs(config.target_score_interval_secs),
+            max_signatures: config.target_score_signatures,
+        })
+    });
+    
+    // Apply buy amount, tip and take profit changes from the config file without a restart
+    let reload_handle = config.config_file.clone()
+        .map(|path| config::spawn_config_watcher(path, control.clone()));
+    
+    // Wait for shutdown signal (or the dashboard being closed)
+    if tui_mode {
+        let app = tui::App::new(state.clone(), control, seller, log_buffer);
+        if let Err(e) = tui::run(app, shutdown_signal()).await {
+            error!("Dashboard error: {:?}", e);
+        }
+    } else {
+        info!("🎯 Bot is running. Press Ctrl+C to stop.");
+        shutdown_signal().await;
+    }
     
     info!("🛑 Shutdown signal received, stopping bot...");
+    notifier.notify_now(NotifyEvent::Stopped { reason: "shutdown signal".to_string() }).await;
     
     // Cleanup
     engine_handle.abort();
+    for handle in [api_handle, metrics_handle, reload_handle, sweeper_handle, scorer_handle].into_iter().flatten() {
+        handle.abort();
+    }
     
     info!("👋 Bot stopped gracefully");
     Ok(())
 }
 
 /// Initialize the logging system
-fn init_logging() {
+/// With a dashboard log buffer, events go to the buffer instead of stdout
+fn init_logging(dashboard_logs: Option<Arc<tui::LogBuffer>>) {
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("info,solana_copytrading_bot=debug"));
     
+    if let Some(buffer) = dashboard_logs {
+        tracing_subscriber::registry()
+            .with(env_filter)
+            .with(tui::LogLayer::new(buffer))
+            .init();
+        return;
+    }
+    
     let json_logging = std::env::var("LOG_JSON")
         .map(|v| v == "true")
         .unwrap_or(false);
@@ -98,7 +231,7 @@ async fn shutdown_signal() {
             .await
             .expect("Failed to install Ctrl+C handler");
     };
-
+    
     #[cfg(unix)]
     let terminate = async {
         signal::unix::signal(signal::unix::SignalKind::terminate())
@@ -106,10 +239,10 @@ async fn shutdown_signal() {
             .recv()
             .await;
     };
-
+    
     #[cfg(not(unix))]
     let terminate = std::future::pending::<()>();
-
+    
     tokio::select! {
         _ = ctrl_c => {},
         _ = terminate => {},
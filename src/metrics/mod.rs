@@ -0,0 +1,7 @@
+//! Prometheus metrics for latency, API errors, spend and PnL
+
+pub mod registry;
+pub mod server;
+
+pub use registry::*;
+pub use server::*;
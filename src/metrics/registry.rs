@@ -0,0 +1,285 @@
+//! Bot metrics registry
+
+use prometheus::{
+    Encoder, Gauge, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::state::StateStats;
+
+/// API endpoint labels for `copybot_api_errors_total`
+pub const ENDPOINT_PUMPPORTAL_TRADE: &str = "pumpportal_trade";
+pub const ENDPOINT_PUMPPORTAL_QUOTE: &str = "pumpportal_quote";
+pub const ENDPOINT_JUPITER_QUOTE: &str = "jupiter_quote";
+pub const ENDPOINT_JUPITER_SWAP: &str = "jupiter_swap";
+
+/// Transaction outcome labels for `copybot_transactions_total`
+pub const OUTCOME_LANDED: &str = "landed";
+pub const OUTCOME_FAILED: &str = "failed";
+pub const OUTCOME_DROPPED: &str = "dropped";
+
+/// All metrics the bot exports
+pub struct BotMetrics {
+    registry: Registry,
+    /// Seconds from receiving a target's log notification to sending our copy (label: side)
+    pub detection_to_send: HistogramVec,
+    /// Seconds from sending a transaction to first seeing its status on chain
+    pub send_to_land: Histogram,
+    /// Slots between the target's transaction and ours landing
+    pub detection_to_land_slots: Histogram,
+    /// Sent transactions by outcome (landed, failed, dropped)
+    pub transactions: IntCounterVec,
+    /// WebSocket stream reconnects
+    pub stream_reconnects: IntCounter,
+    /// PumpPortal/Jupiter API errors (label: endpoint)
+    pub api_errors: IntCounterVec,
+    /// Priority fee / tip lamports requested on sent transactions
+    pub tip_lamports: IntCounter,
+    /// Transaction fees paid on chain (from transaction meta)
+    pub fee_lamports: IntCounter,
+    /// Open positions
+    pub open_positions: IntGauge,
+    /// Realized PnL from the trade history
+    pub realized_pnl_lamports: IntGauge,
+    /// Buys and sells recorded in the trade history
+    pub total_buys: IntGauge,
+    pub total_sells: IntGauge,
+    /// Winning sells as a percentage of sells with a PnL
+    pub win_rate_percent: Gauge,
+    /// Circuit breaker state per venue (0 closed, 1 half-open, 2 open)
+    pub circuit_state: IntGaugeVec,
+    /// Times each venue's circuit breaker tripped
+    pub circuit_trips: IntCounterVec,
+    /// Empty token accounts closed by the rent reclaimer
+    pub token_accounts_closed: IntCounter,
+    /// Rent lamports returned by closing token accounts
+    pub rent_reclaimed_lamports: IntCounter,
+}
+
+impl BotMetrics {
+    /// Create and register all metrics
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        
+        let detection_to_send = HistogramVec::new(
+            HistogramOpts::new(
+                "copybot_detection_to_send_seconds",
+                "Seconds from target log notification to our transaction being sent",
+            )
+            .buckets(vec![0.05, 0.1, 0.2, 0.3, 0.5, 0.75, 1.0, 1.5, 2.0, 3.0, 5.0]),
+            &["side"],
+        )
+        .unwrap();
+        let send_to_land = Histogram::with_opts(
+            HistogramOpts::new(
+                "copybot_send_to_land_seconds",
+                "Seconds from sending a transaction to its status appearing on chain",
+            )
+            .buckets(vec![0.4, 0.8, 1.2, 2.0, 3.0, 5.0, 10.0, 20.0, 30.0, 60.0]),
+        )
+        .unwrap();
+        let detection_to_land_slots = Histogram::with_opts(
+            HistogramOpts::new(
+                "copybot_detection_to_land_slots",
+                "Slots between the target transaction and our copy landing",
+            )
+            .buckets(vec![0.0, 1.0, 2.0, 3.0, 5.0, 8.0, 13.0, 21.0, 50.0]),
+        )
+        .unwrap();
+        let transactions = IntCounterVec::new(
+            Opts::new("copybot_transactions_total", "Sent transactions by outcome"),
+            &["outcome"],
+        )
+        .unwrap();
+        let stream_reconnects = IntCounter::new(
+            "copybot_stream_reconnects_total",
+            "WebSocket stream reconnects",
+        )
+        .unwrap();
+        let api_errors = IntCounterVec::new(
+            Opts::new("copybot_api_errors_total", "PumpPortal/Jupiter API errors by endpoint"),
+            &["endpoint"],
+        )
+        .unwrap();
+        let tip_lamports = IntCounter::new(
+            "copybot_tip_lamports_total",
+            "Priority fee / tip lamports requested on sent transactions",
+        )
+        .unwrap();
+        let fee_lamports = IntCounter::new(
+            "copybot_fee_lamports_total",
+            "Transaction fees paid on chain",
+        )
+        .unwrap();
+        let open_positions = IntGauge::new("copybot_open_positions", "Open positions").unwrap();
+        let realized_pnl_lamports = IntGauge::new(
+            "copybot_realized_pnl_lamports",
+            "Realized PnL from the trade history",
+        )
+        .unwrap();
+        let total_buys = IntGauge::new("copybot_buys", "Buys in the trade history").unwrap();
+        let total_sells = IntGauge::new("copybot_sells", "Sells in the trade history").unwrap();
+        let win_rate_percent = Gauge::new(
+            "copybot_win_rate_percent",
+            "Winning sells as a percentage of sells with a PnL",
+        )
+        .unwrap();
+        let circuit_state = IntGaugeVec::new(
+            Opts::new("copybot_circuit_state", "Circuit breaker state by venue (0 closed, 1 half-open, 2 open)"),
+            &["venue"],
+        )
+        .unwrap();
+        let circuit_trips = IntCounterVec::new(
+            Opts::new("copybot_circuit_trips_total", "Circuit breaker trips by venue"),
+            &["venue"],
+        )
+        .unwrap();
+        let token_accounts_closed = IntCounter::new(
+            "copybot_token_accounts_closed_total",
+            "Token accounts closed to reclaim rent",
+        )
+        .unwrap();
+        let rent_reclaimed_lamports = IntCounter::new(
+            "copybot_rent_reclaimed_lamports_total",
+            "Rent lamports returned by closing token accounts",
+        )
+        .unwrap();
+        
+        registry.register(Box::new(detection_to_send.clone())).unwrap();
+        registry.register(Box::new(send_to_land.clone())).unwrap();
+        registry.register(Box::new(detection_to_land_slots.clone())).unwrap();
+        registry.register(Box::new(transactions.clone())).unwrap();
+        registry.register(Box::new(stream_reconnects.clone())).unwrap();
+        registry.register(Box::new(api_errors.clone())).unwrap();
+        registry.register(Box::new(tip_lamports.clone())).unwrap();
+        registry.register(Box::new(fee_lamports.clone())).unwrap();
+        registry.register(Box::new(open_positions.clone())).unwrap();
+        registry.register(Box::new(realized_pnl_lamports.clone())).unwrap();
+        registry.register(Box::new(total_buys.clone())).unwrap();
+        registry.register(Box::new(total_sells.clone())).unwrap();
+        registry.register(Box::new(win_rate_percent.clone())).unwrap();
+        registry.register(Box::new(circuit_state.clone())).unwrap();
+        registry.register(Box::new(circuit_trips.clone())).unwrap();
+        registry.register(Box::new(token_accounts_closed.clone())).unwrap();
+        registry.register(Box::new(rent_reclaimed_lamports.clone())).unwrap();
+        
+        Self {
+            registry,
+            detection_to_send,
+            send_to_land,
+            detection_to_land_slots,
+            transactions,
+            stream_reconnects,
+            api_errors,
+            tip_lamports,
+            fee_lamports,
+            open_positions,
+            realized_pnl_lamports,
+            total_buys,
+            total_sells,
+            win_rate_percent,
+            circuit_state,
+            circuit_trips,
+            token_accounts_closed,
+            rent_reclaimed_lamports,
+        }
+    }
+    
+    /// Record the time from detection to send for a copy trade
+    pub fn observe_detection_to_send(&self, side: &str, elapsed: Duration) {
+        self.detection_to_send.with_label_values(&[side]).observe(elapsed.as_secs_f64());
+    }
+    
+    /// Count an API error for an endpoint
+    pub fn record_api_error(&self, endpoint: &str) {
+        self.api_errors.with_label_values(&[endpoint]).inc();
+    }
+    
+    /// Count a sent transaction outcome
+    pub fn record_transaction(&self, outcome: &str) {
+        self.transactions.with_label_values(&[outcome]).inc();
+    }
+    
+    /// Set a venue's circuit breaker state
+    pub fn set_circuit_state(&self, venue: &str, state: i64) {
+        self.circuit_state.with_label_values(&[venue]).set(state);
+    }
+    
+    /// Count a circuit breaker trip for a venue
+    pub fn record_circuit_trip(&self, venue: &str) {
+        self.circuit_trips.with_label_values(&[venue]).inc();
+    }
+    
+    /// Count token accounts closed and the rent they returned
+    pub fn record_rent_reclaimed(&self, accounts: usize, lamports: u64) {
+        self.token_accounts_closed.inc_by(accounts as u64);
+        self.rent_reclaimed_lamports.inc_by(lamports);
+    }
+    
+    /// Copy position and PnL figures from the state manager
+    pub fn update_state(&self, stats: &StateStats) {
+        self.open_positions.set(stats.open_positions as i64);
+        self.realized_pnl_lamports.set(stats.total_pnl_lamports);
+        self.total_buys.set(stats.total_buys as i64);
+        self.total_sells.set(stats.total_sells as i64);
+        self.win_rate_percent.set(stats.win_rate());
+    }
+    
+    /// Encode all metrics in the Prometheus text format
+    pub fn encode(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::warn!("⚠️ Failed to encode metrics: {:?}", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+impl Default for BotMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Process-wide metrics, created on first use
+pub fn metrics() -> &'static BotMetrics {
+    static METRICS: OnceLock<BotMetrics> = OnceLock::new();
+    METRICS.get_or_init(BotMetrics::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_encode_metrics() {
+        let metrics = BotMetrics::new();
+        
+        metrics.observe_detection_to_send("buy", Duration::from_millis(250));
+        metrics.record_api_error(ENDPOINT_JUPITER_QUOTE);
+        metrics.record_api_error(ENDPOINT_JUPITER_QUOTE);
+        metrics.record_transaction(OUTCOME_LANDED);
+        metrics.stream_reconnects.inc();
+        metrics.tip_lamports.inc_by(10_000);
+        metrics.update_state(&StateStats {
+            open_positions: 2,
+            total_traded_tokens: 3,
+            total_buys: 3,
+            total_sells: 1,
+            total_pnl_lamports: -5_000,
+            winning_trades: 0,
+            losing_trades: 1,
+        });
+        
+        let text = metrics.encode();
+        assert!(text.contains("copybot_detection_to_send_seconds_count{side=\"buy\"} 1"));
+        assert!(text.contains("copybot_api_errors_total{endpoint=\"jupiter_quote\"} 2"));
+        assert!(text.contains("copybot_transactions_total{outcome=\"landed\"} 1"));
+        assert!(text.contains("copybot_stream_reconnects_total 1"));
+        assert!(text.contains("copybot_tip_lamports_total 10000"));
+        assert!(text.contains("copybot_open_positions 2"));
+        assert!(text.contains("copybot_realized_pnl_lamports -5000"));
+    }
+}
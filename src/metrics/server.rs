@@ -0,0 +1,59 @@
+//! Prometheus scrape endpoint
+
+use anyhow::{Result, Context};
+use axum::{extract::State, http::header, response::IntoResponse, routing::get, Router};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tracing::info;
+
+use crate::state::StateManager;
+use super::registry::metrics;
+
+/// Build the metrics router (`GET /metrics`)
+pub fn router(state: Arc<StateManager>) -> Router {
+    Router::new()
+        .route("/metrics", get(scrape))
+        .with_state(state)
+}
+
+/// Bind the metrics endpoint and serve it until the task is aborted
+pub async fn serve_metrics(bind: &str, state: Arc<StateManager>) -> Result<()> {
+    let listener = TcpListener::bind(bind).await
+        .with_context(|| format!("Failed to bind metrics endpoint on {}", bind))?;
+    info!("📊 Metrics listening on http://{}/metrics", listener.local_addr()?);
+    axum::serve(listener, router(state)).await
+        .context("Metrics server error")
+}
+
+/// Refresh state gauges and return all metrics
+async fn scrape(State(state): State<Arc<StateManager>>) -> impl IntoResponse {
+    let metrics = metrics();
+    metrics.update_state(&state.get_stats());
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics.encode(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Position;
+    use solana_sdk::pubkey::Pubkey;
+    
+    #[tokio::test]
+    async fn test_scrape_reports_state() {
+        let state = Arc::new(StateManager::new());
+        state.open_position(Position::new(Pubkey::new_unique(), 1_000, 100, "t".to_string(), "o".to_string()));
+        
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/metrics", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router(state)).await });
+        
+        let response = reqwest::get(&url).await.unwrap();
+        assert!(response.status().is_success());
+        let body = response.text().await.unwrap();
+        assert!(body.contains("copybot_open_positions 1"));
+        assert!(body.contains("copybot_buys 1"));
+    }
+}
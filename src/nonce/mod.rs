@@ -0,0 +1,7 @@
+//! Durable nonce management for pre-signed emergency sells
+
+pub mod pool;
+pub mod presign;
+
+pub use pool::*;
+pub use presign::*;
@@ -0,0 +1,337 @@
+//! Durable nonce account pool
+
+use anyhow::{Result, Context};
+use parking_lot::RwLock;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    nonce::state::{State as NonceState, Versions as NonceVersions},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    system_instruction,
+    system_program,
+    transaction::Transaction,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn, debug};
+
+use crate::signer::WalletSigner;
+
+/// Offset of the authority pubkey inside nonce account data
+const NONCE_AUTHORITY_OFFSET: usize = 8;
+
+/// Nonce accounts created per transaction (each needs its own signature)
+const CREATE_BATCH_SIZE: usize = 4;
+
+/// A durable nonce account owned by our wallet
+#[derive(Debug, Clone)]
+pub struct NonceAccountInfo {
+    /// Nonce account address
+    pub address: Pubkey,
+    /// Current durable nonce value (used as the transaction blockhash)
+    pub nonce: Hash,
+    /// Token mint whose emergency sell currently uses this nonce
+    pub assigned_to: Option<Pubkey>,
+}
+
+/// Pool of durable nonce accounts used for pre-signed emergency sells
+pub struct NoncePool {
+    rpc_client: AsyncRpcClient,
+    authority: Arc<WalletSigner>,
+    accounts: RwLock<HashMap<Pubkey, NonceAccountInfo>>,
+    /// Idle accounts to keep ready for new positions
+    spare: usize,
+    /// Maximum number of nonce accounts to hold
+    max_accounts: usize,
+}
+
+impl NoncePool {
+    /// Create a new, empty nonce pool
+    pub fn new(rpc_url: String, authority: Arc<WalletSigner>, spare: usize, max_accounts: usize) -> Self {
+        Self {
+            rpc_client: AsyncRpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed()),
+            authority,
+            accounts: RwLock::new(HashMap::new()),
+            spare,
+            max_accounts,
+        }
+    }
+    
+    /// Load nonce accounts we already own (e.g. left over from a previous run)
+    pub async fn load_existing(&self) -> Result<usize> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::DataSize(NonceState::size() as u64),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    NONCE_AUTHORITY_OFFSET,
+                    self.authority.pubkey().to_bytes().to_vec(),
+                )),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        
+        let accounts = self.rpc_client
+            .get_program_accounts_with_config(&system_program::id(), config)
+            .await
+            .context("Failed to fetch existing nonce accounts")?;
+        
+        let mut loaded = 0;
+        let mut pool = self.accounts.write();
+        for (address, account) in accounts {
+            if let Some((authority, nonce)) = decode_nonce_account(&account.data) {
+                if authority == self.authority.pubkey() {
+                    pool.entry(address).or_insert(NonceAccountInfo {
+                        address,
+                        nonce,
+                        assigned_to: None,
+                    });
+                    loaded += 1;
+                }
+            }
+        }
+        
+        info!("🔐 Loaded {} existing nonce account(s)", loaded);
+        Ok(loaded)
+    }
+    
+    /// Create new nonce accounts funded and owned by our wallet
+    pub async fn create_accounts(&self, count: usize) -> Result<Vec<Pubkey>> {
+        let rent = self.rpc_client
+            .get_minimum_balance_for_rent_exemption(NonceState::size())
+            .await
+            .context("Failed to get nonce account rent")?;
+        
+        let authority = self.authority.pubkey();
+        let mut created = Vec::with_capacity(count);
+        
+        for batch in (0..count).collect::<Vec<_>>().chunks(CREATE_BATCH_SIZE) {
+            let nonce_keypairs: Vec<Keypair> = batch.iter().map(|_| Keypair::new()).collect();
+            
+            let instructions: Vec<_> = nonce_keypairs.iter()
+                .flat_map(|kp| system_instruction::create_nonce_account(
+                    &authority,
+                    &kp.pubkey(),
+                    &authority,
+                    rent,
+                ))
+                .collect();
+            
+            let recent_blockhash = self.rpc_client.get_latest_blockhash().await
+                .context("Failed to get recent blockhash")?;
+            
+            let mut tx = Transaction::new_with_payer(&instructions, Some(&authority));
+            {
+                let mut signers: Vec<&dyn Signer> = vec![self.authority.as_ref()];
+                signers.extend(nonce_keypairs.iter().map(|kp| kp as &dyn Signer));
+                tx.try_sign(&signers, recent_blockhash)
+                    .context("Failed to sign nonce account creation")?;
+            }
+            
+            self.rpc_client.send_and_confirm_transaction(&tx).await
+                .context("Failed to create nonce accounts")?;
+            
+            for kp in &nonce_keypairs {
+                let address = kp.pubkey();
+                let nonce = self.fetch_nonce(&address).await?;
+                self.accounts.write().insert(address, NonceAccountInfo {
+                    address,
+                    nonce,
+                    assigned_to: None,
+                });
+                created.push(address);
+            }
+        }
+        
+        info!("🔐 Created {} nonce account(s) ({} lamports rent each)", created.len(), rent);
+        Ok(created)
+    }
+    
+    /// Close idle nonce accounts and return their rent to our wallet
+    pub async fn close_accounts(&self, addresses: &[Pubkey]) -> Result<usize> {
+        let authority = self.authority.pubkey();
+        let mut closed = 0;
+        
+        for address in addresses {
+            let lamports = match self.rpc_client.get_balance(address).await {
+                Ok(l) if l > 0 => l,
+                _ => {
+                    self.accounts.write().remove(address);
+                    continue;
+                }
+            };
+            
+            let ix = system_instruction::withdraw_nonce_account(address, &authority, &authority, lamports);
+            let recent_blockhash = self.rpc_client.get_latest_blockhash().await
+                .context("Failed to get recent blockhash")?;
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&authority),
+                &[self.authority.as_ref()],
+                recent_blockhash,
+            );
+            
+            match self.rpc_client.send_and_confirm_transaction(&tx).await {
+                Ok(_) => {
+                    self.accounts.write().remove(address);
+                    closed += 1;
+                }
+                Err(e) => warn!("⚠️ Failed to close nonce account {}: {:?}", address, e),
+            }
+        }
+        
+        if closed > 0 {
+            info!("🧹 Closed {} idle nonce account(s)", closed);
+        }
+        Ok(closed)
+    }
+    
+    /// Keep `spare` idle accounts ready and close idle accounts beyond that
+    pub async fn maintain(&self) -> Result<()> {
+        let (total, idle) = {
+            let accounts = self.accounts.read();
+            let idle: Vec<Pubkey> = accounts.values()
+                .filter(|a| a.assigned_to.is_none())
+                .map(|a| a.address)
+                .collect();
+            (accounts.len(), idle)
+        };
+        
+        if idle.len() < self.spare {
+            let room = self.max_accounts.saturating_sub(total);
+            let to_create = (self.spare - idle.len()).min(room);
+            if to_create > 0 {
+                self.create_accounts(to_create).await?;
+            }
+        } else if idle.len() > self.spare {
+            self.close_accounts(&idle[self.spare..]).await?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Read the current nonce value of an account from chain
+    pub async fn fetch_nonce(&self, address: &Pubkey) -> Result<Hash> {
+        let account = self.rpc_client.get_account(address).await
+            .with_context(|| format!("Failed to fetch nonce account {}", address))?;
+        
+        decode_nonce_account(&account.data)
+            .map(|(_, nonce)| nonce)
+            .with_context(|| format!("Account {} is not an initialized nonce account", address))
+    }
+    
+    /// Assign an idle nonce account to a token mint (or return the one already assigned)
+    pub fn acquire(&self, token_mint: &Pubkey) -> Option<NonceAccountInfo> {
+        let mut accounts = self.accounts.write();
+        
+        if let Some(existing) = accounts.values().find(|a| a.assigned_to == Some(*token_mint)) {
+            return Some(existing.clone());
+        }
+        
+        let idle = accounts.values_mut().find(|a| a.assigned_to.is_none())?;
+        idle.assigned_to = Some(*token_mint);
+        debug!("Assigned nonce account {} to {}", idle.address, token_mint);
+        Some(idle.clone())
+    }
+    
+    /// Return a nonce account to the idle set without advancing it
+    pub fn release(&self, address: &Pubkey) {
+        if let Some(account) = self.accounts.write().get_mut(address) {
+            account.assigned_to = None;
+        }
+    }
+    
+    /// Rotate a nonce account after its pre-signed transaction was sent
+    ///
+    /// Waits for the transaction to settle so the advanced nonce value is
+    /// read back, then returns the account to the idle set.
+    pub async fn rotate(&self, address: &Pubkey, signature: &Signature, timeout: Duration) -> Result<Hash> {
+        let previous = self.accounts.read().get(address).map(|a| a.nonce);
+        let deadline = tokio::time::Instant::now() + timeout;
+        
+        while tokio::time::Instant::now() < deadline {
+            if let Ok(Some(_)) = self.rpc_client.get_signature_status(signature).await {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        
+        let nonce = self.fetch_nonce(address).await?;
+        if Some(nonce) == previous {
+            debug!("Nonce {} not advanced (transaction {} did not land)", address, signature);
+        }
+        
+        if let Some(account) = self.accounts.write().get_mut(address) {
+            account.nonce = nonce;
+            account.assigned_to = None;
+        }
+        Ok(nonce)
+    }
+}
+
+/// Decode nonce account data into (authority, durable nonce)
+pub fn decode_nonce_account(data: &[u8]) -> Option<(Pubkey, Hash)> {
+    let versions: NonceVersions = bincode::deserialize(data).ok()?;
+    match versions.state() {
+        NonceState::Initialized(data) => Some((data.authority, data.blockhash())),
+        NonceState::Uninitialized => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::nonce::state::DurableNonce;
+    
+    #[test]
+    fn test_decode_nonce_account() {
+        let authority = Pubkey::new_unique();
+        let durable_nonce = DurableNonce::from_blockhash(&Hash::new_unique());
+        let state = NonceState::new_initialized(&authority, durable_nonce, 5_000);
+        let data = bincode::serialize(&NonceVersions::new(state)).unwrap();
+        
+        assert_eq!(data.len(), NonceState::size());
+        assert_eq!(&data[NONCE_AUTHORITY_OFFSET..NONCE_AUTHORITY_OFFSET + 32], authority.as_ref());
+        
+        let (decoded_authority, nonce) = decode_nonce_account(&data).unwrap();
+        assert_eq!(decoded_authority, authority);
+        assert_eq!(nonce, *durable_nonce.as_hash());
+    }
+    
+    #[test]
+    fn test_decode_uninitialized_nonce_account() {
+        let data = bincode::serialize(&NonceVersions::new(NonceState::Uninitialized)).unwrap();
+        assert!(decode_nonce_account(&data).is_none());
+        assert!(decode_nonce_account(&[1, 2, 3]).is_none());
+    }
+    
+    #[test]
+    fn test_acquire_and_release() {
+        let pool = NoncePool::new("http://localhost:8899".to_string(), Arc::new(Keypair::new().into()), 1, 4);
+        let address = Pubkey::new_unique();
+        pool.accounts.write().insert(address, NonceAccountInfo {
+            address,
+            nonce: Hash::new_unique(),
+            assigned_to: None,
+        });
+        
+        let mint = Pubkey::new_unique();
+        assert_eq!(pool.acquire(&mint).unwrap().address, address);
+        // Same mint gets the same account back
+        assert_eq!(pool.acquire(&mint).unwrap().address, address);
+        // No idle accounts left for another mint
+        assert!(pool.acquire(&Pubkey::new_unique()).is_none());
+        
+        pool.release(&address);
+        assert!(pool.acquire(&Pubkey::new_unique()).is_some());
+    }
+}
@@ -0,0 +1,139 @@
+//! Pre-signed emergency sell transactions using durable nonces
+
+use anyhow::{Result, Context};
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_sdk::{
+    address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
+    hash::Hash,
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    signature::Signer,
+    system_instruction,
+    transaction::VersionedTransaction,
+};
+use std::time::Instant;
+
+use crate::jito::insert_instruction;
+
+/// An emergency sell signed ahead of time against a durable nonce
+#[derive(Debug, Clone)]
+pub struct PresignedSell {
+    /// Token mint being sold
+    pub token_mint: Pubkey,
+    /// Raw token amount the transaction sells
+    pub token_amount: u64,
+    /// Nonce account the transaction advances
+    pub nonce_account: Pubkey,
+    /// Signed transaction, ready to send
+    pub transaction: VersionedTransaction,
+    /// When the transaction was signed
+    pub created_at: Instant,
+}
+
+/// Rewrite a swap transaction to use a durable nonce instead of a recent blockhash
+///
+/// Prepends `advance_nonce_account` as the first instruction (required by the
+/// runtime), sets the nonce value as the blockhash and signs with `authority`,
+/// which must also be the fee payer and only signer.
+pub fn apply_durable_nonce(
+    tx: &VersionedTransaction,
+    nonce_account: &Pubkey,
+    nonce: Hash,
+    authority: &impl Signer,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<VersionedTransaction> {
+    let mut message = tx.message.clone();
+    
+    if message.header().num_required_signatures != 1
+        || message.static_account_keys().first() != Some(&authority.pubkey())
+    {
+        anyhow::bail!("Pre-signed sells must have our wallet as the only signer");
+    }
+    
+    let advance_ix = system_instruction::advance_nonce_account(nonce_account, &authority.pubkey());
+    insert_instruction(&mut message, 0, &advance_ix, lookup_tables)
+        .context("Failed to insert advance_nonce_account instruction")?;
+    message.set_recent_blockhash(nonce);
+    
+    VersionedTransaction::try_new(message, &[authority])
+        .context("Failed to sign nonce transaction")
+}
+
+/// Fetch the lookup tables a message references so it can be rewritten
+pub async fn fetch_message_lookup_tables(
+    rpc_client: &AsyncRpcClient,
+    message: &VersionedMessage,
+) -> Result<Vec<AddressLookupTableAccount>> {
+    let lookups = match message.address_table_lookups() {
+        Some(lookups) => lookups,
+        None => return Ok(Vec::new()),
+    };
+    
+    let mut tables = Vec::with_capacity(lookups.len());
+    for lookup in lookups {
+        let account = rpc_client.get_account(&lookup.account_key).await
+            .with_context(|| format!("Failed to fetch lookup table {}", lookup.account_key))?;
+        let table = AddressLookupTable::deserialize(&account.data)
+            .map_err(|e| anyhow::anyhow!("Invalid lookup table {}: {:?}", lookup.account_key, e))?;
+        tables.push(AddressLookupTableAccount {
+            key: lookup.account_key,
+            addresses: table.addresses.to_vec(),
+        });
+    }
+    Ok(tables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::message::v0;
+    use solana_sdk::signature::{Keypair, Signature};
+    
+    #[test]
+    fn test_apply_durable_nonce() {
+        let authority = Keypair::new();
+        let nonce_account = Pubkey::new_unique();
+        let nonce = Hash::new_unique();
+        
+        let swap_ix = system_instruction::transfer(&authority.pubkey(), &Pubkey::new_unique(), 42);
+        let message = v0::Message::try_compile(&authority.pubkey(), &[swap_ix], &[], Hash::default()).unwrap();
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::V0(message),
+        };
+        
+        let nonced = apply_durable_nonce(&tx, &nonce_account, nonce, &authority, &[]).unwrap();
+        
+        assert_eq!(*nonced.message.recent_blockhash(), nonce);
+        assert!(nonced.verify_with_results().iter().all(|ok| *ok));
+        
+        let keys = nonced.message.static_account_keys();
+        let instructions = nonced.message.instructions();
+        assert_eq!(instructions.len(), 2);
+        
+        let advance = &instructions[0];
+        assert_eq!(keys[advance.program_id_index as usize], solana_sdk::system_program::id());
+        assert_eq!(keys[advance.accounts[0] as usize], nonce_account);
+        assert_eq!(keys[advance.accounts[2] as usize], authority.pubkey());
+        assert_eq!(advance.data, system_instruction::advance_nonce_account(&nonce_account, &authority.pubkey()).data);
+        
+        // The original transfer still targets the same accounts
+        let transfer = &instructions[1];
+        assert_eq!(keys[transfer.accounts[0] as usize], authority.pubkey());
+        assert_eq!(transfer.data, tx.message.instructions()[0].data);
+    }
+    
+    #[test]
+    fn test_apply_durable_nonce_rejects_foreign_payer() {
+        let authority = Keypair::new();
+        let other = Pubkey::new_unique();
+        let ix = system_instruction::transfer(&other, &Pubkey::new_unique(), 1);
+        let message = v0::Message::try_compile(&other, &[ix], &[], Hash::default()).unwrap();
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::V0(message),
+        };
+        
+        assert!(apply_durable_nonce(&tx, &Pubkey::new_unique(), Hash::new_unique(), &authority, &[]).is_err());
+    }
+}
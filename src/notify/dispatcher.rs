@@ -0,0 +1,251 @@
+//! Notification dispatch with per-event toggles and rate limiting
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{info, warn, debug};
+
+use super::event::{EventKind, NotifyEvent};
+use super::{DiscordNotifier, Notifier, TelegramNotifier, WebhookNotifier};
+use crate::config::Config;
+
+/// Queued notifications before new ones are dropped
+const QUEUE_CAPACITY: usize = 256;
+
+/// Token bucket limiting how many notifications are delivered per minute
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Allow bursts of `per_minute` and refill at `per_minute` per minute (0 = unlimited)
+    pub fn new(per_minute: u32) -> Self {
+        let capacity = if per_minute == 0 { f64::INFINITY } else { per_minute as f64 };
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_sec: per_minute as f64 / 60.0,
+            last_refill: Instant::now(),
+        }
+    }
+    
+    /// Take a token if one is available at `now`
+    pub fn try_acquire(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+        
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Fans events out to all configured notifiers from a background task
+pub struct NotificationDispatcher {
+    /// None when no notifiers are configured
+    tx: Option<mpsc::Sender<NotifyEvent>>,
+    notifiers: Vec<Arc<dyn Notifier>>,
+    enabled: HashSet<EventKind>,
+}
+
+impl NotificationDispatcher {
+    /// Create a dispatcher that drops every event
+    pub fn disabled() -> Self {
+        Self {
+            tx: None,
+            notifiers: Vec::new(),
+            enabled: HashSet::new(),
+        }
+    }
+    
+    /// Start delivering enabled events to `notifiers` (must be called inside a tokio runtime)
+    pub fn start(
+        notifiers: Vec<Arc<dyn Notifier>>,
+        enabled: HashSet<EventKind>,
+        rate_limit_per_minute: u32,
+    ) -> Self {
+        if notifiers.is_empty() {
+            return Self::disabled();
+        }
+        
+        info!(
+            "🔔 Notifications enabled: {} ({} events, {}/min)",
+            notifiers.iter().map(|n| n.name()).collect::<Vec<_>>().join(", "),
+            enabled.len(),
+            rate_limit_per_minute
+        );
+        
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(Self::run(notifiers.clone(), rx, RateLimiter::new(rate_limit_per_minute)));
+        
+        Self {
+            tx: Some(tx),
+            notifiers,
+            enabled,
+        }
+    }
+    
+    /// Start the notifiers configured in `config`
+    pub fn from_config(config: &Config) -> Self {
+        let mut notifiers: Vec<Arc<dyn Notifier>> = Vec::new();
+        
+        if let (Some(token), Some(chat_id)) = (&config.telegram_bot_token, &config.telegram_chat_id) {
+            let mut telegram = TelegramNotifier::new(token, chat_id);
+            if let Some(api_base) = &config.telegram_api_base {
+                telegram = telegram.with_api_base(api_base);
+            }
+            notifiers.push(Arc::new(telegram));
+        }
+        if let Some(url) = &config.discord_webhook_url {
+            notifiers.push(Arc::new(DiscordNotifier::new(url)));
+        }
+        if let Some(url) = &config.notify_webhook_url {
+            notifiers.push(Arc::new(WebhookNotifier::new(url)));
+        }
+        
+        Self::start(notifiers, config.notify_events.clone(), config.notify_rate_limit_per_min)
+    }
+    
+    /// Check if an event kind is delivered
+    pub fn is_enabled(&self, kind: EventKind) -> bool {
+        self.tx.is_some() && self.enabled.contains(&kind)
+    }
+    
+    /// Queue an event without waiting for delivery
+    pub fn notify(&self, event: NotifyEvent) {
+        if !self.is_enabled(event.kind()) {
+            return;
+        }
+        if let Some(tx) = &self.tx {
+            if tx.try_send(event).is_err() {
+                warn!("⚠️ Notification queue full, dropping event");
+            }
+        }
+    }
+    
+    /// Deliver an event immediately, bypassing the queue and rate limit
+    /// Used for the stop event so it goes out before the process exits
+    pub async fn notify_now(&self, event: NotifyEvent) {
+        if !self.is_enabled(event.kind()) {
+            return;
+        }
+        Self::deliver(&self.notifiers, &event).await;
+    }
+    
+    /// Background delivery loop
+    async fn run(
+        notifiers: Vec<Arc<dyn Notifier>>,
+        mut rx: mpsc::Receiver<NotifyEvent>,
+        mut limiter: RateLimiter,
+    ) {
+        let mut suppressed = 0u32;
+        
+        while let Some(event) = rx.recv().await {
+            if !limiter.try_acquire(Instant::now()) {
+                suppressed += 1;
+                debug!("Notification rate limited ({} suppressed)", suppressed);
+                continue;
+            }
+            if suppressed > 0 {
+                warn!("⚠️ {} notification(s) suppressed by rate limit", suppressed);
+                suppressed = 0;
+            }
+            
+            Self::deliver(&notifiers, &event).await;
+        }
+    }
+    
+    /// Send an event to every notifier, logging failures
+    async fn deliver(notifiers: &[Arc<dyn Notifier>], event: &NotifyEvent) {
+        for notifier in notifiers {
+            match tokio::time::timeout(Duration::from_secs(10), notifier.send(event)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => warn!("⚠️ {} notification failed: {:?}", notifier.name(), e),
+                Err(_) => warn!("⚠️ {} notification timed out", notifier.name()),
+            }
+        }
+    }
+}
+
+impl Default for NotificationDispatcher {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    
+    struct RecordingNotifier {
+        events: Mutex<Vec<EventKind>>,
+    }
+    
+    #[async_trait]
+    impl Notifier for RecordingNotifier {
+        fn name(&self) -> &str {
+            "recording"
+        }
+        
+        async fn send(&self, event: &NotifyEvent) -> Result<()> {
+            self.events.lock().push(event.kind());
+            Ok(())
+        }
+    }
+    
+    #[test]
+    fn test_rate_limiter() {
+        let mut limiter = RateLimiter::new(2);
+        let start = Instant::now();
+        
+        assert!(limiter.try_acquire(start));
+        assert!(limiter.try_acquire(start));
+        assert!(!limiter.try_acquire(start));
+        
+        // One token refills every 30 seconds at 2/min
+        assert!(!limiter.try_acquire(start + Duration::from_secs(10)));
+        assert!(limiter.try_acquire(start + Duration::from_secs(31)));
+        
+        let mut unlimited = RateLimiter::new(0);
+        assert!((0..1000).all(|_| unlimited.try_acquire(start)));
+    }
+    
+    #[tokio::test]
+    async fn test_dispatcher_toggles_and_limits() {
+        let recorder = Arc::new(RecordingNotifier { events: Mutex::new(Vec::new()) });
+        let enabled: HashSet<EventKind> = [EventKind::Stopped, EventKind::Disconnected].into_iter().collect();
+        let dispatcher = NotificationDispatcher::start(vec![recorder.clone()], enabled, 2);
+        
+        dispatcher.notify(NotifyEvent::BuyFailed { token_mint: "m".to_string(), error: "e".to_string() });
+        for attempt in 1..=3 {
+            dispatcher.notify(NotifyEvent::Disconnected { error: "closed".to_string(), attempt });
+        }
+        dispatcher.notify_now(NotifyEvent::Stopped { reason: "test".to_string() }).await;
+        
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        
+        let events = recorder.events.lock();
+        assert_eq!(events.iter().filter(|k| **k == EventKind::Disconnected).count(), 2);
+        assert!(events.contains(&EventKind::Stopped));
+        assert!(!events.contains(&EventKind::BuyFailed));
+    }
+    
+    #[tokio::test]
+    async fn test_disabled_dispatcher() {
+        let dispatcher = NotificationDispatcher::disabled();
+        assert!(!dispatcher.is_enabled(EventKind::Stopped));
+        dispatcher.notify(NotifyEvent::Stopped { reason: "test".to_string() });
+    }
+}
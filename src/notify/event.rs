@@ -0,0 +1,200 @@
+//! Notification events
+
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::token::TokenInfo;
+
+/// Kind of notification, used for per-event toggles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    TargetBuy,
+    BuyLanded,
+    BuyFailed,
+    TakeProfit,
+    CopySell,
+    SafetyExit,
+    Stopped,
+    Disconnected,
+    RiskLimit,
+}
+
+impl EventKind {
+    /// All event kinds
+    pub const ALL: [EventKind; 9] = [
+        EventKind::TargetBuy,
+        EventKind::BuyLanded,
+        EventKind::BuyFailed,
+        EventKind::TakeProfit,
+        EventKind::CopySell,
+        EventKind::SafetyExit,
+        EventKind::Stopped,
+        EventKind::Disconnected,
+        EventKind::RiskLimit,
+    ];
+    
+    /// Name used in `NOTIFY_EVENTS` and webhook payloads
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::TargetBuy => "target_buy",
+            EventKind::BuyLanded => "buy_landed",
+            EventKind::BuyFailed => "buy_failed",
+            EventKind::TakeProfit => "take_profit",
+            EventKind::CopySell => "copy_sell",
+            EventKind::SafetyExit => "safety_exit",
+            EventKind::Stopped => "stopped",
+            EventKind::Disconnected => "disconnected",
+            EventKind::RiskLimit => "risk_limit",
+        }
+    }
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EventKind {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EventKind::ALL.iter()
+            .find(|k| k.as_str() == s)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("Unknown notification event: {}", s))
+    }
+}
+
+/// Parse a comma-separated list of event kinds ("all" or empty enables every kind)
+pub fn parse_event_kinds(input: &str) -> anyhow::Result<HashSet<EventKind>> {
+    let names: Vec<&str> = input.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    
+    if names.is_empty() || names == ["all"] {
+        return Ok(EventKind::ALL.into_iter().collect());
+    }
+    
+    names.into_iter().map(EventKind::from_str).collect()
+}
+
+/// A trade or lifecycle event worth telling the team about
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum NotifyEvent {
+    /// A target wallet bought a token
+    TargetBuy { target: String, token_mint: String, signature: String },
+    /// Our copy buy landed on chain, with what is known about the token
+    BuyLanded { token_mint: String, signature: String, token: Option<Box<TokenInfo>> },
+    /// Our copy buy could not be sent or failed on chain
+    BuyFailed { token_mint: String, error: String },
+    /// A take profit tier was hit and sold
+    TakeProfit { token_mint: String, multiplier: f64, sell_percent: u8, signature: String },
+    /// We sold a position because the target sold (or on operator request)
+    CopySell { token_mint: String, signature: String, manual: bool },
+    /// A held token showed signs of a rug and was sold at the emergency tip
+    SafetyExit { token_mint: String, reason: String, signature: String },
+    /// The bot stopped
+    Stopped { reason: String },
+    /// The transaction stream disconnected
+    Disconnected { error: String, attempt: u32 },
+    /// A risk limit was breached and new entries are paused
+    RiskLimit { limit: String, detail: String },
+}
+
+impl NotifyEvent {
+    /// Kind of this event
+    pub fn kind(&self) -> EventKind {
+        match self {
+            NotifyEvent::TargetBuy { .. } => EventKind::TargetBuy,
+            NotifyEvent::BuyLanded { .. } => EventKind::BuyLanded,
+            NotifyEvent::BuyFailed { .. } => EventKind::BuyFailed,
+            NotifyEvent::TakeProfit { .. } => EventKind::TakeProfit,
+            NotifyEvent::CopySell { .. } => EventKind::CopySell,
+            NotifyEvent::SafetyExit { .. } => EventKind::SafetyExit,
+            NotifyEvent::Stopped { .. } => EventKind::Stopped,
+            NotifyEvent::Disconnected { .. } => EventKind::Disconnected,
+            NotifyEvent::RiskLimit { .. } => EventKind::RiskLimit,
+        }
+    }
+    
+    /// Human-readable message for chat notifiers
+    pub fn message(&self) -> String {
+        match self {
+            NotifyEvent::TargetBuy { target, token_mint, signature } => format!(
+                "🎯 Target {} bought {}\nhttps://solscan.io/tx/{}",
+                short(target), token_mint, signature
+            ),
+            NotifyEvent::BuyLanded { token_mint, signature, token } => format!(
+                "✅ Copy buy landed: {}{}\nhttps://solscan.io/tx/{}",
+                token_mint,
+                token.as_ref().map(|token| format!("\n{}", token.summary())).unwrap_or_default(),
+                signature
+            ),
+            NotifyEvent::BuyFailed { token_mint, error } => format!(
+                "❌ Copy buy failed: {}\n{}",
+                token_mint, error
+            ),
+            NotifyEvent::TakeProfit { token_mint, multiplier, sell_percent, signature } => format!(
+                "📈 Take profit {:.2}x hit on {} (sold up to {}%)\nhttps://solscan.io/tx/{}",
+                multiplier, token_mint, sell_percent, signature
+            ),
+            NotifyEvent::CopySell { token_mint, signature, manual } => format!(
+                "{} {}\nhttps://solscan.io/tx/{}",
+                if *manual { "🖐️ Manual sell:" } else { "🚨 Copy sell:" },
+                token_mint,
+                signature
+            ),
+            NotifyEvent::SafetyExit { token_mint, reason, signature } => format!(
+                "☠️ Emergency exit from {}: {}\nhttps://solscan.io/tx/{}",
+                token_mint, reason, signature
+            ),
+            NotifyEvent::Stopped { reason } => format!("🛑 Bot stopped: {}", reason),
+            NotifyEvent::Disconnected { error, attempt } => format!(
+                "⚠️ Stream disconnected (attempt {}): {}",
+                attempt, error
+            ),
+            NotifyEvent::RiskLimit { limit, detail } => format!(
+                "🛡️ Risk limit {} breached, entries paused: {}",
+                limit, detail
+            ),
+        }
+    }
+}
+
+/// Shorten a pubkey for display
+fn short(value: &str) -> &str {
+    &value[..8.min(value.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_event_kinds() {
+        assert_eq!(parse_event_kinds("").unwrap().len(), EventKind::ALL.len());
+        assert_eq!(parse_event_kinds("all").unwrap().len(), EventKind::ALL.len());
+        
+        let kinds = parse_event_kinds("buy_failed, stopped").unwrap();
+        assert_eq!(kinds.len(), 2);
+        assert!(kinds.contains(&EventKind::BuyFailed));
+        assert!(kinds.contains(&EventKind::Stopped));
+        
+        assert!(parse_event_kinds("buy_failed,bogus").is_err());
+    }
+    
+    #[test]
+    fn test_event_payload() {
+        let event = NotifyEvent::Stopped { reason: "shutdown".to_string() };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "stopped");
+        assert_eq!(json["reason"], "shutdown");
+        assert_eq!(event.kind().as_str(), "stopped");
+    }
+}
@@ -0,0 +1,9 @@
+//! Trade and lifecycle notifications (Telegram, Discord, webhooks)
+
+pub mod event;
+pub mod notifier;
+pub mod dispatcher;
+
+pub use event::*;
+pub use notifier::*;
+pub use dispatcher::*;
@@ -0,0 +1,216 @@
+//! Notification channels (Telegram, Discord, generic webhooks)
+
+use anyhow::{Result, Context};
+use async_trait::async_trait;
+use std::time::Duration;
+
+use super::event::NotifyEvent;
+
+/// Telegram Bot API base URL
+pub const TELEGRAM_API_BASE: &str = "https://api.telegram.org";
+
+/// Request timeout for notification HTTP calls
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A channel that delivers notifications
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Channel name for logs
+    fn name(&self) -> &str;
+    
+    /// Deliver one event
+    async fn send(&self, event: &NotifyEvent) -> Result<()>;
+}
+
+/// Build the HTTP client shared by notifiers
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(NOTIFY_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// POST a JSON body and fail on non-success responses
+async fn post_json(client: &reqwest::Client, url: &str, body: &serde_json::Value) -> Result<()> {
+    let response = client.post(url)
+        .json(body)
+        .send()
+        .await
+        .context("Notification request failed")?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        anyhow::bail!("Notification rejected ({}): {}", status, error_text);
+    }
+    Ok(())
+}
+
+/// Telegram bot `sendMessage` notifier
+pub struct TelegramNotifier {
+    client: reqwest::Client,
+    api_base: String,
+    bot_token: String,
+    chat_id: String,
+}
+
+impl TelegramNotifier {
+    pub fn new(bot_token: impl Into<String>, chat_id: impl Into<String>) -> Self {
+        Self {
+            client: http_client(),
+            api_base: TELEGRAM_API_BASE.to_string(),
+            bot_token: bot_token.into(),
+            chat_id: chat_id.into(),
+        }
+    }
+    
+    /// Use a different Bot API server (e.g. a self-hosted one)
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into();
+        self
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+    
+    async fn send(&self, event: &NotifyEvent) -> Result<()> {
+        let url = format!("{}/bot{}/sendMessage", self.api_base.trim_end_matches('/'), self.bot_token);
+        let body = serde_json::json!({
+            "chat_id": self.chat_id,
+            "text": event.message(),
+            "disable_web_page_preview": true,
+        });
+        post_json(&self.client, &url, &body).await
+    }
+}
+
+/// Discord webhook notifier
+pub struct DiscordNotifier {
+    client: reqwest::Client,
+    webhook_url: String,
+}
+
+impl DiscordNotifier {
+    pub fn new(webhook_url: impl Into<String>) -> Self {
+        Self {
+            client: http_client(),
+            webhook_url: webhook_url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> &str {
+        "discord"
+    }
+    
+    async fn send(&self, event: &NotifyEvent) -> Result<()> {
+        let body = serde_json::json!({ "content": event.message() });
+        post_json(&self.client, &self.webhook_url, &body).await
+    }
+}
+
+/// Generic JSON webhook notifier
+/// Posts the event fields plus a `message` with the human-readable text
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: http_client(),
+            url: url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+    
+    async fn send(&self, event: &NotifyEvent) -> Result<()> {
+        let mut body = serde_json::to_value(event)?;
+        if let Some(obj) = body.as_object_mut() {
+            obj.insert("message".to_string(), serde_json::Value::String(event.message()));
+        }
+        post_json(&self.client, &self.url, &body).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, http::{StatusCode, Uri}, routing::post, Json, Router};
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+    
+    type Received = Arc<Mutex<Vec<(String, serde_json::Value)>>>;
+    
+    /// Local HTTP stub that records every POST and answers with `status`
+    async fn spawn_stub(status: StatusCode) -> (String, Received) {
+        let received: Received = Arc::new(Mutex::new(Vec::new()));
+        let app = Router::new()
+            .route("/*path", post(move |State(received): State<Received>, uri: Uri, Json(body): Json<serde_json::Value>| async move {
+                received.lock().push((uri.path().to_string(), body));
+                status
+            }))
+            .with_state(received.clone());
+        
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (base, received)
+    }
+    
+    fn event() -> NotifyEvent {
+        NotifyEvent::BuyLanded {
+            token_mint: "MintPump".to_string(),
+            signature: "sig123".to_string(),
+            token: None,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_telegram_notifier() {
+        let (base, received) = spawn_stub(StatusCode::OK).await;
+        let notifier = TelegramNotifier::new("TOKEN", "42").with_api_base(&base);
+        
+        notifier.send(&event()).await.unwrap();
+        
+        let received = received.lock();
+        assert_eq!(received[0].0, "/botTOKEN/sendMessage");
+        assert_eq!(received[0].1["chat_id"], "42");
+        assert!(received[0].1["text"].as_str().unwrap().contains("MintPump"));
+    }
+    
+    #[tokio::test]
+    async fn test_discord_and_webhook_notifiers() {
+        let (base, received) = spawn_stub(StatusCode::NO_CONTENT).await;
+        
+        DiscordNotifier::new(format!("{}/discord", base)).send(&event()).await.unwrap();
+        WebhookNotifier::new(format!("{}/hook", base)).send(&event()).await.unwrap();
+        
+        let received = received.lock();
+        assert_eq!(received[0].0, "/discord");
+        assert!(received[0].1["content"].as_str().unwrap().contains("sig123"));
+        assert_eq!(received[1].0, "/hook");
+        assert_eq!(received[1].1["event"], "buy_landed");
+        assert_eq!(received[1].1["token_mint"], "MintPump");
+        assert!(received[1].1["message"].is_string());
+    }
+    
+    #[tokio::test]
+    async fn test_rejected_notification_is_error() {
+        let (base, _) = spawn_stub(StatusCode::TOO_MANY_REQUESTS).await;
+        assert!(DiscordNotifier::new(format!("{}/discord", base)).send(&event()).await.is_err());
+    }
+}
@@ -0,0 +1,303 @@
+//! Circuit breakers that stop calling an execution venue after repeated failures
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::metrics::metrics;
+
+/// Where transactions are built or sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Venue {
+    PumpPortal,
+    Jupiter,
+    Jito,
+    Rpc,
+}
+
+impl Venue {
+    pub const ALL: [Venue; 4] = [Venue::PumpPortal, Venue::Jupiter, Venue::Jito, Venue::Rpc];
+    
+    /// Label used in logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Venue::PumpPortal => "pumpportal",
+            Venue::Jupiter => "jupiter",
+            Venue::Jito => "jito",
+            Venue::Rpc => "rpc",
+        }
+    }
+}
+
+impl fmt::Display for Venue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Breaker state; the metric value is in brackets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Calls go through (0)
+    Closed,
+    /// A single trial call is allowed after the cooldown (1)
+    HalfOpen,
+    /// Calls are rejected until the cooldown ends (2)
+    Open,
+}
+
+impl BreakerState {
+    fn metric_value(&self) -> i64 {
+        match self {
+            BreakerState::Closed => 0,
+            BreakerState::HalfOpen => 1,
+            BreakerState::Open => 2,
+        }
+    }
+}
+
+/// When breakers trip and recover
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BreakerConfig {
+    /// Consecutive failures that trip the breaker (0 = never trip)
+    pub failure_threshold: u32,
+    /// Only failures this recent count towards the threshold
+    pub window: Duration,
+    /// How long a tripped breaker rejects calls before a trial call
+    pub cooldown: Duration,
+}
+
+impl Default for BreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+struct BreakerInner {
+    state: BreakerState,
+    /// Times of the current run of consecutive failures
+    failures: VecDeque<Instant>,
+    opened_at: Option<Instant>,
+    /// A half-open trial call is in flight
+    trial_in_flight: bool,
+}
+
+/// Circuit breaker for one venue
+pub struct CircuitBreaker {
+    venue: Venue,
+    config: BreakerConfig,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(venue: Venue, config: BreakerConfig) -> Self {
+        metrics().set_circuit_state(venue.as_str(), BreakerState::Closed.metric_value());
+        Self {
+            venue,
+            config,
+            inner: Mutex::new(BreakerInner {
+                state: BreakerState::Closed,
+                failures: VecDeque::new(),
+                opened_at: None,
+                trial_in_flight: false,
+            }),
+        }
+    }
+    
+    pub fn venue(&self) -> Venue {
+        self.venue
+    }
+    
+    /// Whether calls are being rejected right now (routing uses this to pick an alternate venue)
+    pub fn is_open(&self) -> bool {
+        let inner = self.inner.lock();
+        match inner.state {
+            BreakerState::Closed => false,
+            BreakerState::Open => !self.cooldown_over(&inner),
+            BreakerState::HalfOpen => inner.trial_in_flight,
+        }
+    }
+    
+    /// Ask to make a call; every allowed call must be followed by `record_success` or `record_failure`
+    pub fn allow(&self) -> bool {
+        let mut inner = self.inner.lock();
+        match inner.state {
+            BreakerState::Closed => true,
+            BreakerState::Open if self.cooldown_over(&inner) => {
+                self.transition(&mut inner, BreakerState::HalfOpen);
+                inner.trial_in_flight = true;
+                true
+            }
+            BreakerState::Open => false,
+            BreakerState::HalfOpen if !inner.trial_in_flight => {
+                inner.trial_in_flight = true;
+                true
+            }
+            BreakerState::HalfOpen => false,
+        }
+    }
+    
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock();
+        inner.failures.clear();
+        inner.trial_in_flight = false;
+        if inner.state != BreakerState::Closed {
+            self.transition(&mut inner, BreakerState::Closed);
+        }
+    }
+    
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock();
+        let now = Instant::now();
+        inner.trial_in_flight = false;
+        
+        if inner.state == BreakerState::HalfOpen {
+            inner.opened_at = Some(now);
+            self.transition(&mut inner, BreakerState::Open);
+            return;
+        }
+        if inner.state == BreakerState::Open || self.config.failure_threshold == 0 {
+            return;
+        }
+        
+        inner.failures.push_back(now);
+        while inner.failures.front().is_some_and(|t| now.duration_since(*t) > self.config.window) {
+            inner.failures.pop_front();
+        }
+        if inner.failures.len() >= self.config.failure_threshold as usize {
+            inner.failures.clear();
+            inner.opened_at = Some(now);
+            metrics().record_circuit_trip(self.venue.as_str());
+            self.transition(&mut inner, BreakerState::Open);
+        }
+    }
+    
+    /// Record the outcome of an allowed call
+    pub fn record<T, E>(&self, result: &Result<T, E>) {
+        match result {
+            Ok(_) => self.record_success(),
+            Err(_) => self.record_failure(),
+        }
+    }
+    
+    fn cooldown_over(&self, inner: &BreakerInner) -> bool {
+        inner.opened_at.is_none_or(|t| t.elapsed() >= self.config.cooldown)
+    }
+    
+    fn transition(&self, inner: &mut BreakerInner, state: BreakerState) {
+        let previous = inner.state;
+        inner.state = state;
+        metrics().set_circuit_state(self.venue.as_str(), state.metric_value());
+        match state {
+            BreakerState::Open if previous == BreakerState::HalfOpen => warn!(
+                "🔌 {} circuit re-opened: trial call failed, retrying in {:?}",
+                self.venue, self.config.cooldown
+            ),
+            BreakerState::Open => warn!(
+                "🔌 {} circuit OPEN after {} consecutive failures, pausing calls for {:?}",
+                self.venue, self.config.failure_threshold, self.config.cooldown
+            ),
+            BreakerState::HalfOpen => info!("🔌 {} circuit half-open, sending a trial call", self.venue),
+            BreakerState::Closed => info!("🔌 {} circuit closed, venue recovered", self.venue),
+        }
+    }
+}
+
+/// One breaker per venue
+pub struct CircuitBreakers {
+    breakers: Vec<Arc<CircuitBreaker>>,
+}
+
+impl CircuitBreakers {
+    pub fn new(config: BreakerConfig) -> Self {
+        Self {
+            breakers: Venue::ALL.iter().map(|venue| Arc::new(CircuitBreaker::new(*venue, config))).collect(),
+        }
+    }
+    
+    /// Breaker for a venue
+    pub fn get(&self, venue: Venue) -> &Arc<CircuitBreaker> {
+        self.breakers.iter()
+            .find(|breaker| breaker.venue() == venue)
+            .expect("every venue has a breaker")
+    }
+    
+    pub fn is_open(&self, venue: Venue) -> bool {
+        self.get(venue).is_open()
+    }
+}
+
+impl Default for CircuitBreakers {
+    fn default() -> Self {
+        Self::new(BreakerConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn breaker(cooldown: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(Venue::Jupiter, BreakerConfig {
+            failure_threshold: 3,
+            window: Duration::from_secs(60),
+            cooldown,
+        })
+    }
+    
+    fn state(breaker: &CircuitBreaker) -> BreakerState {
+        breaker.inner.lock().state
+    }
+    
+    #[test]
+    fn test_trips_after_consecutive_failures() {
+        let breaker = breaker(Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(state(&breaker), BreakerState::Closed);
+        
+        breaker.record_failure();
+        assert_eq!(state(&breaker), BreakerState::Open);
+        assert!(breaker.is_open());
+        assert!(!breaker.allow());
+    }
+    
+    #[test]
+    fn test_half_open_after_cooldown() {
+        let breaker = breaker(Duration::ZERO);
+        for _ in 0..3 {
+            breaker.record_failure();
+        }
+        
+        // One trial call at a time
+        assert!(breaker.allow());
+        assert_eq!(state(&breaker), BreakerState::HalfOpen);
+        assert!(!breaker.allow());
+        breaker.record_failure();
+        assert_eq!(state(&breaker), BreakerState::Open);
+        
+        assert!(breaker.allow());
+        breaker.record_success();
+        assert_eq!(state(&breaker), BreakerState::Closed);
+        assert!(breaker.allow());
+    }
+    
+    #[test]
+    fn test_zero_threshold_never_trips() {
+        let breakers = CircuitBreakers::new(BreakerConfig { failure_threshold: 0, ..Default::default() });
+        for _ in 0..100 {
+            breakers.get(Venue::Rpc).record_failure();
+        }
+        assert!(!breakers.is_open(Venue::Rpc));
+    }
+}
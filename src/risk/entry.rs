@@ -0,0 +1,123 @@
+//! Entry gating: skip or shrink copy buys that arrive too late
+
+use anyhow::Result;
+use std::str::FromStr;
+
+/// What to do when the price has run up past the limit since the target bought
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PriceMoveAction {
+    /// Do not buy
+    #[default]
+    Skip,
+    /// Buy less, in proportion to the move
+    Resize,
+}
+
+impl FromStr for PriceMoveAction {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "skip" => Ok(Self::Skip),
+            "resize" => Ok(Self::Resize),
+            other => anyhow::bail!("Unknown price move action '{}' (expected skip or resize)", other),
+        }
+    }
+}
+
+/// Limits on how late a copy buy may be; zero disables a limit
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EntryGate {
+    /// Maximum rise (percent) of the current price over the target's entry price
+    pub max_price_move_pct: f64,
+    /// Maximum slots between the target's transaction and our send
+    pub max_slot_lag: u64,
+    /// What happens when the price limit is exceeded
+    pub price_move_action: PriceMoveAction,
+}
+
+/// Outcome of gating a copy buy
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryDecision {
+    Proceed,
+    /// Buy this much SOL instead
+    Resize { buy_sol: f64, price_move_pct: f64 },
+    Skip(String),
+}
+
+impl EntryGate {
+    pub fn is_enabled(&self) -> bool {
+        self.checks_price() || self.max_slot_lag > 0
+    }
+    
+    /// Whether prices need to be fetched
+    pub fn checks_price(&self) -> bool {
+        self.max_price_move_pct > 0.0
+    }
+    
+    /// Gate a buy of `buy_sol` given what could be fetched
+    /// Prices are lamports per raw token; a missing figure skips its check.
+    pub fn evaluate(
+        &self,
+        buy_sol: f64,
+        target_slot: u64,
+        current_slot: Option<u64>,
+        target_price: Option<f64>,
+        current_price: Option<f64>,
+    ) -> EntryDecision {
+        if let Some(current_slot) = current_slot {
+            let lag = current_slot.saturating_sub(target_slot);
+            if self.max_slot_lag > 0 && lag > self.max_slot_lag {
+                return EntryDecision::Skip(format!("{} slots behind the target (max {})", lag, self.max_slot_lag));
+            }
+        }
+        
+        let (Some(target_price), Some(current_price)) = (target_price, current_price) else {
+            return EntryDecision::Proceed;
+        };
+        if !self.checks_price() || target_price <= 0.0 || current_price <= 0.0 {
+            return EntryDecision::Proceed;
+        }
+        let price_move_pct = (current_price / target_price - 1.0) * 100.0;
+        if price_move_pct <= self.max_price_move_pct {
+            return EntryDecision::Proceed;
+        }
+        
+        match self.price_move_action {
+            PriceMoveAction::Skip => EntryDecision::Skip(format!(
+                "price up {:.1}% since the target's entry (max {:.1}%)",
+                price_move_pct, self.max_price_move_pct
+            )),
+            PriceMoveAction::Resize => EntryDecision::Resize {
+                buy_sol: buy_sol * target_price / current_price,
+                price_move_pct,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_entry_gate() {
+        let gate = EntryGate { max_price_move_pct: 25.0, max_slot_lag: 5, price_move_action: PriceMoveAction::Skip };
+        
+        assert_eq!(gate.evaluate(1.0, 100, Some(104), Some(1.0), Some(1.2)), EntryDecision::Proceed);
+        assert!(matches!(gate.evaluate(1.0, 100, Some(106), Some(1.0), Some(1.0)), EntryDecision::Skip(_)));
+        assert!(matches!(gate.evaluate(1.0, 100, Some(101), Some(1.0), Some(1.5)), EntryDecision::Skip(_)));
+        
+        // Unknown figures skip their check; price drops are fine
+        assert_eq!(gate.evaluate(1.0, 100, None, None, Some(9.0)), EntryDecision::Proceed);
+        assert_eq!(gate.evaluate(1.0, 100, Some(101), Some(2.0), Some(1.0)), EntryDecision::Proceed);
+        
+        let gate = EntryGate { price_move_action: PriceMoveAction::Resize, ..gate };
+        assert_eq!(
+            gate.evaluate(1.0, 100, Some(101), Some(1.0), Some(2.0)),
+            EntryDecision::Resize { buy_sol: 0.5, price_move_pct: 100.0 }
+        );
+        
+        assert!(!EntryGate::default().is_enabled());
+    }
+}
@@ -0,0 +1,169 @@
+//! Risk limits and the checks behind them
+
+use std::fmt;
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Limits on new entries; zero disables a limit
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RiskLimits {
+    /// Maximum concurrent open positions
+    pub max_open_positions: usize,
+    /// Maximum SOL invested across all open positions
+    pub max_total_exposure_sol: f64,
+    /// Maximum SOL invested in a single token
+    pub max_token_exposure_sol: f64,
+    /// Realized loss (SOL, since UTC midnight) that stops new entries for the day
+    pub daily_loss_limit_sol: f64,
+    /// Maximum copy buys in any 60 second window
+    pub max_buys_per_minute: u32,
+    /// SOL the buying wallet must keep after a buy to pay for sells
+    pub min_sol_reserve: f64,
+}
+
+/// Portfolio figures a buy is checked against
+#[derive(Debug, Clone, Default)]
+pub struct RiskSnapshot {
+    pub open_positions: usize,
+    /// Lamports invested across open positions
+    pub total_invested: u64,
+    /// Lamports already invested in the token being bought
+    pub token_invested: u64,
+    /// Realized PnL in lamports since UTC midnight
+    pub daily_pnl: i64,
+    /// Copy buys sent in the last 60 seconds
+    pub recent_buys: usize,
+    /// Balance of the buying wallet (None when the reserve check is disabled)
+    pub wallet_balance: Option<u64>,
+}
+
+/// A limit that a buy would break
+#[derive(Debug, Clone, PartialEq)]
+pub enum RiskBreach {
+    MaxOpenPositions { open: usize, limit: usize },
+    TotalExposure { exposure_sol: f64, limit_sol: f64 },
+    TokenExposure { exposure_sol: f64, limit_sol: f64 },
+    DailyLoss { loss_sol: f64, limit_sol: f64 },
+    BuyRate { buys: usize, limit: u32 },
+    SolReserve { balance_sol: f64, reserve_sol: f64 },
+}
+
+impl RiskBreach {
+    /// Name of the breached limit (its config key)
+    pub fn limit(&self) -> &'static str {
+        match self {
+            RiskBreach::MaxOpenPositions { .. } => "max_open_positions",
+            RiskBreach::TotalExposure { .. } => "max_total_exposure_sol",
+            RiskBreach::TokenExposure { .. } => "max_token_exposure_sol",
+            RiskBreach::DailyLoss { .. } => "daily_loss_limit_sol",
+            RiskBreach::BuyRate { .. } => "max_buys_per_minute",
+            RiskBreach::SolReserve { .. } => "min_sol_reserve",
+        }
+    }
+}
+
+impl fmt::Display for RiskBreach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RiskBreach::MaxOpenPositions { open, limit } => write!(f, "{} open positions (limit {})", open, limit),
+            RiskBreach::TotalExposure { exposure_sol, limit_sol } => {
+                write!(f, "{:.4} SOL at risk after this buy (limit {} SOL)", exposure_sol, limit_sol)
+            }
+            RiskBreach::TokenExposure { exposure_sol, limit_sol } => {
+                write!(f, "{:.4} SOL in this token after this buy (limit {} SOL)", exposure_sol, limit_sol)
+            }
+            RiskBreach::DailyLoss { loss_sol, limit_sol } => {
+                write!(f, "{:.4} SOL realized loss today (limit {} SOL)", loss_sol, limit_sol)
+            }
+            RiskBreach::BuyRate { buys, limit } => write!(f, "{} buys in the last minute (limit {})", buys, limit),
+            RiskBreach::SolReserve { balance_sol, reserve_sol } => {
+                write!(f, "wallet would keep {:.4} SOL after this buy (reserve {} SOL)", balance_sol, reserve_sol)
+            }
+        }
+    }
+}
+
+impl RiskLimits {
+    /// Whether the reserve check needs the wallet balance
+    pub fn needs_balance(&self) -> bool {
+        self.min_sol_reserve > 0.0
+    }
+    
+    /// Check a buy of `buy_lamports`; returns the first limit it would break
+    pub fn check(&self, snapshot: &RiskSnapshot, buy_lamports: u64) -> Option<RiskBreach> {
+        let sol = |lamports: i128| lamports as f64 / LAMPORTS_PER_SOL;
+        
+        if self.daily_loss_limit_sol > 0.0 && -sol(snapshot.daily_pnl as i128) >= self.daily_loss_limit_sol {
+            return Some(RiskBreach::DailyLoss {
+                loss_sol: -sol(snapshot.daily_pnl as i128),
+                limit_sol: self.daily_loss_limit_sol,
+            });
+        }
+        if self.max_open_positions > 0 && snapshot.open_positions >= self.max_open_positions {
+            return Some(RiskBreach::MaxOpenPositions { open: snapshot.open_positions, limit: self.max_open_positions });
+        }
+        if self.max_buys_per_minute > 0 && snapshot.recent_buys >= self.max_buys_per_minute as usize {
+            return Some(RiskBreach::BuyRate { buys: snapshot.recent_buys, limit: self.max_buys_per_minute });
+        }
+        
+        let total = sol(snapshot.total_invested as i128 + buy_lamports as i128);
+        if self.max_total_exposure_sol > 0.0 && total > self.max_total_exposure_sol {
+            return Some(RiskBreach::TotalExposure { exposure_sol: total, limit_sol: self.max_total_exposure_sol });
+        }
+        let token = sol(snapshot.token_invested as i128 + buy_lamports as i128);
+        if self.max_token_exposure_sol > 0.0 && token > self.max_token_exposure_sol {
+            return Some(RiskBreach::TokenExposure { exposure_sol: token, limit_sol: self.max_token_exposure_sol });
+        }
+        
+        if let (true, Some(balance)) = (self.needs_balance(), snapshot.wallet_balance) {
+            let remaining = sol(balance as i128 - buy_lamports as i128);
+            if remaining < self.min_sol_reserve {
+                return Some(RiskBreach::SolReserve { balance_sol: remaining, reserve_sol: self.min_sol_reserve });
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const SOL: u64 = 1_000_000_000;
+    
+    #[test]
+    fn test_no_limits_allows_everything() {
+        let snapshot = RiskSnapshot {
+            open_positions: 100,
+            total_invested: 1_000 * SOL,
+            daily_pnl: -(50 * SOL as i64),
+            recent_buys: 50,
+            ..Default::default()
+        };
+        assert_eq!(RiskLimits::default().check(&snapshot, SOL), None);
+    }
+    
+    #[test]
+    fn test_each_limit() {
+        let limits = RiskLimits {
+            max_open_positions: 3,
+            max_total_exposure_sol: 1.0,
+            max_token_exposure_sol: 0.3,
+            daily_loss_limit_sol: 0.5,
+            max_buys_per_minute: 2,
+            min_sol_reserve: 0.05,
+        };
+        let buy = SOL / 10;
+        let ok = RiskSnapshot { wallet_balance: Some(SOL), ..Default::default() };
+        assert_eq!(limits.check(&ok, buy), None);
+        
+        let check = |snapshot: RiskSnapshot| limits.check(&snapshot, buy).map(|b| b.limit());
+        assert_eq!(check(RiskSnapshot { open_positions: 3, ..ok.clone() }), Some("max_open_positions"));
+        assert_eq!(check(RiskSnapshot { total_invested: 95 * SOL / 100, ..ok.clone() }), Some("max_total_exposure_sol"));
+        assert_eq!(check(RiskSnapshot { token_invested: 25 * SOL / 100, ..ok.clone() }), Some("max_token_exposure_sol"));
+        assert_eq!(check(RiskSnapshot { daily_pnl: -(SOL as i64) / 2, ..ok.clone() }), Some("daily_loss_limit_sol"));
+        assert_eq!(check(RiskSnapshot { daily_pnl: SOL as i64, ..ok.clone() }), None);
+        assert_eq!(check(RiskSnapshot { recent_buys: 2, ..ok.clone() }), Some("max_buys_per_minute"));
+        assert_eq!(check(RiskSnapshot { wallet_balance: Some(SOL / 8), ..ok.clone() }), Some("min_sol_reserve"));
+    }
+}
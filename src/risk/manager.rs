@@ -0,0 +1,183 @@
+//! Risk manager consulted before every copy buy
+
+use chrono::Utc;
+use parking_lot::Mutex;
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::notify::{NotificationDispatcher, NotifyEvent};
+use crate::state::{StateManager, TradeRecordType};
+use super::{RiskBreach, RiskLimits, RiskSnapshot};
+
+/// Window for `max_buys_per_minute`
+const BUY_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Blocks new entries while a limit is breached; exits are never checked
+pub struct RiskManager {
+    limits: RiskLimits,
+    state: Arc<StateManager>,
+    notifier: Arc<NotificationDispatcher>,
+    rpc_client: AsyncRpcClient,
+    /// Send times of recent copy buys
+    recent_buys: Mutex<VecDeque<Instant>>,
+    /// Limit currently pausing entries (reported once until it clears)
+    breached: Mutex<Option<&'static str>>,
+}
+
+impl RiskManager {
+    pub fn new(
+        limits: RiskLimits,
+        state: Arc<StateManager>,
+        notifier: Arc<NotificationDispatcher>,
+        rpc_url: String,
+    ) -> Self {
+        Self {
+            limits,
+            state,
+            notifier,
+            rpc_client: AsyncRpcClient::new(rpc_url),
+            recent_buys: Mutex::new(VecDeque::new()),
+            breached: Mutex::new(None),
+        }
+    }
+    
+    /// Check a buy of `buy_lamports` of `token_mint` from `wallet`
+    /// A breach pauses entries and is notified once; the pause lifts as soon as a buy passes again.
+    pub async fn check_buy(&self, token_mint: &Pubkey, wallet: &Pubkey, buy_lamports: u64) -> Result<(), RiskBreach> {
+        let wallet_balance = if self.limits.needs_balance() {
+            match self.rpc_client.get_balance(wallet).await {
+                Ok(balance) => Some(balance),
+                Err(e) => {
+                    warn!("⚠️ Could not read balance of {} for the reserve check: {}", wallet, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        
+        let snapshot = self.snapshot(token_mint, wallet_balance);
+        match self.limits.check(&snapshot, buy_lamports) {
+            Some(breach) => {
+                self.report_breach(&breach);
+                Err(breach)
+            }
+            None => {
+                if let Some(limit) = self.breached.lock().take() {
+                    info!("🛡️ Risk limit {} cleared, entries resumed", limit);
+                }
+                Ok(())
+            }
+        }
+    }
+    
+    /// Count a copy buy that was sent
+    pub fn record_buy(&self) {
+        let mut recent = self.recent_buys.lock();
+        recent.push_back(Instant::now());
+        Self::prune(&mut recent);
+    }
+    
+    /// Current portfolio figures from the state manager
+    fn snapshot(&self, token_mint: &Pubkey, wallet_balance: Option<u64>) -> RiskSnapshot {
+        // Positions stay in the state manager until fully sold (amount is 0 until the buy lands)
+        let positions = self.state.get_all_positions();
+        
+        let midnight = Utc::now().date_naive().and_hms_opt(0, 0, 0).map(|t| t.and_utc());
+        let daily_pnl = self.state.get_trade_history().iter()
+            .filter(|t| t.trade_type != TradeRecordType::Buy)
+            .filter(|t| midnight.is_none_or(|m| t.timestamp >= m))
+            .filter_map(|t| t.pnl)
+            .sum();
+        
+        let recent_buys = {
+            let mut recent = self.recent_buys.lock();
+            Self::prune(&mut recent);
+            recent.len()
+        };
+        
+        RiskSnapshot {
+            open_positions: self.state.open_positions_count(),
+            total_invested: positions.iter().map(|p| p.invested_sol).sum(),
+            token_invested: positions.iter().filter(|p| p.token_mint == *token_mint).map(|p| p.invested_sol).sum(),
+            daily_pnl,
+            recent_buys,
+            wallet_balance,
+        }
+    }
+    
+    fn prune(recent: &mut VecDeque<Instant>) {
+        while recent.front().is_some_and(|t| t.elapsed() > BUY_RATE_WINDOW) {
+            recent.pop_front();
+        }
+    }
+    
+    /// Log and notify a breach the first time it pauses entries
+    fn report_breach(&self, breach: &RiskBreach) {
+        let mut breached = self.breached.lock();
+        if *breached == Some(breach.limit()) {
+            info!("🛡️ Entry skipped, risk limit still breached: {}", breach);
+            return;
+        }
+        *breached = Some(breach.limit());
+        
+        warn!("🛡️ Risk limit {} breached, pausing entries: {}", breach.limit(), breach);
+        self.notifier.notify(NotifyEvent::RiskLimit {
+            limit: breach.limit().to_string(),
+            detail: breach.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Position;
+    
+    fn manager(limits: RiskLimits, state: Arc<StateManager>) -> RiskManager {
+        RiskManager::new(limits, state, Arc::new(NotificationDispatcher::disabled()), "http://127.0.0.1:1".to_string())
+    }
+    
+    #[tokio::test]
+    async fn test_pauses_and_resumes_entries() {
+        let state = Arc::new(StateManager::new());
+        let limits = RiskLimits { max_open_positions: 1, max_buys_per_minute: 2, ..Default::default() };
+        let risk = manager(limits, state.clone());
+        let (mint, wallet) = (Pubkey::new_unique(), Pubkey::new_unique());
+        
+        assert!(risk.check_buy(&mint, &wallet, 1).await.is_ok());
+        state.open_position(Position::new(mint, 0, 100, "t".to_string(), "o".to_string()));
+        let breach = risk.check_buy(&Pubkey::new_unique(), &wallet, 1).await.unwrap_err();
+        assert_eq!(breach.limit(), "max_open_positions");
+        assert_eq!(*risk.breached.lock(), Some("max_open_positions"));
+        
+        state.close_position(&mint, 50, TradeRecordType::SellManual, "s".to_string());
+        assert!(risk.check_buy(&Pubkey::new_unique(), &wallet, 1).await.is_ok());
+        assert_eq!(*risk.breached.lock(), None);
+        
+        risk.record_buy();
+        risk.record_buy();
+        let breach = risk.check_buy(&Pubkey::new_unique(), &wallet, 1).await.unwrap_err();
+        assert_eq!(breach.limit(), "max_buys_per_minute");
+    }
+    
+    #[test]
+    fn test_snapshot_counts_todays_losses() {
+        let state = Arc::new(StateManager::new());
+        let mint = Pubkey::new_unique();
+        state.open_position(Position::new(mint, 10, 300, "t".to_string(), "o".to_string()));
+        state.close_position(&mint, 100, TradeRecordType::SellCopyExit, "s".to_string());
+        let other = Pubkey::new_unique();
+        state.open_position(Position::new(other, 10, 500, "t".to_string(), "o".to_string()));
+        
+        let snapshot = manager(RiskLimits::default(), state).snapshot(&other, None);
+        assert_eq!(snapshot.open_positions, 1);
+        assert_eq!(snapshot.total_invested, 500);
+        assert_eq!(snapshot.token_invested, 500);
+        assert_eq!(snapshot.daily_pnl, -200);
+    }
+}
@@ -0,0 +1,12 @@
+//! Portfolio-wide risk limits and entry gating consulted before every copy buy, and
+//! per-venue circuit breakers
+
+pub mod breaker;
+pub mod entry;
+pub mod limits;
+pub mod manager;
+
+pub use breaker::*;
+pub use entry::*;
+pub use limits::*;
+pub use manager::*;
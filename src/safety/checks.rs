@@ -0,0 +1,266 @@
+//! Rug and honeypot checks on a mint, before entry and while holding it
+
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::{
+    transfer_fee::TransferFeeConfig, transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions,
+};
+use spl_token_2022::state::Mint;
+use std::fmt;
+use std::time::Duration;
+
+use super::{CreatorMove, CreatorMoveKind};
+
+/// Safety settings; zero disables a percentage limit
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafetyConfig {
+    /// Check mints and simulate a round trip before every copy buy
+    pub entry_checks: bool,
+    /// SOL spent by the simulated buy
+    pub probe_sol: f64,
+    /// Maximum SOL lost over the simulated buy and sell, in percent
+    pub max_round_trip_tax_pct: f64,
+    /// Maximum Token-2022 transfer fee
+    pub max_transfer_fee_bps: u16,
+    /// Watch held mints for rugs and exit when one is seen
+    pub hold_watch: bool,
+    pub watch_interval: Duration,
+    /// Maximum drop of the SOL side of the pool or bonding curve since entry, in percent
+    /// Only read on Pump.fun, PumpSwap and native routes; other Jupiter buys are not checked.
+    pub max_liquidity_drop_pct: f64,
+    /// Maximum share of supply the creator may sell while we hold, in percent
+    pub max_creator_sell_pct: f64,
+    /// Exit when the creator's wallet sells or transfers a held token
+    pub creator_exit: bool,
+    /// Smallest creator sell or transfer that triggers the exit, in percent of supply
+    pub creator_exit_min_pct: f64,
+}
+
+impl Default for SafetyConfig {
+    fn default() -> Self {
+        Self {
+            entry_checks: false,
+            probe_sol: 0.001,
+            max_round_trip_tax_pct: 20.0,
+            max_transfer_fee_bps: 0,
+            hold_watch: false,
+            watch_interval: Duration::from_secs(10),
+            max_liquidity_drop_pct: 50.0,
+            max_creator_sell_pct: 5.0,
+            creator_exit: false,
+            creator_exit_min_pct: 1.0,
+        }
+    }
+}
+
+/// Mint settings that let its owner take the token back or tax it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MintRisks {
+    pub supply: u64,
+    pub mint_authority: Option<Pubkey>,
+    pub freeze_authority: Option<Pubkey>,
+    /// Token-2022 transfer hook program
+    pub transfer_hook: Option<Pubkey>,
+    /// Highest of the current and scheduled Token-2022 transfer fees
+    pub transfer_fee_bps: u16,
+}
+
+impl MintRisks {
+    /// Read an SPL Token or Token-2022 mint account
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let state = StateWithExtensions::<Mint>::unpack(data).ok()?;
+        let transfer_fee_bps = state.get_extension::<TransferFeeConfig>()
+            .map(|config| {
+                u16::from(config.older_transfer_fee.transfer_fee_basis_points)
+                    .max(u16::from(config.newer_transfer_fee.transfer_fee_basis_points))
+            })
+            .unwrap_or_default();
+        Some(Self {
+            supply: state.base.supply,
+            mint_authority: state.base.mint_authority.into(),
+            freeze_authority: state.base.freeze_authority.into(),
+            transfer_hook: state.get_extension::<TransferHook>().ok().and_then(|hook| hook.program_id.into()),
+            transfer_fee_bps,
+        })
+    }
+}
+
+/// Something about a token that makes holding it unsafe
+#[derive(Debug, Clone, PartialEq)]
+pub enum SafetyIssue {
+    FreezeAuthority(Pubkey),
+    TransferHook(Pubkey),
+    TransferFee { bps: u16 },
+    /// The simulated sell failed or found no route
+    Unsellable(String),
+    RoundTripTax { tax_pct: f64 },
+    /// Mint or freeze authority, transfer hook or fee changed while held
+    MintChanged(String),
+    LiquidityRemoved { drop_pct: f64 },
+    CreatorDump { sold_pct: f64 },
+    CreatorTransfer { moved_pct: f64 },
+}
+
+impl fmt::Display for SafetyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SafetyIssue::FreezeAuthority(authority) => write!(f, "freeze authority {} is active", authority),
+            SafetyIssue::TransferHook(program) => write!(f, "transfer hook {} is set", program),
+            SafetyIssue::TransferFee { bps } => write!(f, "{} bps transfer fee", bps),
+            SafetyIssue::Unsellable(reason) => write!(f, "not sellable: {}", reason),
+            SafetyIssue::RoundTripTax { tax_pct } => write!(f, "{:.1}% lost on a buy and sell", tax_pct),
+            SafetyIssue::MintChanged(change) => write!(f, "mint changed: {}", change),
+            SafetyIssue::LiquidityRemoved { drop_pct } => write!(f, "liquidity down {:.1}%", drop_pct),
+            SafetyIssue::CreatorDump { sold_pct } => write!(f, "creator sold {:.1}% of supply", sold_pct),
+            SafetyIssue::CreatorTransfer { moved_pct } => write!(f, "creator moved {:.1}% of supply", moved_pct),
+        }
+    }
+}
+
+/// What a held token looked like at one check
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HoldSnapshot {
+    pub mint: MintRisks,
+    /// Lamports on the SOL side of the token's pool or bonding curve
+    pub liquidity: Option<u64>,
+    /// Raw tokens held by the creator
+    pub creator_balance: Option<u64>,
+}
+
+impl SafetyConfig {
+    /// Issues with a mint that rule out buying it
+    pub fn mint_issues(&self, mint: &MintRisks) -> Vec<SafetyIssue> {
+        let mut issues = Vec::new();
+        if let Some(authority) = mint.freeze_authority {
+            issues.push(SafetyIssue::FreezeAuthority(authority));
+        }
+        if let Some(program) = mint.transfer_hook {
+            issues.push(SafetyIssue::TransferHook(program));
+        }
+        if mint.transfer_fee_bps > self.max_transfer_fee_bps {
+            issues.push(SafetyIssue::TransferFee { bps: mint.transfer_fee_bps });
+        }
+        issues
+    }
+    
+    /// Issue with a simulated round trip of `sol_in` lamports that came back as `sol_out`
+    pub fn round_trip_issue(&self, sol_in: u64, sol_out: u64) -> Option<SafetyIssue> {
+        if sol_in == 0 {
+            return None;
+        }
+        let tax_pct = sol_in.saturating_sub(sol_out) as f64 / sol_in as f64 * 100.0;
+        (self.max_round_trip_tax_pct > 0.0 && tax_pct > self.max_round_trip_tax_pct)
+            .then_some(SafetyIssue::RoundTripTax { tax_pct })
+    }
+    
+    /// First sign of a rug between the snapshot taken at entry and the current one
+    pub fn hold_issue(&self, entry: &HoldSnapshot, current: &HoldSnapshot) -> Option<SafetyIssue> {
+        let (before, after) = (&entry.mint, &current.mint);
+        if after.freeze_authority.is_some() && after.freeze_authority != before.freeze_authority {
+            return Some(SafetyIssue::MintChanged(format!("freeze authority set to {}", after.freeze_authority?)));
+        }
+        if after.mint_authority.is_some() && after.mint_authority != before.mint_authority {
+            return Some(SafetyIssue::MintChanged(format!("mint authority set to {}", after.mint_authority?)));
+        }
+        if after.transfer_hook.is_some() && after.transfer_hook != before.transfer_hook {
+            return Some(SafetyIssue::MintChanged(format!("transfer hook set to {}", after.transfer_hook?)));
+        }
+        if after.transfer_fee_bps > before.transfer_fee_bps.max(self.max_transfer_fee_bps) {
+            return Some(SafetyIssue::MintChanged(format!("transfer fee raised to {} bps", after.transfer_fee_bps)));
+        }
+        
+        if let (Some(before), Some(after)) = (entry.liquidity, current.liquidity) {
+            let drop_pct = before.saturating_sub(after) as f64 / before.max(1) as f64 * 100.0;
+            if self.max_liquidity_drop_pct > 0.0 && drop_pct > self.max_liquidity_drop_pct {
+                return Some(SafetyIssue::LiquidityRemoved { drop_pct });
+            }
+        }
+        
+        if let (Some(before), Some(after)) = (entry.creator_balance, current.creator_balance) {
+            let sold_pct = before.saturating_sub(after) as f64 / current.mint.supply.max(1) as f64 * 100.0;
+            if self.max_creator_sell_pct > 0.0 && sold_pct > self.max_creator_sell_pct {
+                return Some(SafetyIssue::CreatorDump { sold_pct });
+            }
+        }
+        None
+    }
+    
+    /// Issue with a creator sell or transfer of a held token with `supply` raw tokens, if it is
+    /// large enough to exit on
+    pub fn creator_move_issue(&self, creator_move: &CreatorMove, supply: u64) -> Option<SafetyIssue> {
+        let pct = creator_move.amount as f64 / supply.max(1) as f64 * 100.0;
+        if creator_move.amount == 0 || pct < self.creator_exit_min_pct {
+            return None;
+        }
+        Some(match creator_move.kind {
+            CreatorMoveKind::Sell => SafetyIssue::CreatorDump { sold_pct: pct },
+            CreatorMoveKind::Transfer => SafetyIssue::CreatorTransfer { moved_pct: pct },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::program_option::COption;
+    use spl_token_2022::extension::{ExtensionType, StateWithExtensionsMut};
+    
+    #[test]
+    fn test_mint_issues() {
+        let (authority, hook) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let extensions = [ExtensionType::TransferFeeConfig, ExtensionType::TransferHook];
+        let mut data = vec![0; ExtensionType::try_calculate_account_len::<Mint>(&extensions).unwrap()];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        state.base = Mint {
+            supply: 1_000,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::Some(authority),
+            ..Default::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        state.init_extension::<TransferFeeConfig>(true).unwrap().newer_transfer_fee.transfer_fee_basis_points = 300.into();
+        state.init_extension::<TransferHook>(true).unwrap().program_id = Some(hook).try_into().unwrap();
+        
+        let mint = MintRisks::decode(&data).unwrap();
+        assert_eq!((mint.supply, mint.mint_authority, mint.transfer_fee_bps), (1_000, None, 300));
+        let config = SafetyConfig { max_transfer_fee_bps: 100, ..Default::default() };
+        assert_eq!(
+            config.mint_issues(&mint),
+            vec![
+                SafetyIssue::FreezeAuthority(authority),
+                SafetyIssue::TransferHook(hook),
+                SafetyIssue::TransferFee { bps: 300 },
+            ]
+        );
+        
+        assert_eq!(config.round_trip_issue(1_000, 900), None);
+        assert_eq!(config.round_trip_issue(1_000, 500), Some(SafetyIssue::RoundTripTax { tax_pct: 50.0 }));
+    }
+    
+    #[test]
+    fn test_hold_issue() {
+        let config = SafetyConfig::default();
+        let entry = HoldSnapshot {
+            mint: MintRisks { supply: 1_000_000, ..Default::default() },
+            liquidity: Some(100_000),
+            creator_balance: Some(200_000),
+        };
+        assert_eq!(config.hold_issue(&entry, &entry), None);
+        
+        let frozen = HoldSnapshot {
+            mint: MintRisks { freeze_authority: Some(Pubkey::new_unique()), ..entry.mint.clone() },
+            ..entry.clone()
+        };
+        assert!(matches!(config.hold_issue(&entry, &frozen), Some(SafetyIssue::MintChanged(_))));
+        
+        let pulled = HoldSnapshot { liquidity: Some(40_000), ..entry.clone() };
+        assert_eq!(config.hold_issue(&entry, &pulled), Some(SafetyIssue::LiquidityRemoved { drop_pct: 60.0 }));
+        
+        let dumped = HoldSnapshot { creator_balance: Some(100_000), ..entry.clone() };
+        assert_eq!(config.hold_issue(&entry, &dumped), Some(SafetyIssue::CreatorDump { sold_pct: 10.0 }));
+        // A small sale stays under the limit
+        let trimmed = HoldSnapshot { creator_balance: Some(190_000), ..entry.clone() };
+        assert_eq!(config.hold_issue(&entry, &trimmed), None);
+    }
+}
@@ -0,0 +1,77 @@
+//! Creator wallet sells and transfers of held tokens, used as an exit trigger
+
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+use crate::decoder::{pump_events, PumpEvent, WSOL_MINT};
+
+/// How the creator let go of a held token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreatorMoveKind {
+    Sell,
+    Transfer,
+}
+
+/// Raw tokens of a held mint the creator sold or sent away in one transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreatorMove {
+    pub mint: Pubkey,
+    pub kind: CreatorMoveKind,
+    pub amount: u64,
+}
+
+/// Creator sells of `held` mints in a transaction's Pump.fun trade events
+pub fn creator_sells(logs: &[String], creator: &Pubkey, held: &[Pubkey]) -> Vec<CreatorMove> {
+    pump_events(logs)
+        .into_iter()
+        .filter_map(|event| match event {
+            PumpEvent::Trade(trade) if trade.user == *creator && !trade.is_buy && held.contains(&trade.mint) => {
+                Some(CreatorMove { mint: trade.mint, kind: CreatorMoveKind::Sell, amount: trade.token_amount })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Held mints leaving the creator's accounts, from its SOL and token balance changes
+/// SOL (or WSOL) coming back makes it a sell on any venue, otherwise it is a transfer.
+pub fn creator_outflows(sol_delta: i128, token_deltas: &HashMap<String, i128>, held: &[Pubkey]) -> Vec<CreatorMove> {
+    let sol_delta = sol_delta + token_deltas.get(WSOL_MINT).copied().unwrap_or(0);
+    let kind = if sol_delta > 0 { CreatorMoveKind::Sell } else { CreatorMoveKind::Transfer };
+    held.iter()
+        .filter_map(|mint| {
+            let delta = *token_deltas.get(&mint.to_string())?;
+            (delta < 0).then(|| CreatorMove {
+                mint: *mint,
+                kind,
+                amount: u64::try_from(delta.unsigned_abs()).unwrap_or(u64::MAX),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safety::{SafetyConfig, SafetyIssue};
+    
+    #[test]
+    fn test_creator_outflows() {
+        let (held, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let deltas: HashMap<String, i128> = [(held.to_string(), -50_000), (other.to_string(), -10)].into();
+        
+        let sold = creator_outflows(2_000_000, &deltas, &[held]);
+        assert_eq!(sold, vec![CreatorMove { mint: held, kind: CreatorMoveKind::Sell, amount: 50_000 }]);
+        let sent = creator_outflows(-5_000, &deltas, &[held]);
+        assert_eq!(sent[0].kind, CreatorMoveKind::Transfer);
+        assert!(creator_outflows(0, &HashMap::new(), &[held]).is_empty());
+        
+        let config = SafetyConfig { creator_exit_min_pct: 1.0, ..Default::default() };
+        assert_eq!(config.creator_move_issue(&sold[0], 1_000_000), Some(SafetyIssue::CreatorDump { sold_pct: 5.0 }));
+        assert_eq!(config.creator_move_issue(&sold[0], 100_000_000), None);
+        assert_eq!(
+            config.creator_move_issue(&sent[0], 1_000_000),
+            Some(SafetyIssue::CreatorTransfer { moved_pct: 5.0 })
+        );
+    }
+}
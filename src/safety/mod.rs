@@ -0,0 +1,12 @@
+//! Rug and honeypot protection: mint and round-trip checks before entry, a watcher that
+//! flags held tokens for an emergency exit, and creator sells as an exit trigger
+
+pub mod checks;
+pub mod creator;
+pub mod round_trip;
+pub mod watcher;
+
+pub use checks::*;
+pub use creator::*;
+pub use round_trip::*;
+pub use watcher::*;
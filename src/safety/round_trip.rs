@@ -0,0 +1,335 @@
+//! Simulated buy-then-sell round trip through Jupiter, to catch tokens that cannot be sold
+
+use anyhow::{Context, Result};
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_client::rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig};
+use solana_sdk::{
+    address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
+    commitment_config::CommitmentConfig,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{v0, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::decoder::WSOL_MINT;
+use crate::grpc::send_api_request;
+use crate::metrics::{ENDPOINT_JUPITER_QUOTE, ENDPOINT_JUPITER_SWAP};
+use crate::risk::CircuitBreakers;
+use crate::swap::wsol_mint;
+use crate::token::TokenProgram;
+
+const JUPITER_QUOTE_API: &str = "https://quote-api.jup.ag/v6/quote";
+const JUPITER_SWAP_INSTRUCTIONS_API: &str = "https://quote-api.jup.ag/v6/swap-instructions";
+/// Compute budget for both swaps in one simulated transaction
+const ROUND_TRIP_COMPUTE_UNITS: u32 = 1_400_000;
+/// Accounts each leg's route may use, so both legs usually fit in one transaction
+const ROUND_TRIP_MAX_ACCOUNTS: u32 = 24;
+/// Base fee; the probe sets no compute unit price
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// How a simulated round trip went
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoundTrip {
+    /// Both swaps simulated; `sol_out` is what the sell is quoted to return
+    Sellable { sol_in: u64, sol_out: u64 },
+    /// The sell found no route or the simulation failed
+    Unsellable(String),
+}
+
+/// Buy `lamports` worth of `mint` and sell it straight back in one simulated transaction
+/// `sol_in` is the part of the buy that was sold back and `sol_out` what the simulated sell
+/// returned to the owner.
+/// Errors mean the check could not run (the buy could not be quoted, RPC down), not that the
+/// token is unsafe.
+pub async fn simulate_round_trip(
+    breakers: &CircuitBreakers,
+    rpc_client: &AsyncRpcClient,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    lamports: u64,
+) -> Result<RoundTrip> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()?;
+    let mint = mint.to_string();
+    
+    let buy_quote = quote(breakers, &client, WSOL_MINT, &mint, lamports).await
+        .context("Failed to quote the probe buy")?;
+    // Sell the least the buy may return so the sell never exceeds what was bought
+    let tokens = amount_field(&buy_quote, "otherAmountThreshold").context("Buy quote has no minimum output")?;
+    let bought = amount_field(&buy_quote, "outAmount").context("Buy quote has no output")?;
+    let sell_quote = match quote(breakers, &client, &mint, WSOL_MINT, tokens).await {
+        Ok(quote) => quote,
+        Err(e) => return Ok(RoundTrip::Unsellable(format!("no sell quote: {:#}", e))),
+    };
+    // Only the share of the buy that is sold back is charged against the sell
+    let sol_in = sold_share(lamports, tokens, bought);
+    let quoted_out = amount_field(&sell_quote, "outAmount").unwrap_or_default();
+    
+    let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(ROUND_TRIP_COMPUTE_UNITS)];
+    let mut table_keys = Vec::new();
+    for quote in [buy_quote, sell_quote] {
+        let swap = swap_instructions(breakers, &client, quote, owner).await?;
+        instructions.extend(swap.instructions);
+        table_keys.extend(swap.lookup_tables);
+    }
+    let tables = fetch_lookup_tables(rpc_client, &table_keys).await?;
+    
+    let blockhash = rpc_client.get_latest_blockhash().await?;
+    // Routes too wide for one transaction even within `maxAccounts` are judged from their quotes
+    let transaction = match round_trip_transaction(owner, &instructions, &tables, blockhash) {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            debug!("Round trip of {} not simulated ({:#}) - judged from its quotes", mint, e);
+            return Ok(RoundTrip::Sellable { sol_in, sol_out: quoted_out });
+        }
+    };
+    let fee = LAMPORTS_PER_SIGNATURE * transaction.signatures.len() as u64;
+    
+    // The SOL the sell returned is read from the owner's balances around the simulation
+    let watched = watched_accounts(owner, &Pubkey::from_str(&mint)?);
+    let before = rpc_client.get_multiple_accounts(&watched).await
+        .context("Failed to fetch the probe wallet")?
+        .iter()
+        .map(|account| account.as_ref().map_or(0, |account| account.lamports))
+        .collect::<Vec<_>>();
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(CommitmentConfig::processed()),
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: watched.iter().map(Pubkey::to_string).collect(),
+        }),
+        ..Default::default()
+    };
+    let simulation = rpc_client.simulate_transaction_with_config(&transaction, config).await
+        .context("Failed to simulate the round trip")?
+        .value;
+    if let Some(err) = simulation.err {
+        let last_log = simulation.logs.and_then(|logs| logs.last().cloned()).unwrap_or_default();
+        return Ok(RoundTrip::Unsellable(format!("simulation failed: {} {}", err, last_log)));
+    }
+    let after = simulation.accounts
+        .context("Simulation returned no accounts")?
+        .iter()
+        .map(|account| account.as_ref().map_or(0, |account| account.lamports))
+        .collect::<Vec<_>>();
+    Ok(RoundTrip::Sellable { sol_in, sol_out: sell_proceeds(lamports, &before, &after, fee) })
+}
+
+/// Lamports of a buy of `bought` raw tokens for `lamports` that paid for `sold` of them
+fn sold_share(lamports: u64, sold: u64, bought: u64) -> u64 {
+    if bought == 0 {
+        return lamports;
+    }
+    (lamports as u128 * sold.min(bought) as u128 / bought as u128) as u64
+}
+
+/// The owner and the token and wSOL accounts the round trip may open, whose lamports together
+/// hold its SOL (rent of an account the buy opens stays the owner's)
+fn watched_accounts(owner: &Pubkey, mint: &Pubkey) -> Vec<Pubkey> {
+    let mut accounts = vec![*owner, TokenProgram::Spl.associated_token_address(owner, &wsol_mint())];
+    accounts.extend(TokenProgram::ALL.map(|program| program.associated_token_address(owner, mint)));
+    accounts
+}
+
+/// SOL the sell returned, from the watched lamports before and after a round trip that spent
+/// `lamports` on the buy and `fee` on the transaction
+fn sell_proceeds(lamports: u64, before: &[u64], after: &[u64], fee: u64) -> u64 {
+    let (before, after) = (before.iter().sum::<u64>() as i128, after.iter().sum::<u64>() as i128);
+    (after - before + lamports as i128 + fee as i128).max(0) as u64
+}
+
+/// The round trip as one unsigned v0 transaction, failing when it exceeds the packet size
+fn round_trip_transaction(
+    owner: &Pubkey,
+    instructions: &[Instruction],
+    tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<VersionedTransaction> {
+    let message = v0::Message::try_compile(owner, instructions, tables, blockhash)
+        .context("round trip does not compile")?;
+    let transaction = VersionedTransaction {
+        signatures: vec![Signature::default(); message.header.num_required_signatures as usize],
+        message: VersionedMessage::V0(message),
+    };
+    let size = bincode::serialized_size(&transaction)?;
+    if size > PACKET_DATA_SIZE as u64 {
+        anyhow::bail!("round trip is {} bytes, over the {} byte transaction limit", size, PACKET_DATA_SIZE);
+    }
+    Ok(transaction)
+}
+
+/// Jupiter quote for swapping `amount` of `input_mint` into `output_mint`
+async fn quote(
+    breakers: &CircuitBreakers,
+    client: &reqwest::Client,
+    input_mint: &str,
+    output_mint: &str,
+    amount: u64,
+) -> Result<serde_json::Value> {
+    let url = format!(
+        "{}?inputMint={}&outputMint={}&amount={}&slippageBps=2500&maxAccounts={}",
+        JUPITER_QUOTE_API, input_mint, output_mint, amount, ROUND_TRIP_MAX_ACCOUNTS
+    );
+    let response = send_api_request(breakers, client.get(&url), ENDPOINT_JUPITER_QUOTE).await?;
+    if !response.status().is_success() {
+        anyhow::bail!("{}", response.text().await.unwrap_or_default());
+    }
+    Ok(response.json().await?)
+}
+
+/// Instructions and lookup tables of a Jupiter swap
+struct SwapInstructions {
+    instructions: Vec<Instruction>,
+    lookup_tables: Vec<Pubkey>,
+}
+
+/// Setup, swap and cleanup instructions for a quote (compute budget left to the caller)
+async fn swap_instructions(
+    breakers: &CircuitBreakers,
+    client: &reqwest::Client,
+    quote: serde_json::Value,
+    owner: &Pubkey,
+) -> Result<SwapInstructions> {
+    let request = serde_json::json!({
+        "quoteResponse": quote,
+        "userPublicKey": owner.to_string(),
+        "wrapAndUnwrapSol": true,
+    });
+    let response = send_api_request(breakers, client.post(JUPITER_SWAP_INSTRUCTIONS_API).json(&request), ENDPOINT_JUPITER_SWAP).await?;
+    if !response.status().is_success() {
+        anyhow::bail!("Jupiter swap instructions failed: {}", response.text().await.unwrap_or_default());
+    }
+    let body: serde_json::Value = response.json().await?;
+    
+    let mut values: Vec<&serde_json::Value> = body.get("setupInstructions")
+        .and_then(|v| v.as_array())
+        .map(|setup| setup.iter().collect())
+        .unwrap_or_default();
+    values.push(body.get("swapInstruction").context("No swap instruction")?);
+    values.extend(body.get("cleanupInstruction").filter(|v| !v.is_null()));
+    let instructions = values.into_iter()
+        .map(parse_instruction)
+        .collect::<Option<Vec<_>>>()
+        .context("Malformed Jupiter instruction")?;
+    let lookup_tables = body.get("addressLookupTableAddresses")
+        .and_then(|v| v.as_array())
+        .map(|keys| keys.iter().filter_map(|key| Pubkey::from_str(key.as_str()?).ok()).collect())
+        .unwrap_or_default();
+    Ok(SwapInstructions { instructions, lookup_tables })
+}
+
+/// Raw amount field of a Jupiter quote (amounts are strings)
+fn amount_field(quote: &serde_json::Value, field: &str) -> Option<u64> {
+    quote.get(field)?.as_str()?.parse().ok()
+}
+
+/// Instruction in Jupiter's JSON form (`programId`, `accounts`, base64 `data`)
+fn parse_instruction(value: &serde_json::Value) -> Option<Instruction> {
+    let accounts = value.get("accounts")?.as_array()?.iter()
+        .map(|account| {
+            Some(AccountMeta {
+                pubkey: Pubkey::from_str(account.get("pubkey")?.as_str()?).ok()?,
+                is_signer: account.get("isSigner")?.as_bool()?,
+                is_writable: account.get("isWritable")?.as_bool()?,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(Instruction {
+        program_id: Pubkey::from_str(value.get("programId")?.as_str()?).ok()?,
+        accounts,
+        data: BASE64.decode(value.get("data")?.as_str()?).ok()?,
+    })
+}
+
+/// Address lookup tables by key, skipping duplicates
+async fn fetch_lookup_tables(rpc_client: &AsyncRpcClient, keys: &[Pubkey]) -> Result<Vec<AddressLookupTableAccount>> {
+    let mut keys = keys.to_vec();
+    keys.sort();
+    keys.dedup();
+    let accounts = rpc_client.get_multiple_accounts(&keys).await
+        .context("Failed to fetch lookup tables")?;
+    keys.into_iter()
+        .zip(accounts)
+        .map(|(key, account)| {
+            let account = account.with_context(|| format!("Lookup table {} not found", key))?;
+            let table = AddressLookupTable::deserialize(&account.data)
+                .map_err(|e| anyhow::anyhow!("Invalid lookup table {}: {:?}", key, e))?;
+            Ok(AddressLookupTableAccount { key, addresses: table.addresses.to_vec() })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safety::{SafetyConfig, SafetyIssue};
+    
+    #[test]
+    fn test_parse_jupiter_instruction() {
+        let (program, account) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let value = serde_json::json!({
+            "programId": program.to_string(),
+            "accounts": [{ "pubkey": account.to_string(), "isSigner": false, "isWritable": true }],
+            "data": BASE64.encode([1, 2, 3]),
+        });
+        let instruction = parse_instruction(&value).unwrap();
+        assert_eq!(instruction.program_id, program);
+        assert_eq!(instruction.accounts, vec![AccountMeta::new(account, false)]);
+        assert_eq!(instruction.data, vec![1, 2, 3]);
+        
+        assert!(parse_instruction(&serde_json::json!({ "programId": "bogus" })).is_none());
+        assert_eq!(amount_field(&serde_json::json!({ "outAmount": "42" }), "outAmount"), Some(42));
+    }
+    
+    #[test]
+    fn test_round_trip_transaction_size() {
+        let owner = Pubkey::new_unique();
+        let swap = |data_len| Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![AccountMeta::new(owner, true), AccountMeta::new(Pubkey::new_unique(), false)],
+            data: vec![7; data_len],
+        };
+        let fits = [swap(64), swap(64)];
+        assert!(round_trip_transaction(&owner, &fits, &[], Hash::default()).is_ok());
+        
+        // Two legs with wide routes outgrow a packet
+        let oversized: Vec<Instruction> = (0..12).map(|_| swap(64)).collect();
+        let err = round_trip_transaction(&owner, &oversized, &[], Hash::default()).unwrap_err();
+        assert!(err.to_string().contains("transaction limit"));
+    }
+    
+    #[test]
+    fn test_round_trip_tax() {
+        let config = SafetyConfig::default();
+        let (lamports, fee, rent) = (1_000_000, 5_000, 2_039_280);
+        // The probe sells the 75% of its 1000 tokens the buy's slippage guarantees
+        let sol_in = sold_share(lamports, 750, 1_000);
+        assert_eq!(sol_in, 750_000);
+        
+        // Without pool fees the sold tokens return what they cost; the new token account's rent
+        // moves into it and the fee is the transaction's, not the token's
+        let before = [10_000_000, 0, 0, 0];
+        let after = [10_000_000 - lamports + 750_000 - fee - rent, 0, rent, 0];
+        let sol_out = sell_proceeds(lamports, &before, &after, fee);
+        assert_eq!(sol_out, 750_000);
+        assert_eq!(config.round_trip_issue(sol_in, sol_out), None);
+        
+        // A 30% sell tax shows
+        let after = [10_000_000 - lamports + 525_000 - fee - rent, 0, rent, 0];
+        let sol_out = sell_proceeds(lamports, &before, &after, fee);
+        assert_eq!(config.round_trip_issue(sol_in, sol_out), Some(SafetyIssue::RoundTripTax { tax_pct: 30.0 }));
+    }
+}
@@ -0,0 +1,183 @@
+//! Watches held mints for rugs and raises alerts that trigger an emergency exit
+//!
+//! Liquidity is read from the Pump.fun curve, the PumpSwap pool, or the pool of the native
+//! route the token was bought through. Tokens bought through Jupiter on any other venue have
+//! no liquidity reading, so only their mint and creator are watched.
+
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::mem::Discriminant;
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use super::{HoldSnapshot, MintRisks, SafetyConfig, SafetyIssue};
+use crate::decoder::pumpfun;
+use crate::state::{Position, StateManager};
+use crate::swap::{NativeSwaps, PumpSwap};
+use crate::token::TokenAccountService;
+
+/// A held token that should be sold now
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafetyAlert {
+    pub mint: Pubkey,
+    pub issue: SafetyIssue,
+}
+
+/// First snapshot of a held token, and whether its liquidity was read from the bonding curve
+struct Baseline {
+    snapshot: HoldSnapshot,
+    on_curve: bool,
+    /// Kinds of issue already alerted, each raised once while the token is held
+    alerted: Vec<Discriminant<SafetyIssue>>,
+}
+
+impl Baseline {
+    fn new(snapshot: HoldSnapshot, on_curve: bool) -> Self {
+        Self { snapshot, on_curve, alerted: Vec::new() }
+    }
+    
+    /// Whether `issue` is the first of its kind on this token, marking it alerted
+    fn first_alert(&mut self, issue: &SafetyIssue) -> bool {
+        let kind = std::mem::discriminant(issue);
+        if self.alerted.contains(&kind) {
+            return false;
+        }
+        self.alerted.push(kind);
+        true
+    }
+}
+
+/// Compares each open position against the snapshot taken when it was first seen
+pub struct HoldWatcher {
+    config: SafetyConfig,
+    rpc_client: AsyncRpcClient,
+    token_accounts: Arc<TokenAccountService>,
+    pumpswap: Arc<PumpSwap>,
+    native: Arc<NativeSwaps>,
+    state: Arc<StateManager>,
+    baselines: DashMap<Pubkey, Baseline>,
+    alerts: UnboundedSender<SafetyAlert>,
+}
+
+impl HoldWatcher {
+    pub fn new(
+        config: SafetyConfig,
+        rpc_url: String,
+        token_accounts: Arc<TokenAccountService>,
+        pumpswap: Arc<PumpSwap>,
+        native: Arc<NativeSwaps>,
+        state: Arc<StateManager>,
+        alerts: UnboundedSender<SafetyAlert>,
+    ) -> Self {
+        Self {
+            config,
+            rpc_client: AsyncRpcClient::new(rpc_url),
+            token_accounts,
+            pumpswap,
+            native,
+            state,
+            baselines: DashMap::new(),
+            alerts,
+        }
+    }
+    
+    /// Check every open position on the interval
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            info!("🛡️ Hold watcher started (every {:?})", self.config.watch_interval);
+            let mut ticker = tokio::time::interval(self.config.watch_interval);
+            loop {
+                ticker.tick().await;
+                self.check_all().await;
+            }
+        })
+    }
+    
+    async fn check_all(&self) {
+        let positions = self.state.get_all_positions();
+        self.baselines.retain(|mint, _| positions.iter().any(|position| position.token_mint == *mint));
+        for position in positions {
+            if let Err(e) = self.check(&position).await {
+                debug!("Hold check of {} failed: {:#}", position.token_mint, e);
+            }
+        }
+    }
+    
+    async fn check(&self, position: &Position) -> Result<()> {
+        let mint = position.token_mint;
+        let creator = position.token_info.as_ref().and_then(|info| info.creator);
+        let (snapshot, on_curve) = self.snapshot(&mint, creator).await?;
+        
+        let Some(mut baseline) = self.baselines.get_mut(&mint) else {
+            self.baselines.insert(mint, Baseline::new(snapshot, on_curve));
+            return Ok(());
+        };
+        // A migration moves the liquidity from the curve to the pool
+        if baseline.on_curve != on_curve {
+            baseline.snapshot.liquidity = snapshot.liquidity;
+            baseline.on_curve = on_curve;
+        }
+        // The creator is only known once the token info has been fetched
+        if baseline.snapshot.creator_balance.is_none() {
+            baseline.snapshot.creator_balance = snapshot.creator_balance;
+        }
+        match self.config.hold_issue(&baseline.snapshot, &snapshot) {
+            Some(issue) if baseline.first_alert(&issue) => {
+                warn!("🚨 Rug signal on {}: {}", mint, issue);
+                let _ = self.alerts.send(SafetyAlert { mint, issue });
+            }
+            Some(issue) => debug!("Rug signal on {} already raised: {}", mint, issue),
+            None => {}
+        }
+        Ok(())
+    }
+    
+    /// Current mint state, pool or curve SOL and creator balance
+    async fn snapshot(&self, mint: &Pubkey, creator: Option<Pubkey>) -> Result<(HoldSnapshot, bool)> {
+        let mut keys = vec![*mint];
+        keys.extend(pumpfun::bonding_curve_address(mint));
+        let accounts = self.rpc_client.get_multiple_accounts(&keys).await
+            .context("Failed to fetch the mint")?;
+        let mint_risks = accounts[0].as_ref()
+            .and_then(|account| MintRisks::decode(&account.data))
+            .with_context(|| format!("{} is not a mint", mint))?;
+        let curve = accounts.get(1)
+            .and_then(Option::as_ref)
+            .and_then(|account| pumpfun::decode_bonding_curve(&account.data));
+        
+        let (liquidity, on_curve) = match curve {
+            Some(curve) if !curve.complete => (Some(curve.real_sol_reserves), true),
+            _ if self.pumpswap.is_migrated(mint).await => {
+                let pool = self.pumpswap.pool(mint).await?;
+                let (reserves, _) = self.pumpswap.reserves(&pool).await?;
+                (Some(reserves.quote), false)
+            }
+            _ => (self.native.sol_liquidity(mint).await?, false),
+        };
+        let creator_balance = match creator {
+            Some(creator) => Some(self.token_accounts.balance(&creator, mint).await?.amount),
+            None => None,
+        };
+        Ok((HoldSnapshot { mint: mint_risks, liquidity, creator_balance }, on_curve))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_alerts_once_per_issue() {
+        let mut baseline = Baseline::new(HoldSnapshot::default(), true);
+        assert!(baseline.first_alert(&SafetyIssue::LiquidityRemoved { drop_pct: 60.0 }));
+        // The same signal on later passes, however far it has gone, is not raised again
+        assert!(!baseline.first_alert(&SafetyIssue::LiquidityRemoved { drop_pct: 60.0 }));
+        assert!(!baseline.first_alert(&SafetyIssue::LiquidityRemoved { drop_pct: 90.0 }));
+        // A different signal on the same token still is
+        assert!(baseline.first_alert(&SafetyIssue::CreatorDump { sold_pct: 30.0 }));
+    }
+}
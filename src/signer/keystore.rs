@@ -0,0 +1,191 @@
+//! Passphrase-encrypted keystore file (scrypt + AES-256-GCM)
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{Result, Context};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use std::path::Path;
+use std::str::FromStr;
+use zeroize::Zeroize;
+
+/// Keystore format version
+const KEYSTORE_VERSION: u32 = 1;
+
+/// scrypt cost used for new keystores (N = 2^15, r = 8, p = 1)
+pub const DEFAULT_SCRYPT_LOG_N: u8 = 15;
+
+const SALT_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+/// scrypt parameters stored alongside the ciphertext
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub name: String,
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+    /// Hex-encoded salt
+    pub salt: String,
+}
+
+/// Encrypted keypair as stored on disk
+/// The public key is stored in clear (and authenticated) so the wallet can be identified without the passphrase.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub pubkey: String,
+    pub kdf: KdfParams,
+    pub cipher: String,
+    /// Hex-encoded AES-GCM nonce
+    pub nonce: String,
+    /// Hex-encoded encrypted 64-byte keypair
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    /// Encrypt a keypair with a passphrase
+    pub fn encrypt(keypair: &Keypair, passphrase: &str, log_n: u8) -> Result<Self> {
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        rand::thread_rng().fill_bytes(&mut nonce);
+        
+        let kdf = KdfParams {
+            name: "scrypt".to_string(),
+            log_n,
+            r: 8,
+            p: 1,
+            salt: hex::encode(salt),
+        };
+        let pubkey = keypair.pubkey().to_string();
+        
+        let cipher = derive_cipher(&kdf, passphrase)?;
+        let mut secret = keypair.to_bytes();
+        let ciphertext = cipher.encrypt(&Nonce::from(nonce), Payload { msg: &secret, aad: pubkey.as_bytes() });
+        secret.zeroize();
+        
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            pubkey,
+            kdf,
+            cipher: "aes-256-gcm".to_string(),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext.map_err(|_| anyhow::anyhow!("Keystore encryption failed"))?),
+        })
+    }
+    
+    /// Decrypt the keypair; fails on a wrong passphrase or a modified file
+    pub fn decrypt(&self, passphrase: &str) -> Result<Keypair> {
+        if self.version != KEYSTORE_VERSION {
+            anyhow::bail!("Unsupported keystore version {}", self.version);
+        }
+        if self.cipher != "aes-256-gcm" {
+            anyhow::bail!("Unsupported keystore cipher {}", self.cipher);
+        }
+        
+        let nonce: [u8; NONCE_LEN] = hex::decode(&self.nonce).context("Invalid keystore nonce")?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Invalid keystore nonce length"))?;
+        let ciphertext = hex::decode(&self.ciphertext).context("Invalid keystore ciphertext")?;
+        
+        let cipher = derive_cipher(&self.kdf, passphrase)?;
+        let mut secret = cipher
+            .decrypt(&Nonce::from(nonce), Payload { msg: &ciphertext, aad: self.pubkey.as_bytes() })
+            .map_err(|_| anyhow::anyhow!("Wrong keystore passphrase or corrupted keystore"))?;
+        let keypair = Keypair::from_bytes(&secret).context("Keystore does not contain a valid keypair");
+        secret.zeroize();
+        let keypair = keypair?;
+        
+        if keypair.pubkey() != Pubkey::from_str(&self.pubkey).context("Invalid keystore pubkey")? {
+            anyhow::bail!("Keystore pubkey does not match the decrypted keypair");
+        }
+        Ok(keypair)
+    }
+    
+    /// Read a keystore file
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read keystore {}", path.display()))?;
+        serde_json::from_str(&contents).with_context(|| format!("Invalid keystore {}", path.display()))
+    }
+    
+    /// Write the keystore, refusing to overwrite an existing file
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        
+        let file = options.open(path)
+            .with_context(|| format!("Failed to create keystore {}", path.display()))?;
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(())
+    }
+}
+
+/// Derive the AES key from the passphrase
+fn derive_cipher(kdf: &KdfParams, passphrase: &str) -> Result<Aes256Gcm> {
+    if kdf.name != "scrypt" {
+        anyhow::bail!("Unsupported keystore KDF {}", kdf.name);
+    }
+    let salt = hex::decode(&kdf.salt).context("Invalid keystore salt")?;
+    let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p, KEY_LEN)
+        .map_err(|e| anyhow::anyhow!("Invalid scrypt parameters: {}", e))?;
+    
+    let mut key = [0u8; KEY_LEN];
+    scrypt::scrypt(passphrase.as_bytes(), &salt, &params, &mut key)
+        .map_err(|e| anyhow::anyhow!("Key derivation failed: {}", e))?;
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| anyhow::anyhow!("Invalid key length"));
+    key.zeroize();
+    cipher
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Cheap scrypt cost for tests
+    const TEST_LOG_N: u8 = 4;
+    
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let keypair = Keypair::new();
+        let keystore = Keystore::encrypt(&keypair, "correct horse", TEST_LOG_N).unwrap();
+        
+        assert_eq!(keystore.pubkey, keypair.pubkey().to_string());
+        assert!(!keystore.ciphertext.contains(&hex::encode(&keypair.to_bytes()[..32])));
+        
+        let decrypted = keystore.decrypt("correct horse").unwrap();
+        assert_eq!(decrypted.to_bytes(), keypair.to_bytes());
+    }
+    
+    #[test]
+    fn test_wrong_passphrase_and_tampering_fail() {
+        let keystore = Keystore::encrypt(&Keypair::new(), "correct horse", TEST_LOG_N).unwrap();
+        assert!(keystore.decrypt("wrong").is_err());
+        
+        let mut swapped = keystore.clone();
+        swapped.pubkey = Pubkey::new_unique().to_string();
+        assert!(swapped.decrypt("correct horse").is_err());
+    }
+    
+    #[test]
+    fn test_file_roundtrip_does_not_overwrite() {
+        let path = std::env::temp_dir().join(format!("copybot-keystore-{}.json", uuid::Uuid::new_v4()));
+        let keystore = Keystore::encrypt(&Keypair::new(), "pass", TEST_LOG_N).unwrap();
+        
+        keystore.write(&path).unwrap();
+        assert_eq!(Keystore::read(&path).unwrap(), keystore);
+        assert!(keystore.write(&path).is_err());
+        
+        let _ = std::fs::remove_file(&path);
+    }
+}
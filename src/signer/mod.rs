@@ -0,0 +1,15 @@
+//! Transaction signing backends (keystore, keypair file, remote signer)
+
+pub mod wallet;
+pub mod source;
+pub mod keystore;
+pub mod remote;
+pub mod pool;
+pub mod sweeper;
+
+pub use wallet::*;
+pub use source::*;
+pub use keystore::*;
+pub use remote::*;
+pub use pool::*;
+pub use sweeper::*;
@@ -0,0 +1,239 @@
+//! Pool of wallets that copy buys are spread across
+
+use anyhow::Result;
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::state::Position;
+use super::WalletSigner;
+
+/// How a copy buy picks its wallet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AssignmentStrategy {
+    /// Cycle through the wallets in order
+    #[default]
+    RoundRobin,
+    /// Wallet with the least SOL in open positions
+    LeastExposed,
+    /// Each target wallet always trades through the same wallet
+    PerTarget,
+}
+
+impl FromStr for AssignmentStrategy {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "round_robin" => Ok(Self::RoundRobin),
+            "least_exposed" => Ok(Self::LeastExposed),
+            "per_target" => Ok(Self::PerTarget),
+            other => anyhow::bail!(
+                "Unknown wallet assignment '{}' (expected round_robin, least_exposed or per_target)",
+                other
+            ),
+        }
+    }
+}
+
+/// The main wallet plus optional burner wallets that hold copy trade positions
+/// With no burners every trade goes through the main wallet, as before.
+pub struct WalletPool {
+    main: Arc<WalletSigner>,
+    burners: Vec<Arc<WalletSigner>>,
+    strategy: AssignmentStrategy,
+    cursor: AtomicUsize,
+    /// Sticky target -> wallet assignments for `PerTarget`
+    per_target: DashMap<Pubkey, Pubkey>,
+}
+
+impl WalletPool {
+    /// Create a pool; burners must be distinct from each other and from the main wallet
+    pub fn new(main: Arc<WalletSigner>, burners: Vec<Arc<WalletSigner>>, strategy: AssignmentStrategy) -> Result<Self> {
+        let mut seen = HashSet::from([main.pubkey()]);
+        for burner in &burners {
+            if !seen.insert(burner.pubkey()) {
+                anyhow::bail!("Wallet {} is listed more than once in the wallet pool", burner.pubkey());
+            }
+        }
+        
+        Ok(Self {
+            main,
+            burners,
+            strategy,
+            cursor: AtomicUsize::new(0),
+            per_target: DashMap::new(),
+        })
+    }
+    
+    /// Pool with only the main wallet
+    pub fn single(main: Arc<WalletSigner>) -> Self {
+        Self {
+            main,
+            burners: Vec::new(),
+            strategy: AssignmentStrategy::default(),
+            cursor: AtomicUsize::new(0),
+            per_target: DashMap::new(),
+        }
+    }
+    
+    /// Main wallet (fee payer for pool upkeep, nonce authority, default treasury)
+    pub fn main(&self) -> &Arc<WalletSigner> {
+        &self.main
+    }
+    
+    /// Burner wallets
+    pub fn burners(&self) -> &[Arc<WalletSigner>] {
+        &self.burners
+    }
+    
+    pub fn strategy(&self) -> AssignmentStrategy {
+        self.strategy
+    }
+    
+    /// Wallets that copy buys are assigned to
+    fn trading_wallets(&self) -> Vec<&Arc<WalletSigner>> {
+        if self.burners.is_empty() {
+            vec![&self.main]
+        } else {
+            self.burners.iter().collect()
+        }
+    }
+    
+    /// Find the signer for a wallet in the pool
+    pub fn get(&self, wallet: &Pubkey) -> Option<Arc<WalletSigner>> {
+        std::iter::once(&self.main)
+            .chain(self.burners.iter())
+            .find(|signer| signer.pubkey() == *wallet)
+            .cloned()
+    }
+    
+    /// Signer for a position's wallet, falling back to the main wallet
+    pub fn for_position(&self, position: Option<&Position>) -> Arc<WalletSigner> {
+        position
+            .and_then(|p| p.wallet)
+            .and_then(|wallet| self.get(&wallet))
+            .unwrap_or_else(|| self.main.clone())
+    }
+    
+    /// Pick the wallet for a copy of `target`'s buy given the currently open positions
+    pub fn assign(&self, target: &Pubkey, open_positions: &[Position]) -> Arc<WalletSigner> {
+        let wallets = self.trading_wallets();
+        if wallets.len() == 1 {
+            return wallets[0].clone();
+        }
+        
+        match self.strategy {
+            AssignmentStrategy::RoundRobin => self.next_round_robin(&wallets),
+            AssignmentStrategy::LeastExposed => {
+                let exposure = self.exposure(open_positions);
+                wallets.iter()
+                    .min_by_key(|signer| exposure.get(&signer.pubkey()).copied().unwrap_or(0))
+                    .map(|signer| (*signer).clone())
+                    .unwrap_or_else(|| self.main.clone())
+            }
+            AssignmentStrategy::PerTarget => {
+                let wallet = *self.per_target
+                    .entry(*target)
+                    .or_insert_with(|| self.next_round_robin(&wallets).pubkey());
+                self.get(&wallet).unwrap_or_else(|| self.main.clone())
+            }
+        }
+    }
+    
+    fn next_round_robin(&self, wallets: &[&Arc<WalletSigner>]) -> Arc<WalletSigner> {
+        let index = self.cursor.fetch_add(1, Ordering::Relaxed) % wallets.len();
+        wallets[index].clone()
+    }
+    
+    /// Lamports invested in open positions per wallet
+    pub fn exposure(&self, open_positions: &[Position]) -> HashMap<Pubkey, u64> {
+        let mut exposure = HashMap::new();
+        for position in open_positions {
+            let wallet = position.wallet.unwrap_or_else(|| self.main.pubkey());
+            *exposure.entry(wallet).or_insert(0) += position.invested_sol;
+        }
+        exposure
+    }
+}
+
+impl std::fmt::Debug for WalletPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WalletPool")
+            .field("main", &self.main.pubkey())
+            .field("burners", &self.burners.iter().map(|s| s.pubkey()).collect::<Vec<_>>())
+            .field("strategy", &self.strategy)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Keypair;
+    
+    fn signer() -> Arc<WalletSigner> {
+        Arc::new(Keypair::new().into())
+    }
+    
+    fn position(wallet: &Arc<WalletSigner>, invested: u64) -> Position {
+        Position::new(Pubkey::new_unique(), 0, invested, "t".to_string(), "o".to_string())
+            .with_wallet(wallet.pubkey())
+    }
+    
+    #[test]
+    fn test_single_wallet_always_main() {
+        let main = signer();
+        let pool = WalletPool::single(main.clone());
+        for _ in 0..3 {
+            assert_eq!(pool.assign(&Pubkey::new_unique(), &[]).pubkey(), main.pubkey());
+        }
+        assert_eq!(pool.for_position(None).pubkey(), main.pubkey());
+    }
+    
+    #[test]
+    fn test_round_robin_cycles_burners() {
+        let (a, b) = (signer(), signer());
+        let pool = WalletPool::new(signer(), vec![a.clone(), b.clone()], AssignmentStrategy::RoundRobin).unwrap();
+        let target = Pubkey::new_unique();
+        
+        let picked: Vec<Pubkey> = (0..4).map(|_| pool.assign(&target, &[]).pubkey()).collect();
+        assert_eq!(picked, vec![a.pubkey(), b.pubkey(), a.pubkey(), b.pubkey()]);
+    }
+    
+    #[test]
+    fn test_least_exposed_and_per_target() {
+        let (a, b) = (signer(), signer());
+        let pool = WalletPool::new(signer(), vec![a.clone(), b.clone()], AssignmentStrategy::LeastExposed).unwrap();
+        let open = vec![position(&a, 300), position(&b, 100), position(&b, 100)];
+        assert_eq!(pool.assign(&Pubkey::new_unique(), &open).pubkey(), b.pubkey());
+        
+        let pool = WalletPool::new(signer(), vec![a.clone(), b.clone()], AssignmentStrategy::PerTarget).unwrap();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let wallet = pool.assign(&first, &[]).pubkey();
+        assert_ne!(pool.assign(&second, &[]).pubkey(), wallet);
+        assert_eq!(pool.assign(&first, &[]).pubkey(), wallet);
+    }
+    
+    #[test]
+    fn test_lookup_and_duplicates() {
+        let (main, a) = (signer(), signer());
+        let pool = WalletPool::new(main.clone(), vec![a.clone()], AssignmentStrategy::RoundRobin).unwrap();
+        assert_eq!(pool.for_position(Some(&position(&a, 1))).pubkey(), a.pubkey());
+        assert!(pool.get(&Pubkey::new_unique()).is_none());
+        
+        assert!(WalletPool::new(main.clone(), vec![main.clone()], AssignmentStrategy::RoundRobin).is_err());
+        assert!(WalletPool::new(main, vec![a.clone(), a], AssignmentStrategy::RoundRobin).is_err());
+    }
+    
+    #[test]
+    fn test_parse_strategy() {
+        assert_eq!(AssignmentStrategy::from_str("least-exposed").unwrap(), AssignmentStrategy::LeastExposed);
+        assert_eq!(AssignmentStrategy::from_str("PER_TARGET").unwrap(), AssignmentStrategy::PerTarget);
+        assert!(AssignmentStrategy::from_str("random").is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! Remote signer reached over HTTP on localhost or a Unix socket
+//!
+//! The signing process exposes two endpoints:
+//! - `GET /pubkey` -> `{"pubkey": "<base58>"}`
+//! - `POST /sign` with `{"message": "<base64>"}` -> `{"signature": "<base58>"}`
+//!
+//! Requests carry `Authorization: Bearer <token>` when a token is configured.
+
+use anyhow::{Result, Context};
+use base64::Engine;
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::SignerError;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Timeout for connecting to and hearing back from the signer
+const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where the signing process listens
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteEndpoint {
+    /// `http://host:port`
+    Tcp(String),
+    /// `unix:///path/to/socket`
+    Unix(PathBuf),
+}
+
+impl FromStr for RemoteEndpoint {
+    type Err = anyhow::Error;
+    
+    fn from_str(url: &str) -> Result<Self> {
+        if let Some(path) = url.strip_prefix("unix://") {
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+        if let Some(address) = url.strip_prefix("http://") {
+            return Ok(Self::Tcp(address.trim_end_matches('/').to_string()));
+        }
+        anyhow::bail!("Remote signer URL must start with http:// or unix:// (got {})", url);
+    }
+}
+
+#[derive(Deserialize)]
+struct PubkeyResponse {
+    pubkey: String,
+}
+
+#[derive(Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+/// Signer that forwards messages to a separate signing process
+/// Calls block on local I/O; the public key is fetched once when connecting.
+pub struct RemoteSigner {
+    endpoint: RemoteEndpoint,
+    token: Option<String>,
+    pubkey: Pubkey,
+}
+
+impl RemoteSigner {
+    /// Connect to the signer and fetch its public key
+    pub fn connect(url: &str, token: Option<String>) -> Result<Self> {
+        let endpoint = RemoteEndpoint::from_str(url)?;
+        let mut signer = Self { endpoint, token, pubkey: Pubkey::default() };
+        
+        let response: PubkeyResponse = signer.request("GET", "/pubkey", None)
+            .with_context(|| format!("Remote signer at {} is not reachable", url))?;
+        signer.pubkey = Pubkey::from_str(&response.pubkey).context("Remote signer returned an invalid pubkey")?;
+        Ok(signer)
+    }
+    
+    pub fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        Ok(self.pubkey)
+    }
+    
+    pub fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let body = serde_json::json!({
+            "message": base64::engine::general_purpose::STANDARD.encode(message),
+        });
+        let response: SignResponse = self.request("POST", "/sign", Some(body.to_string()))
+            .map_err(|e| SignerError::Custom(format!("Remote signer: {:#}", e)))?;
+        let signature = Signature::from_str(&response.signature)
+            .map_err(|e| SignerError::Custom(format!("Remote signer returned an invalid signature: {}", e)))?;
+        
+        // Never hand out a signature that does not belong to the key we think we are using
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err(SignerError::Custom("Remote signer returned a signature for a different key".to_string()));
+        }
+        Ok(signature)
+    }
+    
+    /// Send one HTTP/1.1 request and parse the JSON body of a 2xx response
+    fn request<T: serde::de::DeserializeOwned>(&self, method: &str, path: &str, body: Option<String>) -> Result<T> {
+        let body = body.unwrap_or_default();
+        let mut request = format!(
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+            method, path, body.len()
+        );
+        if let Some(token) = &self.token {
+            request.push_str(&format!("Authorization: Bearer {}\r\n", token));
+        }
+        request.push_str("\r\n");
+        request.push_str(&body);
+        
+        let response = match &self.endpoint {
+            RemoteEndpoint::Tcp(address) => {
+                let address = std::net::ToSocketAddrs::to_socket_addrs(address.as_str())?
+                    .next()
+                    .with_context(|| format!("Cannot resolve {}", address))?;
+                let stream = std::net::TcpStream::connect_timeout(&address, REMOTE_SIGNER_TIMEOUT)?;
+                stream.set_read_timeout(Some(REMOTE_SIGNER_TIMEOUT))?;
+                stream.set_write_timeout(Some(REMOTE_SIGNER_TIMEOUT))?;
+                exchange(stream, request.as_bytes())?
+            }
+            #[cfg(unix)]
+            RemoteEndpoint::Unix(path) => {
+                let stream = std::os::unix::net::UnixStream::connect(path)?;
+                stream.set_read_timeout(Some(REMOTE_SIGNER_TIMEOUT))?;
+                stream.set_write_timeout(Some(REMOTE_SIGNER_TIMEOUT))?;
+                exchange(stream, request.as_bytes())?
+            }
+            #[cfg(not(unix))]
+            RemoteEndpoint::Unix(_) => anyhow::bail!("Unix sockets are not supported on this platform"),
+        };
+        
+        parse_response(&response)
+    }
+}
+
+fn exchange(mut stream: impl Read + Write, request: &[u8]) -> Result<Vec<u8>> {
+    stream.write_all(request)?;
+    stream.flush()?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+    Ok(response)
+}
+
+/// Parse a `Connection: close` response with a plain (not chunked) body
+fn parse_response<T: serde::de::DeserializeOwned>(response: &[u8]) -> Result<T> {
+    let text = std::str::from_utf8(response).context("Response is not UTF-8")?;
+    let (head, body) = text.split_once("\r\n\r\n").context("Malformed HTTP response")?;
+    
+    let status: u16 = head.split_whitespace().nth(1)
+        .and_then(|s| s.parse().ok())
+        .context("Malformed HTTP status line")?;
+    if head.lines().any(|l| l.to_ascii_lowercase().starts_with("transfer-encoding: chunked")) {
+        anyhow::bail!("Chunked responses are not supported");
+    }
+    if !(200..300).contains(&status) {
+        anyhow::bail!("HTTP {}: {}", status, body.trim());
+    }
+    serde_json::from_str(body).context("Invalid JSON response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use std::io::BufRead;
+    use std::sync::Arc;
+    
+    /// Minimal signing server answering `requests` connections
+    fn serve(listener: std::net::TcpListener, keypair: Arc<Keypair>, token: &'static str, requests: usize) {
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut authorized = false;
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    authorized |= line.trim() == format!("Authorization: Bearer {}", token);
+                    if let Some(value) = line.strip_prefix("Content-Length: ") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                
+                let (status, reply) = if !authorized {
+                    ("401 Unauthorized", serde_json::json!({"error": "unauthorized"}))
+                } else if request_line.starts_with("GET /pubkey") {
+                    ("200 OK", serde_json::json!({"pubkey": keypair.pubkey().to_string()}))
+                } else {
+                    let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                    let message = base64::engine::general_purpose::STANDARD
+                        .decode(request["message"].as_str().unwrap())
+                        .unwrap();
+                    ("200 OK", serde_json::json!({"signature": keypair.sign_message(&message).to_string()}))
+                };
+                let reply = reply.to_string();
+                write!(stream, "HTTP/1.1 {}\r\nContent-Length: {}\r\n\r\n{}", status, reply.len(), reply).unwrap();
+            }
+        });
+    }
+    
+    #[test]
+    fn test_remote_signer_over_http() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let keypair = Arc::new(Keypair::new());
+        serve(listener, keypair.clone(), "secret", 3);
+        
+        let signer = RemoteSigner::connect(&url, Some("secret".to_string())).unwrap();
+        assert_eq!(signer.try_pubkey().unwrap(), keypair.pubkey());
+        
+        let signature = signer.try_sign_message(b"message").unwrap();
+        assert_eq!(signature, keypair.sign_message(b"message"));
+        
+        let unauthorized = RemoteSigner { token: None, ..signer };
+        assert!(unauthorized.try_sign_message(b"message").is_err());
+    }
+    
+    #[test]
+    fn test_parse_endpoint() {
+        assert_eq!(
+            RemoteEndpoint::from_str("unix:///run/signer.sock").unwrap(),
+            RemoteEndpoint::Unix(PathBuf::from("/run/signer.sock"))
+        );
+        assert_eq!(
+            RemoteEndpoint::from_str("http://127.0.0.1:7070/").unwrap(),
+            RemoteEndpoint::Tcp("127.0.0.1:7070".to_string())
+        );
+        assert!(RemoteEndpoint::from_str("https://signer.example.com").is_err());
+    }
+}
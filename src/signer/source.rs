@@ -0,0 +1,167 @@
+//! Where the wallet key comes from
+
+use anyhow::{Result, Context};
+use solana_sdk::signature::{read_keypair_file, Keypair};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use super::{Keystore, RemoteSigner, WalletSigner, DEFAULT_SCRYPT_LOG_N};
+
+/// Configured signer backend
+#[derive(Clone, PartialEq)]
+pub enum SignerSource {
+    /// Raw key from `PRIVATE_KEY` (kept for existing setups)
+    PrivateKey(String),
+    /// Solana CLI keypair file (JSON array of 64 bytes)
+    KeypairFile(PathBuf),
+    /// Encrypted keystore; the passphrase is read from a file or prompted for
+    Keystore { path: PathBuf, passphrase_file: Option<PathBuf> },
+    /// Separate signing process
+    Remote { url: String, token: Option<String> },
+}
+
+impl SignerSource {
+    /// Load the key (or connect to the signer)
+    pub fn open(&self) -> Result<WalletSigner> {
+        match self {
+            Self::PrivateKey(key) => {
+                warn!("⚠️ PRIVATE_KEY keeps the wallet key in the environment; prefer KEYSTORE_PATH or REMOTE_SIGNER_URL");
+                Ok(parse_keypair(key).context("Invalid PRIVATE_KEY")?.into())
+            }
+            Self::KeypairFile(path) => Ok(read_keypair(path)?.into()),
+            Self::Keystore { path, passphrase_file } => {
+                let keystore = Keystore::read(path)?;
+                let passphrase = match passphrase_file {
+                    Some(file) => read_passphrase_file(file)?,
+                    None => rpassword::prompt_password(format!("Passphrase for keystore {}: ", path.display()))
+                        .context("Failed to read keystore passphrase")?,
+                };
+                Ok(keystore.decrypt(&passphrase)?.into())
+            }
+            Self::Remote { url, token } => Ok(WalletSigner::Remote(RemoteSigner::connect(url, token.clone())?)),
+        }
+    }
+}
+
+impl std::fmt::Debug for SignerSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PrivateKey(_) => f.write_str("PrivateKey(<redacted>)"),
+            Self::KeypairFile(path) => f.debug_tuple("KeypairFile").field(path).finish(),
+            Self::Keystore { path, passphrase_file } => f.debug_struct("Keystore")
+                .field("path", path)
+                .field("passphrase_file", passphrase_file)
+                .finish(),
+            Self::Remote { url, .. } => f.debug_struct("Remote").field("url", url).finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Parse a keypair from various formats (base58, JSON array)
+pub fn parse_keypair(input: &str) -> Result<Keypair> {
+    // Try base58 first
+    if let Ok(bytes) = bs58::decode(input.trim()).into_vec() {
+        if bytes.len() == 64 {
+            return Ok(Keypair::from_bytes(&bytes)?);
+        }
+    }
+    
+    // Try JSON array format
+    if input.trim().starts_with('[') {
+        let bytes: Vec<u8> = serde_json::from_str(input)?;
+        if bytes.len() == 64 {
+            return Ok(Keypair::from_bytes(&bytes)?);
+        }
+    }
+    
+    anyhow::bail!("Invalid keypair format. Expected base58 or JSON array of 64 bytes.");
+}
+
+/// Read a Solana CLI keypair file
+pub fn read_keypair(path: &Path) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|e| anyhow::anyhow!("Failed to read keypair file {}: {}", path.display(), e))
+}
+
+/// Open a wallet pool entry: a Solana CLI keypair file or an encrypted keystore
+pub fn open_wallet_file(path: &Path, passphrase_file: Option<&Path>) -> Result<WalletSigner> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read wallet file {}", path.display()))?;
+    let source = if contents.trim_start().starts_with('[') {
+        SignerSource::KeypairFile(path.to_path_buf())
+    } else {
+        SignerSource::Keystore { path: path.to_path_buf(), passphrase_file: passphrase_file.map(Path::to_path_buf) }
+    };
+    source.open()
+}
+
+/// Read a passphrase file, ignoring a trailing newline
+fn read_passphrase_file(path: &Path) -> Result<String> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read passphrase file {}", path.display()))?;
+    Ok(contents.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Encrypt a Solana CLI keypair file into a new keystore, prompting for the passphrase
+pub fn import_keystore(keypair_path: &Path, keystore_path: &Path) -> Result<()> {
+    let keypair = read_keypair(keypair_path)?;
+    let passphrase = rpassword::prompt_password("New keystore passphrase: ")?;
+    if passphrase.is_empty() {
+        anyhow::bail!("Passphrase must not be empty");
+    }
+    if rpassword::prompt_password("Repeat passphrase: ")? != passphrase {
+        anyhow::bail!("Passphrases do not match");
+    }
+    
+    Keystore::encrypt(&keypair, &passphrase, DEFAULT_SCRYPT_LOG_N)?.write(keystore_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::write_keypair_file;
+    use solana_sdk::signer::Signer;
+    
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("copybot-{}-{}", name, uuid::Uuid::new_v4()))
+    }
+    
+    #[test]
+    fn test_open_local_sources() {
+        let keypair = Keypair::new();
+        
+        let signer = SignerSource::PrivateKey(keypair.to_base58_string()).open().unwrap();
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+        
+        let keypair_path = temp_path("keypair.json");
+        write_keypair_file(&keypair, &keypair_path).unwrap();
+        let signer = SignerSource::KeypairFile(keypair_path.clone()).open().unwrap();
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+        
+        let keystore_path = temp_path("keystore.json");
+        let passphrase_path = temp_path("passphrase");
+        Keystore::encrypt(&keypair, "hunter2", 4).unwrap().write(&keystore_path).unwrap();
+        std::fs::write(&passphrase_path, "hunter2\n").unwrap();
+        let source = SignerSource::Keystore { path: keystore_path.clone(), passphrase_file: Some(passphrase_path.clone()) };
+        let signer = source.open().unwrap();
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+        assert_eq!(signer.sign_message(b"m"), keypair.sign_message(b"m"));
+        
+        let signer = open_wallet_file(&keypair_path, None).unwrap();
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+        let signer = open_wallet_file(&keystore_path, Some(&passphrase_path)).unwrap();
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+        
+        for path in [keypair_path, keystore_path, passphrase_path] {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+    
+    #[test]
+    fn test_debug_redacts_secrets() {
+        let source = SignerSource::PrivateKey("secret-key".to_string());
+        assert!(!format!("{:?}", source).contains("secret-key"));
+        
+        let source = SignerSource::Remote { url: "unix:///run/s.sock".to_string(), token: Some("tok".to_string()) };
+        assert!(!format!("{:?}", source).contains("tok"));
+    }
+}
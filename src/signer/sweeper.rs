@@ -0,0 +1,129 @@
+//! Sweeps idle SOL from burner wallets back to the treasury
+
+use anyhow::{Result, Context};
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{Signature, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn, debug};
+
+use crate::state::StateManager;
+use super::{WalletPool, WalletSigner};
+
+/// Fee for a single-signature transfer
+const TRANSFER_FEE_LAMPORTS: u64 = 5_000;
+
+/// Minimum balance of a system account that keeps lamports (rent exemption for 0 bytes of data)
+const RENT_EXEMPT_MINIMUM: u64 = 890_880;
+
+/// Skip sweeps smaller than this
+const MIN_SWEEP_LAMPORTS: u64 = 1_000_000;
+
+/// Sweeper settings
+#[derive(Debug, Clone)]
+pub struct SweepConfig {
+    /// Where swept SOL goes
+    pub treasury: Pubkey,
+    /// How often burners are checked
+    pub interval: Duration,
+    /// Lamports left in each burner for fees and the next buys
+    pub reserve_lamports: u64,
+}
+
+/// Lamports to move out of a burner holding `balance`, if worth a transaction
+/// A non-zero reserve is raised to the rent-exempt minimum so the transfer cannot fail on rent.
+pub fn sweep_amount(balance: u64, reserve_lamports: u64) -> Option<u64> {
+    let reserve = if reserve_lamports == 0 { 0 } else { reserve_lamports.max(RENT_EXEMPT_MINIMUM) };
+    let amount = balance.checked_sub(reserve)?.checked_sub(TRANSFER_FEE_LAMPORTS)?;
+    (amount >= MIN_SWEEP_LAMPORTS).then_some(amount)
+}
+
+/// Periodically sweep burners that hold no open positions
+pub fn spawn_sweeper(
+    pool: Arc<WalletPool>,
+    state: Arc<StateManager>,
+    rpc_url: String,
+    config: SweepConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let rpc_client = AsyncRpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+        info!(
+            "🧹 Wallet sweeper started ({} burner(s) -> {}, every {:?})",
+            pool.burners().len(), config.treasury, config.interval
+        );
+        
+        let mut ticker = tokio::time::interval(config.interval);
+        loop {
+            ticker.tick().await;
+            
+            let positions = state.get_all_positions();
+            for burner in pool.burners() {
+                let wallet = burner.pubkey();
+                if wallet == config.treasury || positions.iter().any(|p| p.wallet == Some(wallet)) {
+                    continue;
+                }
+                
+                match sweep(&rpc_client, burner, &config).await {
+                    Ok(Some((amount, signature))) => info!(
+                        "🧹 Swept {:.4} SOL from {} to treasury: {}",
+                        amount as f64 / 1_000_000_000.0, wallet, signature
+                    ),
+                    Ok(None) => debug!("Nothing to sweep from {}", wallet),
+                    Err(e) => warn!("⚠️ Sweep from {} failed: {:#}", wallet, e),
+                }
+            }
+        }
+    })
+}
+
+/// Transfer a burner's balance above the reserve to the treasury
+async fn sweep(
+    rpc_client: &AsyncRpcClient,
+    burner: &Arc<WalletSigner>,
+    config: &SweepConfig,
+) -> Result<Option<(u64, Signature)>> {
+    let wallet = burner.pubkey();
+    let balance = rpc_client.get_balance(&wallet).await
+        .context("Failed to get balance")?;
+    let amount = match sweep_amount(balance, config.reserve_lamports) {
+        Some(amount) => amount,
+        None => return Ok(None),
+    };
+    
+    let recent_blockhash = rpc_client.get_latest_blockhash().await
+        .context("Failed to get recent blockhash")?;
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(&wallet, &config.treasury, amount)],
+        Some(&wallet),
+        &[burner.as_ref()],
+        recent_blockhash,
+    );
+    let signature = rpc_client.send_and_confirm_transaction(&tx).await?;
+    Ok(Some((amount, signature)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_sweep_amount() {
+        // Drain completely when no reserve is kept
+        assert_eq!(sweep_amount(50_000_000, 0), Some(50_000_000 - TRANSFER_FEE_LAMPORTS));
+        
+        // Keep the reserve, raised to the rent-exempt minimum
+        assert_eq!(sweep_amount(50_000_000, 10_000_000), Some(40_000_000 - TRANSFER_FEE_LAMPORTS));
+        assert_eq!(sweep_amount(50_000_000, 1), Some(50_000_000 - RENT_EXEMPT_MINIMUM - TRANSFER_FEE_LAMPORTS));
+        
+        // Dust and balances below the reserve are left alone
+        assert_eq!(sweep_amount(10_500_000, 10_000_000), None);
+        assert_eq!(sweep_amount(1_000, 10_000_000), None);
+    }
+}
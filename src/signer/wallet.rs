@@ -0,0 +1,61 @@
+//! The bot's wallet signer
+
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signer::{Signer, SignerError};
+
+use super::RemoteSigner;
+
+/// Signs every transaction the bot sends
+/// Created once at startup and shared as `Arc<WalletSigner>`; the key itself is never copied.
+pub enum WalletSigner {
+    /// Key held in memory (keystore, keypair file or `PRIVATE_KEY`)
+    Local(Keypair),
+    /// Key held by a separate signing process
+    Remote(RemoteSigner),
+}
+
+impl WalletSigner {
+    /// Short description of the backend for logs
+    pub fn backend(&self) -> &'static str {
+        match self {
+            Self::Local(_) => "local",
+            Self::Remote(_) => "remote",
+        }
+    }
+}
+
+impl From<Keypair> for WalletSigner {
+    fn from(keypair: Keypair) -> Self {
+        Self::Local(keypair)
+    }
+}
+
+impl Signer for WalletSigner {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        match self {
+            Self::Local(keypair) => keypair.try_pubkey(),
+            Self::Remote(remote) => remote.try_pubkey(),
+        }
+    }
+    
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        match self {
+            Self::Local(keypair) => keypair.try_sign_message(message),
+            Self::Remote(remote) => remote.try_sign_message(message),
+        }
+    }
+    
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Debug for WalletSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WalletSigner")
+            .field("backend", &self.backend())
+            .field("pubkey", &self.pubkey())
+            .finish()
+    }
+}
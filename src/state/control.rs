@@ -0,0 +1,232 @@
+//! Runtime controls shared between the engine and operator interfaces
+
+use parking_lot::RwLock;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tokio::sync::Notify;
+use tracing::info;
+
+use crate::config::TakeProfitTier;
+
+/// Settings the operator can change while the bot is running
+pub struct BotControl {
+    /// When paused, new copy buys are skipped (exits still run)
+    paused: AtomicBool,
+    /// Whether the take profit monitor sells
+    take_profit_enabled: AtomicBool,
+    /// Wallets whose trades we copy
+    target_wallets: RwLock<Vec<Pubkey>>,
+    /// SOL spent per copy buy (f64 bits)
+    buy_amount_sol: AtomicU64,
+    /// Priority fee per transaction in lamports
+    tip_amount: AtomicU64,
+    /// Take profit tiers (multiplier + cumulative sell_percent)
+    take_profit_tiers: RwLock<Vec<TakeProfitTier>>,
+    /// Signalled when the target wallet set changes so the stream resubscribes
+    targets_changed: Notify,
+}
+
+impl BotControl {
+    /// Create controls from the startup configuration
+    pub fn new(
+        target_wallets: Vec<Pubkey>,
+        buy_amount_sol: f64,
+        tip_amount: u64,
+        take_profit_enabled: bool,
+        take_profit_tiers: Vec<TakeProfitTier>,
+    ) -> Self {
+        Self {
+            paused: AtomicBool::new(false),
+            take_profit_enabled: AtomicBool::new(take_profit_enabled),
+            target_wallets: RwLock::new(target_wallets),
+            buy_amount_sol: AtomicU64::new(buy_amount_sol.to_bits()),
+            tip_amount: AtomicU64::new(tip_amount),
+            take_profit_tiers: RwLock::new(take_profit_tiers),
+            targets_changed: Notify::new(),
+        }
+    }
+    
+    /// Check if copying new buys is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+    
+    /// Pause or resume copying new buys
+    pub fn set_paused(&self, paused: bool) {
+        if self.paused.swap(paused, Ordering::Relaxed) != paused {
+            info!("{} Copying {}", if paused { "⏸️" } else { "▶️" }, if paused { "paused" } else { "resumed" });
+        }
+    }
+    
+    /// Check if take profit selling is enabled
+    pub fn take_profit_enabled(&self) -> bool {
+        self.take_profit_enabled.load(Ordering::Relaxed)
+    }
+    
+    /// Enable or disable take profit selling
+    pub fn set_take_profit_enabled(&self, enabled: bool) {
+        if self.take_profit_enabled.swap(enabled, Ordering::Relaxed) != enabled {
+            info!("📈 Take Profit {}", if enabled { "enabled" } else { "disabled" });
+        }
+    }
+    
+    /// Get the SOL amount spent per copy buy
+    pub fn buy_amount_sol(&self) -> f64 {
+        f64::from_bits(self.buy_amount_sol.load(Ordering::Relaxed))
+    }
+    
+    /// Change the SOL amount spent per copy buy
+    pub fn set_buy_amount_sol(&self, amount: f64) {
+        let previous = f64::from_bits(self.buy_amount_sol.swap(amount.to_bits(), Ordering::Relaxed));
+        if previous != amount {
+            info!("💰 Buy amount changed: {} -> {} SOL", previous, amount);
+        }
+    }
+    
+    /// Get the priority fee per transaction in lamports
+    pub fn tip_amount(&self) -> u64 {
+        self.tip_amount.load(Ordering::Relaxed)
+    }
+    
+    /// Change the priority fee per transaction
+    pub fn set_tip_amount(&self, lamports: u64) {
+        let previous = self.tip_amount.swap(lamports, Ordering::Relaxed);
+        if previous != lamports {
+            info!("💸 Tip changed: {} -> {} lamports", previous, lamports);
+        }
+    }
+    
+    /// Get the current target wallets
+    pub fn target_wallets(&self) -> Vec<Pubkey> {
+        self.target_wallets.read().clone()
+    }
+    
+    /// Check if a wallet is a copy target
+    pub fn is_target(&self, wallet: &Pubkey) -> bool {
+        self.target_wallets.read().contains(wallet)
+    }
+    
+    /// Add a target wallet. Returns false if it was already a target.
+    pub fn add_target_wallet(&self, wallet: Pubkey) -> bool {
+        {
+            let mut targets = self.target_wallets.write();
+            if targets.contains(&wallet) {
+                return false;
+            }
+            targets.push(wallet);
+        }
+        info!("🎯 Target wallet added: {}", wallet);
+        self.targets_changed.notify_one();
+        true
+    }
+    
+    /// Remove a target wallet. Returns false if it was not a target.
+    pub fn remove_target_wallet(&self, wallet: &Pubkey) -> bool {
+        {
+            let mut targets = self.target_wallets.write();
+            let before = targets.len();
+            targets.retain(|w| w != wallet);
+            if targets.len() == before {
+                return false;
+            }
+        }
+        info!("🎯 Target wallet removed: {}", wallet);
+        self.targets_changed.notify_one();
+        true
+    }
+    
+    /// Wait until the target wallet set changes
+    pub async fn targets_changed(&self) {
+        self.targets_changed.notified().await
+    }
+    
+    /// Get the take profit tiers sorted by multiplier
+    pub fn take_profit_tiers(&self) -> Vec<TakeProfitTier> {
+        let mut tiers = self.take_profit_tiers.read().clone();
+        tiers.sort_by(|a, b| a.multiplier.partial_cmp(&b.multiplier).unwrap_or(std::cmp::Ordering::Equal));
+        tiers
+    }
+    
+    /// Replace the take profit tiers after validating them
+    pub fn set_take_profit_tiers(&self, tiers: Vec<TakeProfitTier>) -> Result<(), String> {
+        validate_take_profit_tiers(&tiers)?;
+        info!(
+            "📈 Take Profit tiers updated: {}",
+            serde_json::to_string(&tiers).unwrap_or_else(|_| "[]".to_string())
+        );
+        *self.take_profit_tiers.write() = tiers;
+        Ok(())
+    }
+}
+
+/// Check that tiers have a positive multiplier and a 1-100 sell percent
+pub fn validate_take_profit_tiers(tiers: &[TakeProfitTier]) -> Result<(), String> {
+    for (i, tier) in tiers.iter().enumerate() {
+        if !tier.multiplier.is_finite() || tier.multiplier <= 0.0 {
+            return Err(format!("tier {}: multiplier must be positive", i));
+        }
+        if tier.sell_percent == 0 || tier.sell_percent > 100 {
+            return Err(format!("tier {}: sell_percent must be between 1 and 100", i));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn control() -> BotControl {
+        BotControl::new(
+            vec![Pubkey::new_unique()],
+            0.1,
+            10_000,
+            true,
+            vec![TakeProfitTier { multiplier: 2.0, sell_percent: 50 }],
+        )
+    }
+    
+    #[test]
+    fn test_target_wallets() {
+        let control = control();
+        let wallet = Pubkey::new_unique();
+        
+        assert!(control.add_target_wallet(wallet));
+        assert!(!control.add_target_wallet(wallet));
+        assert!(control.is_target(&wallet));
+        assert_eq!(control.target_wallets().len(), 2);
+        
+        assert!(control.remove_target_wallet(&wallet));
+        assert!(!control.remove_target_wallet(&wallet));
+        assert!(!control.is_target(&wallet));
+    }
+    
+    #[test]
+    fn test_take_profit_tiers() {
+        let control = control();
+        
+        control.set_take_profit_tiers(vec![
+            TakeProfitTier { multiplier: 5.0, sell_percent: 100 },
+            TakeProfitTier { multiplier: 3.0, sell_percent: 50 },
+        ]).unwrap();
+        
+        let tiers = control.take_profit_tiers();
+        assert_eq!(tiers[0].multiplier, 3.0);
+        assert_eq!(tiers[1].multiplier, 5.0);
+        
+        assert!(control.set_take_profit_tiers(vec![
+            TakeProfitTier { multiplier: 2.0, sell_percent: 0 },
+        ]).is_err());
+        assert_eq!(control.take_profit_tiers().len(), 2);
+    }
+    
+    #[test]
+    fn test_pause() {
+        let control = control();
+        assert!(!control.is_paused());
+        control.set_paused(true);
+        assert!(control.is_paused());
+        control.set_paused(false);
+        assert!(!control.is_paused());
+    }
+}
@@ -2,12 +2,17 @@
 
 use dashmap::DashMap;
 use parking_lot::RwLock;
+use serde::Serialize;
 use solana_sdk::pubkey::Pubkey;
-use std::collections::{HashMap, HashSet};
+use std::collections::VecDeque;
 use std::sync::Arc;
 use tracing::{info, debug, warn};
 
-use super::position::{Position, TradeRecord, TradeRecordType};
+use super::position::{Position, TargetEvent, TradeRecord, TradeRecordType};
+use crate::token::TokenInfo;
+
+/// Target events kept for the dashboard
+const MAX_TARGET_EVENTS: usize = 100;
 
 /// Thread-safe state manager
 pub struct StateManager {
@@ -19,6 +24,8 @@ pub struct StateManager {
     traded_tokens: DashMap<Pubkey, ()>,
     /// Pending transactions (signature -> token mint)
     pending_txs: DashMap<String, Pubkey>,
+    /// Recent target wallet trades (newest last)
+    target_events: RwLock<VecDeque<TargetEvent>>,
 }
 
 impl StateManager {
@@ -29,6 +36,7 @@ impl StateManager {
             trade_history: RwLock::new(Vec::new()),
             traded_tokens: DashMap::new(),
             pending_txs: DashMap::new(),
+            target_events: RwLock::new(VecDeque::with_capacity(MAX_TARGET_EVENTS)),
         }
     }
     
@@ -76,6 +84,27 @@ impl StateManager {
         self.positions.get(token_mint).map(|p| p.clone())
     }
     
+    /// Set a position's token amount from an on-chain balance read
+    /// Buys are fire-and-forget, so the filled amount is only known once the balance lands
+    pub fn sync_position_amount(&self, token_mint: &Pubkey, amount: u64) {
+        if let Some(mut position) = self.positions.get_mut(token_mint) {
+            if position.amount != amount {
+                debug!("Position {} amount synced: {} -> {}", token_mint, position.amount, amount);
+                position.amount = amount;
+                if position.entry_price == 0.0 && amount > 0 {
+                    position.entry_price = position.invested_sol as f64 / amount as f64;
+                }
+            }
+        }
+    }
+    
+    /// Attach token metadata and holder info to a position
+    pub fn set_position_info(&self, token_mint: &Pubkey, info: TokenInfo) {
+        if let Some(mut position) = self.positions.get_mut(token_mint) {
+            position.token_info = Some(info);
+        }
+    }
+    
     /// Update a position's current value
     pub fn update_position_value(&self, token_mint: &Pubkey, new_value_sol: u64) {
         if let Some(mut position) = self.positions.get_mut(token_mint) {
@@ -96,7 +125,12 @@ impl StateManager {
         
         if let Some(mut position) = self.positions.get_mut(token_mint) {
             let pnl = if position.invested_sol > 0 {
-                let invested_ratio = amount_sold as f64 / position.amount as f64;
+                // An unfilled position (amount not yet synced) is sold in full
+                let invested_ratio = if position.amount > 0 {
+                    (amount_sold as f64 / position.amount as f64).min(1.0)
+                } else {
+                    1.0
+                };
                 let invested_portion = (position.invested_sol as f64 * invested_ratio) as i64;
                 Some(sol_received as i64 - invested_portion)
             } else {
@@ -129,8 +163,8 @@ impl StateManager {
             }
         }
         
-        // Remove closed positions
-        self.positions.retain(|_, p| !p.is_closed());
+        // Remove the position if this sell closed it
+        self.positions.remove_if(token_mint, |_, p| p.is_closed());
         
         result
     }
@@ -201,6 +235,27 @@ impl StateManager {
         self.pending_txs.contains_key(signature)
     }
     
+    /// Get pending transactions (signature, token mint)
+    pub fn get_pending_txs(&self) -> Vec<(String, Pubkey)> {
+        self.pending_txs.iter()
+            .map(|e| (e.key().clone(), *e.value()))
+            .collect()
+    }
+    
+    /// Record a target wallet trade, dropping the oldest beyond the limit
+    pub fn record_target_event(&self, event: TargetEvent) {
+        let mut events = self.target_events.write();
+        if events.len() >= MAX_TARGET_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+    
+    /// Get recent target wallet trades, newest first
+    pub fn get_target_events(&self) -> Vec<TargetEvent> {
+        self.target_events.read().iter().rev().cloned().collect()
+    }
+    
     /// Export state to JSON (for backup)
     pub fn export_state(&self) -> serde_json::Value {
         let positions: Vec<Position> = self.positions.iter()
@@ -261,7 +316,7 @@ impl Default for StateManager {
 }
 
 /// State statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct StateStats {
     pub open_positions: usize,
     pub total_traded_tokens: usize,
@@ -341,4 +396,36 @@ mod tests {
         assert_eq!(manager.open_positions_count(), 0);
         assert!(manager.has_traded_token(&mint)); // Still marked as traded
     }
+    
+    #[test]
+    fn test_unfilled_position_survives_other_sells() {
+        let manager = StateManager::new();
+        let filled = Pubkey::new_unique();
+        let unfilled = Pubkey::new_unique();
+        
+        manager.open_position(Position::new(filled, 1_000, 100, "t1".to_string(), "o1".to_string()));
+        manager.open_position(Position::new(unfilled, 0, 100, "t2".to_string(), "o2".to_string()));
+        
+        manager.close_position(&filled, 150, TradeRecordType::SellManual, "sell".to_string());
+        assert!(manager.get_position(&unfilled).is_some());
+        
+        manager.sync_position_amount(&unfilled, 500);
+        let position = manager.get_position(&unfilled).unwrap();
+        assert_eq!(position.amount, 500);
+        assert!(manager.has_position(&unfilled));
+    }
+    
+    #[test]
+    fn test_target_events_are_bounded() {
+        let manager = StateManager::new();
+        let target = Pubkey::new_unique();
+        
+        for i in 0..MAX_TARGET_EVENTS + 5 {
+            manager.record_target_event(TargetEvent::new(target, None, true, format!("sig{}", i)));
+        }
+        
+        let events = manager.get_target_events();
+        assert_eq!(events.len(), MAX_TARGET_EVENTS);
+        assert_eq!(events[0].signature, format!("sig{}", MAX_TARGET_EVENTS + 4));
+    }
 }
@@ -2,6 +2,8 @@
 
 pub mod position;
 pub mod manager;
+pub mod control;
 
 pub use position::*;
 pub use manager::*;
+pub use control::*;
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashSet;
 
+use crate::token::TokenInfo;
+
 /// Represents an open trading position
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
@@ -26,6 +28,12 @@ pub struct Position {
     pub target_buy_signature: String,
     /// Our buy transaction signature
     pub our_buy_signature: String,
+    /// Wallet holding the tokens (None = main wallet)
+    #[serde(default)]
+    pub wallet: Option<Pubkey>,
+    /// Metadata, authorities and holder distribution, filled in after the buy
+    #[serde(default)]
+    pub token_info: Option<TokenInfo>,
 }
 
 impl Position {
@@ -53,9 +61,17 @@ impl Position {
             triggered_tp_tiers: HashSet::new(),
             target_buy_signature,
             our_buy_signature,
+            wallet: None,
+            token_info: None,
         }
     }
     
+    /// Set the wallet holding the tokens
+    pub fn with_wallet(mut self, wallet: Pubkey) -> Self {
+        self.wallet = Some(wallet);
+        self
+    }
+    
     /// Calculate current profit multiplier
     pub fn profit_multiplier(&self) -> f64 {
         if self.invested_sol == 0 {
@@ -137,6 +153,10 @@ pub enum TradeRecordType {
     SellTakeProfit,
     SellCopyExit,
     SellManual,
+    /// Emergency exit after a rug signal on a held token
+    SellSafetyExit,
+    /// Exit after the token's creator sold or moved supply
+    SellCreatorExit,
 }
 
 impl TradeRecord {
@@ -181,6 +201,34 @@ impl TradeRecord {
     }
 }
 
+/// A target wallet trade seen on the stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetEvent {
+    /// Target wallet that traded
+    pub target: Pubkey,
+    /// Token mint, if it could be extracted from the logs
+    pub token_mint: Option<Pubkey>,
+    /// Buy or sell
+    pub is_buy: bool,
+    /// Target transaction signature
+    pub signature: String,
+    /// When we saw it
+    pub seen_at: DateTime<Utc>,
+}
+
+impl TargetEvent {
+    /// Create an event seen now
+    pub fn new(target: Pubkey, token_mint: Option<Pubkey>, is_buy: bool, signature: String) -> Self {
+        Self {
+            target,
+            token_mint,
+            is_buy,
+            signature,
+            seen_at: Utc::now(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,176 @@
+//! Concentrated liquidity swap math shared by Raydium CLMM and Orca Whirlpool
+//!
+//! Prices are Q64.64 square roots of token 1 (B) per token 0 (A) in raw units. Quotes are
+//! computed in floating point; they only size the slippage thresholds, the program does the
+//! exact math on chain.
+
+/// Lowest sqrt price either program accepts
+pub const MIN_SQRT_PRICE_X64: u128 = 4_295_048_016;
+/// Highest sqrt price either program accepts
+pub const MAX_SQRT_PRICE_X64: u128 = 79_226_673_515_401_279_992_447_579_055;
+
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+
+/// Fee rates of both programs are in millionths
+const FEE_RATE_DENOMINATOR: f64 = 1_000_000.0;
+
+/// An initialized tick and the liquidity that changes when the price crosses it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tick {
+    pub index: i32,
+    pub liquidity_net: i128,
+}
+
+/// Pool state a quote starts from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClmmState {
+    pub sqrt_price_x64: u128,
+    pub liquidity: u128,
+    pub tick_current: i32,
+    /// Millionths of the input taken as fee
+    pub fee_rate: u32,
+}
+
+/// Outcome of an exact input quote
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwapQuote {
+    /// Input consumed, fee included
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub sqrt_price_x64_after: u128,
+    pub ticks_crossed: usize,
+}
+
+/// Exact input amounts and the price limit handed to a swap instruction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwapLimits {
+    pub amount_in: u64,
+    /// Minimum output
+    pub other_amount_threshold: u64,
+    pub sqrt_price_limit_x64: u128,
+}
+
+/// Sqrt price at a tick (1.0001^tick)
+pub fn sqrt_price_x64_at_tick(tick: i32) -> u128 {
+    (1.0001f64.powf(tick as f64 / 2.0) * Q64) as u128
+}
+
+/// First tick of the array containing `tick`
+pub fn tick_array_start_index(tick: i32, tick_spacing: u16, ticks_per_array: i32) -> i32 {
+    let ticks_in_array = tick_spacing as i32 * ticks_per_array;
+    tick.div_euclid(ticks_in_array) * ticks_in_array
+}
+
+/// Price limit `slippage_bps` away from the current price in the swap direction
+/// A to B swaps push the price down, B to A swaps push it up.
+pub fn sqrt_price_limit_x64(sqrt_price_x64: u128, a_to_b: bool, slippage_bps: u64) -> u128 {
+    let slippage = slippage_bps as f64 / 10_000.0;
+    let factor = if a_to_b { (1.0 - slippage).max(0.0).sqrt() } else { (1.0 + slippage).sqrt() };
+    ((sqrt_price_x64 as f64 * factor) as u128).clamp(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64)
+}
+
+/// Quote swapping exactly `amount_in` through the given initialized ticks
+/// Ticks outside the fetched tick arrays are unknown, so the quote stops at the last one
+/// and reports less input consumed than requested.
+pub fn quote_exact_input(state: &ClmmState, ticks: &[Tick], amount_in: u64, a_to_b: bool) -> SwapQuote {
+    let fee = state.fee_rate as f64 / FEE_RATE_DENOMINATOR;
+    let mut ticks: Vec<Tick> = ticks.iter()
+        .filter(|tick| if a_to_b { tick.index <= state.tick_current } else { tick.index > state.tick_current })
+        .copied()
+        .collect();
+    // Nearest tick first in the swap direction
+    ticks.sort_by_key(|tick| if a_to_b { -tick.index } else { tick.index });
+    
+    let mut sqrt_price = state.sqrt_price_x64 as f64 / Q64;
+    let mut liquidity = state.liquidity as f64;
+    let mut remaining = amount_in as f64;
+    let mut amount_out = 0.0;
+    let mut ticks_crossed = 0;
+    
+    for tick in &ticks {
+        let target = sqrt_price_x64_at_tick(tick.index) as f64 / Q64;
+        if liquidity > 0.0 {
+            let input_to_target = if a_to_b {
+                liquidity * (1.0 / target - 1.0 / sqrt_price)
+            } else {
+                liquidity * (target - sqrt_price)
+            };
+            let available = remaining * (1.0 - fee);
+            if available < input_to_target {
+                let next = if a_to_b {
+                    liquidity * sqrt_price / (liquidity + available * sqrt_price)
+                } else {
+                    sqrt_price + available / liquidity
+                };
+                amount_out += output(liquidity, sqrt_price, next, a_to_b);
+                sqrt_price = next;
+                remaining = 0.0;
+                break;
+            }
+            amount_out += output(liquidity, sqrt_price, target, a_to_b);
+            remaining -= input_to_target / (1.0 - fee);
+        }
+        sqrt_price = target;
+        ticks_crossed += 1;
+        let net = tick.liquidity_net as f64;
+        liquidity = if a_to_b { liquidity - net } else { liquidity + net }.max(0.0);
+    }
+    
+    SwapQuote {
+        amount_in: amount_in - remaining.max(0.0).round() as u64,
+        amount_out: amount_out as u64,
+        sqrt_price_x64_after: (sqrt_price * Q64) as u128,
+        ticks_crossed,
+    }
+}
+
+/// Output for moving the price between two sqrt prices at constant liquidity
+fn output(liquidity: f64, from: f64, to: f64, a_to_b: bool) -> f64 {
+    if a_to_b {
+        liquidity * (from - to)
+    } else {
+        liquidity * (1.0 / from - 1.0 / to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_tick_math() {
+        assert_eq!(sqrt_price_x64_at_tick(0), 1u128 << 64);
+        assert!(sqrt_price_x64_at_tick(-443_636) >= MIN_SQRT_PRICE_X64 - 1_000);
+        assert_eq!(tick_array_start_index(-1, 10, 60), -600);
+        assert_eq!(tick_array_start_index(599, 10, 60), 0);
+        assert_eq!(tick_array_start_index(6_400, 64, 88), 5_632);
+        
+        let price = 1u128 << 64;
+        assert!(sqrt_price_limit_x64(price, true, 2_500) < price);
+        assert!(sqrt_price_limit_x64(price, false, 2_500) > price);
+        assert_eq!(sqrt_price_limit_x64(price, true, 10_000), MIN_SQRT_PRICE_X64);
+    }
+    
+    #[test]
+    fn test_quote_crosses_ticks() {
+        // Price 1.0 with liquidity that halves below tick -100
+        let state = ClmmState { sqrt_price_x64: 1u128 << 64, liquidity: 1_000_000_000, tick_current: 0, fee_rate: 0 };
+        let ticks = [Tick { index: -100, liquidity_net: 500_000_000 }, Tick { index: -1_000, liquidity_net: 500_000_000 }];
+        
+        let small = quote_exact_input(&state, &ticks, 1_000, true);
+        assert_eq!((small.amount_in, small.ticks_crossed), (1_000, 0));
+        assert!(small.amount_out <= 1_000 && small.amount_out >= 998, "out {}", small.amount_out);
+        
+        let large = quote_exact_input(&state, &ticks, 10_000_000, true);
+        assert_eq!(large.ticks_crossed, 1);
+        assert!(large.sqrt_price_x64_after < sqrt_price_x64_at_tick(-100));
+        
+        // Running out of known ticks stops the quote short
+        let huge = quote_exact_input(&state, &ticks, u64::MAX / 2, true);
+        assert!(huge.amount_in < u64::MAX / 2);
+        
+        // Fees reduce the output
+        let with_fee = quote_exact_input(&ClmmState { fee_rate: 3_000, ..state }, &ticks, 1_000_000, true);
+        assert!(with_fee.amount_out < quote_exact_input(&state, &ticks, 1_000_000, true).amount_out);
+    }
+}
@@ -0,0 +1,150 @@
+//! Pieces shared by every native swap: SOL wrapping, priority fees and slippage
+
+use anyhow::{Context, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::Instruction,
+    message::{Message, VersionedMessage},
+    pubkey,
+    pubkey::Pubkey,
+    signature::Signature,
+    system_instruction,
+    transaction::VersionedTransaction,
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+use crate::token::TokenProgram;
+
+/// SPL Memo program, required by swap instructions that support Token-2022 transfers
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Wrapped SOL mint
+pub fn wsol_mint() -> Pubkey {
+    spl_token::native_mint::id()
+}
+
+/// The wallet's WSOL account
+pub fn wsol_account(owner: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, &wsol_mint())
+}
+
+/// Create the wallet's WSOL account if needed and wrap `lamports` into it
+pub fn wrap_sol_instructions(owner: &Pubkey, lamports: u64) -> Result<Vec<Instruction>> {
+    let account = wsol_account(owner);
+    let mut instructions = vec![create_associated_token_account_idempotent(owner, owner, &wsol_mint(), &spl_token::id())];
+    if lamports > 0 {
+        instructions.push(system_instruction::transfer(owner, &account, lamports));
+        instructions.push(spl_token::instruction::sync_native(&spl_token::id(), &account)?);
+    }
+    Ok(instructions)
+}
+
+/// Close the wallet's WSOL account, unwrapping whatever it holds back to SOL
+pub fn unwrap_sol_instruction(owner: &Pubkey) -> Result<Instruction> {
+    Ok(spl_token::instruction::close_account(&spl_token::id(), &wsol_account(owner), owner, owner, &[])?)
+}
+
+/// Wrap a swap with the SOL and token account handling around it
+/// WSOL input is wrapped first, the output account is created if missing and any WSOL left
+/// over is unwrapped at the end.
+pub fn with_sol_wrapping(
+    owner: &Pubkey,
+    input_mint: &Pubkey,
+    amount_in: u64,
+    output_mint: &Pubkey,
+    output_program: &Pubkey,
+    swap: Instruction,
+) -> Result<Vec<Instruction>> {
+    let wsol = wsol_mint();
+    let mut instructions = wrap_sol_instructions(owner, if *input_mint == wsol { amount_in } else { 0 })?;
+    if *output_mint != wsol {
+        instructions.push(create_associated_token_account_idempotent(owner, owner, output_mint, output_program));
+    }
+    instructions.push(swap);
+    instructions.push(unwrap_sol_instruction(owner)?);
+    Ok(instructions)
+}
+
+/// Compute unit limit and a price that spends about `priority_fee_lamports` in total
+pub fn priority_fee_instructions(priority_fee_lamports: u64, compute_units: u32) -> [Instruction; 2] {
+    let micro_lamports = priority_fee_lamports.saturating_mul(1_000_000) / compute_units.max(1) as u64;
+    [
+        ComputeBudgetInstruction::set_compute_unit_limit(compute_units),
+        ComputeBudgetInstruction::set_compute_unit_price(micro_lamports),
+    ]
+}
+
+/// Unsigned transaction paying from `payer`, ready to be signed or given a durable nonce
+pub fn unsigned_transaction(payer: &Pubkey, instructions: &[Instruction], recent_blockhash: Hash) -> VersionedTransaction {
+    let message = Message::new_with_blockhash(instructions, Some(payer), &recent_blockhash);
+    VersionedTransaction {
+        signatures: vec![Signature::default(); message.header.num_required_signatures as usize],
+        message: VersionedMessage::Legacy(message),
+    }
+}
+
+/// Lowest acceptable output for `amount` with `slippage_bps` tolerance
+pub fn min_amount_out(amount: u64, slippage_bps: u64) -> u64 {
+    (amount as u128 * 10_000u128.saturating_sub(slippage_bps as u128) / 10_000) as u64
+}
+
+/// Highest acceptable input for `amount` with `slippage_bps` tolerance
+pub fn max_amount_in(amount: u64, slippage_bps: u64) -> u64 {
+    (amount as u128 * (10_000 + slippage_bps as u128) / 10_000).min(u64::MAX as u128) as u64
+}
+
+/// Fetch accounts in batches of the RPC's 100 key limit, None where an account does not exist
+pub fn fetch_accounts(rpc_client: &RpcClient, keys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+    let mut accounts = Vec::with_capacity(keys.len());
+    for chunk in keys.chunks(100) {
+        accounts.extend(rpc_client.get_multiple_accounts(chunk).context("Failed to fetch accounts")?);
+    }
+    Ok(accounts)
+}
+
+/// Token program owning each mint
+pub fn fetch_mint_programs(rpc_client: &RpcClient, mints: &[Pubkey]) -> Result<Vec<TokenProgram>> {
+    fetch_accounts(rpc_client, mints)?
+        .iter()
+        .zip(mints)
+        .map(|(account, mint)| {
+            account.as_ref()
+                .and_then(|account| TokenProgram::from_id(&account.owner))
+                .with_context(|| format!("{} is not a token mint", mint))
+        })
+        .collect()
+}
+
+/// Raw amount held by an SPL Token or Token-2022 account
+pub fn token_account_amount(data: &[u8]) -> Option<u64> {
+    data.get(64..72)?.try_into().ok().map(u64::from_le_bytes)
+}
+
+/// Raw supply of an SPL Token or Token-2022 mint
+pub fn mint_supply(data: &[u8]) -> Option<u64> {
+    data.get(36..44)?.try_into().ok().map(u64::from_le_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_slippage_and_wrapping() {
+        assert_eq!(min_amount_out(1_000, 2_500), 750);
+        assert_eq!(max_amount_in(1_000, 2_500), 1_250);
+        assert_eq!(max_amount_in(u64::MAX, 100), u64::MAX);
+        
+        let owner = Pubkey::new_unique();
+        assert_eq!(wrap_sol_instructions(&owner, 5).unwrap().len(), 3);
+        assert_eq!(wrap_sol_instructions(&owner, 0).unwrap().len(), 1);
+        assert_eq!(wsol_mint().to_string(), crate::decoder::WSOL_MINT);
+        
+        let tx = unsigned_transaction(&owner, &[unwrap_sol_instruction(&owner).unwrap()], Hash::default());
+        assert_eq!(tx.signatures.len(), 1);
+        assert_eq!(tx.message.static_account_keys()[0], owner);
+    }
+}
@@ -0,0 +1,138 @@
+//! Raydium LaunchLab bonding curve buys and sells
+
+use anyhow::{Context, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use crate::decoder::launchlab::{self, PoolState};
+use crate::decoder::DexProgram;
+use crate::token::TokenProgram;
+use super::{fetch_accounts, fetch_mint_programs, min_amount_out, with_sol_wrapping, wsol_mint};
+
+/// The SOL-quoted LaunchLab pool of `token_mint`, None if the token did not launch there
+pub fn fetch_pool(rpc_client: &RpcClient, token_mint: &Pubkey) -> Result<Option<(Pubkey, PoolState)>> {
+    let address = launchlab::pool_address(token_mint, &wsol_mint()).context("Invalid LaunchLab program id")?;
+    let account = fetch_accounts(rpc_client, &[address])?.pop().flatten();
+    Ok(account.and_then(|account| launchlab::decode_pool_state(&account.data)).map(|pool| (address, pool)))
+}
+
+/// Spot price of `token_mint` in lamports per raw token on its LaunchLab curve, None if it did
+/// not launch there or has left the curve
+pub fn price(rpc_client: &RpcClient, token_mint: &Pubkey) -> Result<Option<f64>> {
+    let Some((_, pool)) = fetch_pool(rpc_client, token_mint)? else { return Ok(None) };
+    Ok(pool.price().filter(|_| pool.is_trading()))
+}
+
+/// buy_exact_in or sell_exact_in instruction for `amount_in` of quote (buy) or base (sell)
+pub fn trade_instruction(
+    owner: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &PoolState,
+    base_program: TokenProgram,
+    is_buy: bool,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<Instruction> {
+    let program_id = DexProgram::RaydiumLaunchLab.program_id().context("Invalid LaunchLab program id")?;
+    let authority = launchlab::authority_address().context("Invalid LaunchLab program id")?;
+    let event_authority = launchlab::event_authority_address().context("Invalid LaunchLab program id")?;
+    let accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new_readonly(authority, false),
+        AccountMeta::new_readonly(pool.global_config, false),
+        AccountMeta::new_readonly(pool.platform_config, false),
+        AccountMeta::new(*pool_address, false),
+        AccountMeta::new(base_program.associated_token_address(owner, &pool.base_mint), false),
+        AccountMeta::new(TokenProgram::Spl.associated_token_address(owner, &pool.quote_mint), false),
+        AccountMeta::new(pool.base_vault, false),
+        AccountMeta::new(pool.quote_vault, false),
+        AccountMeta::new_readonly(pool.base_mint, false),
+        AccountMeta::new_readonly(pool.quote_mint, false),
+        AccountMeta::new_readonly(base_program.id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(event_authority, false),
+        AccountMeta::new_readonly(program_id, false),
+    ];
+    let discriminator = if is_buy { launchlab::BUY_EXACT_IN_DISCRIMINATOR } else { launchlab::SELL_EXACT_IN_DISCRIMINATOR };
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+    // share_fee_rate: no referrer
+    data.extend_from_slice(&0u64.to_le_bytes());
+    Ok(Instruction { program_id, accounts, data })
+}
+
+/// Quote and build a buy with `amount_in` lamports or a sell of `amount_in` raw tokens
+/// Returns the instructions and the quoted output.
+pub fn swap_instructions(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &PoolState,
+    is_buy: bool,
+    amount_in: u64,
+    slippage_bps: u64,
+) -> Result<(Vec<Instruction>, u64)> {
+    if !pool.is_trading() {
+        anyhow::bail!("LaunchLab pool {} has left the bonding curve (status {})", pool_address, pool.status);
+    }
+    let accounts = fetch_accounts(rpc_client, &[pool.global_config, pool.platform_config])?;
+    let trade_fee_rate = accounts[0].as_ref()
+        .and_then(|account| launchlab::decode_trade_fee_rate(&account.data))
+        .context("Invalid LaunchLab global config")?;
+    let platform_fee_rate = accounts[1].as_ref()
+        .and_then(|account| launchlab::decode_platform_fee_rate(&account.data))
+        .unwrap_or(0);
+    let fee_rate = trade_fee_rate + platform_fee_rate;
+    let amount_out = if is_buy { pool.buy_quote(amount_in, fee_rate) } else { pool.sell_quote(amount_in, fee_rate) };
+    if amount_out == 0 {
+        anyhow::bail!("LaunchLab pool {} quotes nothing for {} in", pool_address, amount_in);
+    }
+    
+    let base_program = fetch_mint_programs(rpc_client, &[pool.base_mint])?[0];
+    let swap = trade_instruction(
+        owner,
+        pool_address,
+        pool,
+        base_program,
+        is_buy,
+        amount_in,
+        min_amount_out(amount_out, slippage_bps),
+    )?;
+    let (input_mint, output_mint, output_program) = if is_buy {
+        (pool.quote_mint, pool.base_mint, base_program.id())
+    } else {
+        (pool.base_mint, pool.quote_mint, spl_token::id())
+    };
+    let instructions = with_sol_wrapping(owner, &input_mint, amount_in, &output_mint, &output_program, swap)?;
+    Ok((instructions, amount_out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_trade_instruction() {
+        let mut data = launchlab::POOL_STATE_DISCRIMINATOR.to_vec();
+        data.resize(429, 0);
+        let mint = Pubkey::new_unique();
+        data[205..237].copy_from_slice(mint.as_ref());
+        data[237..269].copy_from_slice(wsol_mint().as_ref());
+        let pool = launchlab::decode_pool_state(&data).unwrap();
+        
+        let (owner, address) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = trade_instruction(&owner, &address, &pool, TokenProgram::Token2022, true, 1_000, 900).unwrap();
+        assert_eq!(ix.accounts.len(), 15);
+        assert_eq!(ix.accounts[5].pubkey, TokenProgram::Token2022.associated_token_address(&owner, &mint));
+        assert_eq!(ix.accounts[11].pubkey, spl_token_2022::id());
+        assert_eq!(ix.data[..8], launchlab::BUY_EXACT_IN_DISCRIMINATOR);
+        assert_eq!((&ix.data[8..16], &ix.data[16..24]), (&1_000u64.to_le_bytes()[..], &900u64.to_le_bytes()[..]));
+        
+        let ix = trade_instruction(&owner, &address, &pool, TokenProgram::Spl, false, 1_000, 900).unwrap();
+        assert_eq!(ix.data[..8], launchlab::SELL_EXACT_IN_DISCRIMINATOR);
+    }
+}
@@ -0,0 +1,156 @@
+//! Meteora pool lookups and spot prices for tokens trading against SOL
+
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::pubkey::Pubkey;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+use crate::decoder::{meteora_amm, meteora_dlmm, DexProgram};
+use super::meteora_amm::PoolVaults;
+use super::wsol_mint;
+
+/// How long a mint without a Meteora SOL pool is remembered before searching again
+const MISSING_POOL_TTL: Duration = Duration::from_secs(300);
+
+/// A token's SOL pool on one of the Meteora programs
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeteoraPool {
+    Dlmm { address: Pubkey, pair: meteora_dlmm::LbPair },
+    DynamicAmm { address: Pubkey, pool: meteora_amm::Pool },
+}
+
+impl MeteoraPool {
+    pub fn address(&self) -> Pubkey {
+        match self {
+            MeteoraPool::Dlmm { address, .. } | MeteoraPool::DynamicAmm { address, .. } => *address,
+        }
+    }
+}
+
+/// Meteora DLMM and dynamic AMM pools of held tokens, for take profit prices
+pub struct MeteoraPools {
+    rpc_client: AsyncRpcClient,
+    /// Pool addresses never change; their state is re-read for every price
+    pools: DashMap<Pubkey, MeteoraPool>,
+    /// Mints found without a pool and when
+    missing: DashMap<Pubkey, Instant>,
+}
+
+impl MeteoraPools {
+    pub fn new(rpc_url: String) -> Self {
+        Self {
+            rpc_client: AsyncRpcClient::new(rpc_url),
+            pools: DashMap::new(),
+            missing: DashMap::new(),
+        }
+    }
+    
+    /// The mint's SOL pool, DLMM first, or None if it has neither
+    pub async fn pool(&self, mint: &Pubkey) -> Result<Option<MeteoraPool>> {
+        if let Some(pool) = self.pools.get(mint) {
+            return Ok(Some(pool.clone()));
+        }
+        if self.missing.get(mint).is_some_and(|since| since.elapsed() < MISSING_POOL_TTL) {
+            return Ok(None);
+        }
+        let pool = match self.find_dlmm_pair(mint).await? {
+            Some(pool) => Some(pool),
+            None => self.find_dynamic_amm_pool(mint).await?,
+        };
+        match &pool {
+            Some(pool) => {
+                info!("🌠 {} trades on Meteora pool {}", mint, pool.address());
+                self.pools.insert(*mint, pool.clone());
+                self.missing.remove(mint);
+            }
+            None => {
+                self.missing.insert(*mint, Instant::now());
+            }
+        }
+        Ok(pool)
+    }
+    
+    async fn find_dlmm_pair(&self, mint: &Pubkey) -> Result<Option<MeteoraPool>> {
+        let program = DexProgram::MeteoraDlmm.program_id().context("Invalid DLMM program id")?;
+        let sides = [meteora_dlmm::TOKEN_X_MINT_OFFSET, meteora_dlmm::TOKEN_Y_MINT_OFFSET];
+        for (address, data) in self.search(&program, mint, sides).await? {
+            if let Some(pair) = meteora_dlmm::decode_lb_pair(&data).filter(|pair| pair.status == 0) {
+                return Ok(Some(MeteoraPool::Dlmm { address, pair }));
+            }
+        }
+        Ok(None)
+    }
+    
+    async fn find_dynamic_amm_pool(&self, mint: &Pubkey) -> Result<Option<MeteoraPool>> {
+        let program = DexProgram::MeteoraDynamicAmm.program_id().context("Invalid dynamic AMM program id")?;
+        let sides = [meteora_amm::TOKEN_A_MINT_OFFSET, meteora_amm::TOKEN_B_MINT_OFFSET];
+        for (address, data) in self.search(&program, mint, sides).await? {
+            if let Some(pool) = meteora_amm::decode_pool(&data).filter(|pool| pool.enabled) {
+                return Ok(Some(MeteoraPool::DynamicAmm { address, pool }));
+            }
+        }
+        Ok(None)
+    }
+    
+    /// Accounts of `program` pairing `mint` with WSOL at the two mint offsets, either way round
+    async fn search(&self, program: &Pubkey, mint: &Pubkey, [first, second]: [usize; 2]) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let mut found = Vec::new();
+        for (mint_offset, sol_offset) in [(first, second), (second, first)] {
+            let config = RpcProgramAccountsConfig {
+                filters: Some(vec![
+                    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(mint_offset, mint.to_bytes().to_vec())),
+                    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(sol_offset, wsol_mint().to_bytes().to_vec())),
+                ]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let accounts = self.rpc_client
+                .get_program_accounts_with_config(program, config)
+                .await
+                .with_context(|| format!("Failed to search Meteora pools of {}", program))?;
+            found.extend(accounts.into_iter().map(|(address, account)| (address, account.data)));
+        }
+        Ok(found)
+    }
+    
+    /// Spot price of a token in lamports per raw token, None if it has no Meteora SOL pool
+    pub async fn price(&self, mint: &Pubkey) -> Result<Option<f64>> {
+        let Some(pool) = self.pool(mint).await? else { return Ok(None) };
+        let price = match pool {
+            MeteoraPool::Dlmm { address, .. } => {
+                // The active bin moves with every swap, so the pair is re-read
+                let data = self.rpc_client.get_account_data(&address).await
+                    .context("Failed to fetch DLMM pair")?;
+                let pair = meteora_dlmm::decode_lb_pair(&data).context("Invalid DLMM pair")?;
+                // Bin prices are Y per X
+                if pair.token_x_mint == *mint { pair.price() } else { 1.0 / pair.price() }
+            }
+            MeteoraPool::DynamicAmm { address, pool } => {
+                let accounts = self.rpc_client.get_multiple_accounts(&PoolVaults::keys(&pool)).await
+                    .context("Failed to fetch Meteora vaults")?;
+                let (vaults, lp_amounts) = PoolVaults::decode_vaults(&accounts)?;
+                let lp_mints = self.rpc_client.get_multiple_accounts(&[vaults[0].lp_mint, vaults[1].lp_mint]).await
+                    .context("Failed to fetch Meteora vault LP mints")?;
+                let vaults = PoolVaults::new(vaults, lp_amounts, &lp_mints)?;
+                let (token, sol) = if pool.token_a_mint == *mint {
+                    (vaults.reserve_a, vaults.reserve_b)
+                } else {
+                    (vaults.reserve_b, vaults.reserve_a)
+                };
+                if token == 0 {
+                    anyhow::bail!("Empty Meteora pool {}", address);
+                }
+                sol as f64 / token as f64
+            }
+        };
+        Ok(Some(price))
+    }
+}
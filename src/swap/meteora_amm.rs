@@ -0,0 +1,192 @@
+//! Meteora dynamic AMM swaps through the pool's vaults
+
+use anyhow::{Context, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use crate::decoder::meteora_amm::{self, Pool, Vault};
+use crate::decoder::DexProgram;
+use super::{fetch_accounts, min_amount_out, mint_supply, token_account_amount, with_sol_wrapping};
+
+/// The pool among a transaction's accounts that trades `token_mint` against `quote_mint`
+pub fn find_pool(accounts: &[(Pubkey, Option<Account>)], token_mint: &Pubkey, quote_mint: &Pubkey) -> Option<(Pubkey, Pool)> {
+    let program = DexProgram::MeteoraDynamicAmm.program_id()?;
+    accounts.iter()
+        .filter_map(|(address, account)| {
+            let account = account.as_ref().filter(|account| account.owner == program)?;
+            Some((*address, meteora_amm::decode_pool(&account.data)?))
+        })
+        .find(|(_, pool)| {
+            let mints = [pool.token_a_mint, pool.token_b_mint];
+            mints.contains(token_mint) && mints.contains(quote_mint)
+        })
+}
+
+/// Both vaults of a pool with the token amounts the pool's LP tokens represent
+#[derive(Debug, Clone)]
+pub struct PoolVaults {
+    pub a: Vault,
+    pub b: Vault,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+}
+
+impl PoolVaults {
+    /// Accounts to read first: both vaults and the pool's LP token account in each
+    pub fn keys(pool: &Pool) -> [Pubkey; 4] {
+        [pool.a_vault, pool.b_vault, pool.a_vault_lp, pool.b_vault_lp]
+    }
+    
+    /// Decode the accounts at `keys`, returning the vaults and the pool's LP balance in each
+    /// The vaults' LP mints have to be read next for their supply.
+    pub fn decode_vaults(accounts: &[Option<Account>]) -> Result<([Vault; 2], [u64; 2])> {
+        let account = |index: usize| accounts.get(index).and_then(Option::as_ref);
+        let vault = |index: usize| account(index).and_then(|account| meteora_amm::decode_vault(&account.data));
+        let lp_amount = |index: usize| account(index).and_then(|account| token_account_amount(&account.data));
+        Ok((
+            [vault(0).context("Missing Meteora vault A")?, vault(1).context("Missing Meteora vault B")?],
+            [
+                lp_amount(2).context("Missing pool LP account in vault A")?,
+                lp_amount(3).context("Missing pool LP account in vault B")?,
+            ],
+        ))
+    }
+    
+    /// Reserves from the decoded vaults and the accounts of both vaults' LP mints
+    pub fn new([a, b]: [Vault; 2], [lp_a, lp_b]: [u64; 2], lp_mints: &[Option<Account>]) -> Result<Self> {
+        let supply = |index: usize| lp_mints.get(index).and_then(Option::as_ref).and_then(|account| mint_supply(&account.data));
+        let reserve_a = a.amount_for_lp(lp_a, supply(0).context("Missing vault A LP mint")?);
+        let reserve_b = b.amount_for_lp(lp_b, supply(1).context("Missing vault B LP mint")?);
+        Ok(Self { a, b, reserve_a, reserve_b })
+    }
+}
+
+/// Fetch a pool's vaults and work out its reserves
+pub fn fetch_vaults(rpc_client: &RpcClient, pool: &Pool) -> Result<PoolVaults> {
+    let (vaults, lp_amounts) = PoolVaults::decode_vaults(&fetch_accounts(rpc_client, &PoolVaults::keys(pool))?)?;
+    let lp_mints = fetch_accounts(rpc_client, &[vaults[0].lp_mint, vaults[1].lp_mint])?;
+    PoolVaults::new(vaults, lp_amounts, &lp_mints)
+}
+
+/// swap instruction selling exactly `amount_in` of one side of the pool
+/// The program only moves SPL Token mints, so both user accounts are plain ATAs.
+pub fn swap_instruction(
+    owner: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &Pool,
+    vaults: &PoolVaults,
+    a_to_b: bool,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<Instruction> {
+    let program_id = DexProgram::MeteoraDynamicAmm.program_id().context("Invalid dynamic AMM program id")?;
+    let vault_program = meteora_amm::vault_program_id().context("Invalid Meteora vault program id")?;
+    let user_a = spl_associated_token_account::get_associated_token_address(owner, &pool.token_a_mint);
+    let user_b = spl_associated_token_account::get_associated_token_address(owner, &pool.token_b_mint);
+    let (user_in, user_out, protocol_fee) = if a_to_b {
+        (user_a, user_b, pool.protocol_token_a_fee)
+    } else {
+        (user_b, user_a, pool.protocol_token_b_fee)
+    };
+    
+    let accounts = vec![
+        AccountMeta::new(*pool_address, false),
+        AccountMeta::new(user_in, false),
+        AccountMeta::new(user_out, false),
+        AccountMeta::new(pool.a_vault, false),
+        AccountMeta::new(pool.b_vault, false),
+        AccountMeta::new(vaults.a.token_vault, false),
+        AccountMeta::new(vaults.b.token_vault, false),
+        AccountMeta::new(vaults.a.lp_mint, false),
+        AccountMeta::new(vaults.b.lp_mint, false),
+        AccountMeta::new(pool.a_vault_lp, false),
+        AccountMeta::new(pool.b_vault_lp, false),
+        AccountMeta::new(protocol_fee, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(vault_program, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    let mut data = meteora_amm::SWAP_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+    Ok(Instruction { program_id, accounts, data })
+}
+
+/// Quote and build an exact input swap of `amount_in` of `input_mint` through a pool
+/// Returns the instructions and the quoted output.
+pub fn swap_instructions(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &Pool,
+    input_mint: &Pubkey,
+    amount_in: u64,
+    slippage_bps: u64,
+) -> Result<(Vec<Instruction>, u64)> {
+    if !pool.enabled {
+        anyhow::bail!("Meteora pool {} is disabled", pool_address);
+    }
+    let a_to_b = *input_mint == pool.token_a_mint;
+    let vaults = fetch_vaults(rpc_client, pool)?;
+    let (reserve_in, reserve_out) = if a_to_b { (vaults.reserve_a, vaults.reserve_b) } else { (vaults.reserve_b, vaults.reserve_a) };
+    let amount_out = pool.quote_exact_input(reserve_in, reserve_out, amount_in);
+    if amount_out == 0 {
+        anyhow::bail!("Meteora pool {} quotes nothing for {} in", pool_address, amount_in);
+    }
+    
+    let swap = swap_instruction(
+        owner,
+        pool_address,
+        pool,
+        &vaults,
+        a_to_b,
+        amount_in,
+        min_amount_out(amount_out, slippage_bps),
+    )?;
+    let output_mint = if a_to_b { pool.token_b_mint } else { pool.token_a_mint };
+    let instructions = with_sol_wrapping(owner, input_mint, amount_in, &output_mint, &spl_token::id(), swap)?;
+    Ok((instructions, amount_out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swap::wsol_mint;
+    
+    #[test]
+    fn test_find_pool_and_swap_instruction() {
+        let keys: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        let mut data = meteora_amm::POOL_DISCRIMINATOR.to_vec();
+        // LP mint, mint A, mint B, vault A, vault B, vault A LP, vault B LP
+        data.extend_from_slice(Pubkey::default().as_ref());
+        data.extend_from_slice(keys[0].as_ref());
+        data.extend_from_slice(wsol_mint().as_ref());
+        for key in &keys[1..5] {
+            data.extend_from_slice(key.as_ref());
+        }
+        data.extend_from_slice(&[0, 1]);
+        data.extend_from_slice(keys[5].as_ref());
+        data.resize(944, 0);
+        
+        let address = Pubkey::new_unique();
+        let program = DexProgram::MeteoraDynamicAmm.program_id().unwrap();
+        let accounts = vec![(address, Some(Account { data, owner: program, ..Account::default() }))];
+        let (found, pool) = find_pool(&accounts, &keys[0], &wsol_mint()).unwrap();
+        assert_eq!((found, pool.b_vault, pool.protocol_token_a_fee), (address, keys[2], keys[5]));
+        assert!(pool.enabled);
+        
+        let vault = Vault { total_amount: 0, token_vault: Pubkey::new_unique(), token_mint: keys[0], lp_mint: Pubkey::new_unique() };
+        let vaults = PoolVaults { a: vault.clone(), b: vault, reserve_a: 0, reserve_b: 0 };
+        let owner = Pubkey::new_unique();
+        let ix = swap_instruction(&owner, &address, &pool, &vaults, true, 1_000, 900).unwrap();
+        assert_eq!(ix.accounts.len(), 15);
+        assert_eq!(ix.accounts[2].pubkey, spl_associated_token_account::get_associated_token_address(&owner, &wsol_mint()));
+        assert_eq!(ix.accounts[11].pubkey, keys[5]);
+        assert_eq!(ix.data[..8], meteora_amm::SWAP_DISCRIMINATOR);
+        assert_eq!((&ix.data[8..16], &ix.data[16..24]), (&1_000u64.to_le_bytes()[..], &900u64.to_le_bytes()[..]));
+    }
+}
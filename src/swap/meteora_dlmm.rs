@@ -0,0 +1,236 @@
+//! Meteora DLMM swaps walking the pair's liquidity bins
+
+use anyhow::{Context, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use std::collections::HashMap;
+
+use crate::decoder::meteora_dlmm::{self, BinArray, LbPair, FEE_PRECISION};
+use crate::decoder::DexProgram;
+use crate::token::TokenProgram;
+use super::{fetch_accounts, min_amount_out, with_sol_wrapping};
+
+/// Bin arrays passed to a swap, the active one and the next two in its direction
+const SWAP_BIN_ARRAYS: usize = 3;
+
+/// Result of walking the bins for an exact input swap
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DlmmQuote {
+    /// Input consumed, less than requested when the loaded bins run dry
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub bins_crossed: u32,
+}
+
+/// Quote an exact input swap through the bins of the given bin arrays
+/// The fee rate is held at the pair's current rate; the program raises the variable fee
+/// as the swap crosses bins, so long walks quote slightly high.
+pub fn quote_exact_input(pair: &LbPair, bin_arrays: &[BinArray], amount_in: u64, swap_for_y: bool) -> DlmmQuote {
+    let bins: HashMap<i32, meteora_dlmm::Bin> = bin_arrays.iter()
+        .flat_map(|array| (array.first_bin_id()..).zip(array.bins.iter().copied()))
+        .collect();
+    let fee_rate = pair.fee_rate() as f64 / FEE_PRECISION as f64;
+    let mut quote = DlmmQuote { amount_in: 0, amount_out: 0, fee: 0, bins_crossed: 0 };
+    let mut remaining = amount_in as f64;
+    let mut bin_id = pair.active_id;
+    
+    while remaining >= 1.0 {
+        let Some(bin) = bins.get(&bin_id) else { break };
+        // X sells into the bin's Y at the bin price, Y buys its X at the inverse
+        let (available, price) = if swap_for_y {
+            (bin.amount_y as f64, pair.bin_price(bin_id))
+        } else {
+            (bin.amount_x as f64, 1.0 / pair.bin_price(bin_id))
+        };
+        let net_to_drain = available / price;
+        let gross_to_drain = net_to_drain / (1.0 - fee_rate);
+        let (gross, out) = if remaining >= gross_to_drain {
+            (gross_to_drain, available)
+        } else {
+            (remaining, remaining * (1.0 - fee_rate) * price)
+        };
+        remaining -= gross;
+        quote.amount_in += gross.ceil() as u64;
+        quote.amount_out += out.floor() as u64;
+        quote.fee += (gross * fee_rate).ceil() as u64;
+        if remaining >= 1.0 {
+            bin_id += if swap_for_y { -1 } else { 1 };
+            quote.bins_crossed += 1;
+        }
+    }
+    quote.amount_in = quote.amount_in.min(amount_in);
+    quote
+}
+
+/// The pair among a transaction's accounts that trades `token_mint` against `quote_mint`
+pub fn find_pair(accounts: &[(Pubkey, Option<Account>)], token_mint: &Pubkey, quote_mint: &Pubkey) -> Option<(Pubkey, LbPair)> {
+    let program = DexProgram::MeteoraDlmm.program_id()?;
+    accounts.iter()
+        .filter_map(|(address, account)| {
+            let account = account.as_ref().filter(|account| account.owner == program)?;
+            Some((*address, meteora_dlmm::decode_lb_pair(&account.data)?))
+        })
+        .find(|(_, pair)| {
+            let mints = [pair.token_x_mint, pair.token_y_mint];
+            mints.contains(token_mint) && mints.contains(quote_mint)
+        })
+}
+
+/// Token programs of the pair's X and Y mints
+pub fn token_programs(pair: &LbPair) -> [TokenProgram; 2] {
+    [pair.token_x_is_token_2022, pair.token_y_is_token_2022]
+        .map(|is_2022| if is_2022 { TokenProgram::Token2022 } else { TokenProgram::Spl })
+}
+
+/// swap instruction selling exactly `amount_in` of one side through the given bin arrays
+pub fn swap_instruction(
+    owner: &Pubkey,
+    pair_address: &Pubkey,
+    pair: &LbPair,
+    swap_for_y: bool,
+    bin_arrays: &[Pubkey],
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<Instruction> {
+    let program_id = DexProgram::MeteoraDlmm.program_id().context("Invalid DLMM program id")?;
+    let event_authority = meteora_dlmm::event_authority_address().context("Invalid DLMM program id")?;
+    let [program_x, program_y] = token_programs(pair);
+    let user_x = program_x.associated_token_address(owner, &pair.token_x_mint);
+    let user_y = program_y.associated_token_address(owner, &pair.token_y_mint);
+    let (user_in, user_out) = if swap_for_y { (user_x, user_y) } else { (user_y, user_x) };
+    
+    let mut accounts = vec![
+        AccountMeta::new(*pair_address, false),
+        // Optional bitmap extension and host fee account, both absent
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new(pair.reserve_x, false),
+        AccountMeta::new(pair.reserve_y, false),
+        AccountMeta::new(user_in, false),
+        AccountMeta::new(user_out, false),
+        AccountMeta::new_readonly(pair.token_x_mint, false),
+        AccountMeta::new_readonly(pair.token_y_mint, false),
+        AccountMeta::new(pair.oracle, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(program_x.id(), false),
+        AccountMeta::new_readonly(program_y.id(), false),
+        AccountMeta::new_readonly(event_authority, false),
+        AccountMeta::new_readonly(program_id, false),
+    ];
+    accounts.extend(bin_arrays.iter().map(|address| AccountMeta::new(*address, false)));
+    let mut data = meteora_dlmm::SWAP_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+    Ok(Instruction { program_id, accounts, data })
+}
+
+/// Quote and build an exact input swap of `amount_in` of `input_mint` through a pair
+/// Returns the instructions and the quote.
+pub fn swap_instructions(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+    pair_address: &Pubkey,
+    pair: &LbPair,
+    input_mint: &Pubkey,
+    amount_in: u64,
+    slippage_bps: u64,
+) -> Result<(Vec<Instruction>, DlmmQuote)> {
+    if pair.status != 0 {
+        anyhow::bail!("DLMM pair {} is disabled (status {})", pair_address, pair.status);
+    }
+    let swap_for_y = *input_mint == pair.token_x_mint;
+    let indexes = pair.initialized_bin_arrays(swap_for_y, SWAP_BIN_ARRAYS);
+    if indexes.is_empty() {
+        anyhow::bail!("DLMM pair {} has no bin arrays near bin {}", pair_address, pair.active_id);
+    }
+    let addresses = indexes.iter()
+        .map(|index| meteora_dlmm::bin_array_address(pair_address, *index))
+        .collect::<Option<Vec<_>>>()
+        .context("Invalid DLMM program id")?;
+    let bin_arrays: Vec<BinArray> = fetch_accounts(rpc_client, &addresses)?
+        .iter()
+        .flatten()
+        .filter_map(|account| meteora_dlmm::decode_bin_array(&account.data))
+        .collect();
+    let quote = quote_exact_input(pair, &bin_arrays, amount_in, swap_for_y);
+    if quote.amount_in < amount_in || quote.amount_out == 0 {
+        anyhow::bail!("DLMM pair {} lacks the liquidity for {} in (quoted {})", pair_address, amount_in, quote.amount_in);
+    }
+    
+    let swap = swap_instruction(
+        owner,
+        pair_address,
+        pair,
+        swap_for_y,
+        &addresses,
+        amount_in,
+        min_amount_out(quote.amount_out, slippage_bps),
+    )?;
+    let [program_x, program_y] = token_programs(pair);
+    let (output_mint, output_program) = if swap_for_y {
+        (pair.token_y_mint, program_y)
+    } else {
+        (pair.token_x_mint, program_x)
+    };
+    let instructions = with_sol_wrapping(owner, input_mint, amount_in, &output_mint, &output_program.id(), swap)?;
+    Ok((instructions, quote))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn pair(active_id: i32) -> LbPair {
+        let mut data = meteora_dlmm::LB_PAIR_DISCRIMINATOR.to_vec();
+        data.resize(904, 0);
+        // 1% bin step with a 1% base fee
+        data[8..10].copy_from_slice(&10_000u16.to_le_bytes());
+        data[76..80].copy_from_slice(&active_id.to_le_bytes());
+        data[80..82].copy_from_slice(&100u16.to_le_bytes());
+        meteora_dlmm::decode_lb_pair(&data).unwrap()
+    }
+    
+    #[test]
+    fn test_quote_walks_bins() {
+        let pair = pair(0);
+        let mut bins = vec![meteora_dlmm::Bin { amount_x: 0, amount_y: 0 }; 70];
+        bins[0].amount_y = 1_000_000;
+        let upper = BinArray { index: 0, bins };
+        let mut bins = vec![meteora_dlmm::Bin { amount_x: 0, amount_y: 0 }; 70];
+        bins[69].amount_y = 1_000_000;
+        let lower = BinArray { index: -1, bins };
+        
+        // A small sale fills inside the active bin at price 1 less the 1% fee
+        let quote = quote_exact_input(&pair, &[upper.clone(), lower.clone()], 10_000, true);
+        assert_eq!((quote.amount_in, quote.amount_out, quote.fee, quote.bins_crossed), (10_000, 9_900, 100, 0));
+        
+        // A large one drains bin 0 and continues into bin -1 of the lower array
+        let quote = quote_exact_input(&pair, &[upper.clone(), lower], 1_500_000, true);
+        assert_eq!(quote.bins_crossed, 1);
+        assert!(quote.amount_out > 1_000_000 && quote.amount_out < 1_500_000);
+        
+        // Without the lower array the walk stops short
+        let quote = quote_exact_input(&pair, &[upper], 1_500_000, true);
+        assert!(quote.amount_in < 1_500_000);
+        assert_eq!(quote.amount_out, 1_000_000);
+    }
+    
+    #[test]
+    fn test_swap_instruction() {
+        let pair = pair(0);
+        let owner = Pubkey::new_unique();
+        let arrays = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let ix = swap_instruction(&owner, &Pubkey::new_unique(), &pair, false, &arrays, 1_000, 900).unwrap();
+        assert_eq!(ix.accounts.len(), 17);
+        assert_eq!(ix.accounts[4].pubkey, TokenProgram::Spl.associated_token_address(&owner, &pair.token_y_mint));
+        assert!(ix.accounts[10].is_signer);
+        assert!(ix.accounts[16].is_writable);
+        assert_eq!(ix.data[..8], meteora_dlmm::SWAP_DISCRIMINATOR);
+        assert_eq!((&ix.data[8..16], &ix.data[16..24]), (&1_000u64.to_le_bytes()[..], &900u64.to_le_bytes()[..]));
+    }
+}
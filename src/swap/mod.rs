@@ -0,0 +1,21 @@
+//! Swap transactions built directly against AMM pools, without an aggregator API
+
+pub mod clmm;
+pub mod common;
+pub mod launchlab;
+pub mod meteora;
+pub mod meteora_amm;
+pub mod meteora_dlmm;
+pub mod native;
+pub mod pumpswap;
+pub mod quote;
+pub mod raydium_clmm;
+pub mod raydium_cpmm;
+pub mod whirlpool;
+
+pub use clmm::*;
+pub use common::*;
+pub use meteora::*;
+pub use native::*;
+pub use pumpswap::*;
+pub use quote::*;
@@ -0,0 +1,287 @@
+//! Copy trades built directly against the pool the target traded on
+
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{account::Account, instruction::Instruction, pubkey::Pubkey, transaction::VersionedTransaction};
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::decoder::DexProgram;
+use super::{
+    fetch_accounts, launchlab, meteora_amm, meteora_dlmm, priority_fee_instructions, raydium_clmm, raydium_cpmm,
+    token_account_amount, unsigned_transaction, whirlpool, wsol_mint,
+};
+
+/// Compute units requested for a native swap; concentrated liquidity swaps crossing ticks
+/// need more than a constant product pool
+const SWAP_COMPUTE_UNITS: u32 = 400_000;
+
+/// A target's SOL trade on a venue with a native builder, and the accounts its pool is among
+#[derive(Debug, Clone, PartialEq)]
+pub struct NativeRoute {
+    pub dex: DexProgram,
+    pub accounts: Vec<Pubkey>,
+}
+
+impl NativeRoute {
+    /// The route of a trade on `dex`, None for venues we have no builder for
+    pub fn new(dex: DexProgram, accounts: Vec<Pubkey>) -> Option<Self> {
+        matches!(
+            dex,
+            DexProgram::RaydiumClmm
+                | DexProgram::OrcaWhirlpool
+                | DexProgram::RaydiumCpmm
+                | DexProgram::RaydiumLaunchLab
+                | DexProgram::MeteoraDlmm
+                | DexProgram::MeteoraDynamicAmm
+        )
+        .then_some(Self { dex, accounts })
+    }
+}
+
+/// Swap transactions against the pools held tokens were bought on
+pub struct NativeSwaps {
+    rpc_client: Arc<RpcClient>,
+    slippage_bps: u64,
+    /// Routes of held tokens, kept for their sells
+    routes: DashMap<Pubkey, NativeRoute>,
+}
+
+impl NativeSwaps {
+    pub fn new(rpc_url: String, slippage_bps: u64) -> Self {
+        Self {
+            rpc_client: Arc::new(RpcClient::new(rpc_url)),
+            slippage_bps,
+            routes: DashMap::new(),
+        }
+    }
+    
+    /// Keep the route a token is bought through, for its buy and later sells
+    pub fn remember(&self, mint: Pubkey, route: NativeRoute) {
+        self.routes.insert(mint, route);
+    }
+    
+    pub fn route(&self, mint: &Pubkey) -> Option<NativeRoute> {
+        self.routes.get(mint).map(|route| route.clone())
+    }
+    
+    pub fn forget(&self, mint: &Pubkey) {
+        self.routes.remove(mint);
+    }
+    
+    /// Spot price of a held token in lamports per raw token from its CPMM pool or LaunchLab
+    /// curve reserves, None for other routes
+    pub async fn price(&self, mint: &Pubkey) -> Result<Option<f64>> {
+        let Some(route) = self.route(mint) else { return Ok(None) };
+        let (rpc_client, mint) = (self.rpc_client.clone(), *mint);
+        tokio::task::spawn_blocking(move || match route.dex {
+            DexProgram::RaydiumCpmm => {
+                let accounts = route_accounts(&rpc_client, &route)?;
+                let (_, pool) = raydium_cpmm::find_pool(&accounts, &mint, &wsol_mint())
+                    .with_context(|| format!("No Raydium CPMM pool for {} in the target's transaction", mint))?;
+                raydium_cpmm::price(&rpc_client, &pool, &mint).map(Some)
+            }
+            DexProgram::RaydiumLaunchLab => launchlab::price(&rpc_client, &mint),
+            _ => Ok(None),
+        })
+        .await
+        .context("Native price reader panicked")?
+    }
+    
+    /// SOL side of a held token's pool or LaunchLab curve in lamports, None without a native
+    /// route or once the token has left the curve
+    pub async fn sol_liquidity(&self, mint: &Pubkey) -> Result<Option<u64>> {
+        let Some(route) = self.route(mint) else { return Ok(None) };
+        let (rpc_client, mint) = (self.rpc_client.clone(), *mint);
+        tokio::task::spawn_blocking(move || sol_liquidity(&rpc_client, &route, &mint))
+            .await
+            .context("Native liquidity reader panicked")?
+    }
+    
+    /// Unsigned buy with `amount_in` lamports or sell of `amount_in` raw tokens through the route
+    pub async fn swap_transaction(
+        &self,
+        route: &NativeRoute,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        is_buy: bool,
+        amount_in: u64,
+        priority_fee_lamports: u64,
+    ) -> Result<VersionedTransaction> {
+        let (rpc_client, route, owner, mint) = (self.rpc_client.clone(), route.clone(), *owner, *mint);
+        let slippage_bps = self.slippage_bps;
+        // The pool builders read accounts with the blocking client
+        tokio::task::spawn_blocking(move || {
+            let mut instructions = priority_fee_instructions(priority_fee_lamports, SWAP_COMPUTE_UNITS).to_vec();
+            instructions.extend(swap_instructions(&rpc_client, &route, &owner, &mint, is_buy, amount_in, slippage_bps)?);
+            let recent_blockhash = rpc_client.get_latest_blockhash()
+                .context("Failed to get recent blockhash")?;
+            Ok(unsigned_transaction(&owner, &instructions, recent_blockhash))
+        })
+        .await
+        .context("Native swap builder panicked")?
+    }
+}
+
+/// Swap instructions between SOL and `mint` on the route's pool (SOL in when buying)
+fn swap_instructions(
+    rpc_client: &RpcClient,
+    route: &NativeRoute,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    is_buy: bool,
+    amount_in: u64,
+    slippage_bps: u64,
+) -> Result<Vec<Instruction>> {
+    let input_mint = if is_buy { wsol_mint() } else { *mint };
+    match route.dex {
+        DexProgram::RaydiumClmm => {
+            let accounts = route_accounts(rpc_client, route)?;
+            let (address, pool) = raydium_clmm::find_pool(&accounts, mint, &wsol_mint())
+                .with_context(|| format!("No Raydium CLMM pool for {} in the target's transaction", mint))?;
+            let (instructions, quote) =
+                raydium_clmm::swap_instructions(rpc_client, owner, &address, &pool, &input_mint, amount_in, slippage_bps)?;
+            debug!("Raydium CLMM quote via {}: {} in, {} out, {} ticks crossed", address, quote.amount_in, quote.amount_out, quote.ticks_crossed);
+            Ok(instructions)
+        }
+        DexProgram::OrcaWhirlpool => {
+            let accounts = route_accounts(rpc_client, route)?;
+            let (address, pool) = whirlpool::find_pool(&accounts, mint, &wsol_mint())
+                .with_context(|| format!("No Orca whirlpool for {} in the target's transaction", mint))?;
+            let (instructions, quote) =
+                whirlpool::swap_instructions(rpc_client, owner, &address, &pool, &input_mint, amount_in, slippage_bps)?;
+            debug!("Whirlpool quote via {}: {} in, {} out, {} ticks crossed", address, quote.amount_in, quote.amount_out, quote.ticks_crossed);
+            Ok(instructions)
+        }
+        DexProgram::RaydiumCpmm => {
+            let accounts = route_accounts(rpc_client, route)?;
+            let (address, pool) = raydium_cpmm::find_pool(&accounts, mint, &wsol_mint())
+                .with_context(|| format!("No Raydium CPMM pool for {} in the target's transaction", mint))?;
+            let (instructions, amount_out) =
+                raydium_cpmm::swap_instructions(rpc_client, owner, &address, &pool, &input_mint, amount_in, slippage_bps)?;
+            debug!("Raydium CPMM quote via {}: {} in, {} out", address, amount_in, amount_out);
+            Ok(instructions)
+        }
+        DexProgram::RaydiumLaunchLab => {
+            // The pool is derived from the mint
+            let (address, pool) = launchlab::fetch_pool(rpc_client, mint)?
+                .with_context(|| format!("{} has no SOL LaunchLab pool", mint))?;
+            let (instructions, amount_out) =
+                launchlab::swap_instructions(rpc_client, owner, &address, &pool, is_buy, amount_in, slippage_bps)?;
+            debug!("LaunchLab quote via {}: {} in, {} out", address, amount_in, amount_out);
+            Ok(instructions)
+        }
+        DexProgram::MeteoraDlmm => {
+            let accounts = route_accounts(rpc_client, route)?;
+            let (address, pair) = meteora_dlmm::find_pair(&accounts, mint, &wsol_mint())
+                .with_context(|| format!("No Meteora DLMM pair for {} in the target's transaction", mint))?;
+            let (instructions, quote) =
+                meteora_dlmm::swap_instructions(rpc_client, owner, &address, &pair, &input_mint, amount_in, slippage_bps)?;
+            debug!("Meteora DLMM quote via {}: {} in, {} out, {} bins crossed", address, quote.amount_in, quote.amount_out, quote.bins_crossed);
+            Ok(instructions)
+        }
+        DexProgram::MeteoraDynamicAmm => {
+            let accounts = route_accounts(rpc_client, route)?;
+            let (address, pool) = meteora_amm::find_pool(&accounts, mint, &wsol_mint())
+                .with_context(|| format!("No Meteora dynamic AMM pool for {} in the target's transaction", mint))?;
+            let (instructions, amount_out) =
+                meteora_amm::swap_instructions(rpc_client, owner, &address, &pool, &input_mint, amount_in, slippage_bps)?;
+            debug!("Meteora dynamic AMM quote via {}: {} in, {} out", address, amount_in, amount_out);
+            Ok(instructions)
+        }
+        dex => anyhow::bail!("No native swap builder for {:?}", dex),
+    }
+}
+
+/// Lamports on the SOL side of the route's pool, read through its decoder
+fn sol_liquidity(rpc_client: &RpcClient, route: &NativeRoute, mint: &Pubkey) -> Result<Option<u64>> {
+    let sol = wsol_mint();
+    // The vault of whichever side is WSOL
+    let vault = |(mint_0, vault_0): (Pubkey, Pubkey), vault_1: Pubkey| if mint_0 == sol { vault_0 } else { vault_1 };
+    let vault_amount = |vault: Pubkey| -> Result<u64> {
+        fetch_accounts(rpc_client, &[vault])?
+            .pop()
+            .flatten()
+            .and_then(|account| token_account_amount(&account.data))
+            .with_context(|| format!("Missing pool vault {}", vault))
+    };
+    match route.dex {
+        DexProgram::RaydiumClmm => {
+            let accounts = route_accounts(rpc_client, route)?;
+            let (_, pool) = raydium_clmm::find_pool(&accounts, mint, &sol)
+                .with_context(|| format!("No Raydium CLMM pool for {} in the target's transaction", mint))?;
+            vault_amount(vault((pool.token_mint_0, pool.token_vault_0), pool.token_vault_1)).map(Some)
+        }
+        DexProgram::OrcaWhirlpool => {
+            let accounts = route_accounts(rpc_client, route)?;
+            let (_, pool) = whirlpool::find_pool(&accounts, mint, &sol)
+                .with_context(|| format!("No Orca whirlpool for {} in the target's transaction", mint))?;
+            vault_amount(vault((pool.token_mint_a, pool.token_vault_a), pool.token_vault_b)).map(Some)
+        }
+        DexProgram::RaydiumCpmm => {
+            let accounts = route_accounts(rpc_client, route)?;
+            let (_, pool) = raydium_cpmm::find_pool(&accounts, mint, &sol)
+                .with_context(|| format!("No Raydium CPMM pool for {} in the target's transaction", mint))?;
+            // Vault balances less the fees owed out of them
+            let (reserve_0, reserve_1) = pool.reserves(vault_amount(pool.token_0_vault)?, vault_amount(pool.token_1_vault)?);
+            Ok(Some(if pool.token_0_mint == sol { reserve_0 } else { reserve_1 }))
+        }
+        DexProgram::RaydiumLaunchLab => Ok(launchlab::fetch_pool(rpc_client, mint)?
+            .filter(|(_, pool)| pool.is_trading())
+            .map(|(_, pool)| pool.real_quote)),
+        DexProgram::MeteoraDlmm => {
+            let accounts = route_accounts(rpc_client, route)?;
+            let (_, pair) = meteora_dlmm::find_pair(&accounts, mint, &sol)
+                .with_context(|| format!("No Meteora DLMM pair for {} in the target's transaction", mint))?;
+            vault_amount(vault((pair.token_x_mint, pair.reserve_x), pair.reserve_y)).map(Some)
+        }
+        DexProgram::MeteoraDynamicAmm => {
+            let accounts = route_accounts(rpc_client, route)?;
+            let (_, pool) = meteora_amm::find_pool(&accounts, mint, &sol)
+                .with_context(|| format!("No Meteora dynamic AMM pool for {} in the target's transaction", mint))?;
+            let vaults = meteora_amm::fetch_vaults(rpc_client, &pool)?;
+            Ok(Some(if pool.token_a_mint == sol { vaults.reserve_a } else { vaults.reserve_b }))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// The route's accounts paired with their on-chain state
+fn route_accounts(rpc_client: &RpcClient, route: &NativeRoute) -> Result<Vec<(Pubkey, Option<Account>)>> {
+    let accounts = fetch_accounts(rpc_client, &route.accounts)?;
+    Ok(route.accounts.iter().copied().zip(accounts).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_native_route() {
+        let accounts = vec![Pubkey::new_unique()];
+        let native = [
+            DexProgram::RaydiumClmm,
+            DexProgram::OrcaWhirlpool,
+            DexProgram::RaydiumCpmm,
+            DexProgram::RaydiumLaunchLab,
+            DexProgram::MeteoraDlmm,
+            DexProgram::MeteoraDynamicAmm,
+        ];
+        for dex in native {
+            assert_eq!(NativeRoute::new(dex, accounts.clone()), Some(NativeRoute { dex, accounts: accounts.clone() }));
+        }
+        // Aggregated and Pump.fun trades keep their own paths
+        for dex in [DexProgram::Jupiter, DexProgram::PumpSwap, DexProgram::Unknown] {
+            assert_eq!(NativeRoute::new(dex, accounts.clone()), None);
+        }
+        
+        let swaps = NativeSwaps::new("http://localhost:8899".to_string(), 2500);
+        let mint = Pubkey::new_unique();
+        let route = NativeRoute::new(DexProgram::OrcaWhirlpool, accounts).unwrap();
+        swaps.remember(mint, route.clone());
+        assert_eq!(swaps.route(&mint), Some(route));
+        swaps.forget(&mint);
+        assert_eq!(swaps.route(&mint), None);
+    }
+}
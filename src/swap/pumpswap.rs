@@ -0,0 +1,389 @@
+//! PumpSwap: the Pump.fun AMM graduated tokens trade on once their bonding curve completes
+
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    system_program,
+    transaction::VersionedTransaction,
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use crate::decoder::pumpfun;
+use crate::decoder::pumpswap::{self, GlobalConfig, Pool};
+use crate::token::{TokenAccountService, TokenProgram};
+use super::{
+    max_amount_in, min_amount_out, priority_fee_instructions, token_account_amount,
+    unsigned_transaction, unwrap_sol_instruction, wrap_sol_instructions, wsol_mint,
+};
+
+/// PumpSwap program
+pub const PUMPSWAP_PROGRAM_ID: Pubkey = pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+
+/// Pump.fun fee program holding PumpSwap's fee tiers
+pub const PUMP_FEE_PROGRAM_ID: Pubkey = pubkey!("pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ");
+
+/// Compute units requested for a swap including SOL wrapping
+const SWAP_COMPUTE_UNITS: u32 = 200_000;
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &PUMPSWAP_PROGRAM_ID).0
+}
+
+/// A graduated token's pool with everything a swap needs
+#[derive(Debug, Clone, PartialEq)]
+pub struct PumpSwapPool {
+    pub address: Pubkey,
+    pub pool: Pool,
+    /// Program owning the base (graduated) mint
+    pub base_token_program: TokenProgram,
+}
+
+/// Pool vault balances and the fees charged on the quote side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolReserves {
+    /// Raw base tokens
+    pub base: u64,
+    /// Lamports
+    pub quote: u64,
+    pub fee_basis_points: u64,
+}
+
+impl PoolReserves {
+    /// Spot price in lamports per raw token
+    pub fn price(&self) -> Option<f64> {
+        (self.base > 0).then(|| self.quote as f64 / self.base as f64)
+    }
+    
+    /// Raw tokens bought by spending `quote_in` lamports, fees included
+    pub fn buy_quote(&self, quote_in: u64) -> u64 {
+        let net = quote_in as u128 * 10_000 / (10_000 + self.fee_basis_points as u128);
+        (self.base as u128 * net / (self.quote as u128 + net).max(1)) as u64
+    }
+    
+    /// Lamports received for selling `base_in` raw tokens, after fees
+    pub fn sell_quote(&self, base_in: u64) -> u64 {
+        let gross = self.quote as u128 * base_in as u128 / (self.base as u128 + base_in as u128).max(1);
+        (gross * 10_000u128.saturating_sub(self.fee_basis_points as u128) / 10_000) as u64
+    }
+}
+
+/// Accounts shared by buy and sell, in instruction order
+fn swap_accounts(owner: &Pubkey, pool: &PumpSwapPool, protocol_fee_recipient: &Pubkey) -> Vec<AccountMeta> {
+    let quote_program = spl_token::id();
+    let keys = &pool.pool;
+    let user_base = pool.base_token_program.associated_token_address(owner, &keys.base_mint);
+    let user_quote = spl_associated_token_account::get_associated_token_address(owner, &keys.quote_mint);
+    let recipient_quote = spl_associated_token_account::get_associated_token_address(protocol_fee_recipient, &keys.quote_mint);
+    let creator_vault_authority = pda(&[b"creator_vault", keys.coin_creator.as_ref()]);
+    let creator_vault = spl_associated_token_account::get_associated_token_address(&creator_vault_authority, &keys.quote_mint);
+    vec![
+        AccountMeta::new(pool.address, false),
+        AccountMeta::new(*owner, true),
+        AccountMeta::new_readonly(pda(&[b"global_config"]), false),
+        AccountMeta::new_readonly(keys.base_mint, false),
+        AccountMeta::new_readonly(keys.quote_mint, false),
+        AccountMeta::new(user_base, false),
+        AccountMeta::new(user_quote, false),
+        AccountMeta::new(keys.pool_base_token_account, false),
+        AccountMeta::new(keys.pool_quote_token_account, false),
+        AccountMeta::new_readonly(*protocol_fee_recipient, false),
+        AccountMeta::new(recipient_quote, false),
+        AccountMeta::new_readonly(pool.base_token_program.id(), false),
+        AccountMeta::new_readonly(quote_program, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(pda(&[b"__event_authority"]), false),
+        AccountMeta::new_readonly(PUMPSWAP_PROGRAM_ID, false),
+        AccountMeta::new(creator_vault, false),
+        AccountMeta::new_readonly(creator_vault_authority, false),
+    ]
+}
+
+/// Fee tier accounts every swap ends with
+fn fee_accounts() -> [AccountMeta; 2] {
+    let fee_config = Pubkey::find_program_address(&[b"fee_config", PUMPSWAP_PROGRAM_ID.as_ref()], &PUMP_FEE_PROGRAM_ID).0;
+    [
+        AccountMeta::new_readonly(fee_config, false),
+        AccountMeta::new_readonly(PUMP_FEE_PROGRAM_ID, false),
+    ]
+}
+
+/// Buy exactly `base_amount_out` raw tokens, spending at most `max_quote_amount_in` lamports of WSOL
+pub fn buy_instruction(
+    owner: &Pubkey,
+    pool: &PumpSwapPool,
+    protocol_fee_recipient: &Pubkey,
+    base_amount_out: u64,
+    max_quote_amount_in: u64,
+) -> Instruction {
+    let mut accounts = swap_accounts(owner, pool, protocol_fee_recipient);
+    accounts.push(AccountMeta::new(pda(&[b"global_volume_accumulator"]), false));
+    accounts.push(AccountMeta::new(pda(&[b"user_volume_accumulator", owner.as_ref()]), false));
+    accounts.extend(fee_accounts());
+    
+    let mut data = pumpswap::BUY_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&base_amount_out.to_le_bytes());
+    data.extend_from_slice(&max_quote_amount_in.to_le_bytes());
+    // track_volume: no volume rewards
+    data.push(0);
+    Instruction { program_id: PUMPSWAP_PROGRAM_ID, accounts, data }
+}
+
+/// Sell `base_amount_in` raw tokens for at least `min_quote_amount_out` lamports of WSOL
+pub fn sell_instruction(
+    owner: &Pubkey,
+    pool: &PumpSwapPool,
+    protocol_fee_recipient: &Pubkey,
+    base_amount_in: u64,
+    min_quote_amount_out: u64,
+) -> Instruction {
+    let mut accounts = swap_accounts(owner, pool, protocol_fee_recipient);
+    accounts.extend(fee_accounts());
+    
+    let mut data = pumpswap::SELL_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&base_amount_in.to_le_bytes());
+    data.extend_from_slice(&min_quote_amount_out.to_le_bytes());
+    Instruction { program_id: PUMPSWAP_PROGRAM_ID, accounts, data }
+}
+
+/// PumpSwap pool lookups, prices and swap transactions for graduated Pump.fun tokens
+pub struct PumpSwap {
+    rpc_client: AsyncRpcClient,
+    token_accounts: Arc<TokenAccountService>,
+    /// Pools of mints seen to have migrated (a pool never moves)
+    pools: DashMap<Pubkey, PumpSwapPool>,
+}
+
+impl PumpSwap {
+    pub fn new(rpc_url: String, token_accounts: Arc<TokenAccountService>) -> Self {
+        Self {
+            rpc_client: AsyncRpcClient::new(rpc_url),
+            token_accounts,
+            pools: DashMap::new(),
+        }
+    }
+    
+    /// Whether a mint is already known to trade on PumpSwap (no RPC call)
+    pub fn is_known_migrated(&self, mint: &Pubkey) -> bool {
+        self.pools.contains_key(mint)
+    }
+    
+    /// Whether a mint's bonding curve has completed and its PumpSwap pool exists
+    /// Lookup failures count as not migrated so callers keep their bonding curve route.
+    pub async fn is_migrated(&self, mint: &Pubkey) -> bool {
+        if self.is_known_migrated(mint) {
+            return true;
+        }
+        let Some(curve_address) = pumpfun::bonding_curve_address(mint) else {
+            return false;
+        };
+        let complete = match self.rpc_client.get_account_data(&curve_address).await {
+            Ok(data) => pumpfun::decode_bonding_curve(&data).is_some_and(|curve| curve.complete),
+            Err(_) => false,
+        };
+        if !complete {
+            return false;
+        }
+        match self.pool(mint).await {
+            Ok(_) => true,
+            Err(e) => {
+                debug!("Bonding curve of {} is complete but no PumpSwap pool yet: {:#}", mint, e);
+                false
+            }
+        }
+    }
+    
+    /// The mint's pool, cached after the first lookup
+    pub async fn pool(&self, mint: &Pubkey) -> Result<PumpSwapPool> {
+        if let Some(pool) = self.pools.get(mint) {
+            return Ok(pool.clone());
+        }
+        let (address, pool) = self.find_pool(mint).await?;
+        let pool = PumpSwapPool {
+            address,
+            base_token_program: self.token_accounts.mint_program(mint).await?,
+            pool,
+        };
+        info!("🎓 {} has graduated to PumpSwap pool {}", mint, address);
+        self.pools.insert(*mint, pool.clone());
+        Ok(pool)
+    }
+    
+    /// The canonical migration pool, else the deepest WSOL pool for the mint
+    async fn find_pool(&self, mint: &Pubkey) -> Result<(Pubkey, Pool)> {
+        if let Some(address) = pumpswap::canonical_pool_address(mint) {
+            if let Ok(data) = self.rpc_client.get_account_data(&address).await {
+                if let Some(pool) = pumpswap::decode_pool(&data) {
+                    return Ok((address, pool));
+                }
+            }
+        }
+        
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(pumpswap::POOL_BASE_MINT_OFFSET, mint.to_bytes().to_vec())),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(pumpswap::POOL_QUOTE_MINT_OFFSET, wsol_mint().to_bytes().to_vec())),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let accounts = self.rpc_client
+            .get_program_accounts_with_config(&PUMPSWAP_PROGRAM_ID, config)
+            .await
+            .context("Failed to search PumpSwap pools")?;
+        accounts.into_iter()
+            .filter_map(|(address, account)| Some((address, pumpswap::decode_pool(&account.data)?)))
+            .max_by_key(|(_, pool)| pool.lp_supply)
+            .with_context(|| format!("No PumpSwap pool for {}", mint))
+    }
+    
+    /// Current vault balances and fees of a pool, read in one call
+    pub async fn reserves(&self, pool: &PumpSwapPool) -> Result<(PoolReserves, GlobalConfig)> {
+        let keys = [pda(&[b"global_config"]), pool.pool.pool_base_token_account, pool.pool.pool_quote_token_account];
+        let accounts = self.rpc_client.get_multiple_accounts(&keys).await
+            .context("Failed to fetch PumpSwap pool vaults")?;
+        let data = |index: usize| accounts.get(index).and_then(Option::as_ref).map(|account| account.data.as_slice());
+        let config = data(0).and_then(pumpswap::decode_global_config)
+            .context("Invalid PumpSwap global config")?;
+        let reserves = PoolReserves {
+            base: data(1).and_then(token_account_amount).context("Missing PumpSwap base vault")?,
+            quote: data(2).and_then(token_account_amount).context("Missing PumpSwap quote vault")?,
+            fee_basis_points: config.total_fee_basis_points(),
+        };
+        Ok((reserves, config))
+    }
+    
+    /// Spot price of a graduated token in lamports per raw token
+    pub async fn price(&self, mint: &Pubkey) -> Result<f64> {
+        let pool = self.pool(mint).await?;
+        let (reserves, _) = self.reserves(&pool).await?;
+        reserves.price().context("Empty PumpSwap pool")
+    }
+    
+    /// Unsigned buy spending about `quote_lamports` SOL, with up to `slippage_bps` more allowed
+    pub async fn buy_transaction(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        quote_lamports: u64,
+        slippage_bps: u64,
+        priority_fee_lamports: u64,
+    ) -> Result<VersionedTransaction> {
+        let pool = self.pool(mint).await?;
+        let (reserves, config) = self.reserves(&pool).await?;
+        let base_amount_out = reserves.buy_quote(quote_lamports);
+        if base_amount_out == 0 {
+            anyhow::bail!("PumpSwap buy of {} lamports would receive no tokens", quote_lamports);
+        }
+        let max_quote_amount_in = max_amount_in(quote_lamports, slippage_bps);
+        let recipient = protocol_fee_recipient(&config)?;
+        debug!("PumpSwap buy: {} raw tokens for at most {} lamports", base_amount_out, max_quote_amount_in);
+        
+        let mut instructions = priority_fee_instructions(priority_fee_lamports, SWAP_COMPUTE_UNITS).to_vec();
+        instructions.extend(wrap_sol_instructions(owner, max_quote_amount_in)?);
+        instructions.push(create_associated_token_account_idempotent(owner, owner, mint, &pool.base_token_program.id()));
+        instructions.push(buy_instruction(owner, &pool, &recipient, base_amount_out, max_quote_amount_in));
+        instructions.push(unwrap_sol_instruction(owner)?);
+        self.transaction(owner, &instructions).await
+    }
+    
+    /// Unsigned sell of `base_amount` raw tokens accepting up to `slippage_bps` less SOL
+    pub async fn sell_transaction(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        base_amount: u64,
+        slippage_bps: u64,
+        priority_fee_lamports: u64,
+    ) -> Result<VersionedTransaction> {
+        let pool = self.pool(mint).await?;
+        let (reserves, config) = self.reserves(&pool).await?;
+        let min_quote_amount_out = min_amount_out(reserves.sell_quote(base_amount), slippage_bps);
+        let recipient = protocol_fee_recipient(&config)?;
+        debug!("PumpSwap sell: {} raw tokens for at least {} lamports", base_amount, min_quote_amount_out);
+        
+        let mut instructions = priority_fee_instructions(priority_fee_lamports, SWAP_COMPUTE_UNITS).to_vec();
+        instructions.extend(wrap_sol_instructions(owner, 0)?);
+        instructions.push(sell_instruction(owner, &pool, &recipient, base_amount, min_quote_amount_out));
+        instructions.push(unwrap_sol_instruction(owner)?);
+        self.transaction(owner, &instructions).await
+    }
+    
+    async fn transaction(&self, owner: &Pubkey, instructions: &[Instruction]) -> Result<VersionedTransaction> {
+        let recent_blockhash = self.rpc_client.get_latest_blockhash().await
+            .context("Failed to get recent blockhash")?;
+        Ok(unsigned_transaction(owner, instructions, recent_blockhash))
+    }
+}
+
+/// First configured protocol fee recipient
+fn protocol_fee_recipient(config: &GlobalConfig) -> Result<Pubkey> {
+    config.protocol_fee_recipients.iter()
+        .find(|recipient| **recipient != Pubkey::default())
+        .copied()
+        .context("PumpSwap has no protocol fee recipient")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swap::wsol_account;
+    
+    #[test]
+    fn test_pool_quotes() {
+        let reserves = PoolReserves { base: 200_000_000_000_000, quote: 80_000_000_000, fee_basis_points: 30 };
+        assert_eq!(reserves.price(), Some(0.0004));
+        
+        // A small buy gets roughly 1 / price tokens per lamport, less the fee
+        let bought = reserves.buy_quote(1_000_000_000);
+        assert!(bought < 2_500_000_000_000 && bought > 2_400_000_000_000, "bought {}", bought);
+        
+        // Selling straight back returns less than was spent
+        let reserves = PoolReserves { base: reserves.base - bought, quote: reserves.quote + 997_000_000, ..reserves };
+        assert!(reserves.sell_quote(bought) < 1_000_000_000);
+        assert_eq!(PoolReserves { base: 0, quote: 0, fee_basis_points: 30 }.sell_quote(0), 0);
+    }
+    
+    #[test]
+    fn test_swap_instructions() {
+        let owner = Pubkey::new_unique();
+        let pool = PumpSwapPool {
+            address: Pubkey::new_unique(),
+            pool: Pool {
+                creator: Pubkey::new_unique(),
+                base_mint: Pubkey::new_unique(),
+                quote_mint: wsol_mint(),
+                lp_mint: Pubkey::new_unique(),
+                pool_base_token_account: Pubkey::new_unique(),
+                pool_quote_token_account: Pubkey::new_unique(),
+                lp_supply: 1,
+                coin_creator: Pubkey::new_unique(),
+            },
+            base_token_program: TokenProgram::Token2022,
+        };
+        let recipient = Pubkey::new_unique();
+        
+        let buy = buy_instruction(&owner, &pool, &recipient, 10, 20);
+        assert_eq!(buy.accounts.len(), 23);
+        assert_eq!(buy.accounts[5].pubkey, TokenProgram::Token2022.associated_token_address(&owner, &pool.pool.base_mint));
+        assert_eq!(buy.accounts[6].pubkey, wsol_account(&owner));
+        assert_eq!(buy.data[..8], pumpswap::BUY_DISCRIMINATOR);
+        assert_eq!((&buy.data[8..16], &buy.data[16..24]), (&10u64.to_le_bytes()[..], &20u64.to_le_bytes()[..]));
+        
+        let sell = sell_instruction(&owner, &pool, &recipient, 10, 5);
+        assert_eq!(sell.accounts.len(), 21);
+        assert!(sell.accounts[1].is_signer);
+    }
+}
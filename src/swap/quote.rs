@@ -0,0 +1,122 @@
+//! Quote assets targets trade against, and how copies mirror them
+
+use anyhow::Result;
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use std::str::FromStr;
+
+use super::wsol_mint;
+
+/// USDC mint
+pub const USDC_MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+/// USDT mint
+pub const USDT_MINT: Pubkey = pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB");
+
+/// What a trade is priced in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuoteAsset {
+    /// SOL or WSOL
+    #[default]
+    Sol,
+    Usdc,
+    Usdt,
+}
+
+impl QuoteAsset {
+    /// The quote asset a mint is, None for any other token
+    pub fn from_mint(mint: &Pubkey) -> Option<Self> {
+        if *mint == wsol_mint() {
+            Some(Self::Sol)
+        } else if *mint == USDC_MINT {
+            Some(Self::Usdc)
+        } else if *mint == USDT_MINT {
+            Some(Self::Usdt)
+        } else {
+            None
+        }
+    }
+    
+    /// Mint swaps in this asset go through (WSOL for SOL)
+    pub fn mint(&self) -> Pubkey {
+        match self {
+            Self::Sol => wsol_mint(),
+            Self::Usdc => USDC_MINT,
+            Self::Usdt => USDT_MINT,
+        }
+    }
+    
+    pub fn is_stable(&self) -> bool {
+        *self != Self::Sol
+    }
+}
+
+/// How copies of trades priced in a stablecoin are paid for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CopyQuoteMode {
+    /// Always buy with and sell to SOL
+    #[default]
+    Sol,
+    /// Use the target's stablecoin when the wallet holds enough of it, SOL otherwise
+    Same,
+}
+
+impl FromStr for CopyQuoteMode {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "sol" => Ok(Self::Sol),
+            "same" => Ok(Self::Same),
+            other => anyhow::bail!("Unknown copy quote mode '{}' (expected sol or same)", other),
+        }
+    }
+}
+
+/// A token-for-token swap read in terms of quote assets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuotedTrade {
+    /// A token bought with a stablecoin
+    Buy { mint: Pubkey, quote: QuoteAsset, quote_amount: u64, token_amount: u64 },
+    /// A token sold for a stablecoin
+    Sell { mint: Pubkey, quote: QuoteAsset, quote_amount: u64, token_amount: u64 },
+    /// One token rotated into another
+    Rotate { sold: Pubkey, sold_amount: u64, bought: Pubkey, bought_amount: u64 },
+}
+
+impl QuotedTrade {
+    /// Read a swap of `sold_amount` of `sold` for `bought_amount` of `bought`
+    /// Swaps between two quote assets are not trades and return None.
+    pub fn classify(sold: Pubkey, sold_amount: u64, bought: Pubkey, bought_amount: u64) -> Option<Self> {
+        match (QuoteAsset::from_mint(&sold), QuoteAsset::from_mint(&bought)) {
+            (Some(_), Some(_)) => None,
+            (Some(quote), None) => Some(Self::Buy { mint: bought, quote, quote_amount: sold_amount, token_amount: bought_amount }),
+            (None, Some(quote)) => Some(Self::Sell { mint: sold, quote, quote_amount: bought_amount, token_amount: sold_amount }),
+            (None, None) => Some(Self::Rotate { sold, sold_amount, bought, bought_amount }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_classify_token_swaps() {
+        let (token, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_eq!(
+            QuotedTrade::classify(USDC_MINT, 5_000_000, token, 1_000),
+            Some(QuotedTrade::Buy { mint: token, quote: QuoteAsset::Usdc, quote_amount: 5_000_000, token_amount: 1_000 })
+        );
+        assert_eq!(
+            QuotedTrade::classify(token, 1_000, USDT_MINT, 7_000_000),
+            Some(QuotedTrade::Sell { mint: token, quote: QuoteAsset::Usdt, quote_amount: 7_000_000, token_amount: 1_000 })
+        );
+        assert_eq!(
+            QuotedTrade::classify(token, 1_000, other, 50),
+            Some(QuotedTrade::Rotate { sold: token, sold_amount: 1_000, bought: other, bought_amount: 50 })
+        );
+        assert_eq!(QuotedTrade::classify(USDC_MINT, 1_000, USDT_MINT, 999), None);
+        
+        assert_eq!("Same".parse::<CopyQuoteMode>().unwrap(), CopyQuoteMode::Same);
+        assert!("usdc".parse::<CopyQuoteMode>().is_err());
+    }
+}
@@ -0,0 +1,318 @@
+//! Raydium concentrated liquidity (CLMM) pools: state decoding, tick arrays and swap_v2
+
+use anyhow::{Context, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+};
+
+use crate::token::TokenProgram;
+use super::{
+    fetch_accounts, fetch_mint_programs, min_amount_out, quote_exact_input, sqrt_price_limit_x64,
+    tick_array_start_index, with_sol_wrapping, ClmmState, SwapLimits, SwapQuote, Tick, MEMO_PROGRAM_ID,
+};
+
+/// Raydium CLMM program
+pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey = pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
+
+/// Pool state account discriminator (Anchor)
+pub const POOL_STATE_DISCRIMINATOR: [u8; 8] = [0xf7, 0xed, 0xe3, 0xf5, 0xd7, 0xc3, 0xde, 0x46];
+/// AMM config account discriminator (Anchor)
+pub const AMM_CONFIG_DISCRIMINATOR: [u8; 8] = [0xda, 0xf4, 0x21, 0x68, 0xcb, 0xcb, 0x2b, 0x6f];
+/// Tick array account discriminator (Anchor)
+pub const TICK_ARRAY_DISCRIMINATOR: [u8; 8] = [0xc0, 0x9b, 0x55, 0xcd, 0x31, 0xf9, 0x81, 0x2a];
+/// swap_v2 instruction discriminator
+pub const SWAP_V2_DISCRIMINATOR: [u8; 8] = [0x2b, 0x04, 0xed, 0x0b, 0x1a, 0xc9, 0x1e, 0x62];
+
+/// Ticks per tick array
+pub const TICK_ARRAY_SIZE: i32 = 60;
+
+/// Tick arrays passed to a swap
+const SWAP_TICK_ARRAYS: usize = 3;
+
+/// Size of one tick in a tick array account
+const TICK_STATE_LEN: usize = 168;
+
+/// Tick arrays tracked by the pool's own bitmap (either side of tick 0)
+const BITMAP_ARRAYS: i32 = 512;
+
+/// Pool fields a swap needs
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolState {
+    pub amm_config: Pubkey,
+    pub token_mint_0: Pubkey,
+    pub token_mint_1: Pubkey,
+    pub token_vault_0: Pubkey,
+    pub token_vault_1: Pubkey,
+    pub observation_key: Pubkey,
+    pub tick_spacing: u16,
+    pub liquidity: u128,
+    pub sqrt_price_x64: u128,
+    pub tick_current: i32,
+    /// Initialized tick arrays within `BITMAP_ARRAYS` of tick 0
+    pub tick_array_bitmap: [u64; 16],
+}
+
+/// Decode a pool state account
+pub fn decode_pool_state(data: &[u8]) -> Option<PoolState> {
+    if data.len() < 1032 || data[0..8] != POOL_STATE_DISCRIMINATOR {
+        return None;
+    }
+    let pubkey = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).ok();
+    let u128_at = |offset: usize| data[offset..offset + 16].try_into().ok().map(u128::from_le_bytes);
+    let mut tick_array_bitmap = [0u64; 16];
+    for (i, word) in tick_array_bitmap.iter_mut().enumerate() {
+        *word = u64::from_le_bytes(data[904 + i * 8..912 + i * 8].try_into().ok()?);
+    }
+    Some(PoolState {
+        amm_config: pubkey(9)?,
+        token_mint_0: pubkey(73)?,
+        token_mint_1: pubkey(105)?,
+        token_vault_0: pubkey(137)?,
+        token_vault_1: pubkey(169)?,
+        observation_key: pubkey(201)?,
+        tick_spacing: u16::from_le_bytes(data[235..237].try_into().ok()?),
+        liquidity: u128_at(237)?,
+        sqrt_price_x64: u128_at(253)?,
+        tick_current: i32::from_le_bytes(data[269..273].try_into().ok()?),
+        tick_array_bitmap,
+    })
+}
+
+/// Trade fee rate (millionths) from an AMM config account
+pub fn decode_trade_fee_rate(data: &[u8]) -> Option<u32> {
+    if data.len() < 51 || data[0..8] != AMM_CONFIG_DISCRIMINATOR {
+        return None;
+    }
+    data[47..51].try_into().ok().map(u32::from_le_bytes)
+}
+
+/// Initialized ticks of a tick array account
+pub fn decode_tick_array(data: &[u8]) -> Option<Vec<Tick>> {
+    let ticks_end = 44 + TICK_ARRAY_SIZE as usize * TICK_STATE_LEN;
+    if data.len() < ticks_end || data[0..8] != TICK_ARRAY_DISCRIMINATOR {
+        return None;
+    }
+    let ticks = data[44..ticks_end]
+        .chunks_exact(TICK_STATE_LEN)
+        .filter_map(|tick| {
+            let liquidity_gross = u128::from_le_bytes(tick[20..36].try_into().ok()?);
+            if liquidity_gross == 0 {
+                return None;
+            }
+            Some(Tick {
+                index: i32::from_le_bytes(tick[0..4].try_into().ok()?),
+                liquidity_net: i128::from_le_bytes(tick[4..20].try_into().ok()?),
+            })
+        })
+        .collect();
+    Some(ticks)
+}
+
+/// Tick array PDA (the start index is encoded big-endian)
+pub fn tick_array_address(pool: &Pubkey, start_index: i32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"tick_array", pool.as_ref(), &start_index.to_be_bytes()],
+        &RAYDIUM_CLMM_PROGRAM_ID,
+    ).0
+}
+
+/// Bitmap extension PDA tracking tick arrays beyond the pool's own bitmap
+pub fn bitmap_extension_address(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"pool_tick_array_bitmap_extension", pool.as_ref()], &RAYDIUM_CLMM_PROGRAM_ID).0
+}
+
+impl PoolState {
+    /// Start indexes of the first `count` initialized tick arrays in the swap direction,
+    /// starting with the array holding the current tick
+    pub fn initialized_tick_arrays(&self, zero_for_one: bool, count: usize) -> Vec<i32> {
+        let ticks_in_array = self.tick_spacing as i32 * TICK_ARRAY_SIZE;
+        let current = tick_array_start_index(self.tick_current, self.tick_spacing, TICK_ARRAY_SIZE);
+        let mut offset = current / ticks_in_array + BITMAP_ARRAYS;
+        let mut starts = Vec::new();
+        while (0..BITMAP_ARRAYS * 2).contains(&offset) && starts.len() < count {
+            if self.tick_array_bitmap[offset as usize / 64] >> (offset % 64) & 1 == 1 {
+                starts.push((offset - BITMAP_ARRAYS) * ticks_in_array);
+            }
+            offset += if zero_for_one { -1 } else { 1 };
+        }
+        starts
+    }
+}
+
+/// The pool among a transaction's accounts that trades `token_mint` against `quote_mint`
+pub fn find_pool(accounts: &[(Pubkey, Option<Account>)], token_mint: &Pubkey, quote_mint: &Pubkey) -> Option<(Pubkey, PoolState)> {
+    accounts.iter()
+        .filter_map(|(address, account)| {
+            let account = account.as_ref().filter(|account| account.owner == RAYDIUM_CLMM_PROGRAM_ID)?;
+            Some((*address, decode_pool_state(&account.data)?))
+        })
+        .find(|(_, pool)| {
+            let mints = [pool.token_mint_0, pool.token_mint_1];
+            mints.contains(token_mint) && mints.contains(quote_mint)
+        })
+}
+
+/// swap_v2 instruction for an exact input swap
+/// `remaining` is the bitmap extension followed by the tick arrays in swap order.
+pub fn swap_v2_instruction(
+    owner: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &PoolState,
+    zero_for_one: bool,
+    programs: [TokenProgram; 2],
+    remaining: &[Pubkey],
+    limits: SwapLimits,
+) -> Instruction {
+    let [program_0, program_1] = programs;
+    let side_0 = (pool.token_mint_0, pool.token_vault_0, program_0.associated_token_address(owner, &pool.token_mint_0));
+    let side_1 = (pool.token_mint_1, pool.token_vault_1, program_1.associated_token_address(owner, &pool.token_mint_1));
+    let (input, output) = if zero_for_one { (side_0, side_1) } else { (side_1, side_0) };
+    
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new_readonly(pool.amm_config, false),
+        AccountMeta::new(*pool_address, false),
+        AccountMeta::new(input.2, false),
+        AccountMeta::new(output.2, false),
+        AccountMeta::new(input.1, false),
+        AccountMeta::new(output.1, false),
+        AccountMeta::new(pool.observation_key, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_token_2022::id(), false),
+        AccountMeta::new_readonly(MEMO_PROGRAM_ID, false),
+        AccountMeta::new_readonly(input.0, false),
+        AccountMeta::new_readonly(output.0, false),
+    ];
+    accounts.extend(remaining.iter().map(|key| AccountMeta::new(*key, false)));
+    
+    let mut data = SWAP_V2_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&limits.amount_in.to_le_bytes());
+    data.extend_from_slice(&limits.other_amount_threshold.to_le_bytes());
+    data.extend_from_slice(&limits.sqrt_price_limit_x64.to_le_bytes());
+    // is_base_input
+    data.push(1);
+    Instruction { program_id: RAYDIUM_CLMM_PROGRAM_ID, accounts, data }
+}
+
+/// Quote and build an exact input swap of `amount_in` of `input_mint` through a pool
+/// Fetches the fee config, mints and the tick arrays the swap walks through.
+pub fn swap_instructions(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &PoolState,
+    input_mint: &Pubkey,
+    amount_in: u64,
+    slippage_bps: u64,
+) -> Result<(Vec<Instruction>, SwapQuote)> {
+    let zero_for_one = *input_mint == pool.token_mint_0;
+    let output_mint = if zero_for_one { pool.token_mint_1 } else { pool.token_mint_0 };
+    let starts = pool.initialized_tick_arrays(zero_for_one, SWAP_TICK_ARRAYS);
+    if starts.is_empty() {
+        anyhow::bail!("No initialized tick arrays around tick {} in pool {}", pool.tick_current, pool_address);
+    }
+    let tick_arrays: Vec<Pubkey> = starts.iter().map(|start| tick_array_address(pool_address, *start)).collect();
+    
+    let mut keys = vec![pool.amm_config];
+    keys.extend(&tick_arrays);
+    let accounts = fetch_accounts(rpc_client, &keys)?;
+    let fee_rate = accounts[0].as_ref()
+        .and_then(|account| decode_trade_fee_rate(&account.data))
+        .context("Invalid Raydium AMM config")?;
+    let ticks: Vec<Tick> = accounts[1..].iter()
+        .flatten()
+        .filter_map(|account| decode_tick_array(&account.data))
+        .flatten()
+        .collect();
+    
+    let state = ClmmState {
+        sqrt_price_x64: pool.sqrt_price_x64,
+        liquidity: pool.liquidity,
+        tick_current: pool.tick_current,
+        fee_rate,
+    };
+    let quote = quote_exact_input(&state, &ticks, amount_in, zero_for_one);
+    if quote.amount_in < amount_in || quote.amount_out == 0 {
+        anyhow::bail!("Pool {} lacks the liquidity for {} in (quoted {})", pool_address, amount_in, quote.amount_in);
+    }
+    
+    let programs = fetch_mint_programs(rpc_client, &[pool.token_mint_0, pool.token_mint_1])?;
+    let (program_0, program_1) = (programs[0], programs[1]);
+    let mut remaining = vec![bitmap_extension_address(pool_address)];
+    remaining.extend(tick_arrays);
+    let swap = swap_v2_instruction(
+        owner,
+        pool_address,
+        pool,
+        zero_for_one,
+        [program_0, program_1],
+        &remaining,
+        SwapLimits {
+            amount_in,
+            other_amount_threshold: min_amount_out(quote.amount_out, slippage_bps),
+            sqrt_price_limit_x64: sqrt_price_limit_x64(pool.sqrt_price_x64, zero_for_one, slippage_bps),
+        },
+    );
+    let output_program = if zero_for_one { program_1 } else { program_0 };
+    let instructions = with_sol_wrapping(owner, input_mint, amount_in, &output_mint, &output_program.id(), swap)?;
+    Ok((instructions, quote))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn pool_data(tick_current: i32, bitmap_offsets: &[usize]) -> Vec<u8> {
+        let mut data = vec![0u8; 1544];
+        data[0..8].copy_from_slice(&POOL_STATE_DISCRIMINATOR);
+        data[235..237].copy_from_slice(&10u16.to_le_bytes());
+        data[237..253].copy_from_slice(&1_000_000u128.to_le_bytes());
+        data[253..269].copy_from_slice(&(1u128 << 64).to_le_bytes());
+        data[269..273].copy_from_slice(&tick_current.to_le_bytes());
+        for offset in bitmap_offsets {
+            data[904 + offset / 8] |= 1 << (offset % 8);
+        }
+        data
+    }
+    
+    #[test]
+    fn test_decode_pool_and_tick_arrays() {
+        // Arrays at -600, 0 and 1200 initialized (offsets 511, 512 and 514)
+        let pool = decode_pool_state(&pool_data(5, &[511, 512, 514])).unwrap();
+        assert_eq!((pool.tick_spacing, pool.tick_current, pool.liquidity), (10, 5, 1_000_000));
+        assert_eq!(pool.initialized_tick_arrays(true, 3), vec![0, -600]);
+        assert_eq!(pool.initialized_tick_arrays(false, 3), vec![0, 1_200]);
+        assert!(decode_pool_state(&pool_data(5, &[])[..1000]).is_none());
+        
+        let mut data = TICK_ARRAY_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[0; 36]);
+        let mut ticks = vec![0u8; TICK_ARRAY_SIZE as usize * TICK_STATE_LEN];
+        ticks[0..4].copy_from_slice(&(-600i32).to_le_bytes());
+        ticks[4..20].copy_from_slice(&(-50i128).to_le_bytes());
+        ticks[20..36].copy_from_slice(&50u128.to_le_bytes());
+        data.extend_from_slice(&ticks);
+        assert_eq!(decode_tick_array(&data), Some(vec![Tick { index: -600, liquidity_net: -50 }]));
+        
+        let pool_address = Pubkey::new_unique();
+        assert_ne!(tick_array_address(&pool_address, 0), tick_array_address(&pool_address, -600));
+    }
+    
+    #[test]
+    fn test_swap_v2_instruction() {
+        let pool = decode_pool_state(&pool_data(0, &[512])).unwrap();
+        let owner = Pubkey::new_unique();
+        let remaining = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let ix = swap_v2_instruction(
+            &owner, &Pubkey::new_unique(), &pool, false, [TokenProgram::Spl, TokenProgram::Token2022], &remaining,
+            SwapLimits { amount_in: 100, other_amount_threshold: 90, sqrt_price_limit_x64: 7 },
+        );
+        assert_eq!(ix.accounts.len(), 15);
+        assert_eq!(ix.accounts[3].pubkey, TokenProgram::Token2022.associated_token_address(&owner, &pool.token_mint_1));
+        assert_eq!(ix.accounts[14].pubkey, remaining[1]);
+        assert_eq!(ix.data.len(), 8 + 8 + 8 + 16 + 1);
+    }
+}
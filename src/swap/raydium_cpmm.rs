@@ -0,0 +1,182 @@
+//! Raydium CPMM swaps against a pool's vaults
+
+use anyhow::{Context, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use crate::decoder::raydium_cpmm::{self, PoolState};
+use crate::decoder::DexProgram;
+use super::{fetch_accounts, min_amount_out, token_account_amount, with_sol_wrapping};
+
+/// The pool among a transaction's accounts that trades `token_mint` against `quote_mint`
+pub fn find_pool(accounts: &[(Pubkey, Option<Account>)], token_mint: &Pubkey, quote_mint: &Pubkey) -> Option<(Pubkey, PoolState)> {
+    let program = DexProgram::RaydiumCpmm.program_id()?;
+    accounts.iter()
+        .filter_map(|(address, account)| {
+            let account = account.as_ref().filter(|account| account.owner == program)?;
+            Some((*address, raydium_cpmm::decode_pool_state(&account.data)?))
+        })
+        .find(|(_, pool)| {
+            let mints = [pool.token_0_mint, pool.token_1_mint];
+            mints.contains(token_mint) && mints.contains(quote_mint)
+        })
+}
+
+/// swap_base_input instruction selling exactly `amount_in` of one side of the pool
+pub fn swap_base_input_instruction(
+    owner: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &PoolState,
+    zero_for_one: bool,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<Instruction> {
+    let program_id = DexProgram::RaydiumCpmm.program_id().context("Invalid CPMM program id")?;
+    let authority = raydium_cpmm::authority_address().context("Invalid CPMM program id")?;
+    let side_0 = (pool.token_0_mint, pool.token_0_vault, pool.token_0_program);
+    let side_1 = (pool.token_1_mint, pool.token_1_vault, pool.token_1_program);
+    let ((input_mint, input_vault, input_program), (output_mint, output_vault, output_program)) =
+        if zero_for_one { (side_0, side_1) } else { (side_1, side_0) };
+    let ata = |mint: &Pubkey, program: &Pubkey| {
+        spl_associated_token_account::get_associated_token_address_with_program_id(owner, mint, program)
+    };
+    
+    let accounts = vec![
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(authority, false),
+        AccountMeta::new_readonly(pool.amm_config, false),
+        AccountMeta::new(*pool_address, false),
+        AccountMeta::new(ata(&input_mint, &input_program), false),
+        AccountMeta::new(ata(&output_mint, &output_program), false),
+        AccountMeta::new(input_vault, false),
+        AccountMeta::new(output_vault, false),
+        AccountMeta::new_readonly(input_program, false),
+        AccountMeta::new_readonly(output_program, false),
+        AccountMeta::new_readonly(input_mint, false),
+        AccountMeta::new_readonly(output_mint, false),
+        AccountMeta::new(pool.observation_key, false),
+    ];
+    let mut data = raydium_cpmm::SWAP_BASE_INPUT_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+    Ok(Instruction { program_id, accounts, data })
+}
+
+/// Quote and build an exact input swap of `amount_in` of `input_mint` through a pool
+/// Returns the instructions and the quoted output.
+pub fn swap_instructions(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &PoolState,
+    input_mint: &Pubkey,
+    amount_in: u64,
+    slippage_bps: u64,
+) -> Result<(Vec<Instruction>, u64)> {
+    if !pool.can_swap() {
+        anyhow::bail!("Swaps are disabled on CPMM pool {}", pool_address);
+    }
+    let zero_for_one = *input_mint == pool.token_0_mint;
+    let accounts = fetch_accounts(rpc_client, &[pool.amm_config, pool.token_0_vault, pool.token_1_vault])?;
+    let trade_fee_rate = accounts[0].as_ref()
+        .and_then(|account| raydium_cpmm::decode_trade_fee_rate(&account.data))
+        .context("Invalid CPMM AMM config")?;
+    let vault_amount = |account: &Option<Account>| account.as_ref().and_then(|account| token_account_amount(&account.data));
+    let (reserve_0, reserve_1) = pool.reserves(
+        vault_amount(&accounts[1]).context("Missing CPMM vault")?,
+        vault_amount(&accounts[2]).context("Missing CPMM vault")?,
+    );
+    let (reserve_in, reserve_out) = if zero_for_one { (reserve_0, reserve_1) } else { (reserve_1, reserve_0) };
+    let amount_out = raydium_cpmm::quote_exact_input(reserve_in, reserve_out, amount_in, trade_fee_rate);
+    if amount_out == 0 {
+        anyhow::bail!("CPMM pool {} quotes nothing for {} in", pool_address, amount_in);
+    }
+    
+    let swap = swap_base_input_instruction(
+        owner,
+        pool_address,
+        pool,
+        zero_for_one,
+        amount_in,
+        min_amount_out(amount_out, slippage_bps),
+    )?;
+    let (output_mint, output_program) = if zero_for_one {
+        (pool.token_1_mint, pool.token_1_program)
+    } else {
+        (pool.token_0_mint, pool.token_0_program)
+    };
+    let instructions = with_sol_wrapping(owner, input_mint, amount_in, &output_mint, &output_program, swap)?;
+    Ok((instructions, amount_out))
+}
+
+/// Spot price of `token_mint` in raw units of the other side per raw token, from the pool's
+/// vault reserves
+pub fn price(rpc_client: &RpcClient, pool: &PoolState, token_mint: &Pubkey) -> Result<f64> {
+    let accounts = fetch_accounts(rpc_client, &[pool.token_0_vault, pool.token_1_vault])?;
+    let vault_amount = |account: &Option<Account>| account.as_ref().and_then(|account| token_account_amount(&account.data));
+    spot_price(
+        pool,
+        token_mint,
+        vault_amount(&accounts[0]).context("Missing CPMM vault")?,
+        vault_amount(&accounts[1]).context("Missing CPMM vault")?,
+    )
+    .context("Empty CPMM pool")
+}
+
+/// Price of `token_mint` from the vault balances, None while its side is empty
+fn spot_price(pool: &PoolState, token_mint: &Pubkey, vault_0_amount: u64, vault_1_amount: u64) -> Option<f64> {
+    let (reserve_0, reserve_1) = pool.reserves(vault_0_amount, vault_1_amount);
+    let (token, other) = if pool.token_0_mint == *token_mint { (reserve_0, reserve_1) } else { (reserve_1, reserve_0) };
+    (token > 0).then(|| other as f64 / token as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swap::wsol_mint;
+    
+    #[test]
+    fn test_find_pool_and_swap_instruction() {
+        let keys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+        let mut data = raydium_cpmm::POOL_STATE_DISCRIMINATOR.to_vec();
+        // amm_config, creator, vault_0, vault_1 and an unused LP mint
+        for key in &keys[..4] {
+            data.extend_from_slice(key.as_ref());
+        }
+        data.extend_from_slice(Pubkey::default().as_ref());
+        data.extend_from_slice(wsol_mint().as_ref());
+        data.extend_from_slice(keys[4].as_ref());
+        data.extend_from_slice(spl_token::id().as_ref());
+        data.extend_from_slice(spl_token_2022::id().as_ref());
+        data.extend_from_slice(keys[5].as_ref());
+        data.resize(637, 0);
+        
+        let address = Pubkey::new_unique();
+        let program = DexProgram::RaydiumCpmm.program_id().unwrap();
+        let accounts = vec![(address, Some(Account { data, owner: program, ..Account::default() }))];
+        let (found, pool) = find_pool(&accounts, &keys[4], &wsol_mint()).unwrap();
+        assert_eq!((found, pool.token_0_vault, pool.token_1_program), (address, keys[2], spl_token_2022::id()));
+        assert!(pool.can_swap());
+        
+        // Selling the Token-2022 token for WSOL
+        let owner = Pubkey::new_unique();
+        let ix = swap_base_input_instruction(&owner, &address, &pool, false, 500, 400).unwrap();
+        assert_eq!(ix.accounts.len(), 13);
+        assert_eq!(
+            ix.accounts[4].pubkey,
+            spl_associated_token_account::get_associated_token_address_with_program_id(&owner, &keys[4], &spl_token_2022::id())
+        );
+        assert_eq!((ix.accounts[6].pubkey, ix.accounts[11].pubkey), (keys[3], wsol_mint()));
+        assert_eq!(ix.data[..8], raydium_cpmm::SWAP_BASE_INPUT_DISCRIMINATOR);
+        assert_eq!((&ix.data[8..16], &ix.data[16..]), (&500u64.to_le_bytes()[..], &400u64.to_le_bytes()[..]));
+        
+        // WSOL is token 0: 2 SOL against 1000 raw tokens, less 0.2 SOL of uncollected fees
+        let pool = PoolState { protocol_fees_token_0: 200_000_000, ..pool };
+        assert_eq!(spot_price(&pool, &keys[4], 2_200_000_000, 1_000), Some(2_000_000.0));
+        assert_eq!(spot_price(&pool, &keys[4], 2_200_000_000, 0), None);
+    }
+}
@@ -0,0 +1,276 @@
+//! Orca Whirlpool pools: state decoding, tick arrays and swap_v2
+
+use anyhow::Result;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+};
+
+use crate::token::TokenProgram;
+use super::{
+    fetch_accounts, fetch_mint_programs, min_amount_out, quote_exact_input, sqrt_price_limit_x64,
+    tick_array_start_index, with_sol_wrapping, ClmmState, SwapLimits, SwapQuote, Tick, MEMO_PROGRAM_ID,
+};
+
+/// Orca Whirlpool program
+pub const WHIRLPOOL_PROGRAM_ID: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+
+/// Whirlpool account discriminator (Anchor)
+pub const WHIRLPOOL_DISCRIMINATOR: [u8; 8] = [0x3f, 0x95, 0xd1, 0x0c, 0xe1, 0x80, 0x63, 0x09];
+/// Tick array account discriminator (Anchor)
+pub const TICK_ARRAY_DISCRIMINATOR: [u8; 8] = [0x45, 0x61, 0xbd, 0xbe, 0x6e, 0x07, 0x42, 0xbb];
+/// swap_v2 instruction discriminator
+pub const SWAP_V2_DISCRIMINATOR: [u8; 8] = [0x2b, 0x04, 0xed, 0x0b, 0x1a, 0xc9, 0x1e, 0x62];
+
+/// Ticks per tick array
+pub const TICK_ARRAY_SIZE: i32 = 88;
+
+/// Size of one tick in a tick array account
+const TICK_LEN: usize = 113;
+
+/// Pool fields a swap needs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Whirlpool {
+    pub tick_spacing: u16,
+    /// Millionths of the input taken as fee
+    pub fee_rate: u16,
+    pub liquidity: u128,
+    pub sqrt_price_x64: u128,
+    pub tick_current: i32,
+    pub token_mint_a: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub token_vault_b: Pubkey,
+}
+
+/// Decode a whirlpool account
+pub fn decode_whirlpool(data: &[u8]) -> Option<Whirlpool> {
+    if data.len() < 245 || data[0..8] != WHIRLPOOL_DISCRIMINATOR {
+        return None;
+    }
+    let pubkey = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).ok();
+    let u128_at = |offset: usize| data[offset..offset + 16].try_into().ok().map(u128::from_le_bytes);
+    Some(Whirlpool {
+        tick_spacing: u16::from_le_bytes(data[41..43].try_into().ok()?),
+        fee_rate: u16::from_le_bytes(data[45..47].try_into().ok()?),
+        liquidity: u128_at(49)?,
+        sqrt_price_x64: u128_at(65)?,
+        tick_current: i32::from_le_bytes(data[81..85].try_into().ok()?),
+        token_mint_a: pubkey(101)?,
+        token_vault_a: pubkey(133)?,
+        token_mint_b: pubkey(181)?,
+        token_vault_b: pubkey(213)?,
+    })
+}
+
+/// Initialized ticks of a tick array account
+/// Ticks only store their liquidity, their index follows from the array's start and spacing.
+pub fn decode_tick_array(data: &[u8], tick_spacing: u16) -> Option<Vec<Tick>> {
+    let ticks_end = 12 + TICK_ARRAY_SIZE as usize * TICK_LEN;
+    if data.len() < ticks_end || data[0..8] != TICK_ARRAY_DISCRIMINATOR {
+        return None;
+    }
+    let start_index = i32::from_le_bytes(data[8..12].try_into().ok()?);
+    let ticks = data[12..ticks_end]
+        .chunks_exact(TICK_LEN)
+        .enumerate()
+        .filter(|(_, tick)| tick[0] != 0)
+        .filter_map(|(i, tick)| {
+            Some(Tick {
+                index: start_index + i as i32 * tick_spacing as i32,
+                liquidity_net: i128::from_le_bytes(tick[1..17].try_into().ok()?),
+            })
+        })
+        .collect();
+    Some(ticks)
+}
+
+/// Tick array PDA (the start index is encoded as a decimal string)
+pub fn tick_array_address(whirlpool: &Pubkey, start_index: i32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"tick_array", whirlpool.as_ref(), start_index.to_string().as_bytes()],
+        &WHIRLPOOL_PROGRAM_ID,
+    ).0
+}
+
+/// Oracle PDA of a whirlpool
+pub fn oracle_address(whirlpool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"oracle", whirlpool.as_ref()], &WHIRLPOOL_PROGRAM_ID).0
+}
+
+impl Whirlpool {
+    /// Start indexes of the three tick arrays a swap passes, beginning with the current one
+    /// B to A swaps look one tick spacing ahead, as the program does.
+    pub fn swap_tick_arrays(&self, a_to_b: bool) -> [i32; 3] {
+        let ticks_in_array = self.tick_spacing as i32 * TICK_ARRAY_SIZE;
+        let tick = if a_to_b { self.tick_current } else { self.tick_current + self.tick_spacing as i32 };
+        let start = tick_array_start_index(tick, self.tick_spacing, TICK_ARRAY_SIZE);
+        let step = if a_to_b { -ticks_in_array } else { ticks_in_array };
+        [start, start + step, start + 2 * step]
+    }
+}
+
+/// The whirlpool among a transaction's accounts that trades `token_mint` against `quote_mint`
+pub fn find_pool(accounts: &[(Pubkey, Option<Account>)], token_mint: &Pubkey, quote_mint: &Pubkey) -> Option<(Pubkey, Whirlpool)> {
+    accounts.iter()
+        .filter_map(|(address, account)| {
+            let account = account.as_ref().filter(|account| account.owner == WHIRLPOOL_PROGRAM_ID)?;
+            Some((*address, decode_whirlpool(&account.data)?))
+        })
+        .find(|(_, pool)| {
+            let mints = [pool.token_mint_a, pool.token_mint_b];
+            mints.contains(token_mint) && mints.contains(quote_mint)
+        })
+}
+
+/// swap_v2 instruction for an exact input swap through three tick arrays
+pub fn swap_v2_instruction(
+    owner: &Pubkey,
+    whirlpool_address: &Pubkey,
+    whirlpool: &Whirlpool,
+    a_to_b: bool,
+    programs: [TokenProgram; 2],
+    tick_arrays: [Pubkey; 3],
+    limits: SwapLimits,
+) -> Instruction {
+    let [program_a, program_b] = programs;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(program_a.id(), false),
+        AccountMeta::new_readonly(program_b.id(), false),
+        AccountMeta::new_readonly(MEMO_PROGRAM_ID, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(*whirlpool_address, false),
+        AccountMeta::new_readonly(whirlpool.token_mint_a, false),
+        AccountMeta::new_readonly(whirlpool.token_mint_b, false),
+        AccountMeta::new(program_a.associated_token_address(owner, &whirlpool.token_mint_a), false),
+        AccountMeta::new(whirlpool.token_vault_a, false),
+        AccountMeta::new(program_b.associated_token_address(owner, &whirlpool.token_mint_b), false),
+        AccountMeta::new(whirlpool.token_vault_b, false),
+    ];
+    accounts.extend(tick_arrays.iter().map(|key| AccountMeta::new(*key, false)));
+    accounts.push(AccountMeta::new(oracle_address(whirlpool_address), false));
+    
+    let mut data = SWAP_V2_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&limits.amount_in.to_le_bytes());
+    data.extend_from_slice(&limits.other_amount_threshold.to_le_bytes());
+    data.extend_from_slice(&limits.sqrt_price_limit_x64.to_le_bytes());
+    // amount_specified_is_input, a_to_b, no remaining accounts info
+    data.extend_from_slice(&[1, a_to_b as u8, 0]);
+    Instruction { program_id: WHIRLPOOL_PROGRAM_ID, accounts, data }
+}
+
+/// Quote and build an exact input swap of `amount_in` of `input_mint` through a whirlpool
+/// Fetches the mints and the tick arrays the swap walks through; arrays that were never
+/// initialized simply contribute no ticks.
+pub fn swap_instructions(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+    whirlpool_address: &Pubkey,
+    whirlpool: &Whirlpool,
+    input_mint: &Pubkey,
+    amount_in: u64,
+    slippage_bps: u64,
+) -> Result<(Vec<Instruction>, SwapQuote)> {
+    let a_to_b = *input_mint == whirlpool.token_mint_a;
+    let output_mint = if a_to_b { whirlpool.token_mint_b } else { whirlpool.token_mint_a };
+    let tick_arrays = whirlpool.swap_tick_arrays(a_to_b).map(|start| tick_array_address(whirlpool_address, start));
+    
+    let ticks: Vec<Tick> = fetch_accounts(rpc_client, &tick_arrays)?
+        .iter()
+        .flatten()
+        .filter_map(|account| decode_tick_array(&account.data, whirlpool.tick_spacing))
+        .flatten()
+        .collect();
+    let state = ClmmState {
+        sqrt_price_x64: whirlpool.sqrt_price_x64,
+        liquidity: whirlpool.liquidity,
+        tick_current: whirlpool.tick_current,
+        fee_rate: whirlpool.fee_rate as u32,
+    };
+    let quote = quote_exact_input(&state, &ticks, amount_in, a_to_b);
+    if quote.amount_in < amount_in || quote.amount_out == 0 {
+        anyhow::bail!("Whirlpool {} lacks the liquidity for {} in (quoted {})", whirlpool_address, amount_in, quote.amount_in);
+    }
+    
+    let programs = fetch_mint_programs(rpc_client, &[whirlpool.token_mint_a, whirlpool.token_mint_b])?;
+    let (program_a, program_b) = (programs[0], programs[1]);
+    let swap = swap_v2_instruction(
+        owner,
+        whirlpool_address,
+        whirlpool,
+        a_to_b,
+        [program_a, program_b],
+        tick_arrays,
+        SwapLimits {
+            amount_in,
+            other_amount_threshold: min_amount_out(quote.amount_out, slippage_bps),
+            sqrt_price_limit_x64: sqrt_price_limit_x64(whirlpool.sqrt_price_x64, a_to_b, slippage_bps),
+        },
+    );
+    let output_program = if a_to_b { program_b } else { program_a };
+    let instructions = with_sol_wrapping(owner, input_mint, amount_in, &output_mint, &output_program.id(), swap)?;
+    Ok((instructions, quote))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_decode_whirlpool_and_tick_arrays() {
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = vec![0u8; 653];
+        data[0..8].copy_from_slice(&WHIRLPOOL_DISCRIMINATOR);
+        data[41..43].copy_from_slice(&64u16.to_le_bytes());
+        data[45..47].copy_from_slice(&3_000u16.to_le_bytes());
+        data[81..85].copy_from_slice(&(-10i32).to_le_bytes());
+        data[101..133].copy_from_slice(mint_a.as_ref());
+        data[181..213].copy_from_slice(mint_b.as_ref());
+        let whirlpool = decode_whirlpool(&data).unwrap();
+        assert_eq!((whirlpool.tick_spacing, whirlpool.fee_rate, whirlpool.tick_current), (64, 3_000, -10));
+        assert_eq!((whirlpool.token_mint_a, whirlpool.token_mint_b), (mint_a, mint_b));
+        assert_eq!(whirlpool.swap_tick_arrays(true), [-5_632, -11_264, -16_896]);
+        // -10 + 64 crosses into the array starting at 0
+        assert_eq!(whirlpool.swap_tick_arrays(false), [0, 5_632, 11_264]);
+        
+        let address = Pubkey::new_unique();
+        let accounts = vec![(address, Some(Account { data, owner: WHIRLPOOL_PROGRAM_ID, ..Account::default() }))];
+        assert_eq!(find_pool(&accounts, &mint_b, &mint_a).map(|(key, _)| key), Some(address));
+        assert!(find_pool(&accounts, &mint_b, &Pubkey::new_unique()).is_none());
+        
+        let mut data = TICK_ARRAY_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&(-5_632i32).to_le_bytes());
+        let mut ticks = vec![0u8; TICK_ARRAY_SIZE as usize * TICK_LEN];
+        ticks[2 * TICK_LEN] = 1;
+        ticks[2 * TICK_LEN + 1..2 * TICK_LEN + 17].copy_from_slice(&25i128.to_le_bytes());
+        data.extend_from_slice(&ticks);
+        assert_eq!(decode_tick_array(&data, 64), Some(vec![Tick { index: -5_504, liquidity_net: 25 }]));
+    }
+    
+    #[test]
+    fn test_swap_v2_instruction() {
+        let whirlpool = Whirlpool {
+            tick_spacing: 64,
+            fee_rate: 3_000,
+            liquidity: 0,
+            sqrt_price_x64: 1 << 64,
+            tick_current: 0,
+            token_mint_a: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+        };
+        let address = Pubkey::new_unique();
+        let tick_arrays = whirlpool.swap_tick_arrays(true).map(|start| tick_array_address(&address, start));
+        let ix = swap_v2_instruction(
+            &Pubkey::new_unique(), &address, &whirlpool, true, [TokenProgram::Spl; 2], tick_arrays,
+            SwapLimits { amount_in: 100, other_amount_threshold: 90, sqrt_price_limit_x64: 7 },
+        );
+        assert_eq!(ix.accounts.len(), 15);
+        assert_eq!(ix.accounts[14].pubkey, oracle_address(&address));
+        assert_eq!(&ix.data[40..], &[1, 1, 0]);
+    }
+}
@@ -0,0 +1,256 @@
+//! Token account service: owning program detection, ATAs, balances and closes
+
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use solana_account_decoder::UiAccountData;
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_client::rpc_response::RpcKeyedAccount;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Program owning a mint and its token accounts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenProgram {
+    Spl,
+    /// Token-2022 (used by newer Pump.fun mints)
+    Token2022,
+}
+
+impl TokenProgram {
+    pub const ALL: [TokenProgram; 2] = [TokenProgram::Spl, TokenProgram::Token2022];
+    
+    pub fn id(&self) -> Pubkey {
+        match self {
+            TokenProgram::Spl => spl_token::id(),
+            TokenProgram::Token2022 => spl_token_2022::id(),
+        }
+    }
+    
+    pub fn from_id(program_id: &Pubkey) -> Option<Self> {
+        Self::ALL.into_iter().find(|program| program.id() == *program_id)
+    }
+    
+    /// Associated token account of a wallet for a mint owned by this program
+    pub fn associated_token_address(&self, wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+        spl_associated_token_account::get_associated_token_address_with_program_id(wallet, mint, &self.id())
+    }
+    
+    /// Close an empty token account, sending its rent to `destination`
+    pub fn close_account(&self, account: &Pubkey, destination: &Pubkey, owner: &Pubkey) -> Result<Instruction> {
+        Ok(spl_token_2022::instruction::close_account(&self.id(), account, destination, owner, &[])?)
+    }
+    
+    /// Burn `amount` raw tokens from an account
+    pub fn burn(&self, account: &Pubkey, mint: &Pubkey, owner: &Pubkey, amount: u64, decimals: u8) -> Result<Instruction> {
+        Ok(spl_token_2022::instruction::burn_checked(&self.id(), account, mint, owner, &[], amount, decimals)?)
+    }
+}
+
+impl fmt::Display for TokenProgram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TokenProgram::Spl => "SPL Token",
+            TokenProgram::Token2022 => "Token-2022",
+        })
+    }
+}
+
+/// A token account and its balance
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenAccount {
+    pub address: Pubkey,
+    pub mint: Pubkey,
+    pub program: TokenProgram,
+    /// Raw amount
+    pub amount: u64,
+    pub decimals: u8,
+    /// Lamports held for rent (0 when read through the ATA fallback)
+    pub lamports: u64,
+}
+
+impl TokenAccount {
+    /// Amount in whole tokens
+    pub fn ui_amount(&self) -> f64 {
+        self.amount as f64 / 10f64.powi(self.decimals as i32)
+    }
+}
+
+/// A wallet's holding of one mint across its token accounts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenBalance {
+    /// Raw amount summed over all accounts
+    pub amount: u64,
+    pub decimals: u8,
+    pub accounts: Vec<TokenAccount>,
+}
+
+impl TokenBalance {
+    fn from_accounts(accounts: Vec<TokenAccount>) -> Self {
+        Self {
+            amount: accounts.iter().map(|account| account.amount).sum(),
+            decimals: accounts.first().map_or(0, |account| account.decimals),
+            accounts,
+        }
+    }
+    
+    /// Program of the held accounts, None when the wallet has no account for the mint
+    pub fn program(&self) -> Option<TokenProgram> {
+        self.accounts.first().map(|account| account.program)
+    }
+}
+
+/// Token account lookups that work the same for SPL Token and Token-2022 mints
+pub struct TokenAccountService {
+    rpc_client: Arc<AsyncRpcClient>,
+    /// Owning program per mint (a mint never changes program)
+    mint_programs: DashMap<Pubkey, TokenProgram>,
+}
+
+impl TokenAccountService {
+    pub fn new(rpc_url: String) -> Self {
+        Self {
+            rpc_client: Arc::new(AsyncRpcClient::new(rpc_url)),
+            mint_programs: DashMap::new(),
+        }
+    }
+    
+    /// Program that owns a mint, cached after the first lookup
+    pub async fn mint_program(&self, mint: &Pubkey) -> Result<TokenProgram> {
+        if let Some(program) = self.mint_programs.get(mint) {
+            return Ok(*program);
+        }
+        let account = self.rpc_client.get_account(mint).await
+            .with_context(|| format!("Failed to fetch mint {}", mint))?;
+        let program = TokenProgram::from_id(&account.owner)
+            .with_context(|| format!("{} is not a token mint (owned by {})", mint, account.owner))?;
+        self.mint_programs.insert(*mint, program);
+        Ok(program)
+    }
+    
+    /// Associated token account of a wallet under the mint's program
+    pub async fn associated_token_address(&self, wallet: &Pubkey, mint: &Pubkey) -> Result<(Pubkey, TokenProgram)> {
+        let program = self.mint_program(mint).await?;
+        Ok((program.associated_token_address(wallet, mint), program))
+    }
+    
+    /// Balance of a mint across all of a wallet's token accounts, read in one call
+    /// Falls back to the ATA under the mint's program if the RPC rejects the owner query.
+    pub async fn balance(&self, owner: &Pubkey, mint: &Pubkey) -> Result<TokenBalance> {
+        match self.rpc_client.get_token_accounts_by_owner(owner, TokenAccountsFilter::Mint(*mint)).await {
+            Ok(keyed) => {
+                let balance = TokenBalance::from_accounts(keyed.iter().filter_map(parse_token_account).collect());
+                if let Some(program) = balance.program() {
+                    self.mint_programs.insert(*mint, program);
+                }
+                Ok(balance)
+            }
+            Err(e) => {
+                debug!("getTokenAccountsByOwner failed for {}: {}, reading the ATA", mint, e);
+                let (address, program) = self.associated_token_address(owner, mint).await?;
+                let Ok(balance) = self.rpc_client.get_token_account_balance(&address).await else {
+                    return Ok(TokenBalance::default());
+                };
+                Ok(TokenBalance::from_accounts(vec![TokenAccount {
+                    address,
+                    mint: *mint,
+                    program,
+                    amount: balance.amount.parse().unwrap_or(0),
+                    decimals: balance.decimals,
+                    lamports: 0,
+                }]))
+            }
+        }
+    }
+    
+    /// Every token account a wallet owns under both programs
+    pub async fn accounts(&self, owner: &Pubkey) -> Result<Vec<TokenAccount>> {
+        let mut accounts = Vec::new();
+        for program in TokenProgram::ALL {
+            let keyed = self.rpc_client
+                .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program.id()))
+                .await
+                .with_context(|| format!("Failed to fetch {} accounts", program))?;
+            accounts.extend(keyed.iter().filter_map(parse_token_account));
+        }
+        Ok(accounts)
+    }
+}
+
+/// Decode a jsonParsed token account as returned by `getTokenAccountsByOwner`
+pub fn parse_token_account(keyed: &RpcKeyedAccount) -> Option<TokenAccount> {
+    let UiAccountData::Json(parsed) = &keyed.account.data else {
+        return None;
+    };
+    let info = parsed.parsed.get("info")?;
+    let token_amount = info.get("tokenAmount")?;
+    Some(TokenAccount {
+        address: Pubkey::from_str(&keyed.pubkey).ok()?,
+        mint: Pubkey::from_str(info.get("mint")?.as_str()?).ok()?,
+        program: TokenProgram::from_id(&Pubkey::from_str(&keyed.account.owner).ok()?)?,
+        amount: token_amount.get("amount")?.as_str()?.parse().ok()?,
+        decimals: u8::try_from(token_amount.get("decimals")?.as_u64()?).ok()?,
+        lamports: keyed.account.lamports,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_account_decoder::{parse_account_data::ParsedAccount, UiAccount};
+    
+    fn keyed_account(owner: Pubkey, mint: Pubkey, amount: &str) -> RpcKeyedAccount {
+        RpcKeyedAccount {
+            pubkey: Pubkey::new_unique().to_string(),
+            account: UiAccount {
+                lamports: 2_039_280,
+                data: UiAccountData::Json(ParsedAccount {
+                    program: "spl-token-2022".to_string(),
+                    parsed: serde_json::json!({
+                        "type": "account",
+                        "info": {
+                            "mint": mint.to_string(),
+                            "tokenAmount": { "amount": amount, "decimals": 6, "uiAmountString": "0" },
+                        },
+                    }),
+                    space: 170,
+                }),
+                owner: owner.to_string(),
+                executable: false,
+                rent_epoch: 0,
+                space: Some(170),
+            },
+        }
+    }
+    
+    #[test]
+    fn test_parse_token_account() {
+        let mint = Pubkey::new_unique();
+        let account = parse_token_account(&keyed_account(spl_token_2022::id(), mint, "1500")).unwrap();
+        assert_eq!(account.program, TokenProgram::Token2022);
+        assert_eq!((account.mint, account.amount, account.decimals), (mint, 1500, 6));
+        assert_eq!((account.lamports, account.ui_amount()), (2_039_280, 0.0015));
+        
+        // Accounts owned by anything but a token program are ignored
+        assert!(parse_token_account(&keyed_account(Pubkey::new_unique(), mint, "1500")).is_none());
+    }
+    
+    #[test]
+    fn test_token_program_addresses_and_closes() {
+        let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let spl_ata = TokenProgram::Spl.associated_token_address(&wallet, &mint);
+        let token_2022_ata = TokenProgram::Token2022.associated_token_address(&wallet, &mint);
+        assert_ne!(spl_ata, token_2022_ata);
+        assert_eq!(spl_ata, spl_associated_token_account::get_associated_token_address(&wallet, &mint));
+        
+        for program in TokenProgram::ALL {
+            let close = program.close_account(&spl_ata, &wallet, &wallet).unwrap();
+            assert_eq!(close.program_id, program.id());
+            assert_eq!(TokenProgram::from_id(&program.id()), Some(program));
+        }
+    }
+}
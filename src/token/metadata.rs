@@ -0,0 +1,269 @@
+//! Token metadata and holder distribution lookups used to enrich positions and notifications
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use super::TokenProgram;
+use crate::decoder::pumpfun;
+
+/// Metaplex Token Metadata program
+pub const METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// How long fetched info is reused; holders and curve progress move, the rest does not
+const INFO_TTL: Duration = Duration::from_secs(60);
+/// Holders counted in the top-holder concentration
+const TOP_HOLDERS: usize = 10;
+
+/// What is known about a token beyond its mint address
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenInfo {
+    pub mint: Pubkey,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+    pub decimals: u8,
+    /// Raw supply
+    pub supply: u64,
+    /// None once revoked
+    pub mint_authority: Option<Pubkey>,
+    /// None once revoked
+    pub freeze_authority: Option<Pubkey>,
+    /// Pump.fun launcher, else the first verified Metaplex creator
+    pub creator: Option<Pubkey>,
+    /// Percent of the Pump.fun bonding curve sold, None off Pump.fun
+    pub curve_progress: Option<f64>,
+    /// Percent of supply in the largest account, excluding the bonding curve
+    pub top_holder_percent: Option<f64>,
+    /// Percent of supply in the ten largest accounts, excluding the bonding curve
+    pub top10_holder_percent: Option<f64>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl TokenInfo {
+    /// One-line description for logs and notifications
+    pub fn summary(&self) -> String {
+        let mut parts = vec![match (&self.symbol, &self.name) {
+            (Some(symbol), Some(name)) => format!("${} ({})", symbol, name),
+            (Some(symbol), None) => format!("${}", symbol),
+            _ => "no metadata".to_string(),
+        }];
+        if let Some(progress) = self.curve_progress {
+            parts.push(format!("curve {:.0}%", progress));
+        }
+        if let Some(top10) = self.top10_holder_percent {
+            parts.push(format!("top10 {:.1}%", top10));
+        }
+        if self.mint_authority.is_some() {
+            parts.push("mint authority set".to_string());
+        }
+        if self.freeze_authority.is_some() {
+            parts.push("freeze authority set".to_string());
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Name, symbol, URI and creators of a Metaplex metadata account
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metadata {
+    pub update_authority: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// Verified creators in listed order
+    pub creators: Vec<Pubkey>,
+}
+
+/// Metaplex metadata PDA of a mint
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", METADATA_PROGRAM_ID.as_ref(), mint.as_ref()], &METADATA_PROGRAM_ID).0
+}
+
+/// Decode a Metaplex metadata account (strings are stored padded with NULs)
+pub fn decode_metadata(data: &[u8]) -> Option<Metadata> {
+    let mut offset = 1;
+    let mut take = |len: usize| {
+        let bytes = data.get(offset..offset + len)?;
+        offset += len;
+        Some(bytes)
+    };
+    let update_authority = Pubkey::new_from_array(take(32)?.try_into().ok()?);
+    take(32)?; // mint
+    let mut string = || {
+        let len = u32::from_le_bytes(take(4)?.try_into().ok()?) as usize;
+        let bytes = take(len)?;
+        Some(String::from_utf8_lossy(bytes).trim_end_matches('\0').trim().to_string())
+    };
+    let (name, symbol, uri) = (string()?, string()?, string()?);
+    take(2)?; // seller fee basis points
+    let mut creators = Vec::new();
+    if take(1).is_some_and(|tag| tag[0] == 1) {
+        let count = u32::from_le_bytes(take(4)?.try_into().ok()?);
+        for _ in 0..count {
+            let address = Pubkey::new_from_array(take(32)?.try_into().ok()?);
+            let verified = take(2)?[0] != 0;
+            if verified {
+                creators.push(address);
+            }
+        }
+    }
+    Some(Metadata { update_authority, name, symbol, uri, creators })
+}
+
+/// Mint and freeze authorities, supply and decimals of an SPL Token or Token-2022 mint
+pub fn decode_mint(data: &[u8]) -> Option<(Option<Pubkey>, Option<Pubkey>, u64, u8)> {
+    let authority = |offset: usize| -> Option<Option<Pubkey>> {
+        let tag = u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?);
+        let key = Pubkey::new_from_array(data.get(offset + 4..offset + 36)?.try_into().ok()?);
+        Some((tag == 1).then_some(key))
+    };
+    let supply = u64::from_le_bytes(data.get(36..44)?.try_into().ok()?);
+    Some((authority(0)?, authority(46)?, supply, *data.get(44)?))
+}
+
+/// Share of `supply` held by the largest and the ten largest of `balances`, in percent
+pub fn holder_concentration(balances: &[u64], supply: u64) -> Option<(f64, f64)> {
+    if supply == 0 {
+        return None;
+    }
+    let mut balances = balances.to_vec();
+    balances.sort_unstable_by(|a, b| b.cmp(a));
+    let percent = |amount: u64| amount as f64 / supply as f64 * 100.0;
+    let top = balances.first().copied().unwrap_or_default();
+    Some((percent(top), percent(balances.iter().take(TOP_HOLDERS).sum())))
+}
+
+/// Fetches and caches `TokenInfo` per mint
+pub struct TokenInfoService {
+    rpc_client: AsyncRpcClient,
+    cache: DashMap<Pubkey, (Instant, TokenInfo)>,
+}
+
+impl TokenInfoService {
+    pub fn new(rpc_url: String) -> Self {
+        Self {
+            rpc_client: AsyncRpcClient::new(rpc_url),
+            cache: DashMap::new(),
+        }
+    }
+    
+    /// Info for a mint, fetched unless cached within the last minute
+    pub async fn get(&self, mint: &Pubkey) -> Result<TokenInfo> {
+        if let Some(entry) = self.cache.get(mint) {
+            if entry.0.elapsed() < INFO_TTL {
+                return Ok(entry.1.clone());
+            }
+        }
+        let info = self.fetch(mint).await?;
+        self.cache.insert(*mint, (Instant::now(), info.clone()));
+        Ok(info)
+    }
+    
+    /// Mint, metadata and bonding curve in one read, with the largest holders alongside
+    async fn fetch(&self, mint: &Pubkey) -> Result<TokenInfo> {
+        let curve_address = pumpfun::bonding_curve_address(mint);
+        let mut keys = vec![*mint, metadata_address(mint)];
+        keys.extend(curve_address);
+        let (accounts, holders) = tokio::join!(
+            self.rpc_client.get_multiple_accounts(&keys),
+            self.rpc_client.get_token_largest_accounts(mint),
+        );
+        let accounts = accounts.context("Failed to fetch mint accounts")?;
+        
+        let mint_account = accounts[0].as_ref().with_context(|| format!("Mint {} not found", mint))?;
+        let (mint_authority, freeze_authority, supply, decimals) =
+            decode_mint(&mint_account.data).with_context(|| format!("{} is not a mint", mint))?;
+        let metadata = accounts[1].as_ref().and_then(|account| decode_metadata(&account.data));
+        let curve = accounts.get(2)
+            .and_then(Option::as_ref)
+            .and_then(|account| pumpfun::decode_bonding_curve(&account.data));
+        
+        // The curve's own token account holds the unsold supply, not a holder
+        let curve_accounts: Vec<String> = curve_address.filter(|_| curve.is_some())
+            .map(|curve| TokenProgram::ALL.map(|program| program.associated_token_address(&curve, mint).to_string()).to_vec())
+            .unwrap_or_default();
+        let concentration = match holders {
+            Ok(holders) => {
+                let balances: Vec<u64> = holders.iter()
+                    .filter(|holder| !curve_accounts.contains(&holder.address))
+                    .filter_map(|holder| holder.amount.amount.parse().ok())
+                    .collect();
+                holder_concentration(&balances, supply)
+            }
+            Err(e) => {
+                debug!("Could not fetch the largest holders of {}: {}", mint, e);
+                None
+            }
+        };
+        
+        Ok(TokenInfo {
+            mint: *mint,
+            name: metadata.as_ref().map(|metadata| metadata.name.clone()),
+            symbol: metadata.as_ref().map(|metadata| metadata.symbol.clone()),
+            uri: metadata.as_ref().map(|metadata| metadata.uri.clone()),
+            decimals,
+            supply,
+            mint_authority,
+            freeze_authority,
+            creator: curve.as_ref()
+                .and_then(|curve| curve.creator)
+                .or_else(|| metadata.as_ref().and_then(|metadata| metadata.creators.first().copied())),
+            curve_progress: curve.as_ref().map(|curve| curve.progress()),
+            top_holder_percent: concentration.map(|(top, _)| top),
+            top10_holder_percent: concentration.map(|(_, top10)| top10),
+            fetched_at: Utc::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn borsh_string(data: &mut Vec<u8>, value: &str, padded: usize) {
+        data.extend_from_slice(&(padded as u32).to_le_bytes());
+        data.extend_from_slice(value.as_bytes());
+        data.resize(data.len() + padded - value.len(), 0);
+    }
+    
+    #[test]
+    fn test_decode_metadata_and_mint() {
+        let (authority, mint, creator, unverified) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = vec![4];
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(mint.as_ref());
+        borsh_string(&mut data, "Pepe Coin", 32);
+        borsh_string(&mut data, "PEPE", 10);
+        borsh_string(&mut data, "https://example.com/pepe.json", 200);
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&2u32.to_le_bytes());
+        for (key, verified) in [(unverified, 0), (creator, 1)] {
+            data.extend_from_slice(key.as_ref());
+            data.extend_from_slice(&[verified, 50]);
+        }
+        let metadata = decode_metadata(&data).unwrap();
+        assert_eq!((metadata.name.as_str(), metadata.symbol.as_str()), ("Pepe Coin", "PEPE"));
+        assert_eq!(metadata.uri, "https://example.com/pepe.json");
+        assert_eq!((metadata.update_authority, metadata.creators), (authority, vec![creator]));
+        assert!(decode_metadata(&data[..100]).is_none());
+        
+        // Mint authority revoked, freeze authority set
+        let mut mint_data = vec![0u8; 82];
+        mint_data[36..44].copy_from_slice(&1_000_000u64.to_le_bytes());
+        mint_data[44] = 6;
+        mint_data[46..50].copy_from_slice(&1u32.to_le_bytes());
+        mint_data[50..82].copy_from_slice(authority.as_ref());
+        assert_eq!(decode_mint(&mint_data), Some((None, Some(authority), 1_000_000, 6)));
+        
+        assert_eq!(holder_concentration(&[100, 400, 250], 1_000), Some((40.0, 75.0)));
+        assert_eq!(holder_concentration(&[], 0), None);
+    }
+}
@@ -0,0 +1,10 @@
+//! Token account lookups and instructions for both SPL Token and Token-2022, the rent
+//! reclaimer for empty accounts, and token metadata enrichment
+
+pub mod accounts;
+pub mod metadata;
+pub mod reclaimer;
+
+pub use accounts::*;
+pub use metadata::*;
+pub use reclaimer::*;
@@ -0,0 +1,235 @@
+//! Closes empty token accounts to reclaim their rent
+
+use anyhow::{Context, Result};
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Signature, Signer},
+    transaction::Transaction,
+};
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{info, warn, debug};
+
+use crate::decoder::{transaction_config, WSOL_MINT};
+use crate::metrics::metrics;
+use crate::signer::{WalletPool, WalletSigner};
+use crate::state::StateManager;
+use super::{TokenAccount, TokenAccountService};
+
+/// Accounts closed per transaction (a dust burn adds a second instruction)
+const CLOSE_BATCH: usize = 8;
+
+/// Wait after a full sell so the sell lands before its account is checked
+const SELL_SETTLE_DELAY: Duration = Duration::from_secs(20);
+
+/// Rent reclaimer settings
+#[derive(Debug, Clone)]
+pub struct ReclaimConfig {
+    /// How often every wallet is swept
+    pub interval: Duration,
+    /// Burn balances below this many whole tokens so their accounts can be closed (0 = never burn)
+    pub burn_dust_below: f64,
+}
+
+/// A token account to close, burning its dust first when `burn` is set
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReclaimAction {
+    pub account: TokenAccount,
+    pub burn: bool,
+}
+
+/// Accounts worth closing: empty ones, and dust below the threshold when burning is enabled
+/// Mints with an open position and wrapped SOL balances are never touched.
+pub fn reclaim_plan(accounts: &[TokenAccount], open_mints: &HashSet<Pubkey>, burn_dust_below: f64) -> Vec<ReclaimAction> {
+    let wsol = Pubkey::from_str(WSOL_MINT).ok();
+    accounts.iter()
+        .filter(|account| !open_mints.contains(&account.mint))
+        .filter_map(|account| {
+            let burn = match account.amount {
+                0 => false,
+                _ if Some(account.mint) != wsol && account.ui_amount() < burn_dust_below => true,
+                _ => return None,
+            };
+            Some(ReclaimAction { account: account.clone(), burn })
+        })
+        .collect()
+}
+
+/// Background sweeper returning rent from empty token accounts in every pool wallet
+pub struct AtaReclaimer {
+    wallets: Arc<WalletPool>,
+    state: Arc<StateManager>,
+    token_accounts: Arc<TokenAccountService>,
+    rpc_client: AsyncRpcClient,
+    config: ReclaimConfig,
+    wake: Notify,
+}
+
+impl AtaReclaimer {
+    pub fn new(
+        wallets: Arc<WalletPool>,
+        state: Arc<StateManager>,
+        token_accounts: Arc<TokenAccountService>,
+        rpc_url: String,
+        config: ReclaimConfig,
+    ) -> Self {
+        Self {
+            wallets,
+            state,
+            token_accounts,
+            rpc_client: AsyncRpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed()),
+            config,
+            wake: Notify::new(),
+        }
+    }
+    
+    /// Ask for a sweep shortly, e.g. after a full sell
+    pub fn request(&self) {
+        self.wake.notify_one();
+    }
+    
+    /// Sweep on the interval and after every request
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            info!(
+                "♻️ Rent reclaimer started (every {:?}, burn dust below {} tokens)",
+                self.config.interval, self.config.burn_dust_below
+            );
+            let mut ticker = tokio::time::interval(self.config.interval);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = self.wake.notified() => tokio::time::sleep(SELL_SETTLE_DELAY).await,
+                }
+                self.sweep_all().await;
+            }
+        })
+    }
+    
+    /// Close reclaimable accounts in every pool wallet
+    async fn sweep_all(&self) {
+        let open_mints: HashSet<Pubkey> = self.state.get_all_positions()
+            .iter()
+            .map(|position| position.token_mint)
+            .collect();
+        
+        let wallets = std::iter::once(self.wallets.main()).chain(self.wallets.burners());
+        for wallet in wallets {
+            match self.sweep_wallet(wallet, &open_mints).await {
+                Ok((0, _)) => debug!("No token accounts to close in {}", wallet.pubkey()),
+                Ok((closed, lamports)) => info!(
+                    "♻️ Closed {} token account(s) in {}, reclaimed {:.6} SOL",
+                    closed, wallet.pubkey(), lamports as f64 / 1_000_000_000.0
+                ),
+                Err(e) => warn!("⚠️ Rent reclaim for {} failed: {:#}", wallet.pubkey(), e),
+            }
+        }
+    }
+    
+    /// Close one wallet's reclaimable accounts in batches
+    /// Returns the accounts closed and the lamports they returned.
+    async fn sweep_wallet(&self, wallet: &Arc<WalletSigner>, open_mints: &HashSet<Pubkey>) -> Result<(usize, u64)> {
+        let owner = wallet.pubkey();
+        let accounts = self.token_accounts.accounts(&owner).await?;
+        let plan = reclaim_plan(&accounts, open_mints, self.config.burn_dust_below);
+        
+        let (mut closed, mut reclaimed) = (0, 0);
+        for batch in plan.chunks(CLOSE_BATCH) {
+            let mut instructions: Vec<Instruction> = Vec::new();
+            for action in batch {
+                let account = &action.account;
+                if action.burn {
+                    instructions.push(account.program.burn(&account.address, &account.mint, &owner, account.amount, account.decimals)?);
+                }
+                instructions.push(account.program.close_account(&account.address, &owner, &owner)?);
+            }
+            
+            let signature = match self.send(wallet, &instructions).await {
+                Ok(signature) => signature,
+                Err(e) => {
+                    warn!("⚠️ Closing {} token account(s) failed: {:#}", batch.len(), e);
+                    continue;
+                }
+            };
+            
+            // Rent actually returned; the accounts' own lamports if the transaction cannot be read back
+            let lamports = match self.rent_returned(&signature, &owner).await {
+                Some(lamports) => lamports,
+                None => batch.iter().map(|action| action.account.lamports).sum(),
+            };
+            let burned = batch.iter().filter(|action| action.burn).count();
+            debug!("Closed {} account(s) ({} dust burned): {}", batch.len(), burned, signature);
+            metrics().record_rent_reclaimed(batch.len(), lamports);
+            closed += batch.len();
+            reclaimed += lamports;
+        }
+        Ok((closed, reclaimed))
+    }
+    
+    async fn send(&self, wallet: &Arc<WalletSigner>, instructions: &[Instruction]) -> Result<Signature> {
+        let recent_blockhash = self.rpc_client.get_latest_blockhash().await
+            .context("Failed to get recent blockhash")?;
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&wallet.pubkey()),
+            &[wallet.as_ref()],
+            recent_blockhash,
+        );
+        Ok(self.rpc_client.send_and_confirm_transaction(&tx).await?)
+    }
+    
+    /// The owner's balance change in a confirmed close transaction, with the fee added back
+    async fn rent_returned(&self, signature: &Signature, owner: &Pubkey) -> Option<u64> {
+        let tx = self.rpc_client.get_transaction_with_config(signature, transaction_config()).await.ok()?;
+        let meta = tx.transaction.meta?;
+        let message = tx.transaction.transaction.decode()?.message;
+        let index = message.static_account_keys().iter().position(|key| key == owner)?;
+        let fee = if index == 0 { meta.fee } else { 0 };
+        (meta.post_balances.get(index)? + fee).checked_sub(*meta.pre_balances.get(index)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token::TokenProgram;
+    
+    fn account(mint: Pubkey, amount: u64) -> TokenAccount {
+        TokenAccount {
+            address: Pubkey::new_unique(),
+            mint,
+            program: TokenProgram::Token2022,
+            amount,
+            decimals: 6,
+            lamports: 2_074_080,
+        }
+    }
+    
+    #[test]
+    fn test_reclaim_plan() {
+        let (empty, dust, held, open) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let wsol = Pubkey::from_str(WSOL_MINT).unwrap();
+        let accounts = vec![
+            account(empty, 0),
+            account(dust, 500_000),
+            account(held, 5_000_000),
+            account(open, 0),
+            account(wsol, 1_000),
+        ];
+        let open_mints = HashSet::from([open]);
+        
+        let plan = reclaim_plan(&accounts, &open_mints, 1.0);
+        let planned: Vec<(Pubkey, bool)> = plan.iter().map(|action| (action.account.mint, action.burn)).collect();
+        assert_eq!(planned, vec![(empty, false), (dust, true)]);
+        
+        // Without a dust threshold only empty accounts are closed
+        assert_eq!(reclaim_plan(&accounts, &open_mints, 0.0).len(), 1);
+    }
+}
@@ -0,0 +1,255 @@
+//! Dashboard state, key bindings and event loop
+
+use anyhow::Result;
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use futures::StreamExt;
+use parking_lot::RwLock;
+use ratatui::DefaultTerminal;
+use solana_sdk::pubkey::Pubkey;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::api::PositionSeller;
+use crate::state::{BotControl, Position, StateManager};
+use super::logs::LogBuffer;
+use super::ui;
+
+/// How often the dashboard redraws without input
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Dashboard state; everything live is read from the engine's shared state on each draw
+pub struct App {
+    pub(super) state: Arc<StateManager>,
+    pub(super) control: Arc<BotControl>,
+    pub(super) logs: Arc<LogBuffer>,
+    seller: Arc<dyn PositionSeller>,
+    selected: usize,
+    confirm_sell: Option<Pubkey>,
+    /// Result of the last action (shared with spawned sells)
+    status: Arc<RwLock<String>>,
+    should_quit: bool,
+}
+
+impl App {
+    pub fn new(
+        state: Arc<StateManager>,
+        control: Arc<BotControl>,
+        seller: Arc<dyn PositionSeller>,
+        logs: Arc<LogBuffer>,
+    ) -> Self {
+        Self {
+            state,
+            control,
+            logs,
+            seller,
+            selected: 0,
+            confirm_sell: None,
+            status: Arc::new(RwLock::new(String::new())),
+            should_quit: false,
+        }
+    }
+    
+    /// Open positions, oldest first so the selection stays put as positions are added
+    pub fn positions(&self) -> Vec<Position> {
+        let mut positions = self.state.get_all_positions();
+        positions.sort_by_key(|p| (p.opened_at, p.token_mint));
+        positions
+    }
+    
+    /// Index of the selected position row
+    pub fn selected(&self) -> usize {
+        self.selected.min(self.state.open_positions_count().saturating_sub(1))
+    }
+    
+    /// Position waiting for sell confirmation
+    pub fn confirm_sell(&self) -> Option<Pubkey> {
+        self.confirm_sell
+    }
+    
+    /// Result of the last action
+    pub fn status(&self) -> String {
+        self.status.read().clone()
+    }
+    
+    pub fn should_quit(&self) -> bool {
+        self.should_quit
+    }
+    
+    /// Apply a key press
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.should_quit = true;
+            return;
+        }
+        
+        // A pending sell only accepts confirm or cancel
+        if let Some(mint) = self.confirm_sell.take() {
+            if key.code == KeyCode::Char('y') {
+                self.start_sell(mint);
+            } else {
+                self.set_status("Sell cancelled".to_string());
+            }
+            return;
+        }
+        
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected().saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected = self.selected() + 1,
+            KeyCode::Char('s') => {
+                if let Some(position) = self.positions().get(self.selected()) {
+                    self.confirm_sell = Some(position.token_mint);
+                }
+            }
+            KeyCode::Char('p') => {
+                let paused = !self.control.is_paused();
+                self.control.set_paused(paused);
+                self.set_status(if paused { "Copy buys paused" } else { "Copy buys resumed" }.to_string());
+            }
+            KeyCode::Char('t') => {
+                let enabled = !self.control.take_profit_enabled();
+                self.control.set_take_profit_enabled(enabled);
+                self.set_status(format!("Take profit {}", if enabled { "enabled" } else { "disabled" }));
+            }
+            _ => {}
+        }
+    }
+    
+    fn set_status(&self, status: String) {
+        *self.status.write() = status;
+    }
+    
+    /// Force-sell in the background and report the result in the status line
+    fn start_sell(&self, mint: Pubkey) {
+        self.set_status(format!("Selling {}...", mint));
+        let seller = self.seller.clone();
+        let status = self.status.clone();
+        
+        tokio::spawn(async move {
+            let message = match seller.force_sell(&mint).await {
+                Ok(sig) => format!("Sold {}: {}", mint, sig),
+                Err(e) => format!("Sell {} failed: {:#}", mint, e),
+            };
+            *status.write() = message;
+        });
+    }
+}
+
+/// Take over the terminal and run the dashboard until the user quits or `shutdown` resolves
+pub async fn run(app: App, shutdown: impl Future<Output = ()>) -> Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, app, shutdown).await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    mut app: App,
+    shutdown: impl Future<Output = ()>,
+) -> Result<()> {
+    let mut events = EventStream::new();
+    let mut refresh = tokio::time::interval(REFRESH_INTERVAL);
+    tokio::pin!(shutdown);
+    
+    while !app.should_quit() {
+        terminal.draw(|frame| ui::draw(frame, &app))?;
+        
+        tokio::select! {
+            _ = &mut shutdown => break,
+            _ = refresh.tick() => {}
+            event = events.next() => match event {
+                Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => app.handle_key(key),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+                None => break,
+            },
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TakeProfitTier;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    
+    struct MockSeller {
+        sold: Mutex<Vec<Pubkey>>,
+    }
+    
+    #[async_trait]
+    impl PositionSeller for MockSeller {
+        async fn force_sell(&self, token_mint: &Pubkey) -> Result<String> {
+            self.sold.lock().push(*token_mint);
+            Ok("manual_sig".to_string())
+        }
+    }
+    
+    fn app() -> (App, Arc<MockSeller>) {
+        let state = Arc::new(StateManager::new());
+        let control = Arc::new(BotControl::new(
+            vec![Pubkey::new_unique()],
+            0.1,
+            10_000,
+            true,
+            vec![TakeProfitTier { multiplier: 2.0, sell_percent: 100 }],
+        ));
+        let seller = Arc::new(MockSeller { sold: Mutex::new(Vec::new()) });
+        let app = App::new(state, control, seller.clone(), Arc::new(LogBuffer::new()));
+        (app, seller)
+    }
+    
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+    
+    #[tokio::test]
+    async fn test_pause_and_take_profit_toggles() {
+        let (mut app, _) = app();
+        
+        press(&mut app, KeyCode::Char('p'));
+        assert!(app.control.is_paused());
+        press(&mut app, KeyCode::Char('p'));
+        assert!(!app.control.is_paused());
+        
+        press(&mut app, KeyCode::Char('t'));
+        assert!(!app.control.take_profit_enabled());
+        assert_eq!(app.status(), "Take profit disabled");
+    }
+    
+    #[tokio::test]
+    async fn test_force_sell_requires_confirmation() {
+        let (mut app, seller) = app();
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        app.state.open_position(Position::new(first, 1_000, 100, "t1".to_string(), "o1".to_string()));
+        app.state.open_position(Position::new(second, 1_000, 100, "t2".to_string(), "o2".to_string()));
+        let second = app.positions()[1].token_mint;
+        
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.selected(), 1);
+        
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.confirm_sell(), Some(second));
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(app.confirm_sell(), None);
+        
+        press(&mut app, KeyCode::Char('s'));
+        press(&mut app, KeyCode::Char('y'));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(*seller.sold.lock(), vec![second]);
+        assert!(app.status().contains("manual_sig"));
+    }
+    
+    #[tokio::test]
+    async fn test_quit_keys() {
+        let (mut app, _) = app();
+        app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert!(app.should_quit());
+    }
+}
@@ -0,0 +1,125 @@
+//! In-memory log capture for the dashboard log pane
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Log lines kept for the log pane
+const MAX_LOG_LINES: usize = 1000;
+
+/// A captured log line
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub at: DateTime<Utc>,
+    pub level: Level,
+    pub message: String,
+}
+
+/// Ring buffer of recent log lines shared between the tracing layer and the dashboard
+#[derive(Debug, Default)]
+pub struct LogBuffer {
+    lines: RwLock<VecDeque<LogLine>>,
+}
+
+impl LogBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Append a line, dropping the oldest beyond the limit
+    pub fn push(&self, line: LogLine) {
+        let mut lines = self.lines.write();
+        if lines.len() >= MAX_LOG_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+    
+    /// Get the last `count` lines, oldest first
+    pub fn tail(&self, count: usize) -> Vec<LogLine> {
+        let lines = self.lines.read();
+        lines.iter().skip(lines.len().saturating_sub(count)).cloned().collect()
+    }
+}
+
+/// Tracing layer writing events into a `LogBuffer` instead of stdout
+pub struct LogLayer {
+    buffer: Arc<LogBuffer>,
+}
+
+impl LogLayer {
+    pub fn new(buffer: Arc<LogBuffer>) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        
+        self.buffer.push(LogLine {
+            at: Utc::now(),
+            level: *event.metadata().level(),
+            message: visitor.message,
+        });
+    }
+}
+
+/// Formats the `message` field followed by any other fields as `key=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let fields = std::mem::take(&mut self.message);
+            let _ = write!(self.message, "{:?}{}", value, fields);
+        } else {
+            let _ = write!(self.message, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+    
+    #[test]
+    fn test_layer_captures_events() {
+        let buffer = Arc::new(LogBuffer::new());
+        let subscriber = tracing_subscriber::registry().with(LogLayer::new(buffer.clone()));
+        
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("first");
+            tracing::warn!(attempt = 2, "stream closed");
+        });
+        
+        let lines = buffer.tail(10);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].message, "first");
+        assert_eq!(lines[1].level, Level::WARN);
+        assert_eq!(lines[1].message, "stream closed attempt=2");
+    }
+    
+    #[test]
+    fn test_buffer_is_bounded() {
+        let buffer = LogBuffer::new();
+        for i in 0..MAX_LOG_LINES + 10 {
+            buffer.push(LogLine { at: Utc::now(), level: Level::INFO, message: i.to_string() });
+        }
+        
+        let lines = buffer.tail(usize::MAX);
+        assert_eq!(lines.len(), MAX_LOG_LINES);
+        assert_eq!(lines[0].message, "10");
+        assert_eq!(buffer.tail(1)[0].message, (MAX_LOG_LINES + 9).to_string());
+    }
+}
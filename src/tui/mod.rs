@@ -0,0 +1,8 @@
+//! Terminal dashboard (`--tui`) over the engine's in-process state
+
+pub mod app;
+pub mod logs;
+pub mod ui;
+
+pub use app::*;
+pub use logs::*;
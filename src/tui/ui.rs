@@ -0,0 +1,257 @@
+//! Dashboard layout and rendering
+
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+use tracing::Level;
+
+use crate::config::TakeProfitTier;
+use crate::state::Position;
+use super::app::App;
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Draw the whole dashboard
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [header, body, logs, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(8),
+        Constraint::Length(10),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    
+    let [positions, side] = Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(body);
+    let [targets, pending] = Layout::vertical([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(side);
+    
+    draw_header(frame, app, header);
+    draw_positions(frame, app, positions);
+    draw_target_events(frame, app, targets);
+    draw_pending(frame, app, pending);
+    draw_logs(frame, app, logs);
+    draw_footer(frame, app, footer);
+}
+
+/// Bot status and realized PnL
+fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
+    let stats = app.state.get_stats();
+    let pnl = stats.total_pnl_sol();
+    
+    let copying = if app.control.is_paused() {
+        Span::styled(" PAUSED ", Style::new().black().on_yellow())
+    } else {
+        Span::styled(" COPYING ", Style::new().black().on_green())
+    };
+    let take_profit = if app.control.take_profit_enabled() { "TP on" } else { "TP off" };
+    
+    let line = Line::from(vec![
+        copying,
+        Span::raw(format!("  {}  |  Realized PnL: ", take_profit)),
+        Span::styled(format!("{:+.4} SOL", pnl), pnl_style(pnl)),
+        Span::raw(format!(
+            "  |  Win rate: {:.1}%  |  Open: {}  |  Buys: {}  Sells: {}",
+            stats.win_rate(),
+            stats.open_positions,
+            stats.total_buys,
+            stats.total_sells
+        )),
+    ]);
+    
+    frame.render_widget(Paragraph::new(line).block(Block::bordered().title(" Copytrading Bot ")), area);
+}
+
+/// Open positions with multiplier and take profit progress
+fn draw_positions(frame: &mut Frame, app: &App, area: Rect) {
+    let tiers = app.control.take_profit_tiers();
+    let take_profit_enabled = app.control.take_profit_enabled();
+    let positions = app.positions();
+    
+    let rows: Vec<Row> = positions.iter().map(|p| {
+        let multiplier = p.profit_multiplier();
+        Row::new(vec![
+            Line::from(short(&p.token_mint.to_string()).to_string()),
+            Line::from(format!("{:.4}", p.invested_sol as f64 / LAMPORTS_PER_SOL)),
+            Line::from(format!("{:.4}", p.current_value_sol as f64 / LAMPORTS_PER_SOL)),
+            Line::styled(format!("{:.2}x", multiplier), pnl_style(multiplier - 1.0)),
+            Line::from(tp_progress(p, &tiers, take_profit_enabled)),
+        ])
+    }).collect();
+    
+    let table = Table::new(rows, [
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(8),
+        Constraint::Min(12),
+    ])
+    .header(Row::new(vec!["Mint", "In (SOL)", "Value", "Mult", "Take profit"]).bold())
+    .block(Block::bordered().title(" Positions "))
+    .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    
+    let mut state = TableState::default();
+    if !positions.is_empty() {
+        state.select(Some(app.selected()));
+    }
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+/// Recent target wallet trades, newest first
+fn draw_target_events(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app.state.get_target_events().into_iter().map(|e| {
+        let (side, style) = if e.is_buy {
+            ("BUY ", Style::new().green())
+        } else {
+            ("SELL", Style::new().red())
+        };
+        let mint = e.token_mint.map(|m| short(&m.to_string()).to_string()).unwrap_or_else(|| "?".to_string());
+        ListItem::new(Line::from(vec![
+            Span::raw(format!("{} ", e.seen_at.format("%H:%M:%S"))),
+            Span::styled(side, style),
+            Span::raw(format!(" {} {}", short(&e.target.to_string()), mint)),
+        ]))
+    }).collect();
+    
+    frame.render_widget(List::new(items).block(Block::bordered().title(" Target events ")), area);
+}
+
+/// Transactions we sent that have not landed yet
+fn draw_pending(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app.state.get_pending_txs().into_iter().map(|(signature, mint)| {
+        ListItem::new(format!("{} {}", short(&signature), short(&mint.to_string())))
+    }).collect();
+    
+    frame.render_widget(List::new(items).block(Block::bordered().title(" Pending transactions ")), area);
+}
+
+/// Most recent log lines that fit the pane
+fn draw_logs(frame: &mut Frame, app: &App, area: Rect) {
+    let lines: Vec<Line> = app.logs.tail(area.height.saturating_sub(2) as usize).into_iter().map(|l| {
+        let style = match l.level {
+            Level::ERROR => Style::new().red(),
+            Level::WARN => Style::new().yellow(),
+            Level::INFO => Style::new(),
+            _ => Style::new().dark_gray(),
+        };
+        Line::from(vec![
+            Span::raw(format!("{} ", l.at.format("%H:%M:%S"))),
+            Span::styled(format!("{:5} ", l.level), style),
+            Span::raw(l.message),
+        ])
+    }).collect();
+    
+    frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Logs ")), area);
+}
+
+/// Key bindings, confirmation prompt or the last action's result
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let line = match app.confirm_sell() {
+        Some(mint) => Line::styled(
+            format!("Sell all of {}? [y] confirm, any other key cancels", mint),
+            Style::new().black().on_red(),
+        ),
+        None => Line::from(vec![
+            Span::styled("↑/↓ select  s sell  p pause  t take profit  q quit", Style::new().fg(Color::Cyan)),
+            Span::raw(format!("  {}", app.status())),
+        ]),
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// Triggered tiers and the next target, e.g. "1/3 next 3.00x"
+fn tp_progress(position: &Position, tiers: &[TakeProfitTier], enabled: bool) -> String {
+    if !enabled {
+        return "off".to_string();
+    }
+    let triggered = (0..tiers.len()).filter(|i| position.is_tp_triggered(*i)).count();
+    match tiers.iter().enumerate().find(|(i, _)| !position.is_tp_triggered(*i)) {
+        Some((_, next)) => format!("{}/{} next {:.2}x", triggered, tiers.len(), next.multiplier),
+        None => format!("{}/{} done", triggered, tiers.len()),
+    }
+}
+
+fn pnl_style(value: f64) -> Style {
+    if value > 0.0 {
+        Style::new().green()
+    } else if value < 0.0 {
+        Style::new().red()
+    } else {
+        Style::new()
+    }
+}
+
+/// Shorten a pubkey or signature for display
+fn short(value: &str) -> &str {
+    &value[..8.min(value.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::PositionSeller;
+    use crate::state::{BotControl, StateManager, TargetEvent};
+    use crate::tui::logs::{LogBuffer, LogLine};
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use solana_sdk::pubkey::Pubkey;
+    use std::sync::Arc;
+    
+    struct NoopSeller;
+    
+    #[async_trait::async_trait]
+    impl PositionSeller for NoopSeller {
+        async fn force_sell(&self, _token_mint: &Pubkey) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+    }
+    
+    fn tiers() -> Vec<TakeProfitTier> {
+        vec![
+            TakeProfitTier { multiplier: 2.0, sell_percent: 50 },
+            TakeProfitTier { multiplier: 3.0, sell_percent: 100 },
+        ]
+    }
+    
+    #[test]
+    fn test_tp_progress() {
+        let mut position = Position::new(Pubkey::new_unique(), 1_000, 100, "t".to_string(), "o".to_string());
+        assert_eq!(tp_progress(&position, &tiers(), true), "0/2 next 2.00x");
+        
+        position.mark_tp_triggered(0);
+        assert_eq!(tp_progress(&position, &tiers(), true), "1/2 next 3.00x");
+        
+        position.mark_tp_triggered(1);
+        assert_eq!(tp_progress(&position, &tiers(), true), "2/2 done");
+        assert_eq!(tp_progress(&position, &tiers(), false), "off");
+    }
+    
+    #[test]
+    fn test_draw_shows_live_state() {
+        let state = Arc::new(StateManager::new());
+        let control = Arc::new(BotControl::new(vec![Pubkey::new_unique()], 0.1, 10_000, true, tiers()));
+        let logs = Arc::new(LogBuffer::new());
+        
+        let mint = Pubkey::new_unique();
+        state.open_position(Position::new(mint, 1_000, 100_000_000, "t".to_string(), "o".to_string()));
+        state.update_position_value(&mint, 250_000_000);
+        state.record_target_event(TargetEvent::new(Pubkey::new_unique(), Some(mint), true, "sig".to_string()));
+        state.add_pending_tx("PendingSig111".to_string(), mint);
+        logs.push(LogLine { at: chrono::Utc::now(), level: Level::INFO, message: "stream connected".to_string() });
+        control.set_paused(true);
+        
+        let app = App::new(state, control, Arc::new(NoopSeller), logs);
+        let mut terminal = Terminal::new(TestBackend::new(140, 30)).unwrap();
+        terminal.draw(|frame| draw(frame, &app)).unwrap();
+        
+        let screen: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        let mint_short = &mint.to_string()[..8];
+        assert!(screen.contains("PAUSED"));
+        assert!(screen.contains("2.50x"));
+        assert!(screen.contains("0/2 next 2.00x"));
+        assert!(screen.contains("BUY"));
+        assert!(screen.contains(mint_short));
+        assert!(screen.contains("PendingS"));
+        assert!(screen.contains("stream connected"));
+    }
+}